@@ -44,6 +44,20 @@ t_aggspec::t_aggspec(
     m_agg(agg),
     m_dependencies(std::vector<t_dep>{t_dep(dep, DEPTYPE_COLUMN)}) {}
 
+t_aggspec::t_aggspec(
+    const std::string& aggname,
+    t_aggtype agg,
+    const std::vector<t_dep>& dependencies,
+    double quantile,
+    std::string expression
+) :
+    m_name(aggname),
+    m_disp_name(aggname),
+    m_agg(agg),
+    m_dependencies(dependencies),
+    m_quantile(quantile),
+    m_expression(std::move(expression)) {}
+
 t_aggspec::t_aggspec(t_aggtype agg, const std::string& dep) :
     m_agg(agg),
     m_dependencies(std::vector<t_dep>{t_dep(dep, DEPTYPE_COLUMN)}) {}
@@ -241,6 +255,36 @@ t_aggspec::agg_str() const {
         case AGGTYPE_STANDARD_DEVIATION: {
             return "stddev";
         }
+        case AGGTYPE_PERCENTILE: {
+            return "percentile";
+        }
+        case AGGTYPE_MODE: {
+            return "mode";
+        }
+        case AGGTYPE_DISTINCT_COUNT_APPROX: {
+            return "distinct_count_approx";
+        }
+        case AGGTYPE_NULL_COUNT: {
+            return "null_count";
+        }
+        case AGGTYPE_SUM_RATIO: {
+            return "sum_ratio";
+        }
+        case AGGTYPE_LAST_BY: {
+            return "last_by";
+        }
+        case AGGTYPE_FIRST_BY: {
+            return "first_by";
+        }
+        case AGGTYPE_COVARIANCE: {
+            return "covariance";
+        }
+        case AGGTYPE_CORRELATION: {
+            return "correlation";
+        }
+        case AGGTYPE_CUSTOM: {
+            return "custom";
+        }
         default: {
             PSP_COMPLAIN_AND_ABORT("Unknown agg type");
             return "unknown";
@@ -306,6 +350,16 @@ t_aggspec::get_agg_two_weight() const {
     return m_agg_two_weight;
 }
 
+double
+t_aggspec::get_quantile() const {
+    return m_quantile;
+}
+
+const std::string&
+t_aggspec::get_expression() const {
+    return m_expression;
+}
+
 t_invmode
 t_aggspec::get_inv_mode() const {
     return m_invmode;
@@ -352,6 +406,10 @@ t_aggspec::get_output_specs(const t_schema& schema) const {
         case AGGTYPE_Q1:
         case AGGTYPE_Q3:
         case AGGTYPE_MEDIAN:
+        case AGGTYPE_PERCENTILE:
+        case AGGTYPE_MODE:
+        case AGGTYPE_LAST_BY:
+        case AGGTYPE_FIRST_BY:
         case AGGTYPE_FIRST:
         case AGGTYPE_LAST_BY_INDEX:
         case AGGTYPE_LAST_MINUS_FIRST:
@@ -375,7 +433,8 @@ t_aggspec::get_output_specs(const t_schema& schema) const {
         }
         case AGGTYPE_MEAN:
         case AGGTYPE_MEAN_BY_COUNT:
-        case AGGTYPE_WEIGHTED_MEAN: {
+        case AGGTYPE_WEIGHTED_MEAN:
+        case AGGTYPE_SUM_RATIO: {
             return mk_col_name_type_vec(name(), DTYPE_F64PAIR);
         }
         case AGGTYPE_JOIN: {
@@ -385,7 +444,10 @@ t_aggspec::get_output_specs(const t_schema& schema) const {
         case AGGTYPE_SCALED_ADD:
         case AGGTYPE_SCALED_MUL:
         case AGGTYPE_VARIANCE:
-        case AGGTYPE_STANDARD_DEVIATION: {
+        case AGGTYPE_STANDARD_DEVIATION:
+        case AGGTYPE_COVARIANCE:
+        case AGGTYPE_CORRELATION:
+        case AGGTYPE_CUSTOM: {
             return mk_col_name_type_vec(name(), DTYPE_FLOAT64);
         }
         case AGGTYPE_UDF_COMBINER:
@@ -401,7 +463,9 @@ t_aggspec::get_output_specs(const t_schema& schema) const {
         case AGGTYPE_AND: {
             return mk_col_name_type_vec(name(), DTYPE_BOOL);
         }
-        case AGGTYPE_DISTINCT_COUNT: {
+        case AGGTYPE_DISTINCT_COUNT:
+        case AGGTYPE_DISTINCT_COUNT_APPROX:
+        case AGGTYPE_NULL_COUNT: {
             return mk_col_name_type_vec(name(), DTYPE_UINT32);
         }
         default: {
@@ -337,6 +337,15 @@ filter_op_to_str(t_filter_op op) {
         case FILTER_OP_CONTAINS: {
             return "contains";
         } break;
+        case FILTER_OP_NOT_CONTAINS: {
+            return "not contains";
+        } break;
+        case FILTER_OP_MATCHES: {
+            return "matches";
+        } break;
+        case FILTER_OP_ICONTAINS: {
+            return "icontains";
+        } break;
         case FILTER_OP_OR: {
             return "or";
         } break;
@@ -392,6 +401,15 @@ str_to_filter_op(const std::string& str) {
     if (str == "contains") {
         return t_filter_op::FILTER_OP_CONTAINS;
     }
+    if (str == "not contains") {
+        return t_filter_op::FILTER_OP_NOT_CONTAINS;
+    }
+    if (str == "matches") {
+        return t_filter_op::FILTER_OP_MATCHES;
+    }
+    if (str == "icontains") {
+        return t_filter_op::FILTER_OP_ICONTAINS;
+    }
     if (str == "not in") {
         return t_filter_op::FILTER_OP_NOT_IN;
     }
@@ -582,6 +600,36 @@ str_to_aggtype(const std::string& str) {
     if (str == "stddev" || str == "standard deviation") {
         return t_aggtype::AGGTYPE_STANDARD_DEVIATION;
     }
+    if (str == "percentile") {
+        return t_aggtype::AGGTYPE_PERCENTILE;
+    }
+    if (str == "mode") {
+        return t_aggtype::AGGTYPE_MODE;
+    }
+    if (str == "count distinct approx" || str == "distinct_count_approx") {
+        return t_aggtype::AGGTYPE_DISTINCT_COUNT_APPROX;
+    }
+    if (str == "null count" || str == "null_count") {
+        return t_aggtype::AGGTYPE_NULL_COUNT;
+    }
+    if (str == "sum ratio" || str == "sum_ratio") {
+        return t_aggtype::AGGTYPE_SUM_RATIO;
+    }
+    if (str == "last by" || str == "last_by") {
+        return t_aggtype::AGGTYPE_LAST_BY;
+    }
+    if (str == "first by" || str == "first_by") {
+        return t_aggtype::AGGTYPE_FIRST_BY;
+    }
+    if (str == "covariance") {
+        return t_aggtype::AGGTYPE_COVARIANCE;
+    }
+    if (str == "correlation") {
+        return t_aggtype::AGGTYPE_CORRELATION;
+    }
+    if (str == "custom") {
+        return t_aggtype::AGGTYPE_CUSTOM;
+    }
 
     std::stringstream ss;
     ss << "Encountered unknown aggregate operation: '" << str << "'"
@@ -109,6 +109,82 @@ psp_close_session(ProtoServer* server, std::uint32_t client_id) {
     server->close_session(client_id);
 }
 
+PERSPECTIVE_EXPORT
+void
+psp_set_session_update_throttle(
+    ProtoServer* server, std::uint32_t client_id, std::uint32_t throttle_ms
+) {
+    server->set_session_update_throttle(client_id, throttle_ms);
+}
+
+PERSPECTIVE_EXPORT
+std::uint32_t
+psp_session_num_views(ProtoServer* server, std::uint32_t client_id) {
+    return server->get_session_num_views(client_id);
+}
+
+PERSPECTIVE_EXPORT
+std::uint32_t
+psp_session_num_subscriptions(ProtoServer* server, std::uint32_t client_id) {
+    return server->get_session_num_subscriptions(client_id);
+}
+
+// The row count of a view, or `-1` if it does not exist.
+PERSPECTIVE_EXPORT
+std::int64_t
+psp_view_num_rows(
+    ProtoServer* server, char* view_id_ptr, std::size_t view_id_len
+) {
+    std::string view_id(view_id_ptr, view_id_len);
+    auto num_rows = server->get_view_num_rows(view_id);
+    return num_rows.has_value() ? *num_rows : -1;
+}
+
+// The id of the session which created a view, or `-1` if it does not exist.
+PERSPECTIVE_EXPORT
+std::int64_t
+psp_view_session_id(
+    ProtoServer* server, char* view_id_ptr, std::size_t view_id_len
+) {
+    std::string view_id(view_id_ptr, view_id_len);
+    auto session_id = server->get_view_session_id(view_id);
+    return session_id.has_value() ? *session_id : -1;
+}
+
+// The id of the table a view was created from, as a `psp_alloc`-ed buffer
+// of `*table_id_len` bytes the caller must `psp_free`, or `nullptr` if the
+// view does not exist.
+PERSPECTIVE_EXPORT
+char*
+psp_view_table_id(
+    ProtoServer* server,
+    char* view_id_ptr,
+    std::size_t view_id_len,
+    std::size_t* table_id_len
+) {
+    std::string view_id(view_id_ptr, view_id_len);
+    auto table_id = server->get_view_table_id(view_id);
+    if (!table_id.has_value()) {
+        *table_id_len = 0;
+        return nullptr;
+    }
+
+    auto* data = (char*)UNINSTRUMENTED_MALLOC(table_id->size());
+    std::copy(table_id->begin(), table_id->end(), data);
+    *table_id_len = table_id->size();
+    return data;
+}
+
+PERSPECTIVE_EXPORT
+EncodedApiEntries*
+psp_process_table(
+    ProtoServer* server, char* table_id_ptr, std::size_t table_id_len
+) {
+    std::string table_id(table_id_ptr, table_id_len);
+    auto responses = server->process_table(table_id);
+    return encode_api_responses(responses);
+}
+
 PERSPECTIVE_EXPORT
 std::size_t
 psp_alloc(std::size_t size) {
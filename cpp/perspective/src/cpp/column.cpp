@@ -337,6 +337,20 @@ t_column::size() const {
     return m_size;
 }
 
+t_uindex
+t_column::nbytes() const {
+    t_uindex rv = m_data->capacity();
+    if (m_status_enabled) {
+        rv += m_status->capacity();
+    }
+
+    if (m_isvlen) {
+        rv += m_vocab->nbytes();
+    }
+
+    return rv;
+}
+
 void
 t_column::set_size(t_uindex size) {
 #ifdef PSP_COLUMN_VERIFY
@@ -12,6 +12,8 @@
 
 #include <perspective/computed_expression.h>
 
+#include <map>
+#include <re2/re2.h>
 #include <utility>
 
 namespace perspective {
@@ -28,11 +30,6 @@ std::size_t t_computed_expression_parser::PARSER_COMPILE_OPTIONS =
 // exprtk::parser<t_tscalar>::settings_t::e_commutative_check;
 // exprtk::parser<t_tscalar>::settings_t::e_strength_reduction;
 
-std::shared_ptr<exprtk::parser<t_tscalar>>
-    t_computed_expression_parser::PARSER =
-        std::make_shared<exprtk::parser<t_tscalar>>(
-            t_computed_expression_parser::PARSER_COMPILE_OPTIONS
-        );
 
 computed_function::bucket t_computed_expression_parser::BUCKET_FN =
     computed_function::bucket();
@@ -160,13 +157,13 @@ t_computed_expression::compute(
 
     expr_definition.register_symbol_table(sym_table);
 
-    if (!t_computed_expression_parser::PARSER->compile(
+    if (!t_computed_expression_parser::parser().compile(
             m_parsed_expression_string, expr_definition
         )) {
         std::stringstream ss;
         ss << "[t_computed_expression::compute] Failed to parse expression: `"
            << m_parsed_expression_string << "`, failed with error: "
-           << t_computed_expression_parser::PARSER->error() << '\n';
+           << t_computed_expression_parser::parser().error() << '\n';
 
         PSP_COMPLAIN_AND_ABORT(ss.str());
     }
@@ -222,26 +219,73 @@ t_computed_expression::get_dtype() const {
     return m_dtype;
 }
 
+bool
+t_computed_expression::is_window() const {
+    return false;
+}
+
+// Report the parser's first error compiling `expression` in `error`.
+static void
+set_parser_error(const std::string& expression, t_expression_error& error) {
+    // Error count should always be above 0 if there is a compile error -
+    // We simply take the first error and return it.
+    if (t_computed_expression_parser::parser().error_count() > 0) {
+        auto parser_error = t_computed_expression_parser::parser().get_error(0);
+
+        // Given an error object and an expression, `update_error` maps the
+        // error to a line and column number inside the expression.
+        exprtk::parser_error::update_error(parser_error, expression);
+
+        // Take the error message and strip the ExprTk error code
+        std::string error_message(parser_error.diagnostic);
+
+        // strip the Exprtk error codes such as "ERR001 -"
+        error.m_error_message =
+            error_message.substr(error_message.find("- ") + 2);
+
+        error.m_line = parser_error.line_no;
+        error.m_column = parser_error.column_no;
+    } else {
+        // If for whatever reason the error count is at 0, output a generic
+        // parser error so that we report back a valid error object.
+        error.m_error_message = "Parser Error";
+        error.m_line = 0;
+        error.m_column = 0;
+    }
+}
+
 /******************************************************************************
  *
  * t_computed_expression_parser
  */
 
-void
-t_computed_expression_parser::init() {
-    t_computed_expression_parser::PARSER->settings()
-        .disable_control_structure(
-            exprtk::parser<t_tscalar>::settings_store::e_ctrl_repeat_loop
-        )
-        .disable_base_function(
-            exprtk::parser<t_tscalar>::settings_store::e_bf_inrange
-        )
-        .disable_base_function(
-            exprtk::parser<t_tscalar>::settings_store::e_bf_min
-        )
-        .disable_base_function(
-            exprtk::parser<t_tscalar>::settings_store::e_bf_max
+exprtk::parser<t_tscalar>&
+t_computed_expression_parser::parser() {
+    // `exprtk::parser` keeps the state of the last compile, so each thread
+    // which compiles expressions needs its own.
+    thread_local std::unique_ptr<exprtk::parser<t_tscalar>> parser = [] {
+        auto parser = std::make_unique<exprtk::parser<t_tscalar>>(
+            t_computed_expression_parser::PARSER_COMPILE_OPTIONS
         );
+
+        parser->settings()
+            .disable_control_structure(
+                exprtk::parser<t_tscalar>::settings_store::e_ctrl_repeat_loop
+            )
+            .disable_base_function(
+                exprtk::parser<t_tscalar>::settings_store::e_bf_inrange
+            )
+            .disable_base_function(
+                exprtk::parser<t_tscalar>::settings_store::e_bf_min
+            )
+            .disable_base_function(
+                exprtk::parser<t_tscalar>::settings_store::e_bf_max
+            );
+
+        return parser;
+    }();
+
+    return *parser;
 }
 
 std::shared_ptr<t_computed_expression>
@@ -299,14 +343,14 @@ t_computed_expression_parser::precompute(
     exprtk::expression<t_tscalar> expr_definition;
     expr_definition.register_symbol_table(sym_table);
 
-    if (!t_computed_expression_parser::PARSER->compile(
+    if (!t_computed_expression_parser::parser().compile(
             parsed_expression_string, expr_definition
         )) {
         std::stringstream ss;
         ss << "[t_computed_expression_parser::precompute] Failed to parse "
               "expression: `"
            << parsed_expression_string << "`, failed with error: "
-           << t_computed_expression_parser::PARSER->error() << '\n';
+           << t_computed_expression_parser::parser().error() << '\n';
         PSP_COMPLAIN_AND_ABORT(ss.str());
     }
 
@@ -391,38 +435,10 @@ t_computed_expression_parser::get_dtype(
     exprtk::expression<t_tscalar> expr_definition;
     expr_definition.register_symbol_table(sym_table);
 
-    if (!t_computed_expression_parser::PARSER->compile(
+    if (!t_computed_expression_parser::parser().compile(
             parsed_expression_string, expr_definition
         )) {
-        // Error count should always be above 0 if there is a compile error -
-        // We simply take the first error and return it.
-        if (t_computed_expression_parser::PARSER->error_count() > 0) {
-            auto parser_error =
-                t_computed_expression_parser::PARSER->get_error(0);
-
-            // Given an error object and an expression, `update_error` maps the
-            // error to a line and column number inside the expression.
-            exprtk::parser_error::update_error(
-                parser_error, parsed_expression_string
-            );
-
-            // Take the error message and strip the ExprTk error code
-            std::string error_message(parser_error.diagnostic);
-
-            // strip the Exprtk error codes such as "ERR001 -"
-            error.m_error_message =
-                error_message.substr(error_message.find("- ") + 2);
-
-            error.m_line = parser_error.line_no;
-            error.m_column = parser_error.column_no;
-        } else {
-            // If for whatever reason the error count is at 0, output a generic
-            // parser error so that we report back a valid error object.
-            error.m_error_message = "Parser Error";
-            error.m_line = 0;
-            error.m_column = 0;
-        }
-
+        set_parser_error(parsed_expression_string, error);
         return DTYPE_NONE;
     }
 
@@ -442,6 +458,93 @@ t_computed_expression_parser::get_dtype(
     return dtype;
 }
 
+/******************************************************************************
+ *
+ * t_custom_aggregate
+ */
+
+t_custom_aggregate::t_custom_aggregate(
+    std::size_t num_columns, std::size_t capacity
+) :
+    m_capacity(capacity),
+    m_buffers(num_columns, std::vector<t_tscalar>(capacity, mktscalar(0.0))) {
+    m_sym_table.add_constants();
+    m_views.reserve(num_columns);
+    for (t_uindex cidx = 0; cidx < num_columns; ++cidx) {
+        m_views.emplace_back(m_buffers[cidx].data(), capacity);
+        m_sym_table.add_vector("COLUMN" + std::to_string(cidx), m_views[cidx]);
+    }
+
+    m_expression.register_symbol_table(m_sym_table);
+}
+
+std::string
+t_custom_aggregate::parse_expression(
+    const std::string& expression, std::vector<std::string>& columns
+) {
+    static const RE2 column_name("\"(.*?[^\\\\])\"");
+    std::map<std::string, std::string> column_ids;
+    std::string parsed;
+    re2::StringPiece input(expression);
+    const char* previous_end = expression.data();
+    std::string cname;
+    while (RE2::FindAndConsume(&input, column_name, &cname)) {
+        const char* match_start = input.data() - cname.size() - 2;
+        parsed.append(previous_end, match_start - previous_end);
+        if (column_ids.count(cname) == 0) {
+            std::string column_id =
+                "COLUMN" + std::to_string(column_ids.size());
+            column_ids[cname] = column_id;
+            columns.push_back(cname);
+        }
+
+        parsed += column_ids[cname];
+        previous_end = input.data();
+    }
+
+    parsed.append(previous_end, expression.data() + expression.size());
+    return parsed;
+}
+
+bool
+t_custom_aggregate::validate(
+    const std::string& parsed_expression,
+    std::size_t num_columns,
+    t_expression_error& error
+) {
+    // A group of one row, read as `0` as `compute_custom_aggregate` reads a
+    // `null`.
+    t_custom_aggregate custom(num_columns, 1);
+    if (!custom.compile(parsed_expression, error)) {
+        return false;
+    }
+
+    t_tscalar value = custom.m_expression.value();
+    if (value.get_dtype() == DTYPE_STR) {
+        error.m_error_message =
+            "Type Error - custom aggregates must evaluate to a number.";
+        error.m_line = 0;
+        error.m_column = 0;
+        return false;
+    }
+
+    return true;
+}
+
+bool
+t_custom_aggregate::compile(
+    const std::string& parsed_expression, t_expression_error& error
+) {
+    if (!t_computed_expression_parser::parser().compile(
+            parsed_expression, m_expression
+        )) {
+        set_parser_error(parsed_expression, error);
+        return false;
+    }
+
+    return true;
+}
+
 t_validated_expression_map::t_validated_expression_map() = default;
 
 void
@@ -13,6 +13,8 @@
 #include <perspective/first.h>
 #include <perspective/config.h>
 
+#include <algorithm>
+
 namespace perspective {
 
 // t_ctxunit
@@ -212,10 +214,18 @@ t_config::setup(
             case AGGTYPE_HIGH_MINUS_LOW:
             case AGGTYPE_MEAN:
             case AGGTYPE_WEIGHTED_MEAN:
+            case AGGTYPE_SUM_RATIO:
+            case AGGTYPE_LAST_BY:
+            case AGGTYPE_FIRST_BY:
+            case AGGTYPE_COVARIANCE:
+            case AGGTYPE_CORRELATION:
+            case AGGTYPE_CUSTOM:
             case AGGTYPE_UNIQUE:
             case AGGTYPE_Q1:
             case AGGTYPE_Q3:
             case AGGTYPE_MEDIAN:
+            case AGGTYPE_PERCENTILE:
+            case AGGTYPE_MODE:
             case AGGTYPE_JOIN:
             case AGGTYPE_DOMINANT:
             case AGGTYPE_PY_AGG:
@@ -226,6 +236,8 @@ t_config::setup(
             case AGGTYPE_ABS_SUM:
             case AGGTYPE_MUL:
             case AGGTYPE_DISTINCT_COUNT:
+            case AGGTYPE_DISTINCT_COUNT_APPROX:
+            case AGGTYPE_NULL_COUNT:
             case AGGTYPE_DISTINCT_LEAF:
             case AGGTYPE_VARIANCE:
             case AGGTYPE_STANDARD_DEVIATION:
@@ -403,7 +415,7 @@ bool
 t_config::has_filters() const {
     switch (m_fmode) {
         case FMODE_SIMPLE_CLAUSES: {
-            return !m_fterms.empty();
+            return !m_fterms.empty() || !m_session_fterms.empty();
         } break;
         default: {
             return false;
@@ -422,6 +434,15 @@ t_config::get_expressions() const {
     return m_expressions;
 }
 
+bool
+t_config::has_windows() const {
+    return std::any_of(
+        m_expressions.begin(),
+        m_expressions.end(),
+        [](const auto& expr) { return expr->is_window(); }
+    );
+}
+
 t_filter_op
 t_config::get_combiner() const {
     return m_combiner;
@@ -432,6 +453,48 @@ t_config::get_totals() const {
     return m_totals;
 }
 
+const t_row_totals&
+t_config::get_row_totals() const {
+    return m_row_totals;
+}
+
+void
+t_config::set_row_totals(const t_row_totals& row_totals) {
+    m_row_totals = row_totals;
+}
+
+const std::vector<t_pivot_order>&
+t_config::get_col_pivot_orders() const {
+    return m_col_pivot_orders;
+}
+
+void
+t_config::set_col_pivot_orders(const std::vector<t_pivot_order>& orders) {
+    m_col_pivot_orders = orders;
+}
+
+const std::vector<t_fterm>&
+t_config::get_session_fterms() const {
+    return m_session_fterms;
+}
+
+void
+t_config::set_session_fterms(const std::vector<t_fterm>& fterms) {
+    m_session_fterms = fterms;
+    m_is_trivial_config = m_is_trivial_config && m_session_fterms.empty();
+}
+
+const std::map<std::string, t_column_mask>&
+t_config::get_column_masks() const {
+    return m_column_masks;
+}
+
+void
+t_config::set_column_masks(const std::map<std::string, t_column_mask>& masks) {
+    m_column_masks = masks;
+    m_is_trivial_config = m_is_trivial_config && m_column_masks.empty();
+}
+
 std::vector<t_pivot>
 t_config::get_pivots() const {
     std::vector<t_pivot> rval = m_row_pivots;
@@ -43,6 +43,8 @@ t_ctx1::init() {
     );
     m_tree->init();
     m_traversal = std::make_shared<t_traversal>(m_tree);
+    m_row_map = t_row_map(m_config.get_row_totals());
+    m_row_map.update(*m_traversal);
 
     // Each context stores its own expression columns in separate
     // `t_data_table`s so that each context's expressions are isolated
@@ -57,7 +59,7 @@ t_index
 t_ctx1::get_row_count() const {
     PSP_TRACE_SENTINEL();
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
-    return m_traversal->size();
+    return m_row_map.size(*m_traversal);
 }
 
 t_index
@@ -89,11 +91,14 @@ t_ctx1::open(t_index idx) {
     m_depth_set = false;
     m_depth = 0;
 
-    if (idx >= t_index(m_traversal->size())) {
+    if (idx >= get_row_count()) {
         return 0;
     }
 
-    t_index retval = m_traversal->expand_node(m_sortby, idx);
+    t_index retval = m_traversal->expand_node(
+        m_sortby, m_row_map.get_traversal_index(idx)
+    );
+    m_row_map.update(*m_traversal);
     m_rows_changed = (retval > 0);
     return retval;
 }
@@ -106,11 +111,13 @@ t_ctx1::close(t_index idx) {
     m_depth_set = false;
     m_depth = 0;
 
-    if (idx >= t_index(m_traversal->size())) {
+    if (idx >= get_row_count()) {
         return 0;
     }
 
-    t_index retval = m_traversal->collapse_node(idx);
+    t_index retval =
+        m_traversal->collapse_node(m_row_map.get_traversal_index(idx));
+    m_row_map.update(*m_traversal);
     m_rows_changed = (retval > 0);
     return retval;
 }
@@ -193,7 +200,8 @@ t_ctx1::get_data(
     const std::vector<t_aggspec>& aggspecs = m_config.get_aggregates();
 
     for (t_index ridx = ext.m_srow; ridx < ext.m_erow; ++ridx) {
-        t_index nidx = m_traversal->get_tree_index(ridx);
+        t_index nidx =
+            m_traversal->get_tree_index(m_row_map.get_traversal_index(ridx));
         t_index pnidx = m_tree->get_parent_idx(nidx);
 
         t_uindex agg_ridx = m_tree->get_aggidx(nidx);
@@ -253,7 +261,8 @@ t_ctx1::get_data(const std::vector<t_uindex>& rows) const {
     // start from 0
     for (t_uindex idx = 0; idx < nrows; ++idx) {
         t_uindex ridx = rows[idx];
-        t_index nidx = m_traversal->get_tree_index(ridx);
+        t_index nidx =
+            m_traversal->get_tree_index(m_row_map.get_traversal_index(ridx));
         t_index pnidx = m_tree->get_parent_idx(nidx);
 
         t_uindex agg_ridx = m_tree->get_aggidx(nidx);
@@ -350,6 +359,8 @@ t_ctx1::step_end() {
     if (m_depth_set) {
         set_depth(m_depth);
     }
+
+    m_row_map.update(*m_traversal);
 }
 
 t_aggspec
@@ -388,7 +399,9 @@ t_ctx1::get_row_path(t_index idx) const {
     if (idx < 0) {
         return {};
     }
-    return ctx_get_path(m_tree, m_traversal, idx);
+    return ctx_get_path(
+        m_tree, m_traversal, m_row_map.get_traversal_index(idx)
+    );
 }
 
 void
@@ -407,6 +420,7 @@ t_ctx1::sort_by(const std::vector<t_sortspec>& sortby) {
         return;
     }
     m_traversal->sort_by(m_config, sortby, *(m_tree));
+    m_row_map.update(*m_traversal);
 }
 
 void
@@ -419,6 +433,7 @@ t_ctx1::set_depth(t_depth depth) {
     depth = std::min<t_depth>(m_config.get_num_rpivots() - 1, depth);
     t_index retval = 0;
     retval = m_traversal->set_depth(m_sortby, depth);
+    m_row_map.update(*m_traversal);
     m_rows_changed = (retval > 0);
     m_depth = depth;
     m_depth_set = true;
@@ -430,14 +445,25 @@ t_ctx1::get_pkeys(const std::vector<std::pair<t_uindex, t_uindex>>& cells
     PSP_TRACE_SENTINEL();
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
 
-    if (!m_traversal->validate_cells(cells)) {
+    std::vector<std::pair<t_uindex, t_uindex>> tcells;
+    tcells.reserve(cells.size());
+    for (const auto& c : cells) {
+        t_index tidx = m_row_map.get_traversal_index(c.first);
+        if (tidx == INVALID_INDEX) {
+            return {};
+        }
+
+        tcells.emplace_back(tidx, c.second);
+    }
+
+    if (!m_traversal->validate_cells(tcells)) {
         std::vector<t_tscalar> rval;
         return rval;
     }
 
     std::vector<t_tscalar> rval;
-    std::vector<t_index> tindices(cells.size());
-    for (const auto& c : cells) {
+    std::vector<t_index> tindices(tcells.size());
+    for (const auto& c : tcells) {
         auto ptidx = m_traversal->get_tree_index(c.first);
         auto pkeys = m_tree->get_pkeys(ptidx);
 
@@ -479,8 +505,8 @@ t_stepdelta
 t_ctx1::get_step_delta(t_index bidx, t_index eidx) {
     PSP_TRACE_SENTINEL();
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
-    bidx = std::min(bidx, t_index(m_traversal->size()));
-    eidx = std::min(eidx, t_index(m_traversal->size()));
+    bidx = std::min(bidx, get_row_count());
+    eidx = std::min(eidx, get_row_count());
 
     t_stepdelta rval(
         m_rows_changed, m_columns_changed, get_cell_delta(bidx, eidx)
@@ -511,10 +537,11 @@ std::vector<t_uindex>
 t_ctx1::get_rows_changed() {
     std::vector<t_uindex> rows;
     const auto& deltas = m_tree->get_deltas();
-    auto eidx = t_uindex(m_traversal->size());
+    auto eidx = t_uindex(get_row_count());
 
     for (t_uindex idx = 0; idx < eidx; ++idx) {
-        t_index ptidx = m_traversal->get_tree_index(idx);
+        t_index ptidx =
+            m_traversal->get_tree_index(m_row_map.get_traversal_index(idx));
         // Retrieve delta from storage and check if the row has been changed
         auto iterators = deltas->get<by_tc_nidx_aggidx>().equal_range(ptidx);
         bool unique_ridx =
@@ -532,11 +559,12 @@ std::vector<t_cellupd>
 t_ctx1::get_cell_delta(t_index bidx, t_index eidx) const {
     PSP_TRACE_SENTINEL();
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
-    eidx = std::min(eidx, t_index(m_traversal->size()));
+    eidx = std::min(eidx, get_row_count());
     std::vector<t_cellupd> rval;
     const auto& deltas = m_tree->get_deltas();
     for (t_index idx = bidx; idx < eidx; ++idx) {
-        t_index ptidx = m_traversal->get_tree_index(idx);
+        t_index ptidx =
+            m_traversal->get_tree_index(m_row_map.get_traversal_index(idx));
         auto iterators = deltas->get<by_tc_nidx_aggidx>().equal_range(ptidx);
         for (auto iter = iterators.first; iter != iterators.second; ++iter) {
             rval.emplace_back(
@@ -556,6 +584,7 @@ t_ctx1::reset(bool reset_expressions) {
     m_tree->init();
     m_tree->set_deltas_enabled(get_feature_state(CTX_FEAT_DELTA));
     m_traversal = std::make_shared<t_traversal>(m_tree);
+    m_row_map.update(*m_traversal);
 
     if (reset_expressions) {
         m_expression_tables->reset();
@@ -613,7 +642,8 @@ t_ctx1::pprint() const {
     const std::vector<t_aggspec>& aggspecs = m_config.get_aggregates();
 
     for (auto ridx = 0; ridx < get_row_count(); ++ridx) {
-        t_index nidx = m_traversal->get_tree_index(ridx);
+        t_index nidx =
+            m_traversal->get_tree_index(m_row_map.get_traversal_index(ridx));
         t_index pnidx = m_tree->get_parent_idx(nidx);
 
         t_uindex agg_ridx = m_tree->get_aggidx(nidx);
@@ -646,7 +676,27 @@ t_ctx1::get_row_idx(const std::vector<t_tscalar>& path) const {
         return nidx;
     }
 
-    return m_traversal->get_traversal_index(nidx);
+    return m_row_map.get_row_index(m_traversal->get_traversal_index(nidx));
+}
+
+t_index
+t_ctx1::get_row_idx_for_pkey(t_tscalar pkey) const {
+    // The rows which are not expanded partition the table, so exactly one of
+    // them contains `pkey` if any does.
+    for (t_index ridx = 0, loop_end = get_row_count(); ridx < loop_end;
+         ++ridx) {
+        t_index tidx = m_row_map.get_traversal_index(ridx);
+        if (m_traversal->get_node_expanded(tidx)) {
+            continue;
+        }
+
+        auto pkeys = m_tree->get_pkeys(m_traversal->get_tree_index(tidx));
+        if (std::find(pkeys.begin(), pkeys.end(), pkey) != pkeys.end()) {
+            return ridx;
+        }
+    }
+
+    return INVALID_INDEX;
 }
 
 t_dtype
@@ -659,7 +709,7 @@ t_ctx1::get_column_dtype(t_uindex idx) const {
 
 t_depth
 t_ctx1::get_trav_depth(t_index idx) const {
-    return m_traversal->get_depth(idx);
+    return m_traversal->get_depth(m_row_map.get_traversal_index(idx));
 }
 
 void
@@ -818,7 +868,7 @@ t_ctx1::unity_get_column_path(t_uindex idx) const {
 
 t_uindex
 t_ctx1::unity_get_row_depth(t_uindex ridx) const {
-    return m_traversal->get_depth(ridx);
+    return m_traversal->get_depth(m_row_map.get_traversal_index(ridx));
 }
 
 t_uindex
@@ -870,7 +920,7 @@ t_ctx1::unity_get_row_count() const {
 
 bool
 t_ctx1::unity_get_row_expanded(t_uindex idx) const {
-    return m_traversal->get_node_expanded(idx);
+    return m_traversal->get_node_expanded(m_row_map.get_traversal_index(idx));
 }
 
 bool
@@ -80,6 +80,8 @@ t_ctx2::init() {
     }
 
     m_rtraversal = std::make_shared<t_traversal>(rtree());
+    m_row_map = t_row_map(m_config.get_row_totals());
+    m_row_map.update(*m_rtraversal);
 
     m_ctraversal = std::make_shared<t_traversal>(ctree());
 
@@ -115,12 +117,16 @@ t_ctx2::step_end() {
     }
     if (m_column_depth_set) {
         set_depth(HEADER_COLUMN, m_column_depth);
+    } else {
+        order_columns();
     }
+
+    m_row_map.update(*m_rtraversal);
 }
 
 t_index
 t_ctx2::get_row_count() const {
-    return m_rtraversal->size();
+    return m_row_map.size(*m_rtraversal);
 }
 
 t_index
@@ -133,6 +139,7 @@ t_ctx2::open(t_header header, t_index idx) {
     t_index retval;
 
     if (header == HEADER_ROW) {
+        idx = m_row_map.get_traversal_index(idx);
         if (!m_rtraversal->is_valid_idx(idx)) {
             return 0;
         }
@@ -143,12 +150,14 @@ t_ctx2::open(t_header header, t_index idx) {
         } else {
             retval = m_rtraversal->expand_node(m_sortby, idx);
         }
+        m_row_map.update(*m_rtraversal);
         m_rows_changed = (retval > 0);
     } else {
         if (!m_ctraversal->is_valid_idx(idx)) {
             return 0;
         }
         retval = m_ctraversal->expand_node(idx);
+        order_columns();
         m_column_depth_set = false;
         m_column_depth = 0;
         m_columns_changed = (retval > 0);
@@ -163,12 +172,14 @@ t_ctx2::close(t_header header, t_index idx) {
 
     switch (header) {
         case HEADER_ROW: {
+            idx = m_row_map.get_traversal_index(idx);
             if (!m_rtraversal->is_valid_idx(idx)) {
                 return 0;
             }
             m_row_depth_set = false;
             m_row_depth = 0;
             retval = m_rtraversal->collapse_node(idx);
+            m_row_map.update(*m_rtraversal);
             m_rows_changed = (retval > 0);
         } break;
         case HEADER_COLUMN: {
@@ -228,6 +239,26 @@ t_ctx2::get_ctraversal_indices() const {
     return {};
 }
 
+t_index
+t_ctx2::get_row_idx_for_pkey(t_tscalar pkey) const {
+    // The rows which are not expanded partition the table, so exactly one of
+    // them contains `pkey` if any does.
+    for (t_index ridx = 0, loop_end = get_row_count(); ridx < loop_end;
+         ++ridx) {
+        t_index tidx = m_row_map.get_traversal_index(ridx);
+        if (m_rtraversal->get_node_expanded(tidx)) {
+            continue;
+        }
+
+        auto pkeys = rtree()->get_pkeys(m_rtraversal->get_tree_index(tidx));
+        if (std::find(pkeys.begin(), pkeys.end(), pkey) != pkeys.end()) {
+            return ridx;
+        }
+    }
+
+    return INVALID_INDEX;
+}
+
 std::pair<t_tscalar, t_tscalar>
 t_ctx2::get_min_max(const std::string& colname) const {
     t_uindex ctx_nrows = get_row_count();
@@ -267,7 +298,9 @@ t_ctx2::get_min_max(const std::string& colname) const {
             if (cinfo.m_idx < 0 || cinfo.m_agg_index != scol) {
                 continue;
             }
-            t_index nidx = m_rtraversal->get_tree_index(cinfo.m_ridx);
+            t_index nidx = m_rtraversal->get_tree_index(
+                m_row_map.get_traversal_index(cinfo.m_ridx)
+            );
             if (rtree()->get_depth(nidx) != row_depth) {
                 continue;
             }
@@ -361,8 +394,9 @@ t_ctx2::get_data(
 
     for (t_index ridx = ext.m_srow; ridx < ext.m_erow; ++ridx) {
         if (ext.m_scol == 0) {
+            t_index tidx = m_row_map.get_traversal_index(ridx);
             retval[(ridx - ext.m_srow) * stride].set(
-                rtree()->get_value(m_rtraversal->get_tree_index(ridx))
+                rtree()->get_value(m_rtraversal->get_tree_index(tidx))
             );
         }
 
@@ -508,9 +542,23 @@ void
 t_ctx2::column_sort_by(const std::vector<t_sortspec>& sortby) {
     PSP_TRACE_SENTINEL();
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    m_column_sortspec = sortby;
     m_ctraversal->sort_by(m_config, sortby, *(ctree()));
 }
 
+void
+t_ctx2::order_columns() {
+    const auto& orders = m_config.get_col_pivot_orders();
+    if (orders.empty()) {
+        return;
+    }
+
+    m_ctraversal->sort_by_order(orders);
+    if (!m_column_sortspec.empty()) {
+        m_ctraversal->sort_by(m_config, m_column_sortspec, *(ctree()));
+    }
+}
+
 void
 t_ctx2::sort_by(const std::vector<t_sortspec>& sortby) {
     PSP_TRACE_SENTINEL();
@@ -520,6 +568,7 @@ t_ctx2::sort_by(const std::vector<t_sortspec>& sortby) {
         return;
     }
     m_rtraversal->sort_by(m_config, sortby, *(rtree()), this);
+    m_row_map.update(*m_rtraversal);
 }
 
 void
@@ -695,13 +744,15 @@ t_ctx2::resolve_cells(const std::vector<std::pair<t_uindex, t_uindex>>& cells
     for (t_index idx = 0, loop_end = cells.size(); idx < loop_end; ++idx) {
         const auto& cell = cells[idx];
 
-        if (cell.first >= m_rtraversal->size() || cell.second == 0
+        if (cell.first >= static_cast<t_uindex>(get_row_count())
+            || cell.second == 0
             || cell.second >= ncols) {
             rval[idx].m_idx = INVALID_INDEX;
             continue;
         }
 
-        const t_tvnode& r_tvnode = m_rtraversal->get_node(cell.first);
+        t_index r_tvidx = m_row_map.get_traversal_index(cell.first);
+        const t_tvnode& r_tvnode = m_rtraversal->get_node(r_tvidx);
 
         t_index r_ptidx = r_tvnode.m_tnid;
         t_depth r_depth = r_tvnode.m_depth;
@@ -729,7 +780,7 @@ t_ctx2::resolve_cells(const std::vector<std::pair<t_uindex, t_uindex>>& cells
 
         rval[idx].m_agg_index = agg_idx;
 
-        if (cell.first == 0) {
+        if (r_tvidx == 0) {
             rval[idx].m_idx = c_ptidx;
             rval[idx].m_treenum = 0;
         } else if (c_path.empty()) {
@@ -818,7 +869,9 @@ t_ctx2::get_row_path(t_index idx) const {
     if (idx < 0) {
         return {};
     }
-    return ctx_get_path(rtree(), m_rtraversal, idx);
+    return ctx_get_path(
+        rtree(), m_rtraversal, m_row_map.get_traversal_index(idx)
+    );
 }
 
 std::vector<t_tscalar>
@@ -907,6 +960,7 @@ t_ctx2::set_depth(t_header header, t_depth depth) {
             new_depth =
                 std::min<t_depth>(m_config.get_num_rpivots() - 1, depth);
             m_rtraversal->set_depth(m_sortby, new_depth);
+            m_row_map.update(*m_rtraversal);
             m_row_depth = new_depth;
             m_row_depth_set = true;
         } break;
@@ -917,6 +971,7 @@ t_ctx2::set_depth(t_header header, t_depth depth) {
             new_depth =
                 std::min<t_depth>(m_config.get_num_cpivots() - 1, depth);
             m_ctraversal->set_depth(m_column_sortby, new_depth);
+            order_columns();
             m_column_depth = new_depth;
             m_column_depth_set = true;
         } break;
@@ -1080,6 +1135,7 @@ t_ctx2::reset(bool reset_expressions) {
 
     m_rtraversal = std::make_shared<t_traversal>(rtree());
     m_ctraversal = std::make_shared<t_traversal>(ctree());
+    m_row_map.update(*m_rtraversal);
 
     if (reset_expressions) {
         m_expression_tables->reset();
@@ -1365,7 +1421,8 @@ t_ctx2::unity_get_row_count() const {
 
 bool
 t_ctx2::unity_get_row_expanded(t_uindex idx) const {
-    return m_rtraversal->get_node_expanded(idx);
+    t_index tidx = m_row_map.get_traversal_index(idx);
+    return m_rtraversal->get_node_expanded(tidx);
 }
 
 bool
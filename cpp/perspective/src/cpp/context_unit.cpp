@@ -354,6 +354,17 @@ t_ctxunit::get_row_delta() {
     return rval;
 }
 
+t_index
+t_ctxunit::get_row_idx_for_pkey(t_tscalar pkey) const {
+    // Rows of a unit context are the rows of the master table.
+    auto lookup = m_gstate->lookup(pkey);
+    if (!lookup.m_exists) {
+        return INVALID_INDEX;
+    }
+
+    return lookup.m_idx;
+}
+
 const tsl::hopscotch_set<t_tscalar>&
 t_ctxunit::get_delta_pkeys() const {
     return m_delta_pkeys;
@@ -19,6 +19,8 @@
 
 #include <perspective/filter_utils.h>
 
+#include <iomanip>
+#include <sstream>
 #include <utility>
 
 namespace perspective {
@@ -315,6 +317,32 @@ t_ctx0::get_min_max(const std::string& colname) const {
     return rval;
 }
 
+// Replaces a non-null `value` of a masked column. Hashes are FNV-1a, so that
+// they are stable across processes and platforms.
+static t_tscalar
+mask_scalar(const t_tscalar& value, t_column_mask mask) {
+    if (!value.is_valid() || value.is_none()) {
+        return value;
+    }
+
+    switch (mask) {
+        case COLUMN_MASK_HASH: {
+            std::uint64_t hash = 14695981039346656037ULL;
+            for (char c : value.to_string()) {
+                hash ^= static_cast<unsigned char>(c);
+                hash *= 1099511628211ULL;
+            }
+
+            std::stringstream ss;
+            ss << std::hex << std::setw(16) << std::setfill('0') << hash;
+            return get_interned_tscalar(ss.str().c_str());
+        }
+        case COLUMN_MASK_REDACT:
+        default:
+            return get_interned_tscalar("****");
+    }
+}
+
 /**
  * @brief Given a start/end row and column index, return the underlying data
  * for the requested subset.
@@ -469,6 +497,11 @@ t_ctx0::get_row_delta() {
     return rval;
 }
 
+t_index
+t_ctx0::get_row_idx_for_pkey(t_tscalar pkey) const {
+    return m_traversal->get_row_idx(pkey);
+}
+
 const tsl::hopscotch_set<t_tscalar>&
 t_ctx0::get_delta_pkeys() const {
     return m_delta_pkeys;
@@ -810,6 +843,14 @@ t_ctx0::read_column_from_gstate(
         std::shared_ptr<t_data_table> master_table = m_gstate->get_table();
         m_gstate->read_column(*master_table, colname, pkeys, out_data);
     }
+
+    const auto& masks = m_config.get_column_masks();
+    auto mask = masks.find(colname);
+    if (mask != masks.end()) {
+        for (auto& value : out_data) {
+            value = mask_scalar(value, mask->second);
+        }
+    }
 }
 
 t_index
@@ -563,6 +563,16 @@ t_data_table::get_capacity() const {
     return m_capacity;
 }
 
+t_uindex
+t_data_table::nbytes() const {
+    t_uindex rv = 0;
+    for (const auto& column : m_columns) {
+        rv += column->nbytes();
+    }
+
+    return rv;
+}
+
 t_data_table*
 t_data_table::clone_(const t_mask& mask) const {
     PSP_TRACE_SENTINEL();
@@ -62,6 +62,13 @@ extract_aggregate(
         case AGGTYPE_Q1:
         case AGGTYPE_Q3:
         case AGGTYPE_MEDIAN:
+        case AGGTYPE_PERCENTILE:
+        case AGGTYPE_MODE:
+        case AGGTYPE_LAST_BY:
+        case AGGTYPE_FIRST_BY:
+        case AGGTYPE_COVARIANCE:
+        case AGGTYPE_CORRELATION:
+        case AGGTYPE_CUSTOM:
         case AGGTYPE_FIRST:
         case AGGTYPE_AND:
         case AGGTYPE_OR:
@@ -81,6 +88,8 @@ extract_aggregate(
         case AGGTYPE_JOIN:
         case AGGTYPE_IDENTITY:
         case AGGTYPE_DISTINCT_COUNT:
+        case AGGTYPE_DISTINCT_COUNT_APPROX:
+        case AGGTYPE_NULL_COUNT:
         case AGGTYPE_DISTINCT_LEAF:
         case AGGTYPE_VARIANCE:
         case AGGTYPE_STANDARD_DEVIATION: {
@@ -98,6 +107,7 @@ extract_aggregate(
         } break;
         case AGGTYPE_MEAN_BY_COUNT:
         case AGGTYPE_WEIGHTED_MEAN:
+        case AGGTYPE_SUM_RATIO:
         case AGGTYPE_MEAN: {
             const auto* pair = aggcol->get_nth<std::pair<double, double>>(ridx);
             t_tscalar rval;
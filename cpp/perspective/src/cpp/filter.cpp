@@ -35,6 +35,8 @@ t_fterm::t_fterm(
     m_is_primary(is_primary) {
     m_use_interned = (op == FILTER_OP_EQ || op == FILTER_OP_NE)
         && threshold.m_type == DTYPE_STR;
+
+    init_regex();
 }
 
 t_fterm::t_fterm(
@@ -51,6 +53,31 @@ t_fterm::t_fterm(
     m_is_primary(false) {
     m_use_interned = (op == FILTER_OP_EQ || op == FILTER_OP_NE)
         && threshold.m_type == DTYPE_STR;
+
+    init_regex();
+}
+
+void
+t_fterm::init_regex() {
+    if (m_op != FILTER_OP_MATCHES) {
+        return;
+    }
+
+    if (m_threshold.get_dtype() != DTYPE_STR) {
+        PSP_COMPLAIN_AND_ABORT(
+            "Filter `matches` on column `" + m_colname
+            + "` requires a string pattern"
+        );
+    }
+
+    m_regex = std::make_shared<RE2>(m_threshold.to_string(), RE2::Quiet);
+    if (!m_regex->ok()) {
+        PSP_COMPLAIN_AND_ABORT(
+            "Invalid regex `" + m_threshold.to_string()
+            + "` in `matches` filter on column `" + m_colname
+            + "`: " + m_regex->error()
+        );
+    }
 }
 
 void
@@ -74,7 +101,10 @@ t_fterm::get_expr() const {
         case FILTER_OP_GTEQ:
         case FILTER_OP_EQ:
         case FILTER_OP_NE:
-        case FILTER_OP_CONTAINS: {
+        case FILTER_OP_CONTAINS:
+        case FILTER_OP_NOT_CONTAINS:
+        case FILTER_OP_ICONTAINS:
+        case FILTER_OP_MATCHES: {
             ss << filter_op_to_str(m_op) << " ";
             ss << m_threshold.to_string(true);
         } break;
@@ -744,6 +744,19 @@ t_gnode::mapping_size() const {
     return m_gstate->mapping_size();
 }
 
+t_uindex
+t_gnode::mapping_size(const std::vector<t_fterm>& fterms) const {
+    auto table = m_gstate->get_table();
+    auto mask = table->filter_cpp(FILTER_OP_AND, fterms);
+
+    // The master table also holds the rows which have been removed.
+    if (m_gstate->mapping_size() != table->size()) {
+        mask &= m_gstate->get_cpp_mask();
+    }
+
+    return mask.count();
+}
+
 t_data_table*
 t_gnode::_get_otable(t_uindex port_id) {
     PSP_TRACE_SENTINEL();
@@ -1279,6 +1292,7 @@ t_gnode::_compute_expressions(
     t_expression_vocab& expression_vocab = *(m_expression_vocab);
     t_regex_mapping& expression_regex_mapping = *(m_expression_regex_mapping);
 
+    m_reset_window_contexts.clear();
     for (const auto& iter : m_contexts) {
         const t_ctx_handle& ctxh = iter.second;
 
@@ -1297,6 +1311,12 @@ t_gnode::_compute_expressions(
                     expression_vocab,
                     expression_regex_mapping
                 );
+
+                if (ctx->get_config().has_windows()) {
+                    _update_window_context(
+                        ctx, iter.first, flattened, prev, existed
+                    );
+                }
             } break;
             case ONE_SIDED_CONTEXT: {
                 auto* ctx = static_cast<t_ctx1*>(ctxh.m_ctx);
@@ -1312,6 +1332,12 @@ t_gnode::_compute_expressions(
                     expression_vocab,
                     expression_regex_mapping
                 );
+
+                if (ctx->get_config().has_windows()) {
+                    _update_window_context(
+                        ctx, iter.first, flattened, prev, existed
+                    );
+                }
             } break;
             case ZERO_SIDED_CONTEXT: {
                 auto* ctx = static_cast<t_ctx0*>(ctxh.m_ctx);
@@ -1327,6 +1353,12 @@ t_gnode::_compute_expressions(
                     expression_vocab,
                     expression_regex_mapping
                 );
+
+                if (ctx->get_config().has_windows()) {
+                    _update_window_context(
+                        ctx, iter.first, flattened, prev, existed
+                    );
+                }
             } break;
             case GROUPED_PKEY_CONTEXT: {
                 auto* ctx = static_cast<t_ctx_grouped_pkey*>(ctxh.m_ctx);
@@ -1342,6 +1374,12 @@ t_gnode::_compute_expressions(
                     expression_vocab,
                     expression_regex_mapping
                 );
+
+                if (ctx->get_config().has_windows()) {
+                    _update_window_context(
+                        ctx, iter.first, flattened, prev, existed
+                    );
+                }
             } break;
             case UNIT_CONTEXT:
                 break;
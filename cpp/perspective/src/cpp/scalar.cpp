@@ -1597,9 +1597,14 @@ t_tscalar::cmp(t_filter_op op, const t_tscalar& other) const {
         case FILTER_OP_ENDS_WITH: {
             return value.ends_with(other);
         } break;
-        case FILTER_OP_CONTAINS: {
+        // `contains` already ignores case, so `icontains` is an alias of it.
+        case FILTER_OP_CONTAINS:
+        case FILTER_OP_ICONTAINS: {
             return value.contains(other);
         } break;
+        case FILTER_OP_NOT_CONTAINS: {
+            return m_status == STATUS_VALID && !value.contains(other);
+        } break;
         case FILTER_OP_IS_NULL: {
             return m_status != STATUS_VALID;
         } break;
@@ -22,18 +22,35 @@
 #include "perspective/sparse_tree.h"
 #include "perspective/table.h"
 #include "perspective/time.h"
+#include "perspective/value_format.h"
 #include "perspective/view.h"
 #include "perspective/view_config.h"
+#include "perspective/window.h"
+#include "rapidjson/document.h"
+#include "rapidjson/stringbuffer.h"
+#include "rapidjson/writer.h"
 #include "re2/re2.h"
+#include <algorithm>
+#include <array>
+#include <arrow/api.h>
+#include <arrow/csv/writer.h>
+#include <arrow/io/memory.h>
+#include <arrow/ipc/reader.h>
+#include <arrow/ipc/writer.h>
 #include <chrono>
 #include <cstdint>
 #include <cstring>
+#include <date/date.h>
 #include <limits>
+#include <map>
 #include <memory>
+#include <optional>
 #include <perspective/server.h>
 #include <re2/stringpiece.h>
+#include <sstream>
 #include <string>
 #include <tsl/hopscotch_map.h>
+#include <tsl/hopscotch_set.h>
 #include <tsl/ordered_map.h>
 #include <vector>
 #include <ctime>
@@ -42,8 +59,20 @@
 #include <sys/resource.h>
 #endif
 
+// Native builds may call the server from several threads at once, for
+// tables which are locked independently by the caller, so `ServerResources`
+// must lock its maps even without `PSP_PARALLEL_FOR`. These are distinct from
+// `PSP_READ_LOCK`/`PSP_WRITE_LOCK`, which the engine uses for its own locks.
+#if defined(PSP_PARALLEL_FOR) || defined(PSP_SERVER_THREADSAFE)
+#define PSP_SERVER_READ_LOCK(X) std::shared_lock<std::shared_mutex> _lock(X);
+#define PSP_SERVER_WRITE_LOCK(X) std::unique_lock<std::shared_mutex> _lock(X);
+#else
+#define PSP_SERVER_READ_LOCK(X)
+#define PSP_SERVER_WRITE_LOCK(X)
+#endif
+
 namespace perspective {
-std::uint32_t server::ProtoServer::m_client_id = 1;
+std::atomic<std::uint32_t> server::ProtoServer::m_client_id{1};
 
 template <>
 std::shared_ptr<t_ctxunit>
@@ -87,6 +116,8 @@ make_context(
     auto expressions = view_config->get_used_expressions();
 
     auto cfg = t_config(columns, fterm, filter_op, expressions);
+    cfg.set_session_fterms(view_config->get_session_fterm());
+    cfg.set_column_masks(view_config->get_column_masks());
     auto ctx0 = std::make_shared<t_ctx0>(*schema, cfg);
     ctx0->init();
     ctx0->sort_by(sortspec);
@@ -119,6 +150,8 @@ make_context(
     auto expressions = view_config->get_used_expressions();
 
     auto cfg = t_config(row_pivots, aggspecs, fterm, filter_op, expressions);
+    cfg.set_session_fterms(view_config->get_session_fterm());
+    cfg.set_row_totals(view_config->get_row_totals());
     auto ctx1 = std::make_shared<t_ctx1>(*schema, cfg);
 
     ctx1->init();
@@ -174,6 +207,9 @@ make_context(
         expressions,
         column_only
     );
+    cfg.set_session_fterms(view_config->get_session_fterm());
+    cfg.set_row_totals(view_config->get_row_totals());
+    cfg.set_col_pivot_orders(view_config->get_col_pivot_orders());
     auto ctx2 = std::make_shared<t_ctx2>(*schema, cfg);
 
     ctx2->init();
@@ -373,12 +409,104 @@ parse_expression_strings(const F& column_expr) {
     return validated_exprs;
 }
 
+/**
+ * @brief Create a `t_computed_window` from a `window` entry of a view config,
+ * whose inputs must be columns of `schema`.
+ */
+static std::shared_ptr<t_computed_window>
+parse_window(
+    const std::string& name,
+    const proto::ViewConfig_Window& window,
+    const t_schema& schema,
+    const t_data_table* gstate_table
+) {
+    if (schema.has_column(name)) {
+        PSP_COMPLAIN_AND_ABORT(
+            "Window name conflicts with an existing column: " + name
+        );
+    }
+
+    auto check_column = [&](const std::string& column) {
+        if (!schema.has_column(column)) {
+            PSP_COMPLAIN_AND_ABORT("Window column not in schema: " + column);
+        }
+    };
+
+    auto function = str_to_window_function(window.function());
+    std::string column;
+    t_dtype column_dtype = DTYPE_NONE;
+    if (window_function_has_column(function)) {
+        if (!window.has_column()) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Window `" + window.function() + "` requires a column: " + name
+            );
+        }
+
+        column = window.column();
+        check_column(column);
+        column_dtype = schema.get_dtype(column);
+        bool is_numeric_function = function == WINDOW_FUNCTION_SUM
+            || function == WINDOW_FUNCTION_MEAN
+            || function == WINDOW_FUNCTION_PERCENT_OF_PREVIOUS;
+
+        if (is_numeric_function && !is_numeric_type(column_dtype)) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Window `" + window.function()
+                + "` requires a numeric column: " + column
+            );
+        }
+    }
+
+    std::vector<std::string> partition_by;
+    for (const auto& col : window.partition_by()) {
+        check_column(col);
+        partition_by.push_back(col);
+    }
+
+    std::vector<std::pair<std::string, t_sorttype>> order_by;
+    for (const auto& sort : window.order_by()) {
+        check_column(sort.column());
+        auto sort_type = str_to_sorttype(sort_op_str_from_proto(sort.op()));
+        if (sort_type != SORTTYPE_ASCENDING
+            && sort_type != SORTTYPE_DESCENDING) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Window order must be `asc` or `desc`: " + sort.column()
+            );
+        }
+
+        order_by.emplace_back(sort.column(), sort_type);
+    }
+
+    std::optional<t_uindex> preceding;
+    if (window.has_preceding()) {
+        preceding = window.preceding();
+    }
+
+    std::optional<t_uindex> following;
+    if (window.has_following()) {
+        following = window.following();
+    }
+
+    return std::make_shared<t_computed_window>(
+        name,
+        function,
+        column,
+        partition_by,
+        order_by,
+        preceding,
+        following,
+        window.offset(),
+        gstate_table,
+        t_computed_window::get_output_dtype(function, column_dtype)
+    );
+}
+
 } // namespace perspective
 
 namespace perspective::server {
 void
 ServerResources::host_table(const t_id& id, std::shared_ptr<Table> table) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     m_tables.emplace(id, std::move(table));
 }
 
@@ -389,7 +517,7 @@ ServerResources::host_view(
     const t_id& table_id,
     std::shared_ptr<ErasedView> view
 ) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     m_view_to_table.emplace(id, table_id);
     m_table_to_view.emplace(table_id, id);
     m_views.emplace(id, std::move(view));
@@ -403,13 +531,13 @@ ServerResources::host_view(
 
 std::shared_ptr<Table>
 ServerResources::get_table(const t_id& id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     return m_tables.at(id);
 }
 
 std::vector<ServerResources::t_id>
 ServerResources::get_table_ids() {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     std::vector<t_id> vec;
     for (auto const& imap : m_tables) {
         vec.push_back(imap.first);
@@ -420,13 +548,13 @@ ServerResources::get_table_ids() {
 
 std::shared_ptr<Table>
 ServerResources::get_table_for_view(const t_id& view_id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     return m_tables.at(m_view_to_table.at(view_id));
 }
 
 ServerResources::t_id
 ServerResources::get_table_id_for_view(const t_id& view_id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     if (!m_view_to_table.contains(view_id)) {
         throw PerspectiveViewNotFoundException();
     }
@@ -436,7 +564,7 @@ ServerResources::get_table_id_for_view(const t_id& view_id) {
 
 std::vector<ServerResources::t_id>
 ServerResources::get_view_ids(const t_id& table_id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     std::vector<t_id> out;
     auto range = m_table_to_view.equal_range(table_id);
     for (auto it = range.first; it != range.second; ++it) {
@@ -447,7 +575,7 @@ ServerResources::get_view_ids(const t_id& table_id) {
 
 std::shared_ptr<ErasedView>
 ServerResources::get_view(const t_id& id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     if (!m_views.contains(id)) {
         throw PerspectiveViewNotFoundException();
     }
@@ -457,12 +585,12 @@ ServerResources::get_view(const t_id& id) {
 
 void
 ServerResources::delete_view(const std::uint32_t& client_id, const t_id& id) {
-    if (!m_view_to_table.contains(id)) {
-        throw PerspectiveViewNotFoundException();
-    }
-
     {
-        PSP_WRITE_LOCK(m_write_lock);
+        PSP_SERVER_WRITE_LOCK(m_write_lock);
+        if (!m_view_to_table.contains(id)) {
+            throw PerspectiveViewNotFoundException();
+        }
+
         auto table_id = m_view_to_table.at(id);
         if (m_views.find(id) != m_views.end()) {
             m_views.erase(id);
@@ -490,7 +618,7 @@ ServerResources::delete_view(const std::uint32_t& client_id, const t_id& id) {
 
 void
 ServerResources::delete_table(const t_id& id) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     if (m_tables.find(id) != m_tables.end()) {
         if (m_table_to_view.find(id) == m_table_to_view.end()) {
             m_tables.erase(id);
@@ -502,19 +630,19 @@ ServerResources::delete_table(const t_id& id) {
 
 void
 ServerResources::mark_table_dirty(const t_id& id) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     m_dirty_tables.insert(id);
 }
 
 void
 ServerResources::mark_table_clean(const t_id& id) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     m_dirty_tables.erase(id);
 }
 
 void
 ServerResources::mark_all_tables_clean() {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     m_dirty_tables.clear();
 }
 
@@ -522,7 +650,7 @@ void
 ServerResources::create_table_on_delete_sub(
     const t_id& table_id, Subscription sub_id
 ) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     if (!m_table_on_delete_subs.contains(table_id)) {
         m_table_on_delete_subs[table_id] = {sub_id};
     } else {
@@ -532,7 +660,7 @@ ServerResources::create_table_on_delete_sub(
 
 std::vector<Subscription>
 ServerResources::get_table_on_delete_sub(const t_id& table_id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     if (!m_table_on_delete_subs.contains(table_id)) {
         return {};
     }
@@ -545,14 +673,11 @@ ServerResources::remove_table_on_delete_sub(
     const std::uint32_t sub_id,
     const std::uint32_t client_id
 ) {
-    {
-        PSP_WRITE_LOCK(m_write_lock);
-        if (!m_table_on_delete_subs.contains(table_id)) {
-            m_table_on_delete_subs[table_id] = {};
-        }
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
+    if (!m_table_on_delete_subs.contains(table_id)) {
+        m_table_on_delete_subs[table_id] = {};
     }
 
-    PSP_READ_LOCK(m_write_lock);
     auto& subs = m_table_on_delete_subs.at(table_id);
     for (auto sub = subs.begin(); sub != subs.end();) {
         if (sub->id == sub_id && sub->client_id == client_id) {
@@ -568,7 +693,7 @@ void
 ServerResources::create_view_on_delete_sub(
     const t_id& view_id, Subscription sub
 ) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     if (!m_view_on_delete_subs.contains(view_id)) {
         m_view_on_delete_subs[view_id] = {sub};
     } else {
@@ -578,7 +703,7 @@ ServerResources::create_view_on_delete_sub(
 
 std::vector<Subscription>
 ServerResources::get_view_on_delete_sub(const t_id& view_id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     if (!m_view_on_delete_subs.contains(view_id)) {
         return {};
     }
@@ -592,7 +717,7 @@ ServerResources::remove_view_on_delete_sub(
     const std::uint32_t sub_id,
     const std::uint32_t client_id
 ) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     if (!m_view_on_delete_subs.contains(view_id)) {
         m_view_on_delete_subs[view_id] = {};
     }
@@ -612,7 +737,7 @@ void
 ServerResources::create_view_on_update_sub(
     const t_id& view_id, Subscription sub
 ) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     if (!m_view_on_update_subs.contains(view_id)) {
         m_view_on_update_subs[view_id] = {sub};
     } else {
@@ -622,7 +747,7 @@ ServerResources::create_view_on_update_sub(
 
 std::vector<Subscription>
 ServerResources::get_view_on_update_sub(const t_id& view_id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     if (!m_view_on_update_subs.contains(view_id)) {
         return {};
     }
@@ -630,15 +755,44 @@ ServerResources::get_view_on_update_sub(const t_id& view_id) {
     return m_view_on_update_subs.at(view_id);
 }
 
+void
+ServerResources::set_view_on_update_sub(
+    const t_id& view_id, const Subscription& sub
+) {
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
+    if (!m_view_on_update_subs.contains(view_id)) {
+        return;
+    }
+
+    for (auto& existing : m_view_on_update_subs[view_id]) {
+        if (existing.id == sub.id && existing.client_id == sub.client_id) {
+            existing = sub;
+        }
+    }
+}
+
+std::vector<ServerResources::t_id>
+ServerResources::get_view_ids_with_on_update_sub() {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    std::vector<t_id> out;
+    for (const auto& [view_id, subs] : m_view_on_update_subs) {
+        if (!subs.empty()) {
+            out.push_back(view_id);
+        }
+    }
+
+    return out;
+}
+
 void
 ServerResources::drop_view_on_update_sub(const t_id& view_id) {
-    PSP_WRITE_LOCK(m_write_lock);
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     m_view_on_update_subs.erase(view_id);
 }
 
 std::vector<std::pair<std::shared_ptr<Table>, const ServerResources::t_id>>
 ServerResources::get_dirty_tables() {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     std::vector<std::pair<std::shared_ptr<Table>, const t_id>> out;
     for (const auto& id : m_dirty_tables) {
         out.emplace_back(m_tables[id], id);
@@ -648,25 +802,231 @@ ServerResources::get_dirty_tables() {
 
 bool
 ServerResources::is_table_dirty(const t_id& id) {
-    PSP_READ_LOCK(m_write_lock);
+    PSP_SERVER_READ_LOCK(m_write_lock);
     return m_dirty_tables.contains(id);
 }
 
 void
 ServerResources::drop_client(const std::uint32_t client_id) {
-    if (m_client_to_view.contains(client_id)) {
+    // Load-bearing copy
+    for (const auto& view_id : get_session_view_ids(client_id)) {
+        delete_view(client_id, view_id);
+    }
+
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
+    m_session_update_throttle.erase(client_id);
+    m_sessions.erase(client_id);
+}
+
+void
+ServerResources::set_session_update_throttle(
+    std::uint32_t client_id, std::uint32_t throttle_ms
+) {
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
+    m_session_update_throttle[client_id] = throttle_ms;
+}
+
+std::uint32_t
+ServerResources::get_session_update_throttle(std::uint32_t client_id) {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    auto throttle = m_session_update_throttle.find(client_id);
+    if (throttle == m_session_update_throttle.end()) {
+        return 0;
+    }
+
+    return throttle->second;
+}
+
+// The upper bounds, in seconds, of the `RequestLatency` buckets.
+static constexpr std::array<double, 12> REQUEST_LATENCY_BOUNDS = {
+    0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5, 1, 5
+};
+
+void
+ServerResources::host_session(std::uint32_t client_id) {
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
+    m_sessions.insert(client_id);
+}
+
+std::vector<std::uint32_t>
+ServerResources::get_session_ids() {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    return {m_sessions.begin(), m_sessions.end()};
+}
+
+std::uint32_t
+ServerResources::get_session_num_views(std::uint32_t client_id) {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    auto views = m_client_to_view.find(client_id);
+    if (views == m_client_to_view.end()) {
+        return 0;
+    }
+
+    return views->second.size();
+}
+
+std::uint32_t
+ServerResources::get_session_num_subscriptions(std::uint32_t client_id) {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    std::uint32_t count = 0;
+    for (const auto* subs_map :
+         {&m_view_on_update_subs,
+          &m_view_on_delete_subs,
+          &m_table_on_delete_subs}) {
+        for (const auto& [_, subs] : *subs_map) {
+            count += std::count_if(
+                subs.begin(),
+                subs.end(),
+                [client_id](const Subscription& sub) {
+                    return sub.client_id == client_id;
+                }
+            );
+        }
+    }
+
+    return count;
+}
 
-        // Load-bearing copy
-        std::vector<t_id> remove = m_client_to_view[client_id];
-        for (const auto& view_id : remove) {
-            delete_view(client_id, view_id);
+std::uint32_t
+ServerResources::get_num_dirty_tables() {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    return m_dirty_tables.size();
+}
+
+void
+ServerResources::record_request_latency(
+    const std::string& request_type, double seconds
+) {
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
+    auto& latency = m_request_latency[request_type];
+    if (latency.buckets.empty()) {
+        latency.buckets.resize(REQUEST_LATENCY_BOUNDS.size() + 1);
+    }
+
+    auto bucket = std::lower_bound(
+        REQUEST_LATENCY_BOUNDS.begin(), REQUEST_LATENCY_BOUNDS.end(), seconds
+    );
+
+    latency.buckets[bucket - REQUEST_LATENCY_BOUNDS.begin()]++;
+    latency.count++;
+    latency.sum += seconds;
+}
+
+std::map<std::string, RequestLatency>
+ServerResources::get_request_latency() {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    return m_request_latency;
+}
+
+bool
+ServerResources::has_session(std::uint32_t client_id) {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    return m_sessions.contains(client_id);
+}
+
+std::vector<ServerResources::t_id>
+ServerResources::get_session_view_ids(std::uint32_t client_id) {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    auto views = m_client_to_view.find(client_id);
+    if (views == m_client_to_view.end()) {
+        return {};
+    }
+
+    return views->second;
+}
+
+std::optional<std::uint32_t>
+ServerResources::get_client_id_for_view(const t_id& id) {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    for (const auto& [client_id, view_ids] : m_client_to_view) {
+        if (std::find(view_ids.begin(), view_ids.end(), id)
+            != view_ids.end()) {
+            return client_id;
+        }
+    }
+
+    return std::nullopt;
+}
+
+std::uint32_t
+ServerResources::get_view_num_subscriptions(const t_id& id) {
+    PSP_SERVER_READ_LOCK(m_write_lock);
+    std::uint32_t count = 0;
+    for (const auto* subs_map :
+         {&m_view_on_update_subs, &m_view_on_delete_subs}) {
+        auto subs = subs_map->find(id);
+        if (subs != subs_map->end()) {
+            count += subs->second.size();
         }
     }
+
+    return count;
 }
 
 std::uint32_t
 ProtoServer::new_session() {
-    return m_client_id++;
+    auto client_id = m_client_id++;
+    m_resources.host_session(client_id);
+    return client_id;
+}
+
+void
+ProtoServer::set_session_update_throttle(
+    std::uint32_t client_id, std::uint32_t throttle_ms
+) {
+    m_resources.set_session_update_throttle(client_id, throttle_ms);
+}
+
+std::uint32_t
+ProtoServer::get_session_num_views(std::uint32_t client_id) {
+    return m_resources.get_session_num_views(client_id);
+}
+
+std::uint32_t
+ProtoServer::get_session_num_subscriptions(std::uint32_t client_id) {
+    return m_resources.get_session_num_subscriptions(client_id);
+}
+
+std::optional<std::uint32_t>
+ProtoServer::get_view_num_rows(const ServerResources::t_id& view_id) {
+    try {
+        return m_resources.get_view(view_id)->num_rows();
+    } catch (const PerspectiveViewNotFoundException&) {
+        return std::nullopt;
+    }
+}
+
+std::optional<std::uint32_t>
+ProtoServer::get_view_session_id(const ServerResources::t_id& view_id) {
+    return m_resources.get_client_id_for_view(view_id);
+}
+
+std::optional<ServerResources::t_id>
+ProtoServer::get_view_table_id(const ServerResources::t_id& view_id) {
+    try {
+        return m_resources.get_table_id_for_view(view_id);
+    } catch (const PerspectiveViewNotFoundException&) {
+        return std::nullopt;
+    }
+}
+
+std::vector<ProtoServerResp<std::string>>
+ProtoServer::process_table(const ServerResources::t_id& table_id) {
+    std::vector<ProtoServerResp<Response>> resp_envs;
+    if (m_resources.is_table_dirty(table_id)) {
+        auto table = m_resources.get_table(table_id);
+        _process_table(table, table_id, resp_envs);
+    }
+
+    std::vector<ProtoServerResp<std::string>> out;
+    for (auto& resp : resp_envs) {
+        ProtoServerResp<std::string> str_resp;
+        str_resp.data = resp.data.SerializeAsString();
+        str_resp.client_id = resp.client_id;
+        out.emplace_back(std::move(str_resp));
+    }
+
+    return out;
 }
 
 void
@@ -674,6 +1034,107 @@ ProtoServer::close_session(const std::uint32_t client_id) {
     m_resources.drop_client(client_id);
 }
 
+// The name of a request's `client_req` field, e.g. `table_update_req`.
+static const char*
+request_type_name(const proto::Request::ClientReqCase proto_case) {
+    using ReqCase = proto::Request::ClientReqCase;
+
+    switch (proto_case) {
+        case ReqCase::kGetFeaturesReq:
+            return "get_features_req";
+        case ReqCase::kGetHostedTablesReq:
+            return "get_hosted_tables_req";
+        case ReqCase::kTableMakePortReq:
+            return "table_make_port_req";
+        case ReqCase::kTableMakeViewReq:
+            return "table_make_view_req";
+        case ReqCase::kTableSchemaReq:
+            return "table_schema_req";
+        case ReqCase::kTableSizeReq:
+            return "table_size_req";
+        case ReqCase::kTableValidateExprReq:
+            return "table_validate_expr_req";
+        case ReqCase::kViewColumnPathsReq:
+            return "view_column_paths_req";
+        case ReqCase::kViewDeleteReq:
+            return "view_delete_req";
+        case ReqCase::kViewDimensionsReq:
+            return "view_dimensions_req";
+        case ReqCase::kViewExpressionSchemaReq:
+            return "view_expression_schema_req";
+        case ReqCase::kViewGetConfigReq:
+            return "view_get_config_req";
+        case ReqCase::kViewSchemaReq:
+            return "view_schema_req";
+        case ReqCase::kViewToArrowReq:
+            return "view_to_arrow_req";
+        case ReqCase::kServerSystemInfoReq:
+            return "server_system_info_req";
+        case ReqCase::kViewCollapseReq:
+            return "view_collapse_req";
+        case ReqCase::kViewExpandReq:
+            return "view_expand_req";
+        case ReqCase::kViewGetMinMaxReq:
+            return "view_get_min_max_req";
+        case ReqCase::kViewOnUpdateReq:
+            return "view_on_update_req";
+        case ReqCase::kViewRemoveOnUpdateReq:
+            return "view_remove_on_update_req";
+        case ReqCase::kViewSetDepthReq:
+            return "view_set_depth_req";
+        case ReqCase::kViewToColumnsStringReq:
+            return "view_to_columns_string_req";
+        case ReqCase::kViewToCsvReq:
+            return "view_to_csv_req";
+        case ReqCase::kViewToRowsStringReq:
+            return "view_to_rows_string_req";
+        case ReqCase::kViewToNdjsonStringReq:
+            return "view_to_ndjson_string_req";
+        case ReqCase::kViewGetExpansionStateReq:
+            return "view_get_expansion_state_req";
+        case ReqCase::kViewSetExpansionStateReq:
+            return "view_set_expansion_state_req";
+        case ReqCase::kViewFindRowByPathReq:
+            return "view_find_row_by_path_req";
+        case ReqCase::kViewFindRowByIndexReq:
+            return "view_find_row_by_index_req";
+        case ReqCase::kViewGetUnderlyingRowsReq:
+            return "view_get_underlying_rows_req";
+        case ReqCase::kViewSetUpdateViewportReq:
+            return "view_set_update_viewport_req";
+        case ReqCase::kMakeTableReq:
+            return "make_table_req";
+        case ReqCase::kTableDeleteReq:
+            return "table_delete_req";
+        case ReqCase::kTableOnDeleteReq:
+            return "table_on_delete_req";
+        case ReqCase::kTableRemoveDeleteReq:
+            return "table_remove_delete_req";
+        case ReqCase::kTableRemoveReq:
+            return "table_remove_req";
+        case ReqCase::kTableReplaceReq:
+            return "table_replace_req";
+        case ReqCase::kTableUpdateReq:
+            return "table_update_req";
+        case ReqCase::kViewOnDeleteReq:
+            return "view_on_delete_req";
+        case ReqCase::kViewRemoveDeleteReq:
+            return "view_remove_delete_req";
+        case ReqCase::kGetHostedViewsReq:
+            return "get_hosted_views_req";
+        case ReqCase::kGetSessionsReq:
+            return "get_sessions_req";
+        case ReqCase::kDeleteHostedViewReq:
+            return "delete_hosted_view_req";
+        case ReqCase::kCloseSessionReq:
+            return "close_session_req";
+        case proto::Request::CLIENT_REQ_NOT_SET:
+            return "unknown";
+    }
+
+    return "unknown";
+}
+
 std::vector<ProtoServerResp<std::string>>
 ProtoServer::handle_request(
     std::uint32_t client_id, const std::string_view& data
@@ -685,6 +1146,8 @@ ProtoServer::handle_request(
 
     auto msg_id = req_env.msg_id();
     auto entity_id = req_env.entity_id();
+    auto req_case = req_env.client_req_case();
+    auto start = std::chrono::steady_clock::now();
     try {
         auto resp_msg = _handle_request(client_id, std::move(req_env));
         for (auto& resp : resp_msg) {
@@ -730,6 +1193,13 @@ ProtoServer::handle_request(
         serialized_responses.emplace_back(std::move(str_resp));
     }
 
+    std::chrono::duration<double> elapsed =
+        std::chrono::steady_clock::now() - start;
+
+    m_resources.record_request_latency(
+        request_type_name(req_case), elapsed.count()
+    );
+
     return serialized_responses;
 }
 
@@ -869,6 +1339,12 @@ needs_poll(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewCollapseReq:
         case ReqCase::kViewExpandReq:
         case ReqCase::kViewSetDepthReq:
+        case ReqCase::kViewGetExpansionStateReq:
+        case ReqCase::kViewSetExpansionStateReq:
+        case ReqCase::kViewFindRowByPathReq:
+        case ReqCase::kViewFindRowByIndexReq:
+        case ReqCase::kViewGetUnderlyingRowsReq:
+        case ReqCase::kViewSetUpdateViewportReq:
             return true;
         case ReqCase::kTableOnDeleteReq:
         case ReqCase::kViewOnDeleteReq:
@@ -885,6 +1361,10 @@ needs_poll(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewRemoveOnUpdateReq:
         case ReqCase::kServerSystemInfoReq:
         case ReqCase::kGetFeaturesReq:
+        case ReqCase::kGetHostedViewsReq:
+        case ReqCase::kGetSessionsReq:
+        case ReqCase::kDeleteHostedViewReq:
+        case ReqCase::kCloseSessionReq:
             return false;
         case proto::Request::CLIENT_REQ_NOT_SET:
             throw std::runtime_error("Unhandled request type 2");
@@ -909,6 +1389,9 @@ entity_type_is_table(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kGetHostedTablesReq:
         case ReqCase::kServerSystemInfoReq:
         case ReqCase::kGetFeaturesReq:
+        case ReqCase::kGetHostedViewsReq:
+        case ReqCase::kGetSessionsReq:
+        case ReqCase::kCloseSessionReq:
         case ReqCase::kTableReplaceReq:
         case ReqCase::kTableDeleteReq:
         case ReqCase::kTableMakeViewReq:
@@ -927,11 +1410,18 @@ entity_type_is_table(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewCollapseReq:
         case ReqCase::kViewExpandReq:
         case ReqCase::kViewSetDepthReq:
+        case ReqCase::kViewGetExpansionStateReq:
+        case ReqCase::kViewSetExpansionStateReq:
+        case ReqCase::kViewFindRowByPathReq:
+        case ReqCase::kViewFindRowByIndexReq:
+        case ReqCase::kViewGetUnderlyingRowsReq:
+        case ReqCase::kViewSetUpdateViewportReq:
         case ReqCase::kViewGetConfigReq:
         case ReqCase::kViewColumnPathsReq:
         case ReqCase::kViewDeleteReq:
         case ReqCase::kViewExpressionSchemaReq:
         case ReqCase::kViewRemoveOnUpdateReq:
+        case ReqCase::kDeleteHostedViewReq:
             return false;
         case proto::Request::CLIENT_REQ_NOT_SET:
             throw std::runtime_error("Unhandled request type 2");
@@ -1011,6 +1501,106 @@ calculate_num_hidden(const ErasedView& view, const t_view_config& config) {
     return num_hidden;
 }
 
+// Serializes a row path element with the same representation `to_json`
+// uses for `__ROW_PATH__`, so the paths returned by `get_expansion_state` can
+// be compared against values read from the view's data.
+static void
+row_path_scalar_to_proto(const t_tscalar& scalar, proto::Scalar* out) {
+    if (!scalar.is_valid() || scalar.is_nan()) {
+        out->set_null(::google::protobuf::NullValue::NULL_VALUE);
+        return;
+    }
+
+    switch (scalar.get_dtype()) {
+        case DTYPE_BOOL:
+            out->set_bool_(scalar.get<bool>());
+            break;
+        case DTYPE_UINT8:
+        case DTYPE_INT8:
+            out->set_float_((double)scalar.get<std::int8_t>());
+            break;
+        case DTYPE_UINT16:
+        case DTYPE_INT16:
+            out->set_float_((double)scalar.get<std::int16_t>());
+            break;
+        case DTYPE_UINT32:
+        case DTYPE_INT32:
+            out->set_float_((double)scalar.get<std::int32_t>());
+            break;
+        case DTYPE_UINT64:
+        case DTYPE_INT64:
+        case DTYPE_TIME:
+            out->set_float_((double)scalar.get<std::int64_t>());
+            break;
+        case DTYPE_FLOAT32:
+            out->set_float_(scalar.get<float>());
+            break;
+        case DTYPE_FLOAT64:
+            out->set_float_(scalar.get<double>());
+            break;
+        case DTYPE_STR:
+            out->set_string(scalar.get<const char*>());
+            break;
+        case DTYPE_DATE: {
+            tm t = scalar.get<t_date>().get_tm();
+            out->set_float_((double)mktime(&t) * 1000);
+            break;
+        }
+        default:
+            out->set_null(::google::protobuf::NullValue::NULL_VALUE);
+            break;
+    }
+}
+
+static bool
+proto_scalar_eq(const proto::Scalar& a, const proto::Scalar& b) {
+    if (a.scalar_case() != b.scalar_case()) {
+        return false;
+    }
+
+    switch (a.scalar_case()) {
+        case proto::Scalar::kBool:
+            return a.bool_() == b.bool_();
+        case proto::Scalar::kFloat:
+            return a.float_() == b.float_();
+        case proto::Scalar::kString:
+            return a.string() == b.string();
+        case proto::Scalar::kNull:
+        case proto::Scalar::SCALAR_NOT_SET:
+            return true;
+    }
+
+    return false;
+}
+
+// Returns the index of the visible row whose row path matches `path`, if any.
+static std::optional<t_uindex>
+find_row_by_path(
+    const ErasedView& view,
+    const google::protobuf::RepeatedPtrField<proto::Scalar>& path
+) {
+    const auto num_rows = view.num_rows();
+    for (t_uindex r = 0; r < num_rows; ++r) {
+        const auto row_path = view.get_row_path(r);
+        if (row_path.size() != static_cast<t_uindex>(path.size())) {
+            continue;
+        }
+
+        bool matches = true;
+        for (std::size_t i = 0; i < row_path.size() && matches; ++i) {
+            proto::Scalar scalar;
+            row_path_scalar_to_proto(row_path[i], &scalar);
+            matches = proto_scalar_eq(scalar, path[static_cast<int>(i)]);
+        }
+
+        if (matches) {
+            return r;
+        }
+    }
+
+    return std::nullopt;
+}
+
 template <typename A>
 static t_tscalar
 coerce_to(const t_dtype dtype, const A& val) {
@@ -1150,24 +1740,1211 @@ coerce_to(const t_dtype dtype, const A& val) {
     }
 }
 
-std::vector<ProtoServerResp<ProtoServer::Response>>
-ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
-    static bool is_init_expr = false;
-    if (!is_init_expr) {
-        t_computed_expression_parser::init();
-        is_init_expr = true;
+// Converts a client-provided index value to a primary key of `dtype`, the type
+// of the table's index column (or `int32` row numbers when it has none).
+static t_tscalar
+proto_to_pkey(const t_dtype dtype, const proto::Scalar& scalar) {
+    switch (scalar.scalar_case()) {
+        case proto::Scalar::kFloat:
+            if (dtype == DTYPE_INT32) {
+                return t_tscalar(static_cast<std::int32_t>(scalar.float_()));
+            }
+
+            return coerce_to(dtype, scalar.float_());
+        case proto::Scalar::kString:
+            return coerce_to(dtype, scalar.string().c_str());
+        case proto::Scalar::kBool:
+            return coerce_to(dtype, scalar.bool_());
+        case proto::Scalar::kNull:
+        case proto::Scalar::SCALAR_NOT_SET:
+            break;
     }
 
-    std::vector<ProtoServerResp<ProtoServer::Response>> proto_resp;
-    // proto::Response resp_env;
+    return mknone();
+}
 
-    auto msg_id = req.msg_id();
-    auto entity_id = req.entity_id();
+// Writes a filter term selecting the source rows whose `column` is `value`,
+// encoded such that `kTableMakeViewReq` parses it back to the same scalar.
+static void
+underlying_filter_to_proto(
+    const std::string& column,
+    const t_tscalar& value,
+    proto::ViewConfig_Filter* out
+) {
+    out->set_column(column);
+    if (!value.is_valid() || value.is_nan()) {
+        out->set_op("is null");
+        return;
+    }
 
-    auto push_resp = [&](Response&& resp) {
-        resp.set_msg_id(msg_id);
-        resp.set_entity_id(entity_id);
-        ProtoServerResp<ProtoServer::Response> resp2;
+    out->set_op("==");
+    auto* scalar = out->add_value();
+    switch (value.get_dtype()) {
+        case DTYPE_UINT8:
+        case DTYPE_INT8:
+        case DTYPE_UINT16:
+        case DTYPE_INT16:
+            scalar->set_string(value.to_string());
+            break;
+        case DTYPE_DATE:
+            scalar->set_string(value.get<t_date>().str());
+            break;
+        default:
+            row_path_scalar_to_proto(value, scalar);
+            break;
+    }
+}
+
+// Interns the string arguments of `filters`, which must precede
+// `parse_filters()` so that the parsed scalars remain valid.
+static void
+intern_filter_strings(
+    const google::protobuf::RepeatedPtrField<proto::ViewConfig_Filter>&
+        filters,
+    t_vocab& vocab
+) {
+    for (const auto& f : filters) {
+        for (const auto& arg : f.value()) {
+            switch (arg.scalar_case()) {
+                case proto::Scalar::kString: {
+#ifdef PSP_SSO_SCALAR
+                    if (!t_tscalar::can_store_inplace(arg.string())) {
+                        vocab.get_interned(arg.string());
+                    }
+#else
+                    vocab.get_interned(arg.string());
+#endif
+                    break;
+                }
+                case proto::Scalar::kBool:
+                case proto::Scalar::kFloat:
+                case proto::Scalar::kNull:
+                case proto::Scalar::SCALAR_NOT_SET:
+                    break;
+            }
+        }
+    }
+}
+
+// Converts `filters` to the `t_view_config` filter terms, coercing each
+// argument to the type of its column in `schema`.
+static std::vector<std::tuple<std::string, std::string, std::vector<t_tscalar>>>
+parse_filters(
+    const google::protobuf::RepeatedPtrField<proto::ViewConfig_Filter>&
+        filters,
+    const t_schema& schema,
+    t_vocab& vocab
+) {
+    std::vector<std::tuple<std::string, std::string, std::vector<t_tscalar>>>
+        filter;
+    filter.reserve(filters.size());
+    for (const auto& f : filters) {
+        std::vector<t_tscalar> args;
+        args.reserve(f.value().size());
+        for (const auto& arg : f.value()) {
+            t_tscalar a;
+            a.clear();
+            switch (arg.scalar_case()) {
+                case proto::Scalar::kBool: {
+                    a.set(arg.bool_());
+                    args.push_back(a);
+                    break;
+                }
+                case proto::Scalar::kFloat: {
+                    a = coerce_to(schema.get_dtype(f.column()), arg.float_());
+                    args.push_back(a);
+                    break;
+                }
+                case proto::Scalar::kString: {
+                    if (!schema.has_column(f.column())) {
+                        PSP_COMPLAIN_AND_ABORT(
+                            "Filter column not in schema: " + f.column()
+                        );
+                    }
+
+#ifdef PSP_SSO_SCALAR
+                    if (!t_tscalar::can_store_inplace(arg.string())) {
+#endif
+                        a = coerce_to(
+                            schema.get_dtype(f.column()),
+                            vocab.unintern_c(vocab.get_interned(arg.string()))
+                        );
+#ifdef PSP_SSO_SCALAR
+                    } else {
+
+                        a = coerce_to(
+                            schema.get_dtype(f.column()), arg.string().c_str()
+                        );
+                    }
+#endif
+                    args.push_back(a);
+                    break;
+                }
+                case proto::Scalar::kNull:
+                    a.set(t_none());
+                    args.push_back(a);
+                    break;
+                case proto::Scalar::SCALAR_NOT_SET:
+                    PSP_COMPLAIN_AND_ABORT(
+                        "Filter scalar type not implemented: "
+                        + std::to_string(arg.scalar_case())
+                    )
+                    break;
+            }
+        }
+
+        filter.emplace_back(f.column(), f.op(), args);
+    }
+
+    return filter;
+}
+
+// The columns of a `Table` a session may not read, per its
+// `proto::ColumnPolicy`.
+struct ColumnRestrictions {
+    tsl::hopscotch_set<std::string> hidden;
+    std::map<std::string, t_column_mask> masked;
+
+    bool
+    empty() const {
+        return hidden.empty() && masked.empty();
+    }
+};
+
+static ColumnRestrictions
+parse_column_policy(const proto::ColumnPolicy& policy) {
+    ColumnRestrictions restrictions;
+    restrictions.hidden.insert(policy.hidden().begin(), policy.hidden().end());
+    for (const auto& [column, mask] : policy.masked()) {
+        switch (mask) {
+            case proto::COLUMN_MASK_HASH:
+                restrictions.masked[column] = COLUMN_MASK_HASH;
+                break;
+            case proto::COLUMN_MASK_REDACT:
+            default:
+                restrictions.masked[column] = COLUMN_MASK_REDACT;
+                break;
+        }
+    }
+
+    return restrictions;
+}
+
+// The reason an expression which reads `column_id_map` may not be computed
+// under `restrictions`, or an empty string if it may.
+static std::string
+restricted_expression_error(
+    const tsl::hopscotch_map<std::string, std::string>& column_id_map,
+    const ColumnRestrictions& restrictions
+) {
+    for (const auto& [_, column] : column_id_map) {
+        if (restrictions.hidden.contains(column)) {
+            return "Value Error - Input column \"" + column
+                + "\" does not exist.";
+        }
+
+        if (restrictions.masked.count(column) > 0) {
+            return "Value Error - Input column \"" + column + "\" is masked.";
+        }
+    }
+
+    return "";
+}
+
+// Aborts if `cfg` names a column which `restrictions` hides, or reads the
+// values of a column it masks other than to display or `count` them.
+static void
+check_column_restrictions(
+    const proto::ViewConfig& cfg,
+    const std::vector<ValidatedExpr>& exprs,
+    const ColumnRestrictions& restrictions
+) {
+    auto check = [&](const std::string& column, bool displayed) {
+        if (restrictions.hidden.contains(column)) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Invalid column '" + column + "' found in View config."
+            );
+        }
+
+        if (!displayed && restrictions.masked.count(column) > 0) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Masked column '" + column + "' can only be displayed."
+            );
+        }
+    };
+
+    if (cfg.columns().has_columns()) {
+        for (const auto& column : cfg.columns().columns().columns()) {
+            check(column, true);
+        }
+    }
+
+    for (const auto& [column, aggs] : cfg.aggregates()) {
+        check(column, true);
+        if (restrictions.masked.count(column) > 0) {
+            for (const auto& agg : aggs.aggregations()) {
+                if (agg != "count") {
+                    PSP_COMPLAIN_AND_ABORT(
+                        "Masked column '" + column
+                        + "' can only be aggregated by count."
+                    );
+                }
+            }
+        }
+    }
+
+    for (const auto& column : cfg.group_by()) {
+        check(column, false);
+    }
+
+    for (const auto& column : cfg.split_by()) {
+        check(column, false);
+    }
+
+    for (const auto& filter : cfg.filter()) {
+        check(filter.column(), false);
+    }
+
+    for (const auto& sort : cfg.sort()) {
+        check(sort.column(), false);
+    }
+
+    for (const auto& expr : exprs) {
+        for (const auto& [_, column] : expr.column_id_map) {
+            check(column, false);
+        }
+    }
+
+    for (const auto& [_, window] : cfg.window()) {
+        if (window.has_column()) {
+            check(window.column(), false);
+        }
+
+        for (const auto& column : window.partition_by()) {
+            check(column, false);
+        }
+
+        for (const auto& sort : window.order_by()) {
+            check(sort.column(), false);
+        }
+    }
+}
+
+// Converts a cell of `View::to_rows()` output to a `proto::Scalar`.
+static void
+json_to_proto_scalar(const rapidjson::Value& value, proto::Scalar* out) {
+    if (value.IsBool()) {
+        out->set_bool_(value.GetBool());
+    } else if (value.IsNumber()) {
+        out->set_float_(value.GetDouble());
+    } else if (value.IsString()) {
+        out->set_string(value.GetString(), value.GetStringLength());
+    } else {
+        out->set_null(::google::protobuf::NullValue::NULL_VALUE);
+    }
+}
+
+// Snapshots the rows of `view` in `viewport` for a `DETAIL` mode `on_update`
+// subscription, via the same serialization `to_json` uses so the cells match
+// its output.
+static ViewRowSnapshot
+take_row_snapshot(const ErasedView& view, const proto::ViewPort& viewport) {
+    auto config = view.get_view_config();
+    auto num_hidden = calculate_num_hidden(view, *config);
+    std::string nidx{view_sides_to_string(view)};
+    auto dims = parse_format_options(
+        viewport,
+        view.num_columns(),
+        view.num_rows(),
+        view.sides(),
+        config->is_column_only(),
+        num_hidden
+    );
+
+    auto json = view.to_rows(
+        dims.start_row,
+        dims.end_row,
+        dims.start_col,
+        dims.end_col,
+        num_hidden,
+        false,
+        false,
+        true,
+        false,
+        view.sides(),
+        view.sides() > 0 && !config->is_column_only(),
+        nidx,
+        config->get_columns().size(),
+        config->get_row_pivots().size()
+    );
+
+    rapidjson::Document document;
+    document.Parse(json.c_str());
+    ViewRowSnapshot snapshot;
+    snapshot.start_row = dims.start_row;
+    snapshot.rows.reserve(document.Size());
+    for (const auto& row : document.GetArray()) {
+        ViewRowSnapshot::Row out;
+        for (const auto& member : row.GetObject()) {
+            std::string name{
+                member.name.GetString(), member.name.GetStringLength()
+            };
+
+            if (name == "__ID__") {
+                rapidjson::StringBuffer buffer;
+                rapidjson::Writer<rapidjson::StringBuffer> writer(buffer);
+                member.value.Accept(writer);
+                out.key = buffer.GetString();
+                for (const auto& scalar : member.value.GetArray()) {
+                    json_to_proto_scalar(scalar, &out.id.emplace_back());
+                }
+            } else if (name != "__ROW_PATH__") {
+                json_to_proto_scalar(member.value, &out.cells[name]);
+            }
+        }
+
+        snapshot.rows.push_back(std::move(out));
+    }
+
+    return snapshot;
+}
+
+// The Arrow of `viewport`, uncompressed so it can be compared with the
+// previous update of a viewport-scoped `on_update` subscription.
+static std::shared_ptr<std::string>
+viewport_to_arrow(const ErasedView& view, const proto::ViewPort& viewport) {
+    auto config = view.get_view_config();
+    auto num_hidden = calculate_num_hidden(view, *config);
+    auto dims = parse_format_options(
+        viewport,
+        view.num_columns(),
+        view.num_rows(),
+        view.sides(),
+        config->is_column_only(),
+        num_hidden
+    );
+
+    return view.to_arrow(
+        dims.start_row, dims.end_row, dims.start_col, dims.end_col, true, false
+    );
+}
+
+static std::pair<
+    std::shared_ptr<arrow::Schema>,
+    std::vector<std::shared_ptr<arrow::RecordBatch>>>
+read_arrow_stream(const std::string& stream) {
+    arrow::io::BufferReader buffer_reader(
+        reinterpret_cast<const std::uint8_t*>(stream.data()),
+        static_cast<std::int64_t>(stream.size())
+    );
+
+    auto reader = arrow::ipc::RecordBatchStreamReader::Open(&buffer_reader);
+    if (!reader.ok()) {
+        PSP_COMPLAIN_AND_ABORT(reader.status().ToString());
+    }
+
+    auto batches = (*reader)->ToRecordBatches();
+    if (!batches.ok()) {
+        PSP_COMPLAIN_AND_ABORT(batches.status().ToString());
+    }
+
+    return {(*reader)->schema(), *batches};
+}
+
+// Concatenates the record batches of `streams`, the Arrow `ROW` mode deltas
+// of a `View` withheld by a throttled `on_update` subscription.
+static std::string
+concat_arrow_streams(const std::vector<std::string>& streams) {
+    std::shared_ptr<arrow::Schema> schema;
+    std::vector<std::shared_ptr<arrow::RecordBatch>> batches;
+    for (const auto& stream : streams) {
+        auto [stream_schema, stream_batches] = read_arrow_stream(stream);
+        if (schema == nullptr) {
+            schema = stream_schema;
+        }
+
+        batches.insert(
+            batches.end(), stream_batches.begin(), stream_batches.end()
+        );
+    }
+
+    arrow::Result<std::shared_ptr<arrow::ResizableBuffer>> allocated =
+        arrow::AllocateResizableBuffer(0);
+    if (!allocated.ok()) {
+        PSP_COMPLAIN_AND_ABORT(allocated.status().ToString());
+    }
+
+    std::shared_ptr<arrow::ResizableBuffer> buffer = *allocated;
+    arrow::io::BufferOutputStream sink(buffer);
+    auto writer = arrow::ipc::MakeStreamWriter(&sink, schema);
+    if (!writer.ok()) {
+        PSP_COMPLAIN_AND_ABORT(writer.status().ToString());
+    }
+
+    for (const auto& batch : batches) {
+        PSP_CHECK_ARROW_STATUS((*writer)->WriteRecordBatch(*batch));
+    }
+
+    PSP_CHECK_ARROW_STATUS((*writer)->Close());
+    PSP_CHECK_ARROW_STATUS(sink.Close());
+    return buffer->ToString();
+}
+
+// The `t_dtype` of each column a JSON exporter may write, by leaf column name,
+// and of each `__ROW_PATH__` level, by `group_by` column.
+struct FormatTypes {
+    tsl::hopscotch_map<std::string, t_dtype> columns;
+    std::vector<std::pair<std::string, t_dtype>> row_path;
+};
+
+static FormatTypes
+get_format_types(const Table& table, const ErasedView& view) {
+    FormatTypes types;
+    for (const auto& [name, type] : view.schema()) {
+        types.columns[name] = str_to_dtype(type);
+    }
+
+    // `group_by` columns are typed by the `Table` or expression they read,
+    // as they are not in the view schema.
+    const auto table_schema = table.get_schema();
+    const auto expression_schema = view.expression_schema();
+    for (const auto& group_by : view.get_view_config()->get_row_pivots()) {
+        auto dtype = DTYPE_STR;
+        if (table_schema.has_column(group_by)) {
+            dtype = table_schema.get_dtype(group_by);
+        } else if (expression_schema.count(group_by) > 0) {
+            dtype = str_to_dtype(expression_schema.at(group_by));
+        }
+
+        types.row_path.emplace_back(group_by, dtype);
+    }
+
+    return types;
+}
+
+// Replaces a numeric JSON `value` of a `column` of type `dtype`, as written by
+// the unformatted JSON exporters, with its formatted text.
+static void
+format_json_value(
+    rapidjson::Value& value,
+    const std::string& column,
+    t_dtype dtype,
+    const t_value_formatter& formatter,
+    rapidjson::Document::AllocatorType& allocator
+) {
+    if (!value.IsNumber()) {
+        return;
+    }
+
+    std::string text;
+    switch (dtype) {
+        case DTYPE_INT8:
+        case DTYPE_UINT8:
+        case DTYPE_INT16:
+        case DTYPE_UINT16:
+        case DTYPE_INT32:
+        case DTYPE_UINT32:
+        case DTYPE_INT64:
+        case DTYPE_UINT64:
+            text = formatter.format_number(column, value.GetDouble(), false);
+            break;
+        case DTYPE_FLOAT32:
+        case DTYPE_FLOAT64:
+            text = formatter.format_number(column, value.GetDouble(), true);
+            break;
+        case DTYPE_TIME:
+            if (!value.IsInt64()) {
+                return;
+            }
+
+            text = formatter.format_datetime(column, value.GetInt64());
+            break;
+        case DTYPE_DATE: {
+            if (!value.IsInt64()) {
+                return;
+            }
+
+            // `date` values are written as local midnight by `write_scalar`.
+            const auto time = static_cast<time_t>(value.GetInt64() / 1000);
+            std::tm* tm = std::localtime(&time);
+            text = formatter.format_date(
+                column, tm->tm_year + 1900, tm->tm_mon + 1, tm->tm_mday
+            );
+
+            break;
+        }
+        default:
+            return;
+    }
+
+    value.SetString(
+        text.c_str(), static_cast<rapidjson::SizeType>(text.size()), allocator
+    );
+}
+
+static void
+format_json_row_path(
+    rapidjson::Value& row_path,
+    const FormatTypes& types,
+    const t_value_formatter& formatter,
+    rapidjson::Document::AllocatorType& allocator
+) {
+    if (!row_path.IsArray()) {
+        return;
+    }
+
+    for (rapidjson::SizeType i = 0;
+         i < row_path.Size() && i < types.row_path.size();
+         ++i) {
+        const auto& [column, dtype] = types.row_path[i];
+        format_json_value(row_path[i], column, dtype, formatter, allocator);
+    }
+}
+
+// Formats the columns of a `to_columns` result or a `to_rows` row in place.
+// With `is_columnar`, each member is an array of values.
+static void
+format_json_object(
+    rapidjson::Value& object,
+    bool is_columnar,
+    const FormatTypes& types,
+    const t_value_formatter& formatter,
+    rapidjson::Document::AllocatorType& allocator
+) {
+    if (!object.IsObject()) {
+        return;
+    }
+
+    for (auto& member : object.GetObject()) {
+        const std::string name = member.name.GetString();
+        if (name == "__INDEX__" || name == "__ID__") {
+            continue;
+        }
+
+        if (name == "__ROW_PATH__") {
+            if (!is_columnar) {
+                format_json_row_path(member.value, types, formatter, allocator);
+            } else if (member.value.IsArray()) {
+                for (auto& row_path : member.value.GetArray()) {
+                    format_json_row_path(row_path, types, formatter, allocator);
+                }
+            }
+
+            continue;
+        }
+
+        const auto leaf = name.substr(name.rfind('|') + 1);
+        auto dtype = types.columns.find(leaf);
+        if (dtype == types.columns.end()) {
+            continue;
+        }
+
+        if (!is_columnar) {
+            format_json_value(
+                member.value, leaf, dtype->second, formatter, allocator
+            );
+        } else if (member.value.IsArray()) {
+            for (auto& value : member.value.GetArray()) {
+                format_json_value(
+                    value, leaf, dtype->second, formatter, allocator
+                );
+            }
+        }
+    }
+}
+
+static std::string
+format_json(
+    const std::string& json,
+    bool is_columnar,
+    const FormatTypes& types,
+    const t_value_formatter& formatter
+) {
+    rapidjson::Document document;
+    document.Parse(json.c_str());
+    if (document.HasParseError()) {
+        PSP_COMPLAIN_AND_ABORT("Failed to parse JSON for formatting");
+    }
+
+    auto& allocator = document.GetAllocator();
+    if (document.IsArray()) {
+        for (auto& row : document.GetArray()) {
+            format_json_object(row, false, types, formatter, allocator);
+        }
+    } else {
+        format_json_object(document, is_columnar, types, formatter, allocator);
+    }
+
+    rapidjson::StringBuffer buffer;
+    rapidjson::Writer<rapidjson::StringBuffer> writer(buffer);
+    document.Accept(writer);
+    return buffer.GetString();
+}
+
+static std::string
+format_ndjson(
+    const std::string& ndjson,
+    const FormatTypes& types,
+    const t_value_formatter& formatter
+) {
+    std::stringstream lines(ndjson);
+    std::stringstream out;
+    std::string line;
+    bool is_first = true;
+    while (std::getline(lines, line)) {
+        if (!is_first) {
+            out << "\n";
+        }
+
+        out << format_json(line, false, types, formatter);
+        is_first = false;
+    }
+
+    return out.str();
+}
+
+template <typename T, typename F>
+static std::shared_ptr<arrow::Array>
+format_arrow_values(const arrow::Array& array, F format) {
+    const auto& typed = static_cast<const T&>(array);
+    arrow::StringBuilder builder;
+    for (std::int64_t i = 0; i < typed.length(); ++i) {
+        if (typed.IsNull(i)) {
+            PSP_CHECK_ARROW_STATUS(builder.AppendNull());
+        } else {
+            PSP_CHECK_ARROW_STATUS(builder.Append(format(typed.Value(i))));
+        }
+    }
+
+    std::shared_ptr<arrow::Array> out;
+    PSP_CHECK_ARROW_STATUS(builder.Finish(&out));
+    return out;
+}
+
+// The formatted text of a numeric, `date32` or `timestamp` Arrow column of
+// `to_arrow` output, or `nullptr` for columns which are written as-is.
+static std::shared_ptr<arrow::Array>
+format_arrow_column(
+    const std::string& column,
+    const arrow::Array& array,
+    const t_value_formatter& formatter
+) {
+    auto number = [&](bool is_float) {
+        return [&, is_float](auto value) {
+            return formatter.format_number(
+                column, static_cast<double>(value), is_float
+            );
+        };
+    };
+
+    switch (array.type_id()) {
+        case arrow::Type::INT8:
+            return format_arrow_values<arrow::Int8Array>(array, number(false));
+        case arrow::Type::UINT8:
+            return format_arrow_values<arrow::UInt8Array>(array, number(false));
+        case arrow::Type::INT16:
+            return format_arrow_values<arrow::Int16Array>(array, number(false));
+        case arrow::Type::UINT16:
+            return format_arrow_values<arrow::UInt16Array>(
+                array, number(false)
+            );
+        case arrow::Type::INT32:
+            return format_arrow_values<arrow::Int32Array>(array, number(false));
+        case arrow::Type::UINT32:
+            return format_arrow_values<arrow::UInt32Array>(
+                array, number(false)
+            );
+        case arrow::Type::INT64:
+            return format_arrow_values<arrow::Int64Array>(array, number(false));
+        case arrow::Type::UINT64:
+            return format_arrow_values<arrow::UInt64Array>(
+                array, number(false)
+            );
+        case arrow::Type::FLOAT:
+            return format_arrow_values<arrow::FloatArray>(array, number(true));
+        case arrow::Type::DOUBLE:
+            return format_arrow_values<arrow::DoubleArray>(array, number(true));
+        case arrow::Type::DATE32:
+            return format_arrow_values<arrow::Date32Array>(
+                array,
+                [&](std::int32_t days) {
+                    const date::year_month_day ymd{
+                        date::sys_days{date::days{days}}
+                    };
+
+                    return formatter.format_date(
+                        column,
+                        static_cast<int>(ymd.year()),
+                        static_cast<unsigned>(ymd.month()),
+                        static_cast<unsigned>(ymd.day())
+                    );
+                }
+            );
+        case arrow::Type::TIMESTAMP:
+            return format_arrow_values<arrow::TimestampArray>(
+                array,
+                [&](std::int64_t ms) {
+                    return formatter.format_datetime(column, ms);
+                }
+            );
+        default:
+            return nullptr;
+    }
+}
+
+// Writes the Arrow `to_arrow` output of a `View` as CSV, with its numeric and
+// temporal columns formatted as text. Row path columns are named
+// `"<column> (Group by N)"` and split columns `"<split>|<column>"`, so their
+// formats are looked up by `<column>`.
+static std::string
+format_csv(const std::string& arrow, const t_value_formatter& formatter) {
+    auto [schema, batches] = read_arrow_stream(arrow);
+    if (schema->num_fields() == 0) {
+        return "";
+    }
+
+    std::vector<std::string> columns;
+    for (const auto& field : schema->fields()) {
+        auto column = field->name();
+        const auto group_by = column.rfind(" (Group by ");
+        if (group_by != std::string::npos && column.back() == ')') {
+            column = column.substr(0, group_by);
+        } else {
+            column = column.substr(column.rfind('|') + 1);
+        }
+
+        columns.push_back(column);
+    }
+
+    std::vector<std::shared_ptr<arrow::RecordBatch>> formatted_batches;
+    std::shared_ptr<arrow::Schema> formatted_schema = schema;
+    for (const auto& batch : batches) {
+        std::vector<std::shared_ptr<arrow::Field>> fields;
+        std::vector<std::shared_ptr<arrow::Array>> arrays;
+        for (int i = 0; i < batch->num_columns(); ++i) {
+            auto array = format_arrow_column(
+                columns[i], *batch->column(i), formatter
+            );
+
+            if (array == nullptr) {
+                fields.push_back(schema->field(i));
+                arrays.push_back(batch->column(i));
+            } else {
+                fields.push_back(
+                    arrow::field(schema->field(i)->name(), arrow::utf8())
+                );
+
+                arrays.push_back(array);
+            }
+        }
+
+        formatted_schema = arrow::schema(fields);
+        formatted_batches.push_back(arrow::RecordBatch::Make(
+            formatted_schema, batch->num_rows(), arrays
+        ));
+    }
+
+    arrow::Result<std::shared_ptr<arrow::ResizableBuffer>> allocated =
+        arrow::AllocateResizableBuffer(0);
+    if (!allocated.ok()) {
+        PSP_COMPLAIN_AND_ABORT(allocated.status().ToString());
+    }
+
+    std::shared_ptr<arrow::ResizableBuffer> buffer = *allocated;
+    arrow::io::BufferOutputStream sink(buffer);
+    auto writer = arrow::csv::MakeCSVWriter(
+        &sink, formatted_schema, arrow::csv::WriteOptions::Defaults()
+    );
+
+    if (!writer.ok()) {
+        PSP_COMPLAIN_AND_ABORT(writer.status().ToString());
+    }
+
+    for (const auto& batch : formatted_batches) {
+        PSP_CHECK_ARROW_STATUS((*writer)->WriteRecordBatch(*batch));
+    }
+
+    PSP_CHECK_ARROW_STATUS((*writer)->Close());
+    PSP_CHECK_ARROW_STATUS(sink.Close());
+    return buffer->ToString();
+}
+
+// The `FormatOptions` of a `formatted` export which has none, the defaults of
+// the viewer's column styles.
+static const proto::FormatOptions&
+default_format_options() {
+    static const proto::FormatOptions options;
+    return options;
+}
+
+// Given the previous indices of the rows which exist before and after an
+// update, in their new order, marks the rows which kept their position
+// relative to each other: the longest increasing subsequence of `indices`.
+// The remaining rows are the fewest which must have moved.
+static std::vector<bool>
+unmoved_rows(const std::vector<std::uint32_t>& indices) {
+    std::vector<std::size_t> tails;
+    std::vector<std::int64_t> parents(indices.size(), -1);
+    for (std::size_t k = 0; k < indices.size(); ++k) {
+        auto tail = std::lower_bound(
+            tails.begin(),
+            tails.end(),
+            indices[k],
+            [&](std::size_t t, std::uint32_t index) {
+                return indices[t] < index;
+            }
+        );
+
+        if (tail != tails.begin()) {
+            parents[k] = static_cast<std::int64_t>(*(tail - 1));
+        }
+
+        if (tail == tails.end()) {
+            tails.push_back(k);
+        } else {
+            *tail = k;
+        }
+    }
+
+    std::vector<bool> unmoved(indices.size(), false);
+    std::int64_t k =
+        tails.empty() ? -1 : static_cast<std::int64_t>(tails.back());
+    while (k >= 0) {
+        unmoved[k] = true;
+        k = parents[k];
+    }
+
+    return unmoved;
+}
+
+// Appends the `RowDelta`s which turn `prev` into `next` to `resp`. A row
+// whose cells did not change is only reported (as a `ROW_UPDATE` without
+// cells) when it moved relative to the other rows, e.g. because of a `sort`,
+// rather than only being shifted by the inserted and deleted rows.
+static void
+diff_row_snapshots(
+    const ViewRowSnapshot& prev,
+    const ViewRowSnapshot& next,
+    proto::ViewOnUpdateResp* resp
+) {
+    tsl::hopscotch_map<std::string, std::uint32_t> prev_rows;
+    for (std::uint32_t i = 0; i < prev.rows.size(); ++i) {
+        prev_rows[prev.rows[i].key] = i;
+    }
+
+    tsl::hopscotch_set<std::string> next_keys;
+    for (const auto& row : next.rows) {
+        next_keys.insert(row.key);
+    }
+
+    for (std::uint32_t i = 0; i < prev.rows.size(); ++i) {
+        if (next_keys.count(prev.rows[i].key) == 0) {
+            auto* delta = resp->add_row_deltas();
+            delta->set_op(proto::ViewOnUpdateResp::ROW_DELETE);
+            delta->mutable_id()->Add(
+                prev.rows[i].id.begin(), prev.rows[i].id.end()
+            );
+            delta->set_old_row_index(prev.start_row + i);
+        }
+    }
+
+    std::vector<std::uint32_t> kept_indices;
+    for (const auto& row : next.rows) {
+        auto prev_row_iter = prev_rows.find(row.key);
+        if (prev_row_iter != prev_rows.end()) {
+            kept_indices.push_back(prev_row_iter->second);
+        }
+    }
+
+    auto unmoved = unmoved_rows(kept_indices);
+    std::size_t kept = 0;
+    for (std::uint32_t j = 0; j < next.rows.size(); ++j) {
+        const auto& row = next.rows[j];
+        auto prev_row_iter = prev_rows.find(row.key);
+        if (prev_row_iter == prev_rows.end()) {
+            auto* delta = resp->add_row_deltas();
+            delta->set_op(proto::ViewOnUpdateResp::ROW_INSERT);
+            delta->mutable_id()->Add(row.id.begin(), row.id.end());
+            delta->set_row_index(next.start_row + j);
+            for (const auto& [column, value] : row.cells) {
+                auto* cell = delta->add_cells();
+                cell->set_column(column);
+                *cell->mutable_new_value() = value;
+            }
+
+            continue;
+        }
+
+        const auto i = prev_row_iter->second;
+        const auto& prev_row = prev.rows[i];
+        proto::ViewOnUpdateResp_RowDelta delta;
+        for (const auto& [column, value] : row.cells) {
+            auto old_value = prev_row.cells.find(column);
+            if (old_value == prev_row.cells.end()) {
+                auto* cell = delta.add_cells();
+                cell->set_column(column);
+                *cell->mutable_new_value() = value;
+            } else if (!proto_scalar_eq(old_value->second, value)) {
+                auto* cell = delta.add_cells();
+                cell->set_column(column);
+                *cell->mutable_old_value() = old_value->second;
+                *cell->mutable_new_value() = value;
+            }
+        }
+
+        // Columns which no longer exist, e.g. a `split_by` value which has
+        // been removed.
+        for (const auto& [column, value] : prev_row.cells) {
+            if (row.cells.find(column) == row.cells.end()) {
+                auto* cell = delta.add_cells();
+                cell->set_column(column);
+                *cell->mutable_old_value() = value;
+            }
+        }
+
+        const bool moved = !unmoved[kept++];
+        if (delta.cells_size() > 0 || moved) {
+            delta.set_op(proto::ViewOnUpdateResp::ROW_UPDATE);
+            delta.mutable_id()->Add(row.id.begin(), row.id.end());
+            delta.set_row_index(next.start_row + j);
+            delta.set_old_row_index(prev.start_row + i);
+            *resp->add_row_deltas() = std::move(delta);
+        }
+    }
+}
+
+// Serialize a `t_view_config` as the `ViewConfig` a client would have
+// created it from.
+static void
+view_config_to_proto(
+    const t_view_config& view_config, proto::ViewConfig* view_config_proto
+) {
+    for (const auto& col : view_config.get_columns()) {
+        view_config_proto->mutable_columns()
+            ->mutable_columns()
+            ->add_columns(col);
+    }
+
+    for (const auto& agg : view_config.get_row_pivots()) {
+        if (agg == "psp_pkey" || agg == "psp_okey") {
+            continue;
+        }
+        view_config_proto->add_group_by(agg);
+    }
+
+    for (const auto& agg : view_config.get_column_pivots()) {
+        view_config_proto->add_split_by(agg);
+    }
+
+    for (const auto& sort : view_config.get_sortspec()) {
+        auto* proto_sort = view_config_proto->mutable_sort();
+        auto* s = proto_sort->Add();
+        s->set_column(sort.m_colname);
+        s->set_op(sort_op_to_proto(sort.m_sort_type));
+    }
+
+    for (const auto& filter : view_config.get_fterm()) {
+        auto* proto_filter = view_config_proto->mutable_filter();
+        auto* f = proto_filter->Add();
+        f->set_column(filter.m_colname);
+        f->set_op(filter_op_to_str(filter.m_op));
+        auto vals = std::vector<t_tscalar>(filter.m_bag.size());
+        if (filter.m_op != FILTER_OP_NOT_IN
+            && filter.m_op != FILTER_OP_IN) {
+            vals.push_back(filter.m_threshold);
+        } else {
+            for (const auto& scalar : filter.m_bag) {
+                vals.push_back(scalar);
+            }
+        }
+
+        for (const auto& scalar : vals) {
+            auto* s = f->mutable_value()->Add();
+            switch (scalar.get_dtype()) {
+                case DTYPE_BOOL:
+                    s->set_bool_(scalar.get<bool>());
+                    break;
+                case DTYPE_FLOAT32:
+                    s->set_float_(scalar.get<float>());
+                    break;
+                case DTYPE_FLOAT64:
+                    s->set_float_(scalar.get<double>());
+                    break;
+                case DTYPE_INT8:
+                    s->set_float_((double)scalar.get<std::int8_t>());
+                    break;
+                case DTYPE_INT16:
+                    s->set_float_((double)scalar.get<std::int16_t>());
+                    break;
+                case DTYPE_INT32:
+                    s->set_float_((double)scalar.get<std::int32_t>());
+                    break;
+                case DTYPE_INT64:
+                    s->set_float_((double)scalar.get<std::int64_t>());
+                    break;
+                case DTYPE_UINT8:
+                    s->set_float_((double)scalar.get<std::uint8_t>());
+                    break;
+                case DTYPE_UINT16:
+                    s->set_float_((double)scalar.get<std::uint16_t>());
+                    break;
+                case DTYPE_UINT32:
+                    s->set_float_((double)scalar.get<std::uint32_t>());
+                    break;
+                case DTYPE_UINT64:
+                    s->set_float_((double)scalar.get<std::uint64_t>());
+                    break;
+                case DTYPE_STR:
+                    s->set_string(scalar.get<const char*>());
+                    break;
+                case DTYPE_DATE: {
+                    auto tm = scalar.get<t_date>();
+                    std::stringstream ss;
+                    ss << std::setfill('0') << std::setw(4) << tm.year()
+                       << "-" << std::setfill('0') << std::setw(2)
+                       << tm.month() << "-" << std::setfill('0')
+                       << std::setw(2) << tm.day();
+                    s->set_string(ss.str());
+                    break;
+                }
+                case DTYPE_TIME:
+                    s->set_float_(
+                        (double)scalar.get<t_time>().raw_value()
+                    );
+                    break;
+                case DTYPE_NONE:
+                    s->set_null(
+                        ::google::protobuf::NullValue::NULL_VALUE
+                    );
+                    break;
+                default:
+                    PSP_COMPLAIN_AND_ABORT(
+                        "Invalid scalar type: " + scalar.to_string()
+                    );
+            }
+        }
+    }
+
+    switch (view_config.get_filter_op()) {
+        case FILTER_OP_OR:
+            view_config_proto->set_filter_op(
+                proto::ViewConfig_FilterReducer::
+                    ViewConfig_FilterReducer_OR
+            );
+            break;
+        case FILTER_OP_AND:
+        default:
+            view_config_proto->set_filter_op(
+                proto::ViewConfig_FilterReducer::
+                    ViewConfig_FilterReducer_AND
+            );
+            break;
+    }
+
+    if (view_config.get_row_pivot_depth() != -1) {
+        view_config_proto->set_group_by_depth(
+            view_config.get_row_pivot_depth()
+        );
+    }
+
+    for (const auto& [column, order] :
+         view_config.get_split_by_order()) {
+        auto* proto_order =
+            &(*view_config_proto->mutable_split_by_order())[column];
+        proto_order->set_natural(order.m_natural);
+        for (const auto& value : order.m_values) {
+            proto_order->add_values(value);
+        }
+    }
+
+    const auto& row_totals = view_config.get_row_totals();
+    if (!row_totals.is_default()) {
+        auto* totals = view_config_proto->mutable_totals();
+        totals->set_hide_grand_total(!row_totals.m_grand_total);
+        totals->set_hide_subtotals(!row_totals.m_subtotals);
+        totals->set_position(
+            row_totals.m_position == TOTALS_POSITION_BOTTOM
+                ? proto::ViewConfig::BOTTOM
+                : proto::ViewConfig::TOP
+        );
+    }
+
+    for (const auto& expr : view_config.get_expressions()) {
+        if (expr->is_window()) {
+            continue;
+        }
+
+        auto* proto_exprs = view_config_proto->mutable_expressions();
+        (*proto_exprs)[expr->get_expression_alias()] =
+            expr->get_expression_string();
+    }
+
+    for (const auto& expr : view_config.get_expressions()) {
+        auto window =
+            std::dynamic_pointer_cast<t_computed_window>(expr);
+
+        if (window == nullptr) {
+            continue;
+        }
+
+        auto& proto_window = (*view_config_proto->mutable_window()
+        )[window->get_expression_alias()];
+
+        proto_window.set_function(
+            window_function_to_str(window->get_function())
+        );
+
+        if (!window->get_column().empty()) {
+            proto_window.set_column(window->get_column());
+        }
+
+        for (const auto& col : window->get_partition_by()) {
+            proto_window.add_partition_by(col);
+        }
+
+        for (const auto& [col, sort_type] : window->get_order_by()) {
+            auto* sort = proto_window.add_order_by();
+            sort->set_column(col);
+            sort->set_op(sort_op_to_proto(sort_type));
+        }
+
+        if (window->get_preceding().has_value()) {
+            proto_window.set_preceding(*window->get_preceding());
+        }
+
+        if (window->get_following().has_value()) {
+            proto_window.set_following(*window->get_following());
+        }
+
+        proto_window.set_offset(window->get_offset());
+    }
+
+    for (const auto& [col, aggs] : view_config.get_aggregates()) {
+        auto* proto_aggs = view_config_proto->mutable_aggregates();
+        auto& agg_list = (*proto_aggs)[col];
+        for (const auto& agg : aggs) {
+            agg_list.add_aggregations(agg);
+        }
+    }
+}
+
+std::vector<ProtoServerResp<ProtoServer::Response>>
+ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
+    // Sessions closed by a `CloseSessionReq` may not make further requests.
+    if (!m_resources.has_session(client_id)) {
+        PSP_COMPLAIN_AND_ABORT("Session closed");
+    }
+
+    std::vector<ProtoServerResp<ProtoServer::Response>> proto_resp;
+    // proto::Response resp_env;
+
+    auto msg_id = req.msg_id();
+    auto entity_id = req.entity_id();
+
+    auto push_resp = [&](Response&& resp) {
+        resp.set_msg_id(msg_id);
+        resp.set_entity_id(entity_id);
+        ProtoServerResp<ProtoServer::Response> resp2;
         resp2.data = std::move(resp);
         resp2.client_id = client_id;
         proto_resp.emplace_back(std::move(resp2));
@@ -1190,7 +2967,10 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             opts.add_options("<=");
             opts.add_options("begins with");
             opts.add_options("contains");
+            opts.add_options("not contains");
+            opts.add_options("icontains");
             opts.add_options("ends with");
+            opts.add_options("matches");
             opts.add_options("in");
             opts.add_options("not in");
             opts.add_options("is not null");
@@ -1352,9 +3132,26 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
         }
         case proto::Request::kTableSizeReq: {
             auto table = m_resources.get_table(req.entity_id());
+            const auto& r = req.table_size_req();
             proto::Response resp;
             auto* tbl_size = resp.mutable_table_size_resp();
-            tbl_size->set_size(table->size());
+            if (r.session_filter().empty()) {
+                tbl_size->set_size(table->size());
+            } else {
+                t_vocab vocab;
+                vocab.init(false);
+                intern_filter_strings(r.session_filter(), vocab);
+                auto filter = parse_filters(
+                    r.session_filter(),
+                    table->get_gnode()->get_output_schema(),
+                    vocab
+                );
+
+                tbl_size->set_size(
+                    table->get_gnode()->mapping_size(make_fterms(filter))
+                );
+            }
+
             push_resp(std::move(resp));
             break;
         }
@@ -1367,10 +3164,22 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             auto table_schema = table->get_schema();
             auto columns = table_schema.columns();
             auto types = table_schema.types();
+            auto restrictions =
+                parse_column_policy(req.table_schema_req().column_policy());
+
             for (std::size_t i = 0; i < table_schema.size(); ++i) {
+                if (restrictions.hidden.contains(columns[i])) {
+                    continue;
+                }
+
+                // Masked values are replaced by strings wherever displayed.
+                auto dtype = restrictions.masked.count(columns[i]) > 0
+                    ? DTYPE_STR
+                    : types[i];
+
                 auto* ktp = output_schema->add_schema();
                 ktp->set_name(columns[i]);
-                ktp->set_type(dtype_to_column_type(types[i]));
+                ktp->set_type(dtype_to_column_type(dtype));
             }
 
             push_resp(std::move(resp));
@@ -1391,6 +3200,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 
             const auto& col_with_expr = r.column_to_expr();
             const auto& exprs = parse_expression_strings(col_with_expr);
+            auto restrictions = parse_column_policy(r.column_policy());
 
             // TODO: validate the expression, mocked out for now
             proto::Response resp;
@@ -1405,6 +3215,19 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 
             legacy_exprs.reserve(exprs.size());
             for (const auto& expr : exprs) {
+                auto error = restricted_expression_error(
+                    expr.column_id_map, restrictions
+                );
+
+                if (!error.empty()) {
+                    auto& errors = *validate_expr->mutable_errors();
+                    auto& proto_err = errors[expr.expression_alias];
+                    proto_err.set_error_message(error);
+                    proto_err.set_line(0);
+                    proto_err.set_column(0);
+                    continue;
+                }
+
                 legacy_exprs.emplace_back(
                     expr.expression_alias,
                     expr.expression,
@@ -1447,6 +3270,46 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                     col_expr;
             }
 
+            // `custom` aggregates are compiled over vectors of each group's
+            // values, exactly as `t_stree` evaluates them.
+            const auto table_schema = table->get_schema();
+            for (const auto& [col_name, expression] : r.custom_aggregates()) {
+                std::vector<std::string> columns;
+                auto parsed =
+                    t_custom_aggregate::parse_expression(expression, columns);
+
+                tsl::hopscotch_map<std::string, std::string> column_id_map;
+                std::string error;
+                for (t_uindex cidx = 0; cidx < columns.size(); ++cidx) {
+                    column_id_map["COLUMN" + std::to_string(cidx)] =
+                        columns[cidx];
+                    if (error.empty()
+                        && !table_schema.has_column(columns[cidx])) {
+                        error = "Value Error - Input column \""
+                            + columns[cidx] + "\" does not exist.";
+                    }
+                }
+
+                if (error.empty()) {
+                    error = restricted_expression_error(
+                        column_id_map, restrictions
+                    );
+                }
+
+                t_expression_error expr_error{error, 0, 0};
+                if (error.empty()
+                    && t_custom_aggregate::validate(
+                        parsed, columns.size(), expr_error
+                    )) {
+                    continue;
+                }
+
+                auto& proto_err = (*validate_expr->mutable_errors())[col_name];
+                proto_err.set_error_message(expr_error.m_error_message);
+                proto_err.set_line(expr_error.m_line);
+                proto_err.set_column(expr_error.m_column);
+            }
+
             push_resp(std::move(resp));
             break;
         }
@@ -1596,6 +3459,16 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 
             std::vector<std::shared_ptr<t_computed_expression>> expressions;
             auto exprs = parse_expression_strings(cfg.expressions());
+            auto restrictions = parse_column_policy(r.column_policy());
+            check_column_restrictions(cfg, exprs, restrictions);
+            const auto& index = table->get_index();
+            if (!index.empty()
+                && (restrictions.hidden.contains(index)
+                    || restrictions.masked.count(index) > 0)) {
+                PSP_COMPLAIN_AND_ABORT(
+                    "Cannot hide or mask the index column '" + index + "'."
+                );
+            }
 
             std::vector<std::tuple<
                 std::string,
@@ -1663,97 +3536,58 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 ));
             }
 
-            t_vocab vocab;
-            vocab.init(false);
-            std::vector<
-                std::tuple<std::string, std::string, std::vector<t_tscalar>>>
-                filter;
-            filter.reserve(cfg.filter().size());
-
-            for (const auto& f : cfg.filter()) {
-                for (const auto& arg : f.value()) {
-                    switch (arg.scalar_case()) {
-                        case proto::Scalar::kString: {
-#ifdef PSP_SSO_SCALAR
-                            if (!t_tscalar::can_store_inplace(arg.string())) {
-                                vocab.get_interned(arg.string());
-                            }
-#else
-                            vocab.get_interned(arg.string());
-#endif
-                            break;
-                        }
-                        case proto::Scalar::kBool:
-                        case proto::Scalar::kFloat:
-                        case proto::Scalar::kNull:
-                        case proto::Scalar::SCALAR_NOT_SET:
-                            break;
-                    }
-                }
+            // Windows are computed over every row of the `Table`, so a
+            // session filter cannot hide rows from them.
+            if (!cfg.window().empty() && !r.session_filter().empty()) {
+                PSP_COMPLAIN_AND_ABORT(
+                    "Windows are not allowed for a filtered session."
+                );
             }
 
-            for (const auto& f : cfg.filter()) {
-                std::vector<t_tscalar> args;
-                args.reserve(f.value().size());
-                for (const auto& arg : f.value()) {
-                    t_tscalar a;
-                    a.clear();
-                    switch (arg.scalar_case()) {
-                        case proto::Scalar::kBool: {
-                            a.set(arg.bool_());
-                            args.push_back(a);
-                            break;
-                        }
-                        case proto::Scalar::kFloat: {
-                            a = coerce_to(
-                                schema->get_dtype(f.column()), arg.float_()
-                            );
-
-                            args.push_back(a);
-                            break;
-                        }
-                        case proto::Scalar::kString: {
-                            if (!schema->has_column(f.column())) {
-                                PSP_COMPLAIN_AND_ABORT(
-                                    "Filter column not in schema: " + f.column()
-                                );
-                            }
+            // Windows may read from expressions but not from other windows,
+            // and are created in name order as `window` is unordered.
+            const t_schema window_input_schema = *schema;
+            std::map<std::string, const proto::ViewConfig_Window*> windows;
+            for (const auto& [name, window] : cfg.window()) {
+                windows[name] = &window;
+            }
 
-#ifdef PSP_SSO_SCALAR
-                            if (!t_tscalar::can_store_inplace(arg.string())) {
-#endif
-                                a = coerce_to(
-                                    schema->get_dtype(f.column()),
-                                    vocab.unintern_c(
-                                        vocab.get_interned(arg.string())
-                                    )
-                                );
-#ifdef PSP_SSO_SCALAR
-                            } else {
+            for (const auto& [name, window] : windows) {
+                auto computed_window = parse_window(
+                    name,
+                    *window,
+                    window_input_schema,
+                    table->get_gnode()->get_table_sptr().get()
+                );
 
-                                a = coerce_to(
-                                    schema->get_dtype(f.column()),
-                                    arg.string().c_str()
-                                );
-                            }
-#endif
-                            args.push_back(a);
-                            break;
-                        }
-                        case proto::Scalar::kNull:
-                            a.set(t_none());
-                            args.push_back(a);
-                            break;
-                        case proto::Scalar::SCALAR_NOT_SET:
-                            PSP_COMPLAIN_AND_ABORT(
-                                "Filter scalar type not implemented: "
-                                + std::to_string(arg.scalar_case())
-                            )
-                            break;
-                    }
+                schema->add_column(name, computed_window->get_dtype());
+                expressions.push_back(computed_window);
+            }
+
+            // The strings of both filter lists are interned before either is
+            // parsed, as parsed scalars point into `vocab`.
+            t_vocab vocab;
+            vocab.init(false);
+            intern_filter_strings(cfg.filter(), vocab);
+            intern_filter_strings(r.session_filter(), vocab);
+            auto filter = parse_filters(cfg.filter(), *schema, vocab);
+            auto session_filter =
+                parse_filters(r.session_filter(), *schema, vocab);
+
+            // A session filter must select rows by the `Table`'s column, not
+            // by an expression of the same name.
+            for (const auto& f : r.session_filter()) {
+                bool shadowed = cfg.window().count(f.column()) > 0;
+                for (const auto& expr : expressions) {
+                    shadowed = shadowed
+                        || expr->get_expression_alias() == f.column();
                 }
 
-                filter.emplace_back(f.column(), f.op(), args);
+                if (shadowed) {
+                    PSP_COMPLAIN_AND_ABORT(
+                        "Cannot redefine filtered column: " + f.column()
+                    );
+                }
             }
 
             const auto& cols = cfg.columns();
@@ -1764,12 +3598,24 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                     cols.columns().columns().end()
                 };
             } else {
-                columns = table->get_column_names();
+                for (const auto& column : table->get_column_names()) {
+                    if (!restrictions.hidden.contains(column)) {
+                        columns.push_back(column);
+                    }
+                }
+
                 for (const auto& f : expressions) {
                     columns.push_back(f->get_expression_alias());
                 }
             }
 
+            // Masked columns are displayed, and hence typed, as strings.
+            for (const auto& [column, _] : restrictions.masked) {
+                if (schema->has_column(column)) {
+                    schema->retype_column(column, DTYPE_STR);
+                }
+            }
+
             LOG_DEBUG(
                 "Creating view config with \n"
                 << "row_pivots: " << row_pivots << '\n'
@@ -1811,12 +3657,43 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 filter_op,
                 column_only
             );
+            config->set_session_filter(session_filter);
             config->init(schema);
 
             if (cfg.has_group_by_depth()) {
                 config->set_row_pivot_depth(cfg.group_by_depth());
             }
 
+            if (cfg.has_totals()) {
+                const auto& totals = cfg.totals();
+                t_row_totals row_totals;
+                row_totals.m_grand_total = !totals.hide_grand_total();
+                row_totals.m_subtotals = !totals.hide_subtotals();
+                row_totals.m_position =
+                    totals.position() == proto::ViewConfig::BOTTOM
+                        ? TOTALS_POSITION_BOTTOM
+                        : TOTALS_POSITION_TOP;
+                config->set_row_totals(row_totals);
+            }
+
+            std::map<std::string, t_pivot_order> split_by_order;
+            for (const auto& [column, order] : cfg.split_by_order()) {
+                t_pivot_order pivot_order;
+                pivot_order.m_natural = order.natural();
+                pivot_order.m_values.assign(
+                    order.values().begin(), order.values().end()
+                );
+                split_by_order[column] = pivot_order;
+            }
+
+            config->set_split_by_order(split_by_order);
+            config->set_column_policy(
+                std::vector<std::string>(
+                    restrictions.hidden.begin(), restrictions.hidden.end()
+                ),
+                restrictions.masked
+            );
+
             std::uint32_t sides;
 
             if (!group_by.empty() || !split_by.empty()) {
@@ -1832,7 +3709,8 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             bool is_unit_context = table->get_index().empty() && sides == 0
                 && row_pivots.empty() && column_pivots.empty()
                 && aggregates.empty() && columns.empty() && sort_str.empty()
-                && cfg.expressions().empty();
+                && cfg.expressions().empty() && cfg.window().empty()
+                && session_filter.empty() && restrictions.empty();
 
             std::shared_ptr<ErasedView> erased_view;
 
@@ -1961,141 +3839,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             auto* view_config_proto =
                 resp.mutable_view_get_config_resp()->mutable_config();
 
-            for (const auto& col : view_config->get_columns()) {
-                view_config_proto->mutable_columns()
-                    ->mutable_columns()
-                    ->add_columns(col);
-            }
-
-            for (const auto& agg : view_config->get_row_pivots()) {
-                if (agg == "psp_pkey" || agg == "psp_okey") {
-                    continue;
-                }
-                view_config_proto->add_group_by(agg);
-            }
-
-            for (const auto& agg : view_config->get_column_pivots()) {
-                view_config_proto->add_split_by(agg);
-            }
-
-            // TODO: Sort, Expressions, and Aggregations
-
-            for (const auto& sort : view_config->get_sortspec()) {
-                auto* proto_sort = view_config_proto->mutable_sort();
-                auto* s = proto_sort->Add();
-                s->set_column(sort.m_colname);
-                s->set_op(sort_op_to_proto(sort.m_sort_type));
-            }
-
-            for (const auto& filter : view_config->get_fterm()) {
-                auto* proto_filter = view_config_proto->mutable_filter();
-                auto* f = proto_filter->Add();
-                f->set_column(filter.m_colname);
-                f->set_op(filter_op_to_str(filter.m_op));
-                auto vals = std::vector<t_tscalar>(filter.m_bag.size());
-                if (filter.m_op != FILTER_OP_NOT_IN
-                    && filter.m_op != FILTER_OP_IN) {
-                    vals.push_back(filter.m_threshold);
-                } else {
-                    for (const auto& scalar : filter.m_bag) {
-                        vals.push_back(scalar);
-                    }
-                }
-
-                for (const auto& scalar : vals) {
-                    auto* s = f->mutable_value()->Add();
-                    switch (scalar.get_dtype()) {
-                        case DTYPE_BOOL:
-                            s->set_bool_(scalar.get<bool>());
-                            break;
-                        case DTYPE_FLOAT32:
-                            s->set_float_(scalar.get<float>());
-                            break;
-                        case DTYPE_FLOAT64:
-                            s->set_float_(scalar.get<double>());
-                            break;
-                        case DTYPE_INT8:
-                            s->set_float_((double)scalar.get<std::int8_t>());
-                            break;
-                        case DTYPE_INT16:
-                            s->set_float_((double)scalar.get<std::int16_t>());
-                            break;
-                        case DTYPE_INT32:
-                            s->set_float_((double)scalar.get<std::int32_t>());
-                            break;
-                        case DTYPE_INT64:
-                            s->set_float_((double)scalar.get<std::int64_t>());
-                            break;
-                        case DTYPE_UINT8:
-                            s->set_float_((double)scalar.get<std::uint8_t>());
-                            break;
-                        case DTYPE_UINT16:
-                            s->set_float_((double)scalar.get<std::uint16_t>());
-                            break;
-                        case DTYPE_UINT32:
-                            s->set_float_((double)scalar.get<std::uint32_t>());
-                            break;
-                        case DTYPE_UINT64:
-                            s->set_float_((double)scalar.get<std::uint64_t>());
-                            break;
-                        case DTYPE_STR:
-                            s->set_string(scalar.get<const char*>());
-                            break;
-                        case DTYPE_DATE: {
-                            auto tm = scalar.get<t_date>();
-                            std::stringstream ss;
-                            ss << std::setfill('0') << std::setw(4) << tm.year()
-                               << "-" << std::setfill('0') << std::setw(2)
-                               << tm.month() << "-" << std::setfill('0')
-                               << std::setw(2) << tm.day();
-                            s->set_string(ss.str());
-                            break;
-                        }
-                        case DTYPE_TIME:
-                            s->set_float_(
-                                (double)scalar.get<t_time>().raw_value()
-                            );
-                            break;
-                        case DTYPE_NONE:
-                            s->set_null(
-                                ::google::protobuf::NullValue::NULL_VALUE
-                            );
-                            break;
-                        default:
-                            PSP_COMPLAIN_AND_ABORT(
-                                "Invalid scalar type: " + scalar.to_string()
-                            );
-                    }
-                }
-            }
-
-            switch (view_config->get_filter_op()) {
-                case FILTER_OP_OR:
-                    view_config_proto->set_filter_op(
-                        proto::ViewConfig_FilterReducer::
-                            ViewConfig_FilterReducer_OR
-                    );
-                    break;
-                case FILTER_OP_AND:
-                default:
-                    view_config_proto->set_filter_op(
-                        proto::ViewConfig_FilterReducer::
-                            ViewConfig_FilterReducer_AND
-                    );
-                    break;
-            }
-
-            if (view_config->get_row_pivot_depth() != -1) {
-                view_config_proto->set_group_by_depth(
-                    view_config->get_row_pivot_depth()
-                );
-            }
-
-            for (const auto& expr : view_config->get_expressions()) {
-                auto* proto_exprs = view_config_proto->mutable_expressions();
-                (*proto_exprs)[expr->get_expression_alias()] =
-                    expr->get_expression_string();
-            }
+            view_config_to_proto(*view_config, view_config_proto);
 
             push_resp(std::move(resp));
             break;
@@ -2138,7 +3882,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
         }
         case proto::Request::kViewToNdjsonStringReq: {
             auto view = m_resources.get_view(req.entity_id());
-            const auto& r = req.view_to_rows_string_req();
+            const auto& r = req.view_to_ndjson_string_req();
             auto config = view->get_view_config();
             std::string nidx{view_sides_to_string(*view)};
             auto num_hidden = calculate_num_hidden(*view, *config);
@@ -2158,7 +3902,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 dims.start_col,
                 dims.end_col,
                 num_hidden,
-                r.formatted(),
+                r.formatted() && !r.has_format(),
                 r.index(),
                 r.id(),
                 r.leaves_only(),
@@ -2169,6 +3913,15 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 view->get_view_config()->get_row_pivots().size()
             );
 
+            if (r.has_format()) {
+                const t_value_formatter formatter{r.format()};
+                const auto types = get_format_types(
+                    *m_resources.get_table_for_view(req.entity_id()), *view
+                );
+
+                json_str = format_ndjson(json_str, types, formatter);
+            }
+
             proto::Response resp;
             auto* view_cols_str = resp.mutable_view_to_ndjson_string_resp();
             view_cols_str->set_ndjson_string(json_str);
@@ -2197,7 +3950,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 dims.start_col,
                 dims.end_col,
                 num_hidden,
-                r.formatted(),
+                r.formatted() && !r.has_format(),
                 r.index(),
                 r.id(),
                 r.leaves_only(),
@@ -2208,6 +3961,15 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 view->get_view_config()->get_row_pivots().size()
             );
 
+            if (r.has_format()) {
+                const t_value_formatter formatter{r.format()};
+                const auto types = get_format_types(
+                    *m_resources.get_table_for_view(req.entity_id()), *view
+                );
+
+                json_str = format_json(json_str, false, types, formatter);
+            }
+
             proto::Response resp;
             auto* view_cols_str = resp.mutable_view_to_rows_string_resp();
             view_cols_str->set_json_string(json_str);
@@ -2237,7 +3999,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 dims.start_col,
                 dims.end_col,
                 num_hidden,
-                r.formatted(),
+                r.formatted() && !r.has_format(),
                 r.index(),
                 r.id(),
                 r.leaves_only(),
@@ -2248,6 +4010,15 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 config->get_row_pivots().size()
             );
 
+            if (r.has_format()) {
+                const t_value_formatter formatter{r.format()};
+                const auto types = get_format_types(
+                    *m_resources.get_table_for_view(req.entity_id()), *view
+                );
+
+                json_str = format_json(json_str, true, types, formatter);
+            }
+
             proto::Response resp;
             auto* view_cols_str = resp.mutable_view_to_columns_string_resp();
             view_cols_str->set_json_string(json_str);
@@ -2275,19 +4046,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             break;
         }
         case proto::Request::kViewDeleteReq: {
-            for (const auto& sub :
-                 m_resources.get_view_on_delete_sub(req.entity_id())) {
-                proto::Response resp;
-                resp.mutable_view_on_delete_resp();
-                resp.set_msg_id(sub.id);
-                resp.set_entity_id(req.entity_id());
-                ProtoServerResp<proto::Response> resp2;
-                resp2.data = std::move(resp);
-                resp2.client_id = sub.client_id;
-                proto_resp.emplace_back(std::move(resp2));
-            }
-
-            m_resources.delete_view(client_id, req.entity_id());
+            _delete_view(client_id, req.entity_id(), proto_resp);
             proto::Response resp;
             resp.mutable_view_delete_resp();
             push_resp(std::move(resp));
@@ -2363,9 +4122,27 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 
             proto::Response resp;
             auto* csv = resp.mutable_view_to_csv_resp()->mutable_csv();
-            *csv = *view->to_csv(
-                dims.start_row, dims.end_row, dims.start_col, dims.end_col
-            );
+            if (r.formatted() || r.has_format()) {
+                const t_value_formatter formatter{
+                    r.has_format() ? r.format() : default_format_options()
+                };
+
+                *csv = format_csv(
+                    *view->to_arrow(
+                        dims.start_row,
+                        dims.end_row,
+                        dims.start_col,
+                        dims.end_col,
+                        true,
+                        false
+                    ),
+                    formatter
+                );
+            } else {
+                *csv = *view->to_csv(
+                    dims.start_row, dims.end_row, dims.start_col, dims.end_col
+                );
+            }
 
             push_resp(std::move(resp));
             break;
@@ -2374,13 +4151,64 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             Subscription sub_info;
             sub_info.id = req.msg_id();
             sub_info.client_id = client_id;
-            m_resources.create_view_on_update_sub(req.entity_id(), sub_info);
-            if (req.view_on_update_req().has_mode()
-                && req.view_on_update_req().mode()
-                    == proto::ViewOnUpdateReq_Mode::ViewOnUpdateReq_Mode_ROW) {
-                auto view = m_resources.get_view(req.entity_id());
+            const auto& r = req.view_on_update_req();
+            auto view = m_resources.get_view(req.entity_id());
+            if (r.has_viewport()) {
+                sub_info.viewport = r.viewport();
+                sub_info.viewport_data = viewport_to_arrow(*view, r.viewport());
+                sub_info.include_viewport_data = r.include_viewport_data();
+            }
+
+            if (r.has_throttle_ms()) {
+                sub_info.throttle_ms = r.throttle_ms();
+            }
+
+            sub_info.throttle = std::make_shared<UpdateThrottle>();
+            if (r.has_mode() && r.mode() == proto::ViewOnUpdateReq::ROW) {
                 view->set_deltas_enabled(true);
+            } else if (r.has_mode()
+                       && r.mode() == proto::ViewOnUpdateReq::DETAIL) {
+                sub_info.snapshot = std::make_shared<ViewRowSnapshot>(
+                    take_row_snapshot(*view, r.viewport())
+                );
+            }
+
+            m_resources.create_view_on_update_sub(req.entity_id(), sub_info);
+            break;
+        }
+        case proto::Request::kViewSetUpdateViewportReq: {
+            const auto& r = req.view_set_update_viewport_req();
+            auto view = m_resources.get_view(req.entity_id());
+            auto subscriptions =
+                m_resources.get_view_on_update_sub(req.entity_id());
+
+            auto sub = std::find_if(
+                subscriptions.begin(),
+                subscriptions.end(),
+                [&](const Subscription& existing) {
+                    return existing.id == r.id()
+                        && existing.client_id == client_id;
+                }
+            );
+
+            if (sub == subscriptions.end()) {
+                PSP_COMPLAIN_AND_ABORT("Unknown on_update subscription");
+            }
+
+            // Snapshot the new region, so the next update is sent only if it
+            // changes after this request.
+            sub->viewport = r.viewport();
+            sub->viewport_data = viewport_to_arrow(*view, r.viewport());
+            if (sub->snapshot != nullptr) {
+                sub->snapshot = std::make_shared<ViewRowSnapshot>(
+                    take_row_snapshot(*view, r.viewport())
+                );
             }
+
+            m_resources.set_view_on_update_sub(req.entity_id(), *sub);
+            proto::Response resp;
+            resp.mutable_view_set_update_viewport_resp();
+            push_resp(std::move(resp));
             break;
         }
         case proto::Request::kViewGetMinMaxReq: {
@@ -2429,6 +4257,181 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             push_resp(std::move(resp));
             break;
         }
+        case proto::Request::kViewGetExpansionStateReq: {
+            auto view = m_resources.get_view(req.entity_id());
+            const auto num_pivots =
+                view->get_view_config()->get_row_pivots().size();
+
+            proto::Response resp;
+            auto* state = resp.mutable_view_get_expansion_state_resp();
+            for (t_uindex r = 0; num_pivots > 0 && r < view->num_rows(); ++r) {
+                const auto row_path = view->get_row_path(r);
+                if (row_path.empty() || row_path.size() >= num_pivots
+                    || view->get_row_expanded(r)) {
+                    continue;
+                }
+
+                auto* path = state->add_collapsed();
+                for (const auto& scalar : row_path) {
+                    row_path_scalar_to_proto(scalar, path->add_path());
+                }
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kViewSetExpansionStateReq: {
+            const auto& r = req.view_set_expansion_state_req();
+            auto view = m_resources.get_view(req.entity_id());
+            const auto num_pivots =
+                view->get_view_config()->get_row_pivots().size();
+
+            if (num_pivots > 0) {
+                // Expand the whole tree, then collapse each path
+                // parents-first; paths nested in an already collapsed row
+                // no longer exist and are skipped.
+                std::vector<const proto::RowPath*> paths;
+                paths.reserve(r.collapsed_size());
+                for (const auto& path : r.collapsed()) {
+                    paths.push_back(&path);
+                }
+
+                std::stable_sort(
+                    paths.begin(),
+                    paths.end(),
+                    [](const proto::RowPath* a, const proto::RowPath* b) {
+                        return a->path_size() < b->path_size();
+                    }
+                );
+
+                view->set_depth(num_pivots);
+                for (const auto* path : paths) {
+                    auto ridx = find_row_by_path(*view, path->path());
+                    if (ridx.has_value() && view->get_row_expanded(*ridx)) {
+                        view->collapse(*ridx);
+                    }
+                }
+            }
+
+            proto::Response resp;
+            resp.mutable_view_set_expansion_state_resp();
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kViewFindRowByPathReq: {
+            const auto& r = req.view_find_row_by_path_req();
+            auto view = m_resources.get_view(req.entity_id());
+            const auto config = view->get_view_config();
+            proto::Response resp;
+            auto* find_resp = resp.mutable_view_find_row_by_path_resp();
+            if (!config->get_row_pivots().empty()
+                && !config->is_column_only()) {
+                auto ridx = find_row_by_path(*view, r.path());
+                if (ridx.has_value()) {
+                    find_resp->set_row_index(*ridx);
+                }
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kViewFindRowByIndexReq: {
+            const auto& r = req.view_find_row_by_index_req();
+            auto view = m_resources.get_view(req.entity_id());
+            auto table = m_resources.get_table_for_view(req.entity_id());
+            const auto& index = table->get_index();
+            const auto dtype = index.empty()
+                ? DTYPE_INT32
+                : table->get_schema().get_dtype(index);
+
+            proto::Response resp;
+            auto* find_resp = resp.mutable_view_find_row_by_index_resp();
+            auto ridx = view->find_row_by_pkey(proto_to_pkey(dtype, r.index()));
+            if (ridx.has_value()) {
+                find_resp->set_row_index(*ridx);
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kViewGetUnderlyingRowsReq: {
+            const auto& r = req.view_get_underlying_rows_req();
+            auto view = m_resources.get_view(req.entity_id());
+            auto table_id = m_resources.get_table_id_for_view(req.entity_id());
+            auto table = m_resources.get_table(table_id);
+            auto config = view->get_view_config();
+
+            proto::Response resp;
+            auto* underlying = resp.mutable_view_get_underlying_rows_resp();
+            underlying->set_table_id(table_id);
+            const auto& hidden = config->get_hidden_columns();
+            for (const auto& col : table->get_schema().columns()) {
+                if (std::find(hidden.begin(), hidden.end(), col)
+                    == hidden.end()) {
+                    underlying->add_columns(col);
+                }
+            }
+
+            const auto& row_pivots = config->get_row_pivots();
+            if (!row_pivots.empty() && !config->is_column_only()) {
+                if (r.row_index() >= view->num_rows()) {
+                    PSP_COMPLAIN_AND_ABORT(
+                        "Row index out of range: "
+                        + std::to_string(r.row_index())
+                    );
+                }
+
+                // The total row has an empty path, and selects every row.
+                auto row_path = view->get_row_path(r.row_index());
+                for (std::size_t i = 0; i < row_path.size(); ++i) {
+                    underlying_filter_to_proto(
+                        row_pivots[i], row_path[i], underlying->add_filter()
+                    );
+                }
+            }
+
+            const auto& column_pivots = config->get_column_pivots();
+            if (!column_pivots.empty() && r.column_path_size() > 0) {
+                const auto path_size =
+                    static_cast<std::size_t>(r.column_path_size());
+
+                std::optional<std::vector<t_tscalar>> column_path;
+                for (const auto& path : view->column_paths_scalar()) {
+                    if (path.size() != path_size) {
+                        continue;
+                    }
+
+                    bool matches = true;
+                    for (std::size_t i = 0; matches && i < path.size(); ++i) {
+                        matches = path[i].to_string()
+                            == r.column_path(static_cast<int>(i));
+                    }
+
+                    if (matches) {
+                        column_path = path;
+                        break;
+                    }
+                }
+
+                if (!column_path.has_value()) {
+                    PSP_COMPLAIN_AND_ABORT("Unknown column path");
+                }
+
+                // The last element of the path is the aggregated column.
+                for (std::size_t i = 0;
+                     i < column_pivots.size() && i + 1 < column_path->size();
+                     ++i) {
+                    underlying_filter_to_proto(
+                        column_pivots[i],
+                        (*column_path)[i],
+                        underlying->add_filter()
+                    );
+                }
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
         case proto::Request::kServerSystemInfoReq: {
             proto::Response resp;
             auto* sys_info = resp.mutable_server_system_info_resp();
@@ -2440,6 +4443,120 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             getrusage(RUSAGE_SELF, &out);
             sys_info->set_heap_size(out.ru_maxrss);
 #endif
+            for (const auto& table_id : m_resources.get_table_ids()) {
+                auto table = m_resources.get_table(table_id);
+                auto view_ids = m_resources.get_view_ids(table_id);
+                auto* stats = sys_info->add_tables();
+                stats->set_entity_id(table_id);
+                stats->set_num_rows(table->size());
+                stats->set_num_views(view_ids.size());
+                stats->set_memory_bytes(
+                    table->get_gnode()->get_table()->nbytes()
+                );
+            }
+
+            for (const auto session_id : m_resources.get_session_ids()) {
+                auto* stats = sys_info->add_sessions();
+                stats->set_client_id(session_id);
+                stats->set_num_views(
+                    m_resources.get_session_num_views(session_id)
+                );
+                stats->set_num_subscriptions(
+                    m_resources.get_session_num_subscriptions(session_id)
+                );
+            }
+
+            for (const auto& [request_type, latency] :
+                 m_resources.get_request_latency()) {
+                auto* stats = sys_info->add_request_latency();
+                stats->set_request_type(request_type);
+                stats->set_count(latency.count);
+                stats->set_sum(latency.sum);
+                for (const auto count : latency.buckets) {
+                    stats->add_buckets(count);
+                }
+            }
+
+            for (const auto bound : REQUEST_LATENCY_BOUNDS) {
+                sys_info->add_latency_bounds(bound);
+            }
+
+            sys_info->set_poll_queue_depth(m_resources.get_num_dirty_tables());
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kGetHostedViewsReq: {
+            proto::Response resp;
+            auto* views = resp.mutable_get_hosted_views_resp();
+            for (const auto& table_id : m_resources.get_table_ids()) {
+                for (const auto& view_id : m_resources.get_view_ids(table_id)) {
+                    auto view = m_resources.get_view(view_id);
+                    auto* hosted = views->add_views();
+                    hosted->set_entity_id(view_id);
+                    hosted->set_table_id(table_id);
+                    auto owner = m_resources.get_client_id_for_view(view_id);
+                    if (owner.has_value()) {
+                        hosted->set_session_id(*owner);
+                    }
+
+                    view_config_to_proto(
+                        *view->get_view_config(), hosted->mutable_config()
+                    );
+
+                    hosted->set_num_subscriptions(
+                        m_resources.get_view_num_subscriptions(view_id)
+                    );
+                }
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kGetSessionsReq: {
+            proto::Response resp;
+            auto* sessions = resp.mutable_get_sessions_resp();
+            for (const auto session_id : m_resources.get_session_ids()) {
+                auto* info = sessions->add_sessions();
+                info->set_session_id(session_id);
+                for (const auto& view_id :
+                     m_resources.get_session_view_ids(session_id)) {
+                    info->add_view_ids(view_id);
+                }
+
+                info->set_num_subscriptions(
+                    m_resources.get_session_num_subscriptions(session_id)
+                );
+            }
+
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kDeleteHostedViewReq: {
+            auto owner = m_resources.get_client_id_for_view(req.entity_id());
+            _delete_view(
+                owner.value_or(client_id), req.entity_id(), proto_resp
+            );
+            proto::Response resp;
+            resp.mutable_delete_hosted_view_resp();
+            push_resp(std::move(resp));
+            break;
+        }
+        case proto::Request::kCloseSessionReq: {
+            auto session_id = req.close_session_req().session_id();
+            if (!m_resources.has_session(session_id)) {
+                PSP_COMPLAIN_AND_ABORT(
+                    "Unknown session " + std::to_string(session_id)
+                );
+            }
+
+            for (const auto& view_id :
+                 m_resources.get_session_view_ids(session_id)) {
+                _delete_view(session_id, view_id, proto_resp);
+            }
+
+            m_resources.drop_client(session_id);
+            proto::Response resp;
+            resp.mutable_close_session_resp();
             push_resp(std::move(resp));
             break;
         }
@@ -2461,9 +4578,30 @@ ProtoServer::_poll() {
     }
 
     m_resources.mark_all_tables_clean();
+    _flush_throttled_updates(resp_envs);
     return resp_envs;
 }
 
+void
+ProtoServer::_delete_view(
+    std::uint32_t client_id,
+    const ServerResources::t_id& view_id,
+    std::vector<ProtoServerResp<ProtoServer::Response>>& outs
+) {
+    for (const auto& sub : m_resources.get_view_on_delete_sub(view_id)) {
+        proto::Response resp;
+        resp.mutable_view_on_delete_resp();
+        resp.set_msg_id(sub.id);
+        resp.set_entity_id(view_id);
+        ProtoServerResp<proto::Response> resp2;
+        resp2.data = std::move(resp);
+        resp2.client_id = sub.client_id;
+        outs.emplace_back(std::move(resp2));
+    }
+
+    m_resources.delete_view(client_id, view_id);
+}
+
 void
 ProtoServer::_process_table_unchecked(
     std::shared_ptr<Table>& table,
@@ -2472,29 +4610,133 @@ ProtoServer::_process_table_unchecked(
 ) {
     table->get_pool()->_process([this, table_id, &outs](auto port_id) {
         // record changes per port.
+        auto now = std::chrono::steady_clock::now();
         auto view_ids = m_resources.get_view_ids(table_id);
         for (const auto& view_id : view_ids) {
             auto view = m_resources.get_view(view_id);
             auto subscriptions = m_resources.get_view_on_update_sub(view_id);
             for (auto& subscription : subscriptions) {
-                Response out;
-                out.set_msg_id(subscription.id);
-                out.set_entity_id(view_id);
-                auto* r = out.mutable_view_on_update_resp();
-                r->set_port_id(port_id);
-                if (view->get_deltas_enabled()) {
-                    *r->mutable_delta() = *view->get_row_delta_as_arrow();
+                auto throttle_ms = subscription.throttle_ms.value_or(
+                    m_resources.get_session_update_throttle(
+                        subscription.client_id
+                    )
+                );
+
+                // Withhold the update if one was sent within `throttle_ms`,
+                // keeping its `ROW` mode delta to send with the next one.
+                auto& throttle = *subscription.throttle;
+                if (now - throttle.last_sent
+                    < std::chrono::milliseconds(throttle_ms)) {
+                    throttle.pending_port_id = port_id;
+                    if (subscription.snapshot == nullptr
+                        && view->get_deltas_enabled()) {
+                        throttle.pending_deltas.push_back(
+                            *view->get_row_delta_as_arrow()
+                        );
+                    }
+
+                    continue;
                 }
 
-                ProtoServerResp<proto::Response> resp2;
-                resp2.data = std::move(out);
-                resp2.client_id = subscription.client_id;
-                outs.emplace_back(std::move(resp2));
+                _send_view_on_update(
+                    view_id, *view, subscription, port_id, true, outs
+                );
             }
         }
     });
 }
 
+void
+ProtoServer::_send_view_on_update(
+    const ServerResources::t_id& view_id,
+    ErasedView& view,
+    Subscription& subscription,
+    std::uint32_t port_id,
+    bool include_row_delta,
+    std::vector<ProtoServerResp<ProtoServer::Response>>& outs
+) {
+    auto& throttle = *subscription.throttle;
+    auto pending_deltas = std::move(throttle.pending_deltas);
+    throttle.pending_deltas.clear();
+    throttle.pending_port_id.reset();
+
+    Response out;
+    out.set_msg_id(subscription.id);
+    out.set_entity_id(view_id);
+    auto* r = out.mutable_view_on_update_resp();
+    r->set_port_id(port_id);
+    if (subscription.viewport.has_value()) {
+        // Skip the update if the subscribed region is unchanged.
+        auto data = viewport_to_arrow(view, *subscription.viewport);
+        if (*data == *subscription.viewport_data) {
+            return;
+        }
+
+        *subscription.viewport_data = std::move(*data);
+        if (subscription.include_viewport_data) {
+            *r->mutable_viewport_data() = *subscription.viewport_data;
+        }
+    }
+
+    if (subscription.snapshot != nullptr) {
+        auto snapshot = take_row_snapshot(
+            view, subscription.viewport.value_or(proto::ViewPort())
+        );
+
+        diff_row_snapshots(*subscription.snapshot, snapshot, r);
+        *subscription.snapshot = std::move(snapshot);
+    } else if (view.get_deltas_enabled()) {
+        if (include_row_delta) {
+            pending_deltas.push_back(*view.get_row_delta_as_arrow());
+        }
+
+        if (pending_deltas.size() == 1) {
+            *r->mutable_delta() = std::move(pending_deltas[0]);
+        } else if (!pending_deltas.empty()) {
+            *r->mutable_delta() = concat_arrow_streams(pending_deltas);
+        }
+    }
+
+    throttle.last_sent = std::chrono::steady_clock::now();
+    ProtoServerResp<proto::Response> resp2;
+    resp2.data = std::move(out);
+    resp2.client_id = subscription.client_id;
+    outs.emplace_back(std::move(resp2));
+}
+
+void
+ProtoServer::_flush_throttled_updates(
+    std::vector<ProtoServerResp<ProtoServer::Response>>& outs
+) {
+    auto now = std::chrono::steady_clock::now();
+    for (const auto& view_id : m_resources.get_view_ids_with_on_update_sub()) {
+        auto view = m_resources.get_view(view_id);
+        auto subscriptions = m_resources.get_view_on_update_sub(view_id);
+        for (auto& subscription : subscriptions) {
+            const auto& throttle = *subscription.throttle;
+            if (!throttle.pending_port_id.has_value()) {
+                continue;
+            }
+
+            auto throttle_ms = subscription.throttle_ms.value_or(
+                m_resources.get_session_update_throttle(subscription.client_id)
+            );
+
+            if (now - throttle.last_sent
+                >= std::chrono::milliseconds(throttle_ms)) {
+                _send_view_on_update(
+                    view_id,
+                    *view,
+                    subscription,
+                    *throttle.pending_port_id,
+                    false,
+                    outs
+                );
+            }
+        }
+    }
+}
+
 void
 ProtoServer::_process_table(
     std::shared_ptr<Table>& table,
@@ -2513,6 +4755,7 @@ void
 ServerResources::remove_view_on_update_sub(
     const t_id& view_id, std::uint32_t sub_id, std::uint32_t client_id
 ) {
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     if (m_view_on_update_subs.find(view_id) != m_view_on_update_subs.end()) {
         auto& subs = m_view_on_update_subs[view_id];
         subs.erase(
@@ -2530,6 +4773,7 @@ ServerResources::remove_view_on_update_sub(
 
 void
 ServerResources::drop_view_on_delete_sub(const t_id& view_id) {
+    PSP_SERVER_WRITE_LOCK(m_write_lock);
     m_view_on_delete_subs.erase(view_id);
 }
 
@@ -30,6 +30,7 @@
 #include <perspective/data_table.h>
 #include <perspective/filter_utils.h>
 #include <perspective/context_two.h>
+#include <perspective/computed_expression.h>
 #include <set>
 #include <utility>
 
@@ -71,6 +72,71 @@ get_dominant(std::vector<t_tscalar>& values) {
     return delem;
 }
 
+// Most frequent valid value, ties broken by the smallest value.
+t_tscalar
+get_mode(const std::vector<t_tscalar>& values) {
+    tsl::hopscotch_map<t_tscalar, t_uindex> counts;
+    for (const auto& v : values) {
+        if (v.is_valid() && !v.is_none()) {
+            ++counts[v];
+        }
+    }
+
+    t_tscalar mode = mknone();
+    t_uindex mode_count = 0;
+    for (const auto& [value, count] : counts) {
+        if (count > mode_count || (count == mode_count && value < mode)) {
+            mode = value;
+            mode_count = count;
+        }
+    }
+
+    return mode;
+}
+
+// HyperLogLog estimate of the number of distinct non-null values, using 2^12
+// registers (~1.6% standard error).
+std::uint32_t
+get_distinct_count_approx(const std::vector<t_tscalar>& values) {
+    constexpr std::uint32_t PRECISION = 12;
+    constexpr std::uint32_t NUM_REGISTERS = 1 << PRECISION;
+    std::vector<std::uint8_t> registers(NUM_REGISTERS, 0);
+    for (const auto& v : values) {
+        if (!v.is_valid() || v.is_none()) {
+            continue;
+        }
+
+        // `std::hash` is only 32 bits wide on wasm32, so spread it with the
+        // splitmix64 finalizer before splitting into index and rank.
+        std::uint64_t h = std::hash<t_tscalar>{}(v);
+        h += 0x9e3779b97f4a7c15ULL;
+        h = (h ^ (h >> 30)) * 0xbf58476d1ce4e5b9ULL;
+        h = (h ^ (h >> 27)) * 0x94d049bb133111ebULL;
+        h ^= h >> 31;
+        const std::uint64_t idx = h >> (64 - PRECISION);
+        const std::uint64_t rest = (h << PRECISION) | (1ULL << (PRECISION - 1));
+        const auto rank = static_cast<std::uint8_t>(__builtin_clzll(rest) + 1);
+        registers[idx] = std::max(registers[idx], rank);
+    }
+
+    double sum = 0;
+    std::uint32_t zeros = 0;
+    for (auto r : registers) {
+        sum += std::ldexp(1.0, -static_cast<int>(r));
+        zeros += r == 0 ? 1 : 0;
+    }
+
+    const double m = NUM_REGISTERS;
+    const double alpha = 0.7213 / (1.0 + 1.079 / m);
+    double estimate = alpha * m * m / sum;
+    if (estimate <= 2.5 * m && zeros > 0) {
+        // Linear counting for small cardinalities.
+        estimate = m * std::log(m / static_cast<double>(zeros));
+    }
+
+    return static_cast<std::uint32_t>(std::llround(estimate));
+}
+
 t_tree_unify_rec::t_tree_unify_rec(
     t_uindex sptidx, t_uindex daggidx, t_uindex saggidx, t_uindex nstrands
 ) :
@@ -1270,6 +1336,178 @@ t_stree::update_agg_table(
                 dst->set_valid(dst_ridx, valid);
                 new_value.set(nr / dr);
             } break;
+            case AGGTYPE_SUM_RATIO: {
+                auto pkeys = get_pkeys(nidx);
+
+                double nr = 0;
+                double dr = 0;
+                std::vector<t_tscalar> values;
+                std::vector<t_tscalar> divisors;
+
+                read_column_from_gstate(
+                    gstate,
+                    expression_master_table,
+                    spec.get_dependencies()[0].name(),
+                    pkeys,
+                    values
+                );
+
+                read_column_from_gstate(
+                    gstate,
+                    expression_master_table,
+                    spec.get_dependencies()[1].name(),
+                    pkeys,
+                    divisors
+                );
+
+                // Only rows where both columns are valid contribute, so that
+                // the numerator and denominator cover the same rows.
+                for (t_uindex idx = 0, loop_end = values.size();
+                     idx < loop_end && idx < divisors.size();
+                     ++idx) {
+                    const auto& value = values[idx];
+                    const auto& divisor = divisors[idx];
+                    if (value.is_valid() && divisor.is_valid()
+                        && !value.is_nan() && !divisor.is_nan()) {
+                        nr += value.to_double();
+                        dr += divisor.to_double();
+                    }
+                }
+
+                auto* dst_pair =
+                    dst->get_nth<std::pair<double, double>>(dst_ridx);
+                old_value.set(dst_pair->first / dst_pair->second);
+
+                dst_pair->first = nr;
+                dst_pair->second = dr;
+
+                bool valid = (dr != 0);
+                dst->set_valid(dst_ridx, valid);
+                new_value.set(nr / dr);
+            } break;
+            case AGGTYPE_LAST_BY:
+            case AGGTYPE_FIRST_BY: {
+                old_value.set(dst->get_scalar(dst_ridx));
+                auto pkeys = get_pkeys(nidx);
+
+                std::vector<t_tscalar> values;
+                std::vector<t_tscalar> keys;
+
+                read_column_from_gstate(
+                    gstate,
+                    expression_master_table,
+                    spec.get_dependencies()[0].name(),
+                    pkeys,
+                    values
+                );
+
+                read_column_from_gstate(
+                    gstate,
+                    expression_master_table,
+                    spec.get_dependencies()[1].name(),
+                    pkeys,
+                    keys
+                );
+
+                // The value at the max (`last by`) or min (`first by`) of the
+                // key column, preferring the earliest row on ties.
+                bool is_last = spec.agg() == AGGTYPE_LAST_BY;
+                t_index found = -1;
+                for (t_uindex idx = 0, loop_end = values.size();
+                     idx < loop_end && idx < keys.size();
+                     ++idx) {
+                    const auto& key = keys[idx];
+                    if (!key.is_valid() || key.is_none()) {
+                        continue;
+                    }
+
+                    if (found < 0
+                        || (is_last ? key > keys[found] : key < keys[found])) {
+                        found = static_cast<t_index>(idx);
+                    }
+                }
+
+                if (found < 0) {
+                    new_value.set(mknone());
+                } else {
+                    new_value.set(values[found]);
+                }
+
+                dst->set_scalar(dst_ridx, new_value);
+            } break;
+            case AGGTYPE_COVARIANCE:
+            case AGGTYPE_CORRELATION: {
+                old_value.set(dst->get_scalar(dst_ridx));
+                auto pkeys = get_pkeys(nidx);
+
+                std::vector<t_tscalar> xs;
+                std::vector<t_tscalar> ys;
+
+                read_column_from_gstate(
+                    gstate,
+                    expression_master_table,
+                    spec.get_dependencies()[0].name(),
+                    pkeys,
+                    xs
+                );
+
+                read_column_from_gstate(
+                    gstate,
+                    expression_master_table,
+                    spec.get_dependencies()[1].name(),
+                    pkeys,
+                    ys
+                );
+
+                // Welford-style co-moment over rows where both columns are
+                // valid, matching the population `var` aggregate.
+                double count = 0;
+                double mean_x = 0;
+                double mean_y = 0;
+                double m2_x = 0;
+                double m2_y = 0;
+                double c_xy = 0;
+
+                for (t_uindex idx = 0, loop_end = xs.size();
+                     idx < loop_end && idx < ys.size();
+                     ++idx) {
+                    const auto& sx = xs[idx];
+                    const auto& sy = ys[idx];
+                    if (!sx.is_valid() || !sy.is_valid() || sx.is_nan()
+                        || sy.is_nan()) {
+                        continue;
+                    }
+
+                    double x = sx.to_double();
+                    double y = sy.to_double();
+                    count++;
+                    double dx = x - mean_x;
+                    mean_x += dx / count;
+                    double next_mean_y = mean_y + (y - mean_y) / count;
+                    m2_x += dx * (x - mean_x);
+                    m2_y += (y - mean_y) * (y - next_mean_y);
+                    c_xy += dx * (y - next_mean_y);
+                    mean_y = next_mean_y;
+                }
+
+                bool valid = count >= 2;
+                double value = 0;
+                if (valid && spec.agg() == AGGTYPE_COVARIANCE) {
+                    value = c_xy / count;
+                } else if (valid) {
+                    double denom = std::sqrt(m2_x * m2_y);
+                    valid = denom != 0;
+                    value = valid ? c_xy / denom : 0;
+                }
+
+                if (valid) {
+                    new_value.set(value);
+                    dst->set_scalar(dst_ridx, new_value);
+                    dst->set_valid(dst_ridx, true);
+                } else {
+                    dst->set_valid(dst_ridx, false);
+                }
+            } break;
             case AGGTYPE_UNIQUE: {
                 auto pkeys = get_pkeys(nidx);
                 old_value.set(dst->get_scalar(dst_ridx));
@@ -1378,6 +1616,26 @@ t_stree::update_agg_table(
 
                 dst->set_scalar(dst_ridx, new_value);
             } break;
+            case AGGTYPE_PERCENTILE: {
+                old_value.set(dst->get_scalar(dst_ridx));
+                auto pkeys = get_pkeys(nidx);
+                const auto quantile = static_cast<float>(spec.get_quantile());
+
+                new_value.set(
+                    reduce_from_gstate<
+                        std::function<t_tscalar(std::vector<t_tscalar>&)>>(
+                        gstate,
+                        expression_master_table,
+                        spec.get_dependencies()[0].name(),
+                        pkeys,
+                        [&](std::vector<t_tscalar>& values) {
+                            return get_aggregate_median(values, quantile);
+                        }
+                    )
+                );
+
+                dst->set_scalar(dst_ridx, new_value);
+            } break;
             case AGGTYPE_JOIN: {
                 old_value.set(dst->get_scalar(dst_ridx));
                 auto pkeys = get_pkeys(nidx);
@@ -1491,6 +1749,25 @@ t_stree::update_agg_table(
 
                 dst->set_scalar(dst_ridx, new_value);
             } break;
+            case AGGTYPE_MODE: {
+                old_value.set(dst->get_scalar(dst_ridx));
+                auto pkeys = get_pkeys(nidx);
+
+                new_value.set(
+                    reduce_from_gstate<
+                        std::function<t_tscalar(std::vector<t_tscalar>&)>>(
+                        gstate,
+                        expression_master_table,
+                        spec.get_dependencies()[0].name(),
+                        pkeys,
+                        [](std::vector<t_tscalar>& values) {
+                            return get_mode(values);
+                        }
+                    )
+                );
+
+                dst->set_scalar(dst_ridx, new_value);
+            } break;
             case AGGTYPE_FIRST: {
                 old_value.set(dst->get_scalar(dst_ridx));
                 auto pair = first_last_helper(
@@ -1800,6 +2077,51 @@ t_stree::update_agg_table(
 
                 dst->set_scalar(dst_ridx, new_value);
             } break;
+            case AGGTYPE_DISTINCT_COUNT_APPROX: {
+                old_value.set(dst->get_scalar(dst_ridx));
+                auto pkeys = get_pkeys(nidx);
+
+                new_value.set(
+                    reduce_from_gstate<
+                        std::function<std::uint32_t(std::vector<t_tscalar>&)>>(
+                        gstate,
+                        expression_master_table,
+                        spec.get_dependencies()[0].name(),
+                        pkeys,
+                        [](std::vector<t_tscalar>& values) {
+                            return get_distinct_count_approx(values);
+                        }
+                    )
+                );
+
+                dst->set_scalar(dst_ridx, new_value);
+            } break;
+            case AGGTYPE_NULL_COUNT: {
+                old_value.set(dst->get_scalar(dst_ridx));
+                auto pkeys = get_pkeys(nidx);
+
+                new_value.set(
+                    reduce_from_gstate<
+                        std::function<std::uint32_t(std::vector<t_tscalar>&)>>(
+                        gstate,
+                        expression_master_table,
+                        spec.get_dependencies()[0].name(),
+                        pkeys,
+                        [](std::vector<t_tscalar>& values) {
+                            std::uint32_t rv = 0;
+                            for (const auto& v : values) {
+                                if (!v.is_valid() || v.is_none()) {
+                                    ++rv;
+                                }
+                            }
+
+                            return rv;
+                        }
+                    )
+                );
+
+                dst->set_scalar(dst_ridx, new_value);
+            } break;
             case AGGTYPE_DISTINCT_LEAF: {
                 auto pkeys = get_pkeys(nidx);
                 old_value.set(dst->get_scalar(dst_ridx));
@@ -1829,6 +2151,20 @@ t_stree::update_agg_table(
                     dst->set_scalar(dst_ridx, new_value);
                 }
             } break;
+            case AGGTYPE_CUSTOM: {
+                old_value.set(dst->get_scalar(dst_ridx));
+                auto pkeys = get_pkeys(nidx);
+                new_value.set(compute_custom_aggregate(
+                    spec, gstate, expression_master_table, pkeys
+                ));
+
+                if (new_value.is_none()) {
+                    dst->set_valid(dst_ridx, false);
+                } else {
+                    dst->set_scalar(dst_ridx, new_value);
+                    dst->set_valid(dst_ridx, true);
+                }
+            } break;
             case AGGTYPE_VARIANCE:
             case AGGTYPE_STANDARD_DEVIATION: {
                 old_value.set(dst->get_scalar(dst_ridx));
@@ -2672,6 +3008,74 @@ t_stree::apply_from_gstate(
     return gstate.apply(*gstate_master_table, colname, pkeys, value, fn);
 }
 
+/**
+ * Evaluate a `custom` aggregate's expression with each referenced column bound
+ * to an ExprTK vector of the group's values. `null` values are read as `0`.
+ */
+t_tscalar
+t_stree::compute_custom_aggregate(
+    const t_aggspec& spec,
+    const t_gstate& gstate,
+    const t_data_table& expression_master_table,
+    const std::vector<t_tscalar>& pkeys
+) const {
+    // ExprTK does not allow zero-length vectors.
+    if (pkeys.empty()) {
+        return mknone();
+    }
+
+    // `deps[0]` is the aggregated column, and `COLUMN<n>` is `deps[n + 1]`.
+    const auto& deps = spec.get_dependencies();
+    auto& custom = m_custom_aggregates[spec.name()];
+    if (custom == nullptr || custom->m_capacity < pkeys.size()) {
+        std::size_t capacity = pkeys.size();
+        if (custom != nullptr) {
+            capacity = std::max(capacity, custom->m_capacity * 2);
+        }
+
+        auto compiled =
+            std::make_shared<t_custom_aggregate>(deps.size() - 1, capacity);
+        t_expression_error error;
+        if (!compiled->compile(spec.get_expression(), error)) {
+            std::stringstream ss;
+            ss << "[t_stree::compute_custom_aggregate] Failed to parse "
+                  "expression: `"
+               << spec.get_expression()
+               << "`, failed with error: " << error.m_error_message << '\n';
+            PSP_COMPLAIN_AND_ABORT(ss.str());
+        }
+
+        custom = compiled;
+    }
+
+    for (t_uindex cidx = 0; cidx < custom->m_buffers.size(); ++cidx) {
+        std::vector<t_tscalar> values;
+        read_column_from_gstate(
+            gstate,
+            expression_master_table,
+            deps[cidx + 1].name(),
+            pkeys,
+            values
+        );
+
+        auto& buffer = custom->m_buffers[cidx];
+        for (t_uindex ridx = 0; ridx < values.size(); ++ridx) {
+            const auto& v = values[ridx];
+            buffer[ridx] =
+                mktscalar(v.is_valid() && !v.is_none() ? v.to_double() : 0.0);
+        }
+
+        custom->m_views[cidx].set_size(values.size());
+    }
+
+    t_tscalar value = custom->m_expression.value();
+    if (!value.is_valid() || value.is_none() || value.is_nan()) {
+        return mknone();
+    }
+
+    return mktscalar(value.to_double());
+}
+
 template <typename FN_T>
 typename FN_T::result_type
 t_stree::reduce_from_gstate(
@@ -15,6 +15,8 @@
 #include <perspective/sparse_tree.h>
 #include <perspective/arg_sort.h>
 #include <perspective/sort_specification.h>
+#include <cctype>
+#include <numeric>
 
 namespace perspective {
 
@@ -692,6 +694,225 @@ t_traversal::get_tree() const {
     return m_tree.get();
 }
 
+std::vector<t_index>
+t_traversal::get_row_indices(const t_row_totals& totals) const {
+    std::vector<t_index> rows;
+    rows.reserve(m_nodes->size());
+    auto is_visible = [&](t_index idx) {
+        const t_tvnode& node = (*m_nodes)[idx];
+        if (node.m_depth == 0) {
+            return totals.m_grand_total;
+        }
+
+        return totals.m_subtotals || node.m_ndesc == 0;
+    };
+
+    // Totals which are positioned at the bottom wait on this stack until a
+    // node at the same or a shallower depth closes their group.
+    std::vector<t_index> pending;
+    bool is_bottom = totals.m_position == TOTALS_POSITION_BOTTOM;
+    for (t_index idx = 0, loop_end = m_nodes->size(); idx < loop_end; ++idx) {
+        const t_tvnode& node = (*m_nodes)[idx];
+        while (!pending.empty()
+               && (*m_nodes)[pending.back()].m_depth >= node.m_depth) {
+            if (is_visible(pending.back())) {
+                rows.push_back(pending.back());
+            }
+
+            pending.pop_back();
+        }
+
+        if (is_bottom && node.m_ndesc > 0) {
+            pending.push_back(idx);
+        } else if (is_visible(idx)) {
+            rows.push_back(idx);
+        }
+    }
+
+    while (!pending.empty()) {
+        if (is_visible(pending.back())) {
+            rows.push_back(pending.back());
+        }
+
+        pending.pop_back();
+    }
+
+    return rows;
+}
+
+// Compare strings such that runs of digits compare by numeric value, so
+// e.g. "Q2" precedes "Q10".
+static bool
+natural_less(const std::string& a, const std::string& b) {
+    auto is_digit = [](char c) {
+        return std::isdigit(static_cast<unsigned char>(c)) != 0;
+    };
+
+    std::size_t i = 0;
+    std::size_t j = 0;
+    while (i < a.size() && j < b.size()) {
+        if (!is_digit(a[i]) || !is_digit(b[j])) {
+            if (a[i] != b[j]) {
+                return a[i] < b[j];
+            }
+
+            ++i;
+            ++j;
+            continue;
+        }
+
+        // Skip leading zeros, then a longer run of digits is a larger number.
+        while (i + 1 < a.size() && a[i] == '0' && is_digit(a[i + 1])) {
+            ++i;
+        }
+
+        while (j + 1 < b.size() && b[j] == '0' && is_digit(b[j + 1])) {
+            ++j;
+        }
+
+        std::size_t iend = i;
+        while (iend < a.size() && is_digit(a[iend])) {
+            ++iend;
+        }
+
+        std::size_t jend = j;
+        while (jend < b.size() && is_digit(b[jend])) {
+            ++jend;
+        }
+
+        if (iend - i != jend - j) {
+            return iend - i < jend - j;
+        }
+
+        int cmp = a.compare(i, iend - i, b, j, jend - j);
+        if (cmp != 0) {
+            return cmp < 0;
+        }
+
+        i = iend;
+        j = jend;
+    }
+
+    return a.size() - i < b.size() - j;
+}
+
+void
+t_traversal::sort_by_order(const std::vector<t_pivot_order>& orders) {
+    std::vector<t_tvnode> new_nodes;
+    new_nodes.reserve(m_nodes->size());
+    sort_subtree_by_order(0, orders, new_nodes);
+    std::swap(*m_nodes, new_nodes);
+}
+
+void
+t_traversal::sort_subtree_by_order(
+    t_index idx,
+    const std::vector<t_pivot_order>& orders,
+    std::vector<t_tvnode>& out_nodes
+) const {
+    const t_tvnode& node = (*m_nodes)[idx];
+    t_index new_idx = out_nodes.size();
+    out_nodes.push_back(node);
+    if (node.m_ndesc == 0) {
+        return;
+    }
+
+    std::vector<std::pair<t_index, t_index>> children;
+    get_child_indices(idx, children);
+
+    std::vector<t_index> sorted(children.size());
+    std::iota(sorted.begin(), sorted.end(), 0);
+    if (node.m_depth < orders.size() && !orders[node.m_depth].is_default()) {
+        const auto& order = orders[node.m_depth];
+        std::vector<std::string> values(children.size());
+        for (t_uindex i = 0, loop_end = children.size(); i < loop_end; ++i) {
+            values[i] = m_tree->get_value(children[i].second).to_string();
+        }
+
+        if (order.m_natural) {
+            std::stable_sort(
+                sorted.begin(),
+                sorted.end(),
+                [&](t_index a, t_index b) {
+                    return natural_less(values[a], values[b]);
+                }
+            );
+        } else {
+            // Unlisted values rank after all listed values.
+            std::vector<t_uindex> ranks(children.size());
+            for (t_uindex i = 0, loop_end = children.size(); i < loop_end;
+                 ++i) {
+                auto iter = std::find(
+                    order.m_values.begin(), order.m_values.end(), values[i]
+                );
+                ranks[i] = std::distance(order.m_values.begin(), iter);
+            }
+
+            std::stable_sort(
+                sorted.begin(),
+                sorted.end(),
+                [&](t_index a, t_index b) { return ranks[a] < ranks[b]; }
+            );
+        }
+    }
+
+    for (auto cidx : sorted) {
+        t_index child_idx = out_nodes.size();
+        sort_subtree_by_order(children[cidx].first, orders, out_nodes);
+        out_nodes[child_idx].m_rel_pidx = child_idx - new_idx;
+    }
+}
+
+t_row_map::t_row_map(const t_row_totals& totals) : m_totals(totals) {}
+
+void
+t_row_map::update(const t_traversal& traversal) {
+    if (m_totals.is_default()) {
+        return;
+    }
+
+    m_rows = traversal.get_row_indices(m_totals);
+    m_lookup.assign(traversal.size(), INVALID_INDEX);
+    for (t_index ridx = 0, loop_end = m_rows.size(); ridx < loop_end; ++ridx) {
+        m_lookup[m_rows[ridx]] = ridx;
+    }
+}
+
+t_uindex
+t_row_map::size(const t_traversal& traversal) const {
+    if (m_totals.is_default()) {
+        return traversal.size();
+    }
+
+    return m_rows.size();
+}
+
+t_index
+t_row_map::get_traversal_index(t_index ridx) const {
+    if (m_totals.is_default()) {
+        return ridx;
+    }
+
+    if (ridx < 0 || static_cast<t_uindex>(ridx) >= m_rows.size()) {
+        return INVALID_INDEX;
+    }
+
+    return m_rows[ridx];
+}
+
+t_index
+t_row_map::get_row_index(t_index tidx) const {
+    if (m_totals.is_default() || tidx == INVALID_INDEX) {
+        return tidx;
+    }
+
+    if (tidx < 0 || static_cast<t_uindex>(tidx) >= m_lookup.size()) {
+        return INVALID_INDEX;
+    }
+
+    return m_lookup[tidx];
+}
+
 bool
 t_traversal::get_node_expanded(t_index idx) const {
     if (idx < 0 || static_cast<t_uindex>(idx) > m_nodes->size()) {
@@ -0,0 +1,506 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#include <perspective/first.h>
+#include <perspective/value_format.h>
+#include <algorithm>
+#include <array>
+#include <cctype>
+#include <chrono>
+#include <cmath>
+#include <cstdio>
+#include <date/date.h>
+#include <vector>
+
+namespace perspective {
+
+using DatetimeStyle = proto::ColumnFormat::DatetimeStyle;
+
+static const std::array<const char*, 12> MONTH_NAMES = {
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December"
+};
+
+static const std::array<const char*, 7> WEEKDAY_NAMES = {
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday"
+};
+
+// The separators, date field order and clock of `tag`, matched by
+// `language-region` and then by language. An empty `tag` is `en-US`, and
+// locales which are not in this table are an error.
+static t_value_formatter::t_locale
+get_locale(const std::string& tag) {
+    using t_locale = t_value_formatter::t_locale;
+    std::string lower;
+    for (const char c : tag) {
+        lower.push_back(
+            c == '_' ? '-' : std::tolower(static_cast<unsigned char>(c))
+        );
+    }
+
+    const std::string language = lower.substr(0, lower.find('-'));
+    const std::string nbsp = "\u00a0";
+    const auto dmy = t_value_formatter::DATE_ORDER_DMY;
+    const auto ymd = t_value_formatter::DATE_ORDER_YMD;
+    if (lower == "en-gb" || lower == "en-ie") {
+        return t_locale{".", ",", dmy, "/", false, false, true};
+    }
+
+    if (lower == "en-au" || lower == "en-nz" || lower == "en-in") {
+        return t_locale{".", ",", dmy, "/", true, false, true};
+    }
+
+    if (lower == "en-ca") {
+        return t_locale{".", ",", ymd, "-", true, false, true};
+    }
+
+    if (lower == "de-ch") {
+        return t_locale{".", "\u2019", dmy, ".", false, false, false};
+    }
+
+    if (lower == "fr-ca") {
+        return t_locale{",", nbsp, ymd, "-", false, false, false};
+    }
+
+    if (language == "de") {
+        return t_locale{",", ".", dmy, ".", false, false, false};
+    }
+
+    if (language == "fr") {
+        return t_locale{",", "\u202f", dmy, "/", false, false, false};
+    }
+
+    if (language == "es" || language == "it" || language == "pt") {
+        return t_locale{",", ".", dmy, "/", false, false, false};
+    }
+
+    if (language == "nl") {
+        return t_locale{",", ".", dmy, "-", false, false, false};
+    }
+
+    if (language == "da") {
+        return t_locale{",", ".", dmy, ".", false, false, false};
+    }
+
+    if (language == "ru" || language == "uk" || language == "pl"
+        || language == "cs" || language == "sk" || language == "fi"
+        || language == "nb" || language == "no") {
+        return t_locale{",", nbsp, dmy, ".", false, false, false};
+    }
+
+    if (language == "sv") {
+        return t_locale{",", nbsp, ymd, "-", false, false, false};
+    }
+
+    if (language == "ja" || language == "zh") {
+        return t_locale{".", ",", ymd, "/", false, false, false};
+    }
+
+    if (language == "ko") {
+        return t_locale{".", ",", ymd, ".", true, false, false};
+    }
+
+    if (lower.empty() || lower == "en" || lower == "en-us") {
+        return t_locale{
+            ".", ",", t_value_formatter::DATE_ORDER_MDY, "/", true, true, true
+        };
+    }
+
+    PSP_COMPLAIN_AND_ABORT(
+        "Unsupported locale \"" + tag
+        + "\", expected one of \"en-US\", \"en-GB\", \"en-IE\", "
+          "\"en-AU\", \"en-NZ\", \"en-IN\", \"en-CA\", \"de-CH\", "
+          "\"fr-CA\" or a \"de\", \"fr\", \"es\", \"it\", \"pt\", "
+          "\"nl\", \"da\", \"ru\", \"uk\", \"pl\", \"cs\", \"sk\", "
+          "\"fi\", \"nb\", \"no\", \"sv\", \"ja\", \"zh\" or \"ko\" "
+          "locale"
+    );
+
+    return {};
+}
+
+// Parses `UTC` (or `GMT`, `Z`), optionally followed by a fixed offset such
+// as `+05:30`, `-0500` or `+5`, into minutes east of UTC.
+static std::int32_t
+parse_time_zone(const std::string& time_zone) {
+    std::string offset = time_zone;
+    for (const std::string prefix : {"Etc/UTC", "UTC", "GMT", "Z"}) {
+        if (offset.rfind(prefix, 0) == 0) {
+            offset = offset.substr(prefix.size());
+            break;
+        }
+    }
+
+    if (offset.empty()) {
+        return 0;
+    }
+
+    std::string digits;
+    for (std::size_t i = 1; i < offset.size(); ++i) {
+        if (offset[i] != ':') {
+            digits.push_back(offset[i]);
+        }
+    }
+
+    const bool is_valid = (offset[0] == '+' || offset[0] == '-')
+        && !digits.empty() && digits.size() <= 4
+        && std::all_of(digits.begin(), digits.end(), [](char c) {
+               return std::isdigit(static_cast<unsigned char>(c));
+           });
+
+    if (!is_valid) {
+        PSP_COMPLAIN_AND_ABORT(
+            "Unsupported time zone \"" + time_zone
+            + "\", expected \"UTC\" or a fixed offset such as \"+05:30\" "
+              "(IANA time zone names are not supported)"
+        );
+    }
+
+    std::int32_t hours = 0;
+    std::int32_t minutes = 0;
+    if (digits.size() <= 2) {
+        hours = std::stoi(digits);
+    } else {
+        hours = std::stoi(digits.substr(0, digits.size() - 2));
+        minutes = std::stoi(digits.substr(digits.size() - 2));
+    }
+
+    if (hours > 14 || minutes > 59) {
+        PSP_COMPLAIN_AND_ABORT(
+            "Invalid time zone offset \"" + time_zone + "\""
+        );
+    }
+
+    return (offset[0] == '-' ? -1 : 1) * (hours * 60 + minutes);
+}
+
+static std::string
+pad2(std::uint32_t value) {
+    return (value < 10 ? "0" : "") + std::to_string(value);
+}
+
+static std::string
+time_zone_name(std::int32_t offset_minutes) {
+    if (offset_minutes == 0) {
+        return "UTC";
+    }
+
+    const auto offset = static_cast<std::uint32_t>(std::abs(offset_minutes));
+    return std::string("UTC") + (offset_minutes < 0 ? "-" : "+")
+        + pad2(offset / 60) + ":" + pad2(offset % 60);
+}
+
+static std::string
+group_digits(const std::string& digits, const std::string& separator) {
+    std::string out;
+    for (std::size_t i = 0; i < digits.size(); ++i) {
+        if (i > 0 && (digits.size() - i) % 3 == 0) {
+            out += separator;
+        }
+
+        out.push_back(digits[i]);
+    }
+
+    return out;
+}
+
+static std::string
+format_time(
+    std::uint32_t hours,
+    std::uint32_t minutes,
+    std::uint32_t seconds,
+    DatetimeStyle style,
+    bool hour12,
+    const std::string& time_zone_name
+) {
+    if (style == proto::ColumnFormat::DATETIME_STYLE_DISABLED) {
+        return "";
+    }
+
+    std::string out = hour12 ? std::to_string(hours % 12 == 0 ? 12 : hours % 12)
+                             : pad2(hours);
+
+    out += ":" + pad2(minutes);
+    if (style != proto::ColumnFormat::DATETIME_STYLE_SHORT) {
+        out += ":" + pad2(seconds);
+    }
+
+    if (hour12) {
+        out += hours < 12 ? " AM" : " PM";
+    }
+
+    if (style == proto::ColumnFormat::DATETIME_STYLE_LONG
+        || style == proto::ColumnFormat::DATETIME_STYLE_FULL) {
+        out += " " + time_zone_name;
+    }
+
+    return out;
+}
+
+t_value_formatter::t_value_formatter(const proto::FormatOptions& options) :
+    m_locale(get_locale(options.locale())),
+    m_offset_minutes(parse_time_zone(options.time_zone())),
+    m_time_zone_name(time_zone_name(m_offset_minutes)) {
+    for (const auto& column : options.columns()) {
+        // `medium`, `long` and `full` dates name the month (and weekday), and
+        // only English names are available.
+        const auto style = column.second.date_style();
+        if (!m_locale.english_names && column.second.has_date_style()
+            && style != proto::ColumnFormat::DATETIME_STYLE_SHORT
+            && style != proto::ColumnFormat::DATETIME_STYLE_DISABLED) {
+            PSP_COMPLAIN_AND_ABORT(
+                "Unsupported date style for column \"" + column.first
+                + "\" in locale \"" + options.locale()
+                + "\", only \"short\" and \"disabled\" are supported "
+                  "outside of English locales"
+            );
+        }
+
+        m_columns[column.first] = column.second;
+    }
+}
+
+const proto::ColumnFormat&
+t_value_formatter::column_format(const std::string& column) const {
+    auto format = m_columns.find(column);
+    if (format == m_columns.end()) {
+        return m_default_format;
+    }
+
+    return format->second;
+}
+
+std::string
+t_value_formatter::format_number(
+    const std::string& column, double value, bool is_float
+) const {
+    if (std::isnan(value)) {
+        return "NaN";
+    }
+
+    const auto& format = column_format(column);
+    const bool is_percent =
+        format.style() == proto::ColumnFormat::NUMBER_STYLE_PERCENT;
+
+    if (is_percent) {
+        value *= 100;
+    }
+
+    // As `Intl.NumberFormat`, which the viewer defaults to 2 fraction digits
+    // for `float` columns and 0 for `integer` columns.
+    const std::uint32_t default_digits = is_float ? 2 : 0;
+    std::uint32_t max_digits = format.has_maximum_fraction_digits()
+        ? format.maximum_fraction_digits()
+        : default_digits;
+
+    std::uint32_t min_digits = format.has_minimum_fraction_digits()
+        ? format.minimum_fraction_digits()
+        : std::min(default_digits, max_digits);
+
+    min_digits = std::min(min_digits, 20U);
+    max_digits = std::min(std::max(max_digits, min_digits), 20U);
+    std::string sign = value < 0 ? "-" : "";
+    if (std::isinf(value)) {
+        return sign + "\u221e";
+    }
+
+    const auto precision = static_cast<int>(max_digits);
+    const auto size =
+        std::snprintf(nullptr, 0, "%.*f", precision, std::fabs(value));
+
+    std::vector<char> buffer(size + 1);
+    std::snprintf(
+        buffer.data(), buffer.size(), "%.*f", precision, std::fabs(value)
+    );
+
+    const std::string digits(buffer.data(), size);
+    const auto point = digits.find('.');
+    std::string integer = digits.substr(0, point);
+    std::string fraction =
+        point == std::string::npos ? "" : digits.substr(point + 1);
+
+    while (fraction.size() > min_digits && fraction.back() == '0') {
+        fraction.pop_back();
+    }
+
+    if (std::all_of(integer.begin(), integer.end(), [](char c) {
+            return c == '0';
+        })
+        && std::all_of(fraction.begin(), fraction.end(), [](char c) {
+               return c == '0';
+           })) {
+        sign = "";
+    }
+
+    if (!format.has_use_grouping() || format.use_grouping()) {
+        integer = group_digits(integer, m_locale.group);
+    }
+
+    std::string out = sign + integer;
+    if (!fraction.empty()) {
+        out += m_locale.decimal + fraction;
+    }
+
+    if (is_percent) {
+        out += "%";
+    }
+
+    return out;
+}
+
+std::string
+t_value_formatter::format_date_style(
+    std::int32_t year,
+    std::uint32_t month,
+    std::uint32_t day,
+    std::uint32_t weekday,
+    DatetimeStyle style
+) const {
+    std::string out;
+    switch (style) {
+        case proto::ColumnFormat::DATETIME_STYLE_SHORT: {
+            const bool is_short = m_locale.short_numeric;
+            const auto y =
+                is_short ? pad2(std::abs(year) % 100) : std::to_string(year);
+
+            const auto m = is_short ? std::to_string(month) : pad2(month);
+            const auto d = is_short ? std::to_string(day) : pad2(day);
+            const auto& sep = m_locale.date_separator;
+            switch (m_locale.order) {
+                case DATE_ORDER_MDY:
+                    return m + sep + d + sep + y;
+                case DATE_ORDER_DMY:
+                    return d + sep + m + sep + y;
+                case DATE_ORDER_YMD:
+                    return y + sep + m + sep + d;
+            }
+
+            return out;
+        }
+        case proto::ColumnFormat::DATETIME_STYLE_MEDIUM:
+        case proto::ColumnFormat::DATETIME_STYLE_LONG:
+        case proto::ColumnFormat::DATETIME_STYLE_FULL: {
+            std::string name = MONTH_NAMES.at(month - 1);
+            if (style == proto::ColumnFormat::DATETIME_STYLE_MEDIUM) {
+                name = name.substr(0, 3);
+            }
+
+            const auto y = std::to_string(year);
+            const auto d = std::to_string(day);
+            switch (m_locale.order) {
+                case DATE_ORDER_MDY:
+                    out = name + " " + d + ", " + y;
+                    break;
+                case DATE_ORDER_DMY:
+                    out = d + " " + name + " " + y;
+                    break;
+                case DATE_ORDER_YMD:
+                    out = y + " " + name + " " + d;
+                    break;
+            }
+
+            if (style == proto::ColumnFormat::DATETIME_STYLE_FULL) {
+                out = std::string(WEEKDAY_NAMES.at(weekday)) + ", " + out;
+            }
+
+            return out;
+        }
+        default:
+            return out;
+    }
+}
+
+std::string
+t_value_formatter::format_date(
+    const std::string& column,
+    std::int32_t year,
+    std::uint32_t month,
+    std::uint32_t day
+) const {
+    const auto& format = column_format(column);
+    const auto style = format.has_date_style()
+        ? format.date_style()
+        : proto::ColumnFormat::DATETIME_STYLE_SHORT;
+
+    const date::year_month_day ymd{
+        date::year{year}, date::month{month}, date::day{day}
+    };
+
+    const auto weekday = date::weekday{date::sys_days{ymd}}.c_encoding();
+    return format_date_style(year, month, day, weekday, style);
+}
+
+std::string
+t_value_formatter::format_datetime(
+    const std::string& column, std::int64_t ms
+) const {
+    const auto& format = column_format(column);
+    const auto date_style = format.has_date_style()
+        ? format.date_style()
+        : proto::ColumnFormat::DATETIME_STYLE_SHORT;
+
+    const auto time_style = format.has_time_style()
+        ? format.time_style()
+        : proto::ColumnFormat::DATETIME_STYLE_MEDIUM;
+
+    const date::sys_time<std::chrono::milliseconds> time{
+        std::chrono::milliseconds{ms + std::int64_t{m_offset_minutes} * 60000}
+    };
+
+    const auto days = date::floor<date::days>(time);
+    const date::year_month_day ymd{days};
+    const date::hh_mm_ss<std::chrono::milliseconds> hms{time - days};
+    const auto date_part = format_date_style(
+        static_cast<int>(ymd.year()),
+        static_cast<unsigned>(ymd.month()),
+        static_cast<unsigned>(ymd.day()),
+        date::weekday{days}.c_encoding(),
+        date_style
+    );
+
+    const auto time_part = format_time(
+        hms.hours().count(),
+        hms.minutes().count(),
+        hms.seconds().count(),
+        time_style,
+        m_locale.hour12,
+        m_time_zone_name
+    );
+
+    if (date_part.empty()) {
+        return time_part;
+    }
+
+    if (time_part.empty()) {
+        return date_part;
+    }
+
+    return date_part + ", " + time_part;
+}
+
+} // end namespace perspective
@@ -1419,6 +1419,17 @@ View<CTX_T>::get_row_path(t_uindex idx) const {
     return m_ctx->unity_get_row_path(idx);
 }
 
+template <typename CTX_T>
+std::optional<t_uindex>
+View<CTX_T>::find_row_by_pkey(t_tscalar pkey) const {
+    auto ridx = m_ctx->get_row_idx_for_pkey(pkey);
+    if (ridx == INVALID_INDEX || static_cast<t_uindex>(ridx) < m_row_offset) {
+        return std::nullopt;
+    }
+
+    return static_cast<t_uindex>(ridx) - m_row_offset;
+}
+
 template <typename CTX_T>
 t_stepdelta
 View<CTX_T>::get_step_delta(t_index bidx, t_index eidx) const {
@@ -1510,6 +1521,8 @@ View<CTX_T>::_map_aggregate_types(
         if (agg.name() == name) {
             switch (agg.agg()) {
                 case AGGTYPE_DISTINCT_COUNT:
+                case AGGTYPE_DISTINCT_COUNT_APPROX:
+                case AGGTYPE_NULL_COUNT:
                 case AGGTYPE_COUNT: {
                     return "integer";
                 } break;
@@ -1519,7 +1532,11 @@ View<CTX_T>::_map_aggregate_types(
                 case AGGTYPE_PCT_SUM_PARENT:
                 case AGGTYPE_PCT_SUM_GRAND_TOTAL:
                 case AGGTYPE_VARIANCE:
-                case AGGTYPE_STANDARD_DEVIATION: {
+                case AGGTYPE_STANDARD_DEVIATION:
+                case AGGTYPE_SUM_RATIO:
+                case AGGTYPE_COVARIANCE:
+                case AGGTYPE_CORRELATION:
+                case AGGTYPE_CUSTOM: {
                     return "float";
                 } break;
                 default: {
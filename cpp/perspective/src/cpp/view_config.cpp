@@ -12,10 +12,131 @@
 
 #include <perspective/view_config.h>
 
+#include <map>
 #include <utility>
 
 namespace perspective {
 
+/**
+ * Parse the quantile argument of a `["percentile", "0.95"]` aggregate,
+ * which must lie strictly between 0 and 1.
+ */
+static double
+parse_quantile(const std::vector<std::string>& aggregate) {
+    if (aggregate.size() < 2) {
+        PSP_COMPLAIN_AND_ABORT("`percentile` requires a quantile argument");
+    }
+
+    double quantile = 0;
+    try {
+        quantile = std::stod(aggregate.at(1));
+    } catch (const std::exception&) {
+        PSP_COMPLAIN_AND_ABORT(
+            "Invalid `percentile` quantile: " + aggregate.at(1)
+        );
+    }
+
+    if (!(quantile > 0 && quantile < 1)) {
+        PSP_COMPLAIN_AND_ABORT(
+            "`percentile` quantile must be between 0 and 1, got "
+            + aggregate.at(1)
+        );
+    }
+
+    return quantile;
+}
+
+/**
+ * Parse the expression argument of a `["custom", "sum(\"x\")"]` aggregate,
+ * replacing each `"column"` reference with an id and pushing the column
+ * onto `dependencies`. The id `COLUMN<n>` refers to `dependencies[n + 1]`, as
+ * `dependencies[0]` is the aggregated column itself.
+ */
+static std::string
+parse_custom_expression(
+    const std::vector<std::string>& aggregate, std::vector<t_dep>& dependencies
+) {
+    if (aggregate.size() < 2) {
+        PSP_COMPLAIN_AND_ABORT("`custom` requires an expression argument");
+    }
+
+    std::vector<std::string> columns;
+    std::string parsed =
+        t_custom_aggregate::parse_expression(aggregate.at(1), columns);
+    for (const auto& column : columns) {
+        dependencies.emplace_back(column, DEPTYPE_COLUMN);
+    }
+
+    return parsed;
+}
+
+/**
+ * Aggregates which take a second column as an argument, e.g.
+ * `["weighted mean", "weights"]`.
+ */
+static bool
+is_column_parameterized(t_aggtype agg_type) {
+    switch (agg_type) {
+        case AGGTYPE_WEIGHTED_MEAN:
+        case AGGTYPE_SUM_RATIO:
+        case AGGTYPE_LAST_BY:
+        case AGGTYPE_FIRST_BY:
+        case AGGTYPE_COVARIANCE:
+        case AGGTYPE_CORRELATION:
+            return true;
+        default:
+            return false;
+    }
+}
+
+/**
+ * Parse an aggregate from its `ViewConfig` form, pushing the column argument
+ * (if any) onto `dependencies`, the `percentile` argument into `quantile` and
+ * the `custom` argument into `expression`.
+ */
+static t_aggtype
+parse_aggregate(
+    const std::vector<std::string>& aggregate,
+    std::vector<t_dep>& dependencies,
+    double& quantile,
+    std::string& expression
+) {
+    t_aggtype agg_type = str_to_aggtype(aggregate.at(0));
+    if (agg_type == AGGTYPE_PERCENTILE) {
+        quantile = parse_quantile(aggregate);
+    } else if (agg_type == AGGTYPE_CUSTOM) {
+        expression = parse_custom_expression(aggregate, dependencies);
+    } else if (is_column_parameterized(agg_type)) {
+        if (aggregate.size() < 2) {
+            PSP_COMPLAIN_AND_ABORT(
+                "`" + aggregate.at(0) + "` requires a column argument"
+            );
+        }
+
+        dependencies.emplace_back(aggregate.at(1), DEPTYPE_COLUMN);
+    }
+
+    return agg_type;
+}
+
+/**
+ * The aggregate of a column in a column-only view, whose rows are shown as
+ * they are, so `ANY` suffices - except for `percentile`, which keeps its
+ * quantile as column sorts still compare the aggregate of each column's
+ * total.
+ */
+static t_aggtype
+parse_column_only_aggregate(
+    const std::vector<std::string>& aggregate, double& quantile
+) {
+    if (str_to_aggtype(aggregate.at(0)) == AGGTYPE_PERCENTILE) {
+        quantile = parse_quantile(aggregate);
+        return AGGTYPE_PERCENTILE;
+    }
+
+    return AGGTYPE_ANY;
+}
+
 t_view_config::t_view_config(
     t_vocab vocab,
     const std::vector<std::string>& row_pivots,
@@ -43,6 +164,37 @@ t_view_config::t_view_config(
     m_filter_op(std::move(filter_op)),
     m_column_only(column_only) {}
 
+std::vector<t_fterm>
+make_fterms(
+    const std::vector<
+        std::tuple<std::string, std::string, std::vector<t_tscalar>>>& filter
+) {
+    std::vector<t_fterm> fterms;
+    fterms.reserve(filter.size());
+    for (const auto& term : filter) {
+        t_filter_op op = str_to_filter_op(std::get<1>(term));
+        switch (op) {
+            case FILTER_OP_NOT_IN:
+            case FILTER_OP_IN: {
+                fterms.emplace_back(
+                    std::get<0>(term), op, mktscalar(0), std::get<2>(term)
+                );
+            } break;
+            default: {
+                t_tscalar filter_term = std::get<2>(term)[0];
+                fterms.emplace_back(
+                    std::get<0>(term),
+                    op,
+                    filter_term,
+                    std::vector<t_tscalar>()
+                );
+            }
+        }
+    }
+
+    return fterms;
+}
+
 void
 t_view_config::init(const std::shared_ptr<t_schema>& schema) {
     validate(schema);
@@ -109,6 +261,16 @@ t_view_config::validate(const std::shared_ptr<t_schema>& schema) {
         }
     }
 
+    for (const auto& filter : m_session_filter) {
+        const std::string& col = std::get<0>(filter);
+        if (!schema->has_column(col)) {
+            std::stringstream ss;
+            ss << "Invalid column '" << col << "' found in session filters."
+               << '\n';
+            PSP_COMPLAIN_AND_ABORT(ss.str());
+        }
+    }
+
     for (const auto& sort : m_sort) {
         const std::string& col = sort[0];
         if (!schema->has_column(col) && expression_aliases.count(col) == 0) {
@@ -136,6 +298,10 @@ t_view_config::get_used_expressions() {
         used_cols.insert(std::get<0>(i));
     }
 
+    for (const auto& i : m_session_filter) {
+        used_cols.insert(std::get<0>(i));
+    }
+
     for (auto i : m_sort) {
         used_cols.insert(i[0]);
     }
@@ -158,6 +324,17 @@ t_view_config::get_used_expressions() {
         std::inserter(used_cols, used_cols.end())
     );
 
+    // Windows may read from other expressions which are not otherwise used.
+    for (const auto& expr : m_expressions) {
+        if (expr->is_window()
+            && used_cols.find(expr->get_expression_alias()) != used_cols.end()
+        ) {
+            for (const auto& [_, column] : expr->get_column_ids()) {
+                used_cols.insert(column);
+            }
+        }
+    }
+
     auto iter = std::remove_if(
         exprs.begin(),
         exprs.end(),
@@ -192,6 +369,25 @@ t_view_config::add_filter_term(
     m_filter.push_back(term);
 }
 
+void
+t_view_config::set_session_filter(
+    const std::vector<
+        std::tuple<std::string, std::string, std::vector<t_tscalar>>>& filter
+) {
+    PSP_VERBOSE_ASSERT(!m_init, "session filter set after init");
+    m_session_filter = filter;
+}
+
+void
+t_view_config::set_column_policy(
+    const std::vector<std::string>& hidden,
+    const std::map<std::string, t_column_mask>& masks
+) {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    m_hidden_columns = hidden;
+    m_column_masks = masks;
+}
+
 void
 t_view_config::set_row_pivot_depth(std::int32_t depth) {
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
@@ -204,6 +400,20 @@ t_view_config::set_column_pivot_depth(std::int32_t depth) {
     m_column_pivot_depth = depth;
 }
 
+void
+t_view_config::set_row_totals(const t_row_totals& totals) {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    m_row_totals = totals;
+}
+
+void
+t_view_config::set_split_by_order(
+    const std::map<std::string, t_pivot_order>& orders
+) {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    m_split_by_order = orders;
+}
+
 std::vector<std::string>
 t_view_config::get_row_pivots() const {
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
@@ -222,6 +432,11 @@ t_view_config::get_aggspecs() const {
     return m_aggspecs;
 }
 
+tsl::ordered_map<std::string, std::vector<std::string>>
+t_view_config::get_aggregates() const {
+    return m_aggregates;
+}
+
 std::vector<std::string>
 t_view_config::get_columns() const {
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
@@ -234,6 +449,12 @@ t_view_config::get_fterm() const {
     return m_fterm;
 }
 
+std::vector<t_fterm>
+t_view_config::get_session_fterm() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    return m_session_fterm;
+}
+
 std::vector<t_sortspec>
 t_view_config::get_sortspec() const {
     PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
@@ -276,6 +497,52 @@ t_view_config::get_column_pivot_depth() const {
     return m_column_pivot_depth;
 }
 
+const t_row_totals&
+t_view_config::get_row_totals() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    return m_row_totals;
+}
+
+const std::map<std::string, t_pivot_order>&
+t_view_config::get_split_by_order() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    return m_split_by_order;
+}
+
+const std::vector<std::string>&
+t_view_config::get_hidden_columns() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    return m_hidden_columns;
+}
+
+const std::map<std::string, t_column_mask>&
+t_view_config::get_column_masks() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    return m_column_masks;
+}
+
+std::vector<t_pivot_order>
+t_view_config::get_col_pivot_orders() const {
+    PSP_VERBOSE_ASSERT(m_init, "touching uninited object");
+    std::vector<t_pivot_order> orders;
+    bool has_order = false;
+    for (const auto& pivot : m_column_pivots) {
+        auto iter = m_split_by_order.find(pivot);
+        if (iter == m_split_by_order.end()) {
+            orders.emplace_back();
+        } else {
+            orders.push_back(iter->second);
+            has_order = has_order || !iter->second.is_default();
+        }
+    }
+
+    if (!has_order) {
+        orders.clear();
+    }
+
+    return orders;
+}
+
 // PRIVATE
 void
 t_view_config::fill_aggspecs(const std::shared_ptr<t_schema>& schema) {
@@ -330,8 +597,14 @@ t_view_config::fill_aggspecs(const std::shared_ptr<t_schema>& schema) {
 
             std::vector<t_dep> dependencies{t_dep(column, DEPTYPE_COLUMN)};
             t_aggtype agg_type;
+            double quantile = 0.5;
+            std::string expression;
 
-            if (is_column_only) {
+            if (is_column_only && m_aggregates.count(column) > 0) {
+                agg_type = parse_column_only_aggregate(
+                    m_aggregates.at(column), quantile
+                );
+            } else if (is_column_only) {
                 // Always sort by `ANY` in column only views
                 agg_type = t_aggtype::AGGTYPE_ANY;
             } else if ((is_row_pivot && is_row_sort) || (is_column_pivot && !is_row_sort)) {
@@ -340,18 +613,16 @@ t_view_config::fill_aggspecs(const std::shared_ptr<t_schema>& schema) {
                 agg_type = t_aggtype::AGGTYPE_UNIQUE;
             } else if (m_aggregates.count(column) > 0) {
                 auto col = m_aggregates.at(column);
-                if (col.at(0) == "weighted mean") {
-                    dependencies.emplace_back(col.at(1), DEPTYPE_COLUMN);
-                    agg_type = AGGTYPE_WEIGHTED_MEAN;
-                } else {
-                    agg_type = str_to_aggtype(col.at(0));
-                }
+                agg_type =
+                    parse_aggregate(col, dependencies, quantile, expression);
             } else {
                 t_dtype dtype = schema->get_dtype(column);
                 agg_type = _get_default_aggregate(dtype);
             }
 
-            m_aggspecs.emplace_back(column, agg_type, dependencies);
+            m_aggspecs.emplace_back(
+                column, agg_type, dependencies, quantile, expression
+            );
             m_aggregate_names.push_back(column);
         }
     }
@@ -359,26 +630,8 @@ t_view_config::fill_aggspecs(const std::shared_ptr<t_schema>& schema) {
 
 void
 t_view_config::fill_fterm() {
-    for (auto filter : m_filter) {
-        t_filter_op op = str_to_filter_op(std::get<1>(filter));
-        switch (op) {
-            case FILTER_OP_NOT_IN:
-            case FILTER_OP_IN: {
-                m_fterm.emplace_back(
-                    std::get<0>(filter), op, mktscalar(0), std::get<2>(filter)
-                );
-            } break;
-            default: {
-                t_tscalar filter_term = std::get<2>(filter)[0];
-                m_fterm.emplace_back(
-                    std::get<0>(filter),
-                    op,
-                    filter_term,
-                    std::vector<t_tscalar>()
-                );
-            }
-        }
-    }
+    m_fterm = make_fterms(m_filter);
+    m_session_fterm = make_fterms(m_session_filter);
 }
 
 void
@@ -416,20 +669,18 @@ t_view_config::make_aggspec(
 ) {
     t_aggtype agg_type;
     t_aggspec aggspec;
+    double quantile = 0.5;
+    std::string expression;
 
     // Maximum of 2 dependencies, based on the aggregate type
     std::vector<t_dep> dependencies{t_dep(column, DEPTYPE_COLUMN)};
     dependencies.reserve(2);
 
     if (m_column_only) {
-        agg_type = t_aggtype::AGGTYPE_ANY;
+        agg_type = parse_column_only_aggregate(aggregate, quantile);
     } else {
-        if (aggregate.at(0) == "weighted mean") {
-            dependencies.emplace_back(aggregate.at(1), DEPTYPE_COLUMN);
-            agg_type = AGGTYPE_WEIGHTED_MEAN;
-        } else {
-            agg_type = str_to_aggtype(aggregate.at(0));
-        }
+        agg_type =
+            parse_aggregate(aggregate, dependencies, quantile, expression);
     }
 
     if (agg_type == AGGTYPE_FIRST || agg_type == AGGTYPE_LAST_BY_INDEX
@@ -439,7 +690,8 @@ t_view_config::make_aggspec(
             column, column, agg_type, dependencies, SORTTYPE_ASCENDING
         );
     } else {
-        aggspec = t_aggspec(column, agg_type, dependencies);
+        aggspec =
+            t_aggspec(column, agg_type, dependencies, quantile, expression);
     }
 
     m_aggspecs.push_back(aggspec);
@@ -0,0 +1,673 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#include <perspective/first.h>
+#include <perspective/window.h>
+
+#include <algorithm>
+#include <deque>
+#include <tsl/hopscotch_set.h>
+
+namespace perspective {
+
+t_window_function
+str_to_window_function(const std::string& str) {
+    if (str == "sum") {
+        return WINDOW_FUNCTION_SUM;
+    }
+    if (str == "mean") {
+        return WINDOW_FUNCTION_MEAN;
+    }
+    if (str == "min") {
+        return WINDOW_FUNCTION_MIN;
+    }
+    if (str == "max") {
+        return WINDOW_FUNCTION_MAX;
+    }
+    if (str == "count") {
+        return WINDOW_FUNCTION_COUNT;
+    }
+    if (str == "row number") {
+        return WINDOW_FUNCTION_ROW_NUMBER;
+    }
+    if (str == "rank") {
+        return WINDOW_FUNCTION_RANK;
+    }
+    if (str == "dense rank") {
+        return WINDOW_FUNCTION_DENSE_RANK;
+    }
+    if (str == "lag") {
+        return WINDOW_FUNCTION_LAG;
+    }
+    if (str == "lead") {
+        return WINDOW_FUNCTION_LEAD;
+    }
+    if (str == "percent of previous") {
+        return WINDOW_FUNCTION_PERCENT_OF_PREVIOUS;
+    }
+
+    std::stringstream ss;
+    ss << "Unknown window function: `" << str << "`" << std::endl;
+    PSP_COMPLAIN_AND_ABORT(ss.str());
+    return WINDOW_FUNCTION_SUM;
+}
+
+std::string
+window_function_to_str(t_window_function function) {
+    switch (function) {
+        case WINDOW_FUNCTION_SUM:
+            return "sum";
+        case WINDOW_FUNCTION_MEAN:
+            return "mean";
+        case WINDOW_FUNCTION_MIN:
+            return "min";
+        case WINDOW_FUNCTION_MAX:
+            return "max";
+        case WINDOW_FUNCTION_COUNT:
+            return "count";
+        case WINDOW_FUNCTION_ROW_NUMBER:
+            return "row number";
+        case WINDOW_FUNCTION_RANK:
+            return "rank";
+        case WINDOW_FUNCTION_DENSE_RANK:
+            return "dense rank";
+        case WINDOW_FUNCTION_LAG:
+            return "lag";
+        case WINDOW_FUNCTION_LEAD:
+            return "lead";
+        case WINDOW_FUNCTION_PERCENT_OF_PREVIOUS:
+            return "percent of previous";
+    }
+
+    PSP_COMPLAIN_AND_ABORT("Unknown window function");
+    return "";
+}
+
+bool
+window_function_has_column(t_window_function function) {
+    switch (function) {
+        case WINDOW_FUNCTION_ROW_NUMBER:
+        case WINDOW_FUNCTION_RANK:
+        case WINDOW_FUNCTION_DENSE_RANK:
+            return false;
+        default:
+            return true;
+    }
+}
+
+// The columns a window reads from, in the `(id, name)` format of
+// `t_computed_expression::get_column_ids()`.
+static std::vector<std::pair<std::string, std::string>>
+window_column_ids(
+    const std::string& column,
+    const std::vector<std::string>& partition_by,
+    const std::vector<std::pair<std::string, t_sorttype>>& order_by
+) {
+    std::vector<std::pair<std::string, std::string>> column_ids;
+    if (!column.empty()) {
+        column_ids.emplace_back(column, column);
+    }
+
+    for (const auto& name : partition_by) {
+        column_ids.emplace_back(name, name);
+    }
+
+    for (const auto& [name, _] : order_by) {
+        column_ids.emplace_back(name, name);
+    }
+
+    return column_ids;
+}
+
+t_computed_window::t_computed_window(
+    std::string name,
+    t_window_function function,
+    std::string column,
+    std::vector<std::string> partition_by,
+    std::vector<std::pair<std::string, t_sorttype>> order_by,
+    std::optional<t_uindex> preceding,
+    std::optional<t_uindex> following,
+    t_uindex offset,
+    const t_data_table* gstate_table,
+    t_dtype dtype
+) :
+    t_computed_expression(
+        std::move(name),
+        window_function_to_str(function),
+        "",
+        window_column_ids(column, partition_by, order_by),
+        dtype
+    ),
+    m_function(function),
+    m_column(std::move(column)),
+    m_partition_by(std::move(partition_by)),
+    m_order_by(std::move(order_by)),
+    m_preceding(preceding),
+    m_following(following),
+    m_offset(offset),
+    m_gstate_table(gstate_table) {}
+
+t_dtype
+t_computed_window::get_output_dtype(
+    t_window_function function, t_dtype column_dtype
+) {
+    switch (function) {
+        case WINDOW_FUNCTION_SUM:
+        case WINDOW_FUNCTION_MEAN:
+        case WINDOW_FUNCTION_PERCENT_OF_PREVIOUS:
+            return DTYPE_FLOAT64;
+        case WINDOW_FUNCTION_COUNT:
+        case WINDOW_FUNCTION_ROW_NUMBER:
+        case WINDOW_FUNCTION_RANK:
+        case WINDOW_FUNCTION_DENSE_RANK:
+            return DTYPE_INT64;
+        case WINDOW_FUNCTION_MIN:
+        case WINDOW_FUNCTION_MAX:
+        case WINDOW_FUNCTION_LAG:
+        case WINDOW_FUNCTION_LEAD:
+            return column_dtype;
+    }
+
+    return DTYPE_NONE;
+}
+
+void
+t_computed_window::compute(
+    const std::shared_ptr<t_data_table>& source_table,
+    const t_gstate::t_mapping& pkey_map,
+    const std::shared_ptr<t_data_table>& destination_table,
+    t_expression_vocab& vocab,
+    t_regex_mapping& regex_mapping
+) const {
+    bool is_master = source_table.get() == m_gstate_table;
+    if (is_master) {
+        compute_master(*source_table, pkey_map, *destination_table);
+    }
+
+    auto output_column = destination_table->add_column_sptr(
+        get_expression_alias(), get_dtype(), true
+    );
+
+    auto num_rows = source_table->size();
+    output_column->reserve(num_rows);
+
+    std::shared_ptr<const t_column> pkey_column;
+    if (!is_master && source_table->get_schema().has_column("psp_pkey")) {
+        pkey_column = source_table->get_const_column("psp_pkey");
+    }
+
+    for (t_uindex ridx = 0; ridx < num_rows; ++ridx) {
+        t_uindex master_ridx = ridx;
+        if (!is_master) {
+            if (pkey_column == nullptr) {
+                output_column->clear(ridx);
+                continue;
+            }
+
+            auto iter = pkey_map.find(pkey_column->get_scalar(ridx));
+            if (iter == pkey_map.end()) {
+                output_column->clear(ridx);
+                continue;
+            }
+
+            master_ridx = iter->second;
+        }
+
+        if (master_ridx >= m_values.size()
+            || !m_values[master_ridx].is_valid()) {
+            output_column->clear(ridx);
+            continue;
+        }
+
+        output_column->set_scalar(ridx, m_values[master_ridx]);
+    }
+}
+
+// Window inputs may be "real" columns from the master table or expression
+// columns, which have been computed on the master expression table already.
+static std::shared_ptr<const t_column>
+get_input_column(
+    const std::string& name,
+    const t_data_table& source_table,
+    const t_data_table& expression_table
+) {
+    if (source_table.get_schema().has_column(name)) {
+        return source_table.get_const_column(name);
+    }
+
+    if (expression_table.get_schema().has_column(name)) {
+        return expression_table.get_const_column(name);
+    }
+
+    PSP_COMPLAIN_AND_ABORT("Window column not in schema: " + name);
+    return nullptr;
+}
+
+void
+t_computed_window::compute_master(
+    const t_data_table& source_table,
+    const t_gstate::t_mapping& pkey_map,
+    const t_data_table& destination_table
+) const {
+    m_prev_values.swap(m_values);
+    m_values.assign(source_table.size(), mknone());
+
+    std::shared_ptr<const t_column> value_column;
+    if (!m_column.empty()) {
+        value_column =
+            get_input_column(m_column, source_table, destination_table);
+    }
+
+    std::vector<std::shared_ptr<const t_column>> partition_columns;
+    partition_columns.reserve(m_partition_by.size());
+    for (const auto& name : m_partition_by) {
+        partition_columns.push_back(
+            get_input_column(name, source_table, destination_table)
+        );
+    }
+
+    std::vector<std::shared_ptr<const t_column>> order_columns;
+    order_columns.reserve(m_order_by.size());
+    for (const auto& [name, _] : m_order_by) {
+        order_columns.push_back(
+            get_input_column(name, source_table, destination_table)
+        );
+    }
+
+    auto pkey_column = source_table.get_const_column("psp_pkey");
+
+    // Only rows in `pkey_map` are live - the rest of the master table is
+    // free space left by removed rows.
+    std::vector<t_uindex> rows;
+    rows.reserve(pkey_map.size());
+    for (const auto& [_, ridx] : pkey_map) {
+        rows.push_back(ridx);
+    }
+
+    // Sort by partition, then by `order_by`, then by primary key so ties are
+    // broken consistently between updates.
+    std::sort(rows.begin(), rows.end(), [&](t_uindex a, t_uindex b) {
+        for (const auto& column : partition_columns) {
+            t_tscalar x = column->get_scalar(a);
+            t_tscalar y = column->get_scalar(b);
+            if (x < y) {
+                return true;
+            }
+
+            if (y < x) {
+                return false;
+            }
+        }
+
+        for (t_uindex idx = 0; idx < order_columns.size(); ++idx) {
+            t_tscalar x = order_columns[idx]->get_scalar(a);
+            t_tscalar y = order_columns[idx]->get_scalar(b);
+            bool desc = m_order_by[idx].second == SORTTYPE_DESCENDING;
+            if (x < y) {
+                return !desc;
+            }
+
+            if (y < x) {
+                return desc;
+            }
+        }
+
+        return pkey_column->get_scalar(a) < pkey_column->get_scalar(b);
+    });
+
+    auto is_same_partition = [&](t_uindex a, t_uindex b) {
+        return std::all_of(
+            partition_columns.begin(),
+            partition_columns.end(),
+            [&](const auto& column) {
+                return column->get_scalar(a) == column->get_scalar(b);
+            }
+        );
+    };
+
+    t_uindex begin = 0;
+    while (begin < rows.size()) {
+        t_uindex end = begin + 1;
+        while (end < rows.size() && is_same_partition(rows[begin], rows[end])
+        ) {
+            ++end;
+        }
+
+        compute_partition(
+            rows, begin, end, value_column.get(), order_columns
+        );
+
+        begin = end;
+    }
+
+    m_changed_rows.clear();
+    for (t_uindex ridx = 0;
+         ridx < std::max(m_values.size(), m_prev_values.size());
+         ++ridx) {
+        t_tscalar value = ridx < m_values.size() ? m_values[ridx] : mknone();
+        t_tscalar prev_value =
+            ridx < m_prev_values.size() ? m_prev_values[ridx] : mknone();
+
+        if (value != prev_value) {
+            m_changed_rows.push_back(ridx);
+        }
+    }
+}
+
+bool
+t_computed_window::is_incremental(
+    const t_data_table& flattened, const t_gstate::t_mapping& pkey_map
+) const {
+    if (m_changed_rows.empty()) {
+        return true;
+    }
+
+    if (m_changed_rows.size() > flattened.size()) {
+        return false;
+    }
+
+    // Removed rows are not in `pkey_map`, so any row whose value was cleared
+    // by a removal is never in `updated_rows`.
+    auto pkey_column = flattened.get_const_column("psp_pkey");
+    tsl::hopscotch_set<t_uindex> updated_rows;
+    for (t_uindex ridx = 0; ridx < flattened.size(); ++ridx) {
+        auto iter = pkey_map.find(pkey_column->get_scalar(ridx));
+        if (iter != pkey_map.end()) {
+            updated_rows.insert(iter->second);
+        }
+    }
+
+    return std::all_of(
+        m_changed_rows.begin(),
+        m_changed_rows.end(),
+        [&](t_uindex ridx) { return updated_rows.count(ridx) > 0; }
+    );
+}
+
+// The value of `values` for `ridx`, or `std::nullopt` for a `null`.
+static std::optional<t_tscalar>
+get_window_value(const std::vector<t_tscalar>& values, t_uindex ridx) {
+    if (ridx >= values.size() || !values[ridx].is_valid()
+        || values[ridx].is_none()) {
+        return std::nullopt;
+    }
+
+    return values[ridx];
+}
+
+void
+t_computed_window::compute_previous(
+    const t_data_table& source_table,
+    const t_gstate::t_mapping& pkey_map,
+    t_data_table& prev_table,
+    t_data_table& delta_table
+) const {
+    auto prev_column = prev_table.get_column(get_expression_alias());
+    auto delta_column = delta_table.get_column(get_expression_alias());
+    auto pkey_column = source_table.get_const_column("psp_pkey");
+    for (t_uindex ridx = 0; ridx < source_table.size(); ++ridx) {
+        auto iter = pkey_map.find(pkey_column->get_scalar(ridx));
+        std::optional<t_tscalar> prev_value;
+        std::optional<t_tscalar> value;
+        if (iter != pkey_map.end()) {
+            prev_value = get_window_value(m_prev_values, iter->second);
+            value = get_window_value(m_values, iter->second);
+        }
+
+        if (prev_value.has_value()) {
+            prev_column->set_scalar(ridx, *prev_value);
+        } else {
+            prev_column->clear(ridx);
+        }
+
+        if (!prev_value.has_value() || !value.has_value()) {
+            delta_column->clear(ridx);
+        } else if (get_dtype() == DTYPE_FLOAT64) {
+            delta_column->set_scalar(
+                ridx, mktscalar(value->to_double() - prev_value->to_double())
+            );
+        } else if (get_dtype() == DTYPE_INT64) {
+            delta_column->set_scalar(
+                ridx,
+                mktscalar<std::int64_t>(
+                    value->to_int64() - prev_value->to_int64()
+                )
+            );
+        } else {
+            delta_column->clear(ridx);
+        }
+    }
+}
+
+void
+t_computed_window::compute_partition(
+    const std::vector<t_uindex>& rows,
+    t_uindex begin,
+    t_uindex end,
+    const t_column* value_column,
+    const std::vector<std::shared_ptr<const t_column>>& order_columns
+) const {
+    auto is_tie = [&](t_uindex a, t_uindex b) {
+        return std::all_of(
+            order_columns.begin(),
+            order_columns.end(),
+            [&](const auto& column) {
+                return column->get_scalar(a) == column->get_scalar(b);
+            }
+        );
+    };
+
+    switch (m_function) {
+        case WINDOW_FUNCTION_ROW_NUMBER: {
+            for (t_uindex idx = begin; idx < end; ++idx) {
+                m_values[rows[idx]] = mktscalar<std::int64_t>(
+                    static_cast<std::int64_t>(idx - begin + 1)
+                );
+            }
+        } break;
+        case WINDOW_FUNCTION_RANK:
+        case WINDOW_FUNCTION_DENSE_RANK: {
+            std::int64_t rank = 1;
+            std::int64_t dense_rank = 1;
+            for (t_uindex idx = begin; idx < end; ++idx) {
+                if (idx > begin && !is_tie(rows[idx - 1], rows[idx])) {
+                    rank = static_cast<std::int64_t>(idx - begin + 1);
+                    dense_rank++;
+                }
+
+                m_values[rows[idx]] = mktscalar<std::int64_t>(
+                    m_function == WINDOW_FUNCTION_RANK ? rank : dense_rank
+                );
+            }
+        } break;
+        case WINDOW_FUNCTION_LAG:
+        case WINDOW_FUNCTION_LEAD: {
+            for (t_uindex idx = begin; idx < end; ++idx) {
+                if (m_function == WINDOW_FUNCTION_LAG
+                    && idx >= begin + m_offset) {
+                    m_values[rows[idx]] =
+                        value_column->get_scalar(rows[idx - m_offset]);
+                } else if (m_function == WINDOW_FUNCTION_LEAD
+                           && idx + m_offset < end) {
+                    m_values[rows[idx]] =
+                        value_column->get_scalar(rows[idx + m_offset]);
+                }
+            }
+        } break;
+        case WINDOW_FUNCTION_PERCENT_OF_PREVIOUS: {
+            for (t_uindex idx = begin + 1; idx < end; ++idx) {
+                t_tscalar x = value_column->get_scalar(rows[idx]);
+                t_tscalar y = value_column->get_scalar(rows[idx - 1]);
+                if (!x.is_valid() || !y.is_valid() || y.to_double() == 0) {
+                    continue;
+                }
+
+                m_values[rows[idx]] =
+                    mktscalar(x.to_double() / y.to_double() * 100);
+            }
+        } break;
+        default: {
+            compute_frame(rows, begin, end, value_column);
+        } break;
+    }
+}
+
+void
+t_computed_window::compute_frame(
+    const std::vector<t_uindex>& rows,
+    t_uindex begin,
+    t_uindex end,
+    const t_column* value_column
+) const {
+    t_uindex size = end - begin;
+    std::vector<t_tscalar> values(size);
+    for (t_uindex pos = 0; pos < size; ++pos) {
+        values[pos] = value_column->get_scalar(rows[begin + pos]);
+    }
+
+    // The first and last position of the frame of `pos`, inclusive, both of
+    // which are non-decreasing as `pos` increases.
+    auto frame_start = [&](t_uindex pos) -> t_uindex {
+        if (!m_preceding.has_value() || *m_preceding > pos) {
+            return 0;
+        }
+
+        return pos - *m_preceding;
+    };
+
+    auto frame_end = [&](t_uindex pos) -> t_uindex {
+        if (!m_following.has_value()) {
+            return size - 1;
+        }
+
+        return std::min(pos + *m_following, size - 1);
+    };
+
+    switch (m_function) {
+        case WINDOW_FUNCTION_SUM:
+        case WINDOW_FUNCTION_MEAN:
+        case WINDOW_FUNCTION_COUNT: {
+            // Prefix sums of non-null values and their counts
+            std::vector<double> sums(size + 1, 0);
+            std::vector<std::int64_t> counts(size + 1, 0);
+            for (t_uindex pos = 0; pos < size; ++pos) {
+                bool is_valid = values[pos].is_valid()
+                    && (m_function == WINDOW_FUNCTION_COUNT
+                        || values[pos].is_numeric());
+
+                sums[pos + 1] =
+                    sums[pos] + (is_valid ? values[pos].to_double() : 0);
+                counts[pos + 1] = counts[pos] + (is_valid ? 1 : 0);
+            }
+
+            for (t_uindex pos = 0; pos < size; ++pos) {
+                t_uindex start = frame_start(pos);
+                t_uindex stop = frame_end(pos) + 1;
+                double sum = sums[stop] - sums[start];
+                std::int64_t count = counts[stop] - counts[start];
+                t_tscalar& value = m_values[rows[begin + pos]];
+                if (m_function == WINDOW_FUNCTION_COUNT) {
+                    value = mktscalar<std::int64_t>(count);
+                } else if (count > 0) {
+                    value = mktscalar(
+                        m_function == WINDOW_FUNCTION_SUM ? sum : sum / count
+                    );
+                }
+            }
+        } break;
+        case WINDOW_FUNCTION_MIN:
+        case WINDOW_FUNCTION_MAX: {
+            // Monotonic queue of the positions of candidate values in the
+            // current frame, whose front is the min (or max) of the frame.
+            std::deque<t_uindex> queue;
+            auto is_dominated = [&](t_uindex a, t_uindex b) {
+                return m_function == WINDOW_FUNCTION_MIN
+                    ? !(values[a] < values[b])
+                    : !(values[b] < values[a]);
+            };
+
+            t_uindex next = 0;
+            for (t_uindex pos = 0; pos < size; ++pos) {
+                t_uindex stop = frame_end(pos);
+                for (; next <= stop; ++next) {
+                    if (!values[next].is_valid()) {
+                        continue;
+                    }
+
+                    while (!queue.empty() && is_dominated(queue.back(), next)
+                    ) {
+                        queue.pop_back();
+                    }
+
+                    queue.push_back(next);
+                }
+
+                t_uindex start = frame_start(pos);
+                while (!queue.empty() && queue.front() < start) {
+                    queue.pop_front();
+                }
+
+                if (!queue.empty()) {
+                    m_values[rows[begin + pos]] = values[queue.front()];
+                }
+            }
+        } break;
+        default: {
+            PSP_COMPLAIN_AND_ABORT(
+                "Not a frame window function: "
+                + window_function_to_str(m_function)
+            );
+        } break;
+    }
+}
+
+bool
+t_computed_window::is_window() const {
+    return true;
+}
+
+t_window_function
+t_computed_window::get_function() const {
+    return m_function;
+}
+
+const std::string&
+t_computed_window::get_column() const {
+    return m_column;
+}
+
+const std::vector<std::string>&
+t_computed_window::get_partition_by() const {
+    return m_partition_by;
+}
+
+const std::vector<std::pair<std::string, t_sorttype>>&
+t_computed_window::get_order_by() const {
+    return m_order_by;
+}
+
+std::optional<t_uindex>
+t_computed_window::get_preceding() const {
+    return m_preceding;
+}
+
+std::optional<t_uindex>
+t_computed_window::get_following() const {
+    return m_following;
+}
+
+t_uindex
+t_computed_window::get_offset() const {
+    return m_offset;
+}
+
+} // end namespace perspective
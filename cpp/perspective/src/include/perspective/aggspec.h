@@ -44,6 +44,14 @@ public:
         const std::string& aggname, t_aggtype agg, const std::string& dep
     );
 
+    t_aggspec(
+        const std::string& aggname,
+        t_aggtype agg,
+        const std::vector<t_dep>& dependencies,
+        double quantile,
+        std::string expression
+    );
+
     t_aggspec(t_aggtype agg, const std::string& dep);
 
     t_aggspec(
@@ -85,6 +93,9 @@ public:
     double get_agg_one_weight() const;
     double get_agg_two_weight() const;
 
+    double get_quantile() const;
+    const std::string& get_expression() const;
+
     t_invmode get_inv_mode() const;
 
     std::vector<std::string> get_input_depnames() const;
@@ -111,6 +122,8 @@ private:
     t_uindex m_agg_two_idx;
     double m_agg_one_weight;
     double m_agg_two_weight;
+    double m_quantile = 0.5;
+    std::string m_expression;
     t_invmode m_invmode;
     // t_uindex m_kernel;
 };
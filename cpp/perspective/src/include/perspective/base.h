@@ -232,7 +232,10 @@ enum t_filter_op {
     FILTER_OP_NOT_IN,
     FILTER_OP_AND,
     FILTER_OP_IS_NULL,
-    FILTER_OP_IS_NOT_NULL
+    FILTER_OP_IS_NOT_NULL,
+    FILTER_OP_NOT_CONTAINS,
+    FILTER_OP_MATCHES,
+    FILTER_OP_ICONTAINS
 };
 
 PERSPECTIVE_EXPORT std::string filter_op_to_str(t_filter_op op);
@@ -291,7 +294,17 @@ enum t_aggtype {
     AGGTYPE_PCT_SUM_PARENT,
     AGGTYPE_PCT_SUM_GRAND_TOTAL,
     AGGTYPE_VARIANCE,
-    AGGTYPE_STANDARD_DEVIATION
+    AGGTYPE_STANDARD_DEVIATION,
+    AGGTYPE_PERCENTILE,
+    AGGTYPE_MODE,
+    AGGTYPE_DISTINCT_COUNT_APPROX,
+    AGGTYPE_NULL_COUNT,
+    AGGTYPE_SUM_RATIO,
+    AGGTYPE_LAST_BY,
+    AGGTYPE_FIRST_BY,
+    AGGTYPE_COVARIANCE,
+    AGGTYPE_CORRELATION,
+    AGGTYPE_CUSTOM
 };
 
 PERSPECTIVE_EXPORT t_aggtype str_to_aggtype(const std::string& str);
@@ -300,6 +313,31 @@ PERSPECTIVE_EXPORT std::string _get_default_aggregate_string(t_dtype dtype);
 
 enum t_totals { TOTALS_BEFORE, TOTALS_HIDDEN, TOTALS_AFTER };
 
+enum t_totals_position { TOTALS_POSITION_TOP, TOTALS_POSITION_BOTTOM };
+
+/**
+ * @brief Which group-by total rows a `t_ctx1` or `t_ctx2` emits, and whether
+ * they precede or follow the rows they total.
+ */
+struct PERSPECTIVE_EXPORT t_row_totals {
+    bool m_grand_total = true;
+    bool m_subtotals = true;
+    t_totals_position m_position = TOTALS_POSITION_TOP;
+
+    bool
+    is_default() const {
+        return m_grand_total && m_subtotals
+            && m_position == TOTALS_POSITION_TOP;
+    }
+};
+
+/**
+ * @brief How the values of a masked column are replaced in a `t_ctx0`'s
+ * output: with a fixed placeholder, or with a digest of the value, so that
+ * equal values remain equal.
+ */
+enum t_column_mask { COLUMN_MASK_REDACT, COLUMN_MASK_HASH };
+
 enum t_ctx_type {
     UNIT_CONTEXT,
     ZERO_SIDED_CONTEXT,
@@ -137,6 +137,9 @@ public:
 
     t_uindex size() const;
 
+    // The bytes allocated for this column's data, status and vocabulary.
+    t_uindex nbytes() const;
+
     t_uindex get_vlenidx() const;
 
     const char* unintern_c(t_uindex idx) const;
@@ -56,7 +56,9 @@ public:
         t_dtype dtype
     );
 
-    void compute(
+    virtual ~t_computed_expression() = default;
+
+    virtual void compute(
         const std::shared_ptr<t_data_table>& source_table,
         const t_gstate::t_mapping& pkey_map,
         const std::shared_ptr<t_data_table>& destination_table,
@@ -64,6 +66,13 @@ public:
         t_regex_mapping& regex_mapping
     ) const;
 
+    /**
+     * @brief Whether this expression is a `t_computed_window`, which is
+     * configured by the `window` field of a view config rather than by an
+     * ExprTK expression string.
+     */
+    virtual bool is_window() const;
+
     const std::string& get_expression_alias() const;
     const std::string& get_expression_string() const;
     const std::string& get_parsed_expression_string() const;
@@ -81,7 +90,13 @@ private:
 
 class PERSPECTIVE_EXPORT t_computed_expression_parser {
 public:
-    static void init();
+    /**
+     * @brief Returns this thread's expression parser, creating it on first
+     * use.
+     *
+     * @return exprtk::parser<t_tscalar>&
+     */
+    static exprtk::parser<t_tscalar>& parser();
 
     /**
      * @brief Given expression strings, validate the expression's dtype and
@@ -145,8 +160,6 @@ public:
         t_regex_mapping& regex_mapping
     );
 
-    static std::shared_ptr<exprtk::parser<t_tscalar>> PARSER;
-
     // Applied to the parser
     static std::size_t PARSER_COMPILE_OPTIONS;
 
@@ -176,6 +189,65 @@ public:
     static t_tscalar FALSE_SCALAR;
 };
 
+/**
+ * @brief A `custom` aggregate's compiled expression. Each `COLUMN<n>` is bound
+ * to a `vector_view` over a buffer of `capacity` values, which is resized to
+ * the group being aggregated, so the expression is only recompiled when a
+ * group outgrows the buffers.
+ */
+struct PERSPECTIVE_EXPORT t_custom_aggregate {
+    PSP_NON_COPYABLE(t_custom_aggregate);
+
+    t_custom_aggregate(std::size_t num_columns, std::size_t capacity);
+
+    /**
+     * @brief Replace each `"column"` reference of a `custom` aggregate's
+     * expression with an id, pushing each referenced column onto `columns`
+     * once. The id `COLUMN<n>` refers to `columns[n]`.
+     *
+     * @param expression
+     * @param columns
+     * @return std::string
+     */
+    static std::string parse_expression(
+        const std::string& expression, std::vector<std::string>& columns
+    );
+
+    /**
+     * @brief Check that a parsed `custom` aggregate expression over
+     * `num_columns` columns compiles and evaluates to a number, with the same
+     * vector bindings as the aggregate itself, reporting the first problem
+     * in `error`.
+     *
+     * @param parsed_expression
+     * @param num_columns
+     * @param error
+     * @return bool
+     */
+    static bool validate(
+        const std::string& parsed_expression,
+        std::size_t num_columns,
+        t_expression_error& error
+    );
+
+    /**
+     * @brief Compile `parsed_expression` against this aggregate's vectors,
+     * reporting the parser's first error in `error` if it fails.
+     *
+     * @param parsed_expression
+     * @param error
+     * @return bool
+     */
+    bool
+    compile(const std::string& parsed_expression, t_expression_error& error);
+
+    std::size_t m_capacity;
+    std::vector<std::vector<t_tscalar>> m_buffers;
+    std::vector<exprtk::vector_view<t_tscalar>> m_views;
+    exprtk::symbol_table<t_tscalar> m_sym_table;
+    exprtk::expression<t_tscalar> m_expression;
+};
+
 /**
  * @brief a `t_schema`-like container for validated expression results that
  * offers fast lookups.
@@ -186,8 +186,42 @@ public:
 
     std::vector<std::shared_ptr<t_computed_expression>> get_expressions() const;
 
+    /**
+     * @brief Whether any of this config's expressions are window functions,
+     * which must be recalculated over every row when the table is updated.
+     */
+    bool has_windows() const;
+
     t_totals get_totals() const;
 
+    /**
+     * @brief Which group-by total rows are emitted, and where, as opposed to
+     * `get_totals()` which places the column totals of a `t_ctx2`.
+     */
+    const t_row_totals& get_row_totals() const;
+    void set_row_totals(const t_row_totals& row_totals);
+
+    /**
+     * @brief The order of each column pivot's values, indexed by pivot. Empty
+     * if every column pivot uses its default order.
+     */
+    const std::vector<t_pivot_order>& get_col_pivot_orders() const;
+    void set_col_pivot_orders(const std::vector<t_pivot_order>& orders);
+
+    /**
+     * @brief Filter terms which every row must pass in addition to, and
+     * independently of, `get_fterms()` and `get_combiner()`.
+     */
+    const std::vector<t_fterm>& get_session_fterms() const;
+    void set_session_fterms(const std::vector<t_fterm>& fterms);
+
+    /**
+     * @brief The columns whose values are replaced in the context's output,
+     * by column name.
+     */
+    const std::map<std::string, t_column_mask>& get_column_masks() const;
+    void set_column_masks(const std::map<std::string, t_column_mask>& masks);
+
     t_filter_op get_combiner() const;
 
     std::string get_parent_pkey_column() const;
@@ -218,6 +252,8 @@ private:
     std::vector<t_sortspec> m_sortspecs;
     std::vector<t_sortspec> m_col_sortspecs;
     std::vector<t_fterm> m_fterms;
+    std::vector<t_fterm> m_session_fterms;
+    std::map<std::string, t_column_mask> m_column_masks;
     std::vector<std::shared_ptr<t_computed_expression>> m_expressions;
     t_filter_op m_combiner;
     bool m_column_only;
@@ -228,6 +264,8 @@ private:
 
     // Internal
     t_totals m_totals;
+    t_row_totals m_row_totals;
+    std::vector<t_pivot_order> m_col_pivot_orders;
     std::map<std::string, t_index> m_detail_colmap;
     std::string m_parent_pkey_column;
     std::string m_child_pkey_column;
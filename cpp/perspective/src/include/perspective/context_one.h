@@ -46,6 +46,12 @@ public:
 
     t_index get_row_idx(const std::vector<t_tscalar>& path) const;
 
+    /**
+     * @brief The index of the deepest visible row whose group contains the
+     * table row with primary key `pkey`, or `INVALID_INDEX` if there is none.
+     */
+    t_index get_row_idx_for_pkey(t_tscalar pkey) const;
+
     t_depth get_trav_depth(t_index idx) const;
 
     std::pair<t_tscalar, t_tscalar> get_min_max(const std::string& colname
@@ -55,6 +61,7 @@ public:
 
 private:
     std::shared_ptr<t_traversal> m_traversal;
+    t_row_map m_row_map;
     std::shared_ptr<t_stree> m_tree;
     std::vector<t_sortspec> m_sortby;
     std::shared_ptr<t_expression_tables> m_expression_tables;
@@ -59,6 +59,12 @@ public:
     std::pair<t_tscalar, t_tscalar> get_min_max(const std::string& colname
     ) const;
 
+    /**
+     * @brief The index of the deepest visible row whose group contains the
+     * table row with primary key `pkey`, or `INVALID_INDEX` if there is none.
+     */
+    t_index get_row_idx_for_pkey(t_tscalar pkey) const;
+
     using t_ctxbase<t_ctx2>::get_data;
 
 protected:
@@ -81,13 +87,23 @@ protected:
 
     t_uindex calc_translated_colidx(t_uindex n_aggs, t_uindex cidx) const;
 
+    /**
+     * @brief Reorder the column traversal by the config's column pivot
+     * orders, if any, then reapply the column sort on top of them.
+     */
+    void order_columns();
+
 private:
     std::shared_ptr<t_traversal> m_rtraversal;
+    t_row_map m_row_map;
     std::shared_ptr<t_traversal> m_ctraversal;
     std::vector<t_sortspec> m_sortby;
     bool m_rows_changed;
     std::vector<std::shared_ptr<t_stree>> m_trees;
     std::vector<t_sortspec> m_column_sortby;
+
+    // The last `column_sort_by()` sort, reapplied by `order_columns()`.
+    std::vector<t_sortspec> m_column_sortspec;
     t_depth m_row_depth;
     bool m_row_depth_set;
     t_depth m_column_depth;
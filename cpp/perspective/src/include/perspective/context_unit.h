@@ -57,6 +57,12 @@ public:
     std::pair<t_tscalar, t_tscalar> get_min_max(const std::string& colname
     ) const;
 
+    /**
+     * @brief The index of the row containing the table row with primary key
+     * `pkey`, or `INVALID_INDEX` if there is none.
+     */
+    t_index get_row_idx_for_pkey(t_tscalar pkey) const;
+
     using t_ctxbase<t_ctxunit>::get_data;
 
     std::vector<t_tscalar> get_data(
@@ -90,6 +90,9 @@ public:
 
     t_uindex size() const;
     t_uindex get_capacity() const;
+
+    // The bytes allocated for this table's columns.
+    t_uindex nbytes() const;
     t_dtype get_dtype(const std::string& colname) const;
 
     std::shared_ptr<t_column> get_column(std::string_view colname);
@@ -19,7 +19,9 @@
 #include <perspective/scalar.h>
 #include <perspective/exports.h>
 #include <functional>
+#include <memory>
 #include <set>
+#include <re2/re2.h>
 
 namespace perspective {
 
@@ -158,6 +160,10 @@ struct PERSPECTIVE_EXPORT t_fterm {
             case FILTER_OP_IN: {
                 rv = std::find(m_bag.begin(), m_bag.end(), s) != m_bag.end();
             } break;
+            case FILTER_OP_MATCHES: {
+                rv = s.is_valid() && s.get_dtype() == DTYPE_STR
+                    && RE2::PartialMatch(s.to_string(), *m_regex);
+            } break;
             default: {
                 rv = s.cmp(m_op, m_threshold);
             } break;
@@ -177,6 +183,13 @@ struct PERSPECTIVE_EXPORT t_fterm {
     bool m_negated;
     bool m_is_primary;
     bool m_use_interned;
+
+    // Compiled pattern for `FILTER_OP_MATCHES`, shared between copies of this
+    // term as `RE2` is not copyable.
+    std::shared_ptr<RE2> m_regex;
+
+private:
+    void init_regex();
 };
 
 class PERSPECTIVE_EXPORT t_filter {
@@ -23,7 +23,21 @@ filter_table_for_config(const t_data_table& tbl, const t_config& config) {
 
     switch (config.get_fmode()) {
         case FMODE_SIMPLE_CLAUSES: {
-            return tbl.filter_cpp(config.get_combiner(), config.get_fterms());
+            const auto& session_fterms = config.get_session_fterms();
+            if (session_fterms.empty()) {
+                return tbl.filter_cpp(
+                    config.get_combiner(), config.get_fterms()
+                );
+            }
+
+            auto mask = tbl.filter_cpp(FILTER_OP_AND, session_fterms);
+            if (!config.get_fterms().empty()) {
+                mask &= tbl.filter_cpp(
+                    config.get_combiner(), config.get_fterms()
+                );
+            }
+
+            return mask;
         } break;
         default: {
         }
@@ -18,6 +18,7 @@
 #include <perspective/exports.h>
 #include <perspective/context_handle.h>
 #include <perspective/pivot.h>
+#include <perspective/filter.h>
 #include <perspective/env_vars.h>
 #include <perspective/rlookup.h>
 #include <perspective/gnode_state.h>
@@ -27,6 +28,8 @@
 #include <perspective/computed_function.h>
 #include <perspective/expression_tables.h>
 #include <perspective/regex.h>
+#include <perspective/window.h>
+#include <tsl/hopscotch_set.h>
 #include <tsl/ordered_map.h>
 #include <perspective/parallel_for.h>
 #include <chrono>
@@ -196,6 +199,15 @@ public:
 
     t_uindex mapping_size() const;
 
+    /**
+     * @brief The number of rows in the gnode state which pass every term of
+     * `fterms`.
+     *
+     * @param fterms
+     * @return t_uindex
+     */
+    t_uindex mapping_size(const std::vector<t_fterm>& fterms) const;
+
     // helper function for JS interface
     void promote_column(const std::string& name, t_dtype new_type);
 
@@ -358,6 +370,40 @@ protected:
         const std::shared_ptr<t_data_table>& flattened
     );
 
+    /**
+     * @brief Correct the window columns of a context's transitional expression
+     * tables after `compute_expressions`, which reads their current values for
+     * `prev` too. Windows depend on rows outside of any single update, so if
+     * the update changed the window value of a row it does not contain (e.g. a
+     * removed row, or an edit early in a running total), the context is reset
+     * instead, and `notify_context` rebuilds it from the full state.
+     *
+     * @tparam CTX_T
+     * @param ctx
+     * @param name
+     * @param flattened
+     * @param prev
+     * @param existed
+     */
+    template <typename CTX_T>
+    void _update_window_context(
+        CTX_T* ctx,
+        const std::string& name,
+        const std::shared_ptr<t_data_table>& flattened,
+        const std::shared_ptr<t_data_table>& prev,
+        const std::shared_ptr<t_data_table>& existed
+    );
+
+    /**
+     * @brief Reset a context with window columns and recompute its expressions
+     * from the gstate master table.
+     *
+     * @tparam CTX_T
+     * @param ctx
+     */
+    template <typename CTX_T>
+    void _reset_window_context(CTX_T* ctx);
+
 private:
     /**
      * @brief Process the input data table by flattening it, calculating
@@ -401,6 +447,10 @@ private:
     std::shared_ptr<t_expression_vocab> m_expression_vocab;
     std::shared_ptr<t_regex_mapping> m_expression_regex_mapping;
 
+    // The names of the contexts with window columns which were reset by the
+    // current update, and must be rebuilt from the full state.
+    tsl::hopscotch_set<std::string> m_reset_window_contexts;
+
 #ifdef PSP_PARALLEL_FOR
     std::shared_mutex* m_lock;
 #endif
@@ -424,6 +474,16 @@ t_gnode::notify_context(
 ) {
     CTX_T* ctx = ctxh.get<CTX_T>();
 
+    // Contexts with window columns may have been reset in
+    // `_compute_expressions`.
+    if (m_reset_window_contexts.count(name) > 0) {
+        update_context_from_state<CTX_T>(
+            ctx, name, m_gstate->get_pkeyed_table()
+        );
+
+        return;
+    }
+
     // Tables from the gnode which do not have the expressions applied yet
     std::shared_ptr<t_data_table> delta = m_oports[PSP_PORT_DELTA]->get_table();
     std::shared_ptr<t_data_table> prev = m_oports[PSP_PORT_PREV]->get_table();
@@ -471,6 +531,68 @@ t_gnode::notify_context(
     ctx->step_end();
 }
 
+template <typename CTX_T>
+void
+t_gnode::_update_window_context(
+    CTX_T* ctx,
+    const std::string& name,
+    const std::shared_ptr<t_data_table>& flattened,
+    const std::shared_ptr<t_data_table>& prev,
+    const std::shared_ptr<t_data_table>& existed
+) {
+    const t_gstate::t_mapping& pkey_map = m_gstate->get_pkey_map();
+    std::shared_ptr<t_expression_tables> expression_tables =
+        ctx->get_expression_tables();
+
+    bool is_incremental = true;
+    for (const auto& expr : ctx->get_config().get_expressions()) {
+        if (!expr->is_window()) {
+            continue;
+        }
+
+        const auto& window = static_cast<const t_computed_window&>(*expr);
+        if (!window.is_incremental(*flattened, pkey_map)) {
+            is_incremental = false;
+            break;
+        }
+
+        window.compute_previous(
+            *prev,
+            pkey_map,
+            *(expression_tables->m_prev),
+            *(expression_tables->m_delta)
+        );
+    }
+
+    if (is_incremental) {
+        expression_tables->calculate_transitions(existed);
+        return;
+    }
+
+    m_reset_window_contexts.insert(name);
+    _reset_window_context(ctx);
+}
+
+template <typename CTX_T>
+void
+t_gnode::_reset_window_context(CTX_T* ctx) {
+    t_expression_vocab& expression_vocab = *(m_expression_vocab);
+    t_regex_mapping& expression_regex_mapping = *(m_expression_regex_mapping);
+
+    ctx->reset();
+    ctx->compute_expressions(
+        m_gstate->get_table(),
+        m_gstate->get_pkey_map(),
+        expression_vocab,
+        expression_regex_mapping
+    );
+
+    ctx->get_expression_tables()->set_flattened(m_gstate->get_pkeyed_table(
+        ctx->get_expression_tables()->m_master->get_schema(),
+        ctx->get_expression_tables()->m_master
+    ));
+}
+
 /**
  * @brief Given a flattened `t_data_table`, update the context with the table.
  *
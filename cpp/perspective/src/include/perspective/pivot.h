@@ -16,6 +16,8 @@
 #include <perspective/base.h>
 #include <perspective/raw_types.h>
 #include <perspective/exports.h>
+#include <string>
+#include <vector>
 
 namespace perspective {
 
@@ -45,4 +47,20 @@ private:
     t_pivot_mode m_mode;
 };
 
+/**
+ * @brief The order of a pivot's values, which replaces the default (sorted)
+ * order of its children in a `t_traversal`. `m_values` are compared to the
+ * string form of each value, and values not listed follow those which are,
+ * in their default order.
+ */
+struct PERSPECTIVE_EXPORT t_pivot_order {
+    bool m_natural = false;
+    std::vector<std::string> m_values;
+
+    bool
+    is_default() const {
+        return !m_natural && m_values.empty();
+    }
+};
+
 } // namespace perspective
@@ -18,13 +18,21 @@
 #include "perspective/schema.h"
 #include "perspective/view.h"
 #include "perspective/view_config.h"
+#include <algorithm>
+#include <atomic>
+#include <chrono>
 #include <cstdint>
+#include <map>
 #include <memory>
+#include <optional>
+#include <set>
+#include <shared_mutex>
 #include <tsl/hopscotch_set.h>
 #include <utility>
 #include <perspective/table.h>
 #include <string>
 #include <tsl/hopscotch_map.h>
+#include <tsl/ordered_map.h>
 #include <perspective.pb.h>
 
 namespace perspective {
@@ -229,6 +237,10 @@ namespace server {
         [[nodiscard]]
         virtual std::vector<std::vector<std::string>> column_paths() const = 0;
 
+        [[nodiscard]]
+        virtual std::vector<std::vector<t_tscalar>>
+        column_paths_scalar() const = 0;
+
         [[nodiscard]]
         virtual std::map<std::string, std::string>
         expression_schema() const = 0;
@@ -249,6 +261,20 @@ namespace server {
         virtual t_index expand(std::int32_t row_idx) = 0;
 
         virtual void set_depth(std::int32_t depth) = 0;
+
+        [[nodiscard]]
+        virtual bool get_row_expanded(std::int32_t row_idx) const = 0;
+
+        /**
+         * @brief The row path of the row at `row_idx`, ordered from the root
+         * `group_by` value to the leaf.
+         */
+        [[nodiscard]]
+        virtual std::vector<t_tscalar> get_row_path(t_uindex row_idx) const = 0;
+
+        [[nodiscard]]
+        virtual std::optional<t_uindex> find_row_by_pkey(t_tscalar pkey
+        ) const = 0;
     };
 
     template <typename CTX_T>
@@ -440,6 +466,12 @@ namespace server {
             return out;
         }
 
+        [[nodiscard]]
+        std::vector<std::vector<t_tscalar>>
+        column_paths_scalar() const override {
+            return m_view->column_paths();
+        }
+
         [[nodiscard]]
         std::map<std::string, std::string>
         expression_schema() const override {
@@ -489,6 +521,26 @@ namespace server {
             m_view->set_depth(depth, num_pivots);
         }
 
+        [[nodiscard]]
+        bool
+        get_row_expanded(std::int32_t row_idx) const override {
+            return m_view->get_row_expanded(row_idx);
+        }
+
+        [[nodiscard]]
+        std::vector<t_tscalar>
+        get_row_path(t_uindex row_idx) const override {
+            auto row_path = m_view->get_row_path(row_idx);
+            std::reverse(row_path.begin(), row_path.end());
+            return row_path;
+        }
+
+        [[nodiscard]]
+        std::optional<t_uindex>
+        find_row_by_pkey(t_tscalar pkey) const override {
+            return m_view->find_row_by_pkey(pkey);
+        }
+
     private:
         std::shared_ptr<View<CTX_T>> m_view;
     };
@@ -510,9 +562,61 @@ namespace server {
         using CtxViewBase<t_ctx2>::CtxViewBase;
     };
 
+    /**
+     * @brief The rows of a `View` as of the last update sent to a `DETAIL`
+     * mode `on_update` subscriber, which the next update is diffed against.
+     * Each row is keyed by its serialized `__ID__`, and its cells are kept in
+     * the same representation `to_json` uses.
+     */
+    struct ViewRowSnapshot {
+        struct Row {
+            std::string key;
+            std::vector<proto::Scalar> id;
+            tsl::ordered_map<std::string, proto::Scalar> cells;
+        };
+
+        // The view row index of `rows[0]`, when the snapshot is of a
+        // `viewport`.
+        std::uint32_t start_row = 0;
+        std::vector<Row> rows;
+    };
+
+    // The state of a throttled `on_update` subscription, shared by its
+    // copies.
+    struct UpdateThrottle {
+        std::chrono::steady_clock::time_point last_sent;
+
+        // The port of the latest update withheld since `last_sent`, and the
+        // `ROW` mode deltas of all of them.
+        std::optional<std::uint32_t> pending_port_id;
+        std::vector<std::string> pending_deltas;
+    };
+
     struct Subscription {
         uint32_t id;
         uint32_t client_id;
+
+        // Only set for `DETAIL` mode `on_update` subscriptions.
+        std::shared_ptr<ViewRowSnapshot> snapshot;
+
+        // Only set for `on_update` subscriptions scoped to a `viewport`, with
+        // the Arrow of that region as of the last update sent.
+        std::optional<proto::ViewPort> viewport;
+        std::shared_ptr<std::string> viewport_data;
+        bool include_viewport_data = false;
+
+        // Only set for `on_update` subscriptions, which use the session's
+        // throttle if `throttle_ms` is unset.
+        std::optional<std::uint32_t> throttle_ms;
+        std::shared_ptr<UpdateThrottle> throttle;
+    };
+
+    // The time taken to handle one type of request, bucketed by the
+    // `ServerSystemInfoResp.latency_bounds`.
+    struct RequestLatency {
+        std::uint64_t count = 0;
+        double sum = 0;
+        std::vector<std::uint64_t> buckets;
     };
 
     /**
@@ -549,6 +653,9 @@ namespace server {
         // `on_update()`
         void create_view_on_update_sub(const t_id& view_id, Subscription sub);
         std::vector<Subscription> get_view_on_update_sub(const t_id& view_id);
+        void
+        set_view_on_update_sub(const t_id& view_id, const Subscription& sub);
+        std::vector<t_id> get_view_ids_with_on_update_sub();
         void remove_view_on_update_sub(
             const t_id& view_id, std::uint32_t sub_id, std::uint32_t client_id
         );
@@ -578,6 +685,29 @@ namespace server {
         bool is_table_dirty(const t_id& id);
         void drop_client(const std::uint32_t);
 
+        // The default `throttle_ms` of a session's `on_update` subscriptions.
+        void set_session_update_throttle(
+            std::uint32_t client_id, std::uint32_t throttle_ms
+        );
+        std::uint32_t get_session_update_throttle(std::uint32_t client_id);
+
+        // `ServerSystemInfoReq` metrics.
+        void host_session(std::uint32_t client_id);
+        std::vector<std::uint32_t> get_session_ids();
+        std::uint32_t get_session_num_views(std::uint32_t client_id);
+        std::uint32_t get_session_num_subscriptions(std::uint32_t client_id);
+        std::uint32_t get_num_dirty_tables();
+
+        // Admin requests.
+        bool has_session(std::uint32_t client_id);
+        std::vector<t_id> get_session_view_ids(std::uint32_t client_id);
+        std::optional<std::uint32_t> get_client_id_for_view(const t_id& id);
+        std::uint32_t get_view_num_subscriptions(const t_id& id);
+        void record_request_latency(
+            const std::string& request_type, double seconds
+        );
+        std::map<std::string, RequestLatency> get_request_latency();
+
     protected:
         tsl::hopscotch_map<t_id, t_id> m_view_to_table;
         std::multimap<t_id, t_id> m_table_to_view;
@@ -595,8 +725,13 @@ namespace server {
             m_table_on_delete_subs;
 
         tsl::hopscotch_set<t_id> m_dirty_tables;
+        tsl::hopscotch_map<std::uint32_t, std::uint32_t>
+            m_session_update_throttle;
+
+        std::set<std::uint32_t> m_sessions;
+        std::map<std::string, RequestLatency> m_request_latency;
 
-#ifdef PSP_PARALLEL_FOR
+#if defined(PSP_PARALLEL_FOR) || defined(PSP_SERVER_THREADSAFE)
         std::shared_mutex m_write_lock;
 #endif
     };
@@ -617,6 +752,29 @@ namespace server {
         std::vector<ProtoServerResp<std::string>>
         handle_request(std::uint32_t client_id, const std::string_view& data);
         std::vector<ProtoServerResp<std::string>> poll();
+        void set_session_update_throttle(
+            std::uint32_t client_id, std::uint32_t throttle_ms
+        );
+
+        // The usage counted against a session's quotas.
+        std::uint32_t get_session_num_views(std::uint32_t client_id);
+        std::uint32_t get_session_num_subscriptions(std::uint32_t client_id);
+        std::optional<std::uint32_t>
+        get_view_num_rows(const ServerResources::t_id& view_id);
+
+        // The session which created a view, so that a session restricted by
+        // filters or column policies can be kept to its own views.
+        std::optional<std::uint32_t>
+        get_view_session_id(const ServerResources::t_id& view_id);
+
+        // Requests for different tables may run concurrently, so long as
+        // each is locked against its own table, which for a view is
+        // `get_view_table_id`. `process_table` flushes a table's pending
+        // updates while the caller still holds its write lock.
+        std::optional<ServerResources::t_id>
+        get_view_table_id(const ServerResources::t_id& view_id);
+        std::vector<ProtoServerResp<std::string>>
+        process_table(const ServerResources::t_id& table_id);
 
     private:
         void handle_process_table(
@@ -641,7 +799,25 @@ namespace server {
             std::vector<ProtoServerResp<Response>>& outs
         );
 
-        static std::uint32_t m_client_id;
+        void _send_view_on_update(
+            const ServerResources::t_id& view_id,
+            ErasedView& view,
+            Subscription& subscription,
+            std::uint32_t port_id,
+            bool include_row_delta,
+            std::vector<ProtoServerResp<Response>>& outs
+        );
+
+        void
+        _flush_throttled_updates(std::vector<ProtoServerResp<Response>>& outs);
+
+        void _delete_view(
+            std::uint32_t client_id,
+            const ServerResources::t_id& view_id,
+            std::vector<ProtoServerResp<Response>>& outs
+        );
+
+        static std::atomic<std::uint32_t> m_client_id;
         ServerResources m_resources;
     };
 
@@ -34,6 +34,7 @@ SUPPRESS_WARNINGS_VC(4503)
 #include <vector>
 #include <algorithm>
 #include <deque>
+#include <map>
 #include <sstream>
 #include <queue>
 
@@ -43,6 +44,7 @@ class t_gstate;
 class t_dtree_ctx;
 class t_config;
 class t_ctx2;
+struct t_custom_aggregate;
 
 using boost::multi_index_container;
 using namespace boost::multi_index;
@@ -66,6 +68,11 @@ struct by_idx_lfidx {};
 
 PERSPECTIVE_EXPORT t_tscalar get_dominant(std::vector<t_tscalar>& values);
 
+PERSPECTIVE_EXPORT t_tscalar get_mode(const std::vector<t_tscalar>& values);
+
+PERSPECTIVE_EXPORT std::uint32_t
+get_distinct_count_approx(const std::vector<t_tscalar>& values);
+
 struct t_build_strand_table_metadata {
     t_schema m_flattened_schema;
     t_schema m_strand_schema;
@@ -437,6 +444,13 @@ protected:
         const std::function<bool(const t_tscalar&, t_tscalar&)>& fn
     ) const;
 
+    t_tscalar compute_custom_aggregate(
+        const t_aggspec& spec,
+        const t_gstate& gstate,
+        const t_data_table& expression_master_table,
+        const std::vector<t_tscalar>& pkeys
+    ) const;
+
     template <typename FN_T>
     typename FN_T::result_type reduce_from_gstate(
         const t_gstate& gstate,
@@ -468,6 +482,10 @@ private:
     t_symtable m_symtable;
     bool m_has_delta;
     std::string m_grand_agg_str;
+
+    // The compiled expression of each `custom` aggregate, by aggspec name.
+    mutable std::map<std::string, std::shared_ptr<t_custom_aggregate>>
+        m_custom_aggregates;
 };
 
 } // end namespace perspective
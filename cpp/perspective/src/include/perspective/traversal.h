@@ -127,16 +127,75 @@ public:
 
     bool is_valid_idx(t_index idx) const;
 
+    /**
+     * @brief The traversal indices of the rows visible under `totals`, in
+     * display order. Expanded nodes are subtotals (or the grand total, for
+     * the root), and move after their descendants when `totals` is
+     * positioned at the bottom.
+     */
+    std::vector<t_index> get_row_indices(const t_row_totals& totals) const;
+
+    /**
+     * @brief Reorder the children of each expanded node by the
+     * `t_pivot_order` of their pivot, where `orders[d]` orders the children
+     * of nodes at depth `d`. The order of siblings which compare equal is
+     * preserved.
+     */
+    void sort_by_order(const std::vector<t_pivot_order>& orders);
+
     const t_stree* get_tree() const;
 
     void populate_root_children(const t_stnode_vec& rchildren);
     void populate_root_children(const std::shared_ptr<const t_stree>& tree);
 
 private:
+    void sort_subtree_by_order(
+        t_index idx,
+        const std::vector<t_pivot_order>& orders,
+        std::vector<t_tvnode>& out_nodes
+    ) const;
+
     std::shared_ptr<const t_stree> m_tree;
     std::shared_ptr<std::vector<t_tvnode>> m_nodes;
 };
 
+/**
+ * @brief Maps the rows of a `t_ctx1` or `t_ctx2` to the indices of its row
+ * `t_traversal`, hiding and moving total rows per `t_row_totals`. This is
+ * the identity map when the totals are the default, so contexts can always
+ * translate through it.
+ */
+class PERSPECTIVE_EXPORT t_row_map {
+public:
+    t_row_map() = default;
+
+    explicit t_row_map(const t_row_totals& totals);
+
+    /**
+     * @brief Recalculate the map, which must be called whenever `traversal`
+     * adds, removes or reorders nodes.
+     */
+    void update(const t_traversal& traversal);
+
+    t_uindex size(const t_traversal& traversal) const;
+
+    /**
+     * @brief The traversal index of row `ridx`.
+     */
+    t_index get_traversal_index(t_index ridx) const;
+
+    /**
+     * @brief The row of traversal index `tidx`, or `INVALID_INDEX` if it is
+     * hidden.
+     */
+    t_index get_row_index(t_index tidx) const;
+
+private:
+    t_row_totals m_totals;
+    std::vector<t_index> m_rows;
+    std::vector<t_index> m_lookup;
+};
+
 /**
  * @brief Sort implementation for `t_ctx1` and `t_ctx2` contexts.
  *
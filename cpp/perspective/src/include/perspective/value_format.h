@@ -0,0 +1,94 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#pragma once
+
+#include <perspective/first.h>
+#include <perspective/base.h>
+#include <perspective/exports.h>
+#include <cstdint>
+#include <string>
+#include <tsl/hopscotch_map.h>
+#include <perspective.pb.h>
+
+namespace perspective {
+
+/**
+ * @brief Formats `integer`, `float`, `date` and `datetime` values as text for
+ * `formatted` view output, from a `proto::FormatOptions` which follows the
+ * viewer's `number_format` and `date_format` column styles.
+ *
+ * Locales are limited to a built-in table of separators, date field orders
+ * and clocks, month and weekday names are English, and the time zone is `UTC`
+ * or a fixed offset. Any other locale, time zone, or a date style which names
+ * the month in a non-English locale, is an error.
+ */
+class PERSPECTIVE_EXPORT t_value_formatter {
+public:
+    explicit t_value_formatter(const proto::FormatOptions& options);
+
+    std::string
+    format_number(const std::string& column, double value, bool is_float)
+        const;
+
+    /**
+     * @brief Format a `date`, with `month` in the range [1..12].
+     */
+    std::string format_date(
+        const std::string& column,
+        std::int32_t year,
+        std::uint32_t month,
+        std::uint32_t day
+    ) const;
+
+    /**
+     * @brief Format a `datetime` of `ms` since the epoch, in the time zone of
+     * the `proto::FormatOptions`.
+     */
+    std::string
+    format_datetime(const std::string& column, std::int64_t ms) const;
+
+    enum t_date_order { DATE_ORDER_MDY, DATE_ORDER_DMY, DATE_ORDER_YMD };
+
+    struct t_locale {
+        std::string decimal;
+        std::string group;
+        t_date_order order;
+        std::string date_separator;
+        bool hour12;
+
+        // Short dates have unpadded days and months and 2 digit years.
+        bool short_numeric;
+
+        // Whether the (English) month and weekday names apply to this locale.
+        bool english_names;
+    };
+
+private:
+    const proto::ColumnFormat& column_format(const std::string& column) const;
+
+    std::string format_date_style(
+        std::int32_t year,
+        std::uint32_t month,
+        std::uint32_t day,
+        std::uint32_t weekday,
+        proto::ColumnFormat::DatetimeStyle style
+    ) const;
+
+    t_locale m_locale;
+    std::int32_t m_offset_minutes;
+    std::string m_time_zone_name;
+    tsl::hopscotch_map<std::string, proto::ColumnFormat> m_columns;
+    proto::ColumnFormat m_default_format;
+};
+
+} // end namespace perspective
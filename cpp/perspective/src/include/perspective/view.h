@@ -27,6 +27,7 @@
 #include <cstddef>
 #include <memory>
 #include <map>
+#include <optional>
 #include <arrow/api.h>
 #ifdef PSP_ENABLE_PYTHON
 #include <thread>
@@ -382,6 +383,15 @@ public:
     std::vector<t_sortspec> get_sort() const;
     std::vector<std::shared_ptr<t_computed_expression>> get_expressions() const;
     std::vector<t_tscalar> get_row_path(t_uindex idx) const;
+
+    /**
+     * @brief The index of the row containing the table row with primary key
+     * `pkey`; for a `View` with `group_by`, the deepest visible group row.
+     *
+     * @param pkey
+     * @return std::optional<t_uindex>
+     */
+    std::optional<t_uindex> find_row_by_pkey(t_tscalar pkey) const;
     t_stepdelta get_step_delta(t_index bidx, t_index eidx) const;
     t_dtype get_column_dtype(t_uindex idx) const;
     bool is_column_only() const;
@@ -25,6 +25,18 @@
 
 namespace perspective {
 
+/**
+ * @brief Convert filters of the form `(column, op, arguments)`, as passed to
+ * `t_view_config`, to the `t_fterm` objects used by the engine.
+ *
+ * @param filter
+ * @return std::vector<t_fterm>
+ */
+PERSPECTIVE_EXPORT std::vector<t_fterm> make_fterms(
+    const std::vector<
+        std::tuple<std::string, std::string, std::vector<t_tscalar>>>& filter
+);
+
 /**
  * @brief The `t_view_config` API provides a unified view configuration object,
  * which specifies how the `View` transforms the underlying `Table`. By storing
@@ -91,6 +103,20 @@ public:
         const std::tuple<std::string, std::string, std::vector<t_tscalar>>& term
     );
 
+    /**
+     * @brief Set filters which every row of the `View` must pass regardless
+     * of `filter` and `filter_op`, e.g. those a `Server` session restricts
+     * its `Table`s by. Unlike `filter`, these are not part of the config
+     * returned to the user. Must be called before `init()`.
+     *
+     * @param filter
+     */
+    void set_session_filter(
+        const std::vector<
+            std::tuple<std::string, std::string, std::vector<t_tscalar>>>&
+            filter
+    );
+
     /**
      * @brief Set the number of pivot levels the engine should generate.
      *
@@ -99,16 +125,50 @@ public:
     void set_row_pivot_depth(std::int32_t depth);
     void set_column_pivot_depth(std::int32_t depth);
 
+    /**
+     * @brief Set which total rows the engine should generate for pivoted
+     * contexts, and where they are placed relative to their children.
+     *
+     * @param totals
+     */
+    void set_row_totals(const t_row_totals& totals);
+
+    /**
+     * @brief Set the order of the values of `split_by` columns, keyed by
+     * column name.
+     *
+     * @param orders
+     */
+    void
+    set_split_by_order(const std::map<std::string, t_pivot_order>& orders);
+
+    /**
+     * @brief Set the columns of the `Table` which are hidden from this view's
+     * user, and those whose values the view must mask, by column name.
+     *
+     * @param hidden
+     * @param masks
+     */
+    void set_column_policy(
+        const std::vector<std::string>& hidden,
+        const std::map<std::string, t_column_mask>& masks
+    );
+
     std::vector<std::string> get_row_pivots() const;
 
     std::vector<std::string> get_column_pivots() const;
 
     std::vector<t_aggspec> get_aggspecs() const;
 
+    tsl::ordered_map<std::string, std::vector<std::string>>
+    get_aggregates() const;
+
     std::vector<std::string> get_columns() const;
 
     std::vector<t_fterm> get_fterm() const;
 
+    std::vector<t_fterm> get_session_fterm() const;
+
     std::vector<t_sortspec> get_sortspec() const;
 
     std::vector<t_sortspec> get_col_sortspec() const;
@@ -122,6 +182,20 @@ public:
     std::int32_t get_row_pivot_depth() const;
     std::int32_t get_column_pivot_depth() const;
 
+    const t_row_totals& get_row_totals() const;
+
+    const std::map<std::string, t_pivot_order>& get_split_by_order() const;
+
+    const std::vector<std::string>& get_hidden_columns() const;
+
+    const std::map<std::string, t_column_mask>& get_column_masks() const;
+
+    /**
+     * @brief The order of each column pivot's values, indexed by pivot, or
+     * an empty vector if no column pivot has an order.
+     */
+    std::vector<t_pivot_order> get_col_pivot_orders() const;
+
 private:
     bool m_init;
 
@@ -145,8 +219,8 @@ private:
     void fill_aggspecs(const std::shared_ptr<t_schema>& schema);
 
     /**
-     * @brief Fill the `m_fterm` vector with `t_fterm` objects which define the
-     * view's filters.
+     * @brief Fill the `m_fterm` and `m_session_fterm` vectors with `t_fterm`
+     * objects which define the view's filters.
      *
      * @return void
      */
@@ -191,6 +265,8 @@ private:
     std::vector<std::string> m_columns;
     std::vector<std::tuple<std::string, std::string, std::vector<t_tscalar>>>
         m_filter;
+    std::vector<std::tuple<std::string, std::string, std::vector<t_tscalar>>>
+        m_session_filter;
     std::vector<std::vector<std::string>> m_sort;
     std::vector<std::shared_ptr<t_computed_expression>> m_expressions;
 
@@ -210,6 +286,8 @@ private:
 
     std::vector<t_fterm> m_fterm;
 
+    std::vector<t_fterm> m_session_fterm;
+
     std::vector<t_sortspec> m_sortspec;
 
     std::vector<t_sortspec> m_col_sortspec;
@@ -223,6 +301,23 @@ private:
     std::int32_t m_row_pivot_depth;
     std::int32_t m_column_pivot_depth;
 
+    /**
+     * @brief Visibility and position of the grand total and subtotal rows.
+     */
+    t_row_totals m_row_totals;
+
+    /**
+     * @brief The order of the values of `split_by` columns, by column name.
+     */
+    std::map<std::string, t_pivot_order> m_split_by_order;
+
+    /**
+     * @brief The `Table` columns this view's user may not see, and those
+     * whose values are masked in its output.
+     */
+    std::vector<std::string> m_hidden_columns;
+    std::map<std::string, t_column_mask> m_column_masks;
+
     /**
      * @brief the `t_filter_op` used to return data in the case of multiple
      * filters being applied.
@@ -0,0 +1,187 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#pragma once
+
+#include <perspective/first.h>
+#include <perspective/base.h>
+#include <perspective/exports.h>
+#include <perspective/computed_expression.h>
+#include <optional>
+
+namespace perspective {
+
+enum t_window_function {
+    WINDOW_FUNCTION_SUM,
+    WINDOW_FUNCTION_MEAN,
+    WINDOW_FUNCTION_MIN,
+    WINDOW_FUNCTION_MAX,
+    WINDOW_FUNCTION_COUNT,
+    WINDOW_FUNCTION_ROW_NUMBER,
+    WINDOW_FUNCTION_RANK,
+    WINDOW_FUNCTION_DENSE_RANK,
+    WINDOW_FUNCTION_LAG,
+    WINDOW_FUNCTION_LEAD,
+    WINDOW_FUNCTION_PERCENT_OF_PREVIOUS
+};
+
+PERSPECTIVE_EXPORT t_window_function
+str_to_window_function(const std::string& str);
+
+PERSPECTIVE_EXPORT std::string
+window_function_to_str(t_window_function function);
+
+/**
+ * @brief Whether `function` reads the values of an input column, or only the
+ * position of each row in its partition (e.g. `rank`).
+ */
+PERSPECTIVE_EXPORT bool window_function_has_column(t_window_function function);
+
+/**
+ * @brief A window function column, e.g. a running total or a rank, which is
+ * calculated for each row from the other rows in its partition.
+ *
+ * Unlike `t_computed_expression`, a window cannot be calculated from a row
+ * in isolation, so it is always calculated over every row of the `t_gstate`
+ * master table. When computed on any other table (e.g. the transitional
+ * tables of an update), the values calculated for the master table are read
+ * by primary key.
+ */
+class PERSPECTIVE_EXPORT t_computed_window : public t_computed_expression {
+public:
+    /**
+     * @brief Construct a new `t_computed_window`.
+     *
+     * @param name the name of the output column.
+     * @param function
+     * @param column the input column, or an empty string for functions which
+     * only read the position of each row, e.g. `rank`.
+     * @param partition_by
+     * @param order_by
+     * @param preceding the number of rows before the current row to
+     * aggregate, or `std::nullopt` for the start of the partition.
+     * @param following the number of rows after the current row to aggregate,
+     * or `std::nullopt` for the end of the partition.
+     * @param offset the distance to the row read by `lag` and `lead`.
+     * @param gstate_table the `t_gstate` master table this window is
+     * calculated over.
+     * @param dtype
+     */
+    t_computed_window(
+        std::string name,
+        t_window_function function,
+        std::string column,
+        std::vector<std::string> partition_by,
+        std::vector<std::pair<std::string, t_sorttype>> order_by,
+        std::optional<t_uindex> preceding,
+        std::optional<t_uindex> following,
+        t_uindex offset,
+        const t_data_table* gstate_table,
+        t_dtype dtype
+    );
+
+    /**
+     * @brief The output dtype of `function` given the dtype of its input
+     * column.
+     */
+    static t_dtype
+    get_output_dtype(t_window_function function, t_dtype column_dtype);
+
+    void compute(
+        const std::shared_ptr<t_data_table>& source_table,
+        const t_gstate::t_mapping& pkey_map,
+        const std::shared_ptr<t_data_table>& destination_table,
+        t_expression_vocab& vocab,
+        t_regex_mapping& regex_mapping
+    ) const override;
+
+    bool is_window() const override;
+
+    /**
+     * @brief Whether every row whose value changed in the last calculation
+     * over the master table is a row of `flattened`, so the update can be
+     * applied to a context from its transitional tables alone.
+     *
+     * @param flattened
+     * @param pkey_map
+     */
+    bool is_incremental(
+        const t_data_table& flattened, const t_gstate::t_mapping& pkey_map
+    ) const;
+
+    /**
+     * @brief Write the values from before the last calculation over the
+     * master table to `prev_table`, and their difference from the current
+     * values to `delta_table`, for each row of `source_table`. `compute`
+     * writes the current values to every transitional table.
+     *
+     * @param source_table
+     * @param pkey_map
+     * @param prev_table
+     * @param delta_table
+     */
+    void compute_previous(
+        const t_data_table& source_table,
+        const t_gstate::t_mapping& pkey_map,
+        t_data_table& prev_table,
+        t_data_table& delta_table
+    ) const;
+
+    t_window_function get_function() const;
+    const std::string& get_column() const;
+    const std::vector<std::string>& get_partition_by() const;
+    const std::vector<std::pair<std::string, t_sorttype>>& get_order_by() const;
+    std::optional<t_uindex> get_preceding() const;
+    std::optional<t_uindex> get_following() const;
+    t_uindex get_offset() const;
+
+private:
+    void compute_master(
+        const t_data_table& source_table,
+        const t_gstate::t_mapping& pkey_map,
+        const t_data_table& destination_table
+    ) const;
+
+    void compute_partition(
+        const std::vector<t_uindex>& rows,
+        t_uindex begin,
+        t_uindex end,
+        const t_column* value_column,
+        const std::vector<std::shared_ptr<const t_column>>& order_columns
+    ) const;
+
+    void compute_frame(
+        const std::vector<t_uindex>& rows,
+        t_uindex begin,
+        t_uindex end,
+        const t_column* value_column
+    ) const;
+
+    t_window_function m_function;
+    std::string m_column;
+    std::vector<std::string> m_partition_by;
+    std::vector<std::pair<std::string, t_sorttype>> m_order_by;
+    std::optional<t_uindex> m_preceding;
+    std::optional<t_uindex> m_following;
+    t_uindex m_offset;
+    const t_data_table* m_gstate_table;
+
+    // The output value for each row of the master table, indexed by row.
+    mutable std::vector<t_tscalar> m_values;
+
+    // `m_values` before the last calculation over the master table, and the
+    // rows whose value it changed.
+    mutable std::vector<t_tscalar> m_prev_values;
+    mutable std::vector<t_uindex> m_changed_rows;
+};
+
+} // end namespace perspective
@@ -13,10 +13,12 @@
 use std::fs::File;
 use std::io::Read;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
+use axum::http::{HeaderMap, StatusCode};
 use axum::routing::get_service;
 use axum::Router;
-use perspective::client::{TableInitOptions, UpdateData};
+use perspective::client::{ReadOnly, TableInitOptions, UpdateData};
 use perspective::server::Server;
 use tower_http::services::{ServeDir, ServeFile};
 use tower_http::trace::TraceLayer;
@@ -47,14 +49,30 @@ async fn load_server_arrow(server: &Server) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Only allow `/metrics` scrapes from the loopback interface, as the metrics
+/// report the name and size of every [`perspective::Table`].
+fn identify_scraper(_headers: &HeaderMap, addr: &SocketAddr) -> Result<String, StatusCode> {
+    if addr.ip().is_loopback() {
+        Ok("scraper".to_owned())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
 /// Host a combination HTTP file server + WebSocket server, which serves a
 /// simple Perspective application. The app's HTML, etc., assets are served
 /// from the root, while the app's embedded WebAssembly [`perspective::Client`]
-/// will connect to this server over a WebSocket via the path `/ws`.
+/// will connect to this server over a WebSocket via the path `/ws`, and the
+/// engine's metrics can be scraped by Prometheus from `/metrics` by a scraper
+/// on the same host.
 async fn start_web_server_and_block(server: Server) -> Result<(), AppError> {
+    let metrics =
+        perspective::axum::metrics_handler_with_authorizer(identify_scraper, Arc::new(ReadOnly));
+
     let app = Router::new()
         .route("/", get_service(ServeFile::new("src/index.html")))
         .route("/ws", perspective::axum::websocket_handler())
+        .route("/metrics", metrics)
         .fallback_service(ServeDir::new(ROOT_PATH))
         .with_state(server)
         .layer(TraceLayer::new_for_http());
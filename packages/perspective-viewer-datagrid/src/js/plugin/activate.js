@@ -32,6 +32,7 @@ import { deselect_all_listener } from "../event_handlers/deselect_all.js";
 
 import { createModel } from "../model/create.js";
 import { dispatch_click_listener } from "../event_handlers/dispatch_click";
+import { underlying_rows_listener } from "../event_handlers/underlying_rows";
 
 import { addAreaMouseSelection } from "../event_handlers/select_region.js";
 
@@ -128,6 +129,16 @@ export async function activate(view) {
             dispatch_click_listener.bind(this.model, this.regular_table, viewer)
         );
 
+        // Drill-through to the source rows of an aggregated cell
+        this.regular_table.addEventListener(
+            "dblclick",
+            underlying_rows_listener.bind(
+                this.model,
+                this.regular_table,
+                viewer
+            )
+        );
+
         // Editing
         const selected_position_map = new WeakMap();
         this.regular_table.addStyleListener(
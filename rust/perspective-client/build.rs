@@ -55,9 +55,41 @@ fn prost_build() -> Result<()> {
             .type_attribute("ViewOnUpdateResp", "#[derive(ts_rs::TS)]")
             .field_attribute("ViewOnUpdateResp.delta", "#[ts(as = \"Vec::<u8>\")]")
             .field_attribute("ViewOnUpdateResp.delta", "#[serde(with = \"serde_bytes\")]")
+            .field_attribute(
+                "ViewOnUpdateResp.viewport_data",
+                "#[ts(as = \"Vec::<u8>\")]",
+            )
+            .field_attribute(
+                "ViewOnUpdateResp.viewport_data",
+                "#[serde(with = \"serde_bytes\")]",
+            )
+            .field_attribute(
+                "ViewOnUpdateResp.row_deltas",
+                "#[serde(skip_serializing_if = \"Vec::is_empty\")]",
+            )
+            .type_attribute("ViewOnUpdateResp.RowDelta", "#[derive(ts_rs::TS)]")
+            .field_attribute("ViewOnUpdateResp.RowDelta.op", "#[ts(as = \"String\")]")
+            .field_attribute(
+                "ViewOnUpdateResp.RowDelta.op",
+                "#[serde(serialize_with = \"crate::view::serialize_row_op\")]",
+            )
+            .field_attribute(
+                "ViewOnUpdateResp.RowDelta.id",
+                "#[ts(as = \"Vec::<crate::config::Scalar>\")]",
+            )
+            .type_attribute("ViewOnUpdateResp.CellDelta", "#[derive(ts_rs::TS)]")
+            .field_attribute(
+                "ViewOnUpdateResp.CellDelta.old_value",
+                "#[ts(as = \"Option::<crate::config::Scalar>\")]",
+            )
+            .field_attribute(
+                "ViewOnUpdateResp.CellDelta.new_value",
+                "#[ts(as = \"Option::<crate::config::Scalar>\")]",
+            )
             .field_attribute("ViewToArrowResp.arrow", "#[serde(skip)]")
             .field_attribute("from_arrow", "#[serde(skip)]")
             .type_attribute(".", "#[derive(serde::Serialize)]")
+            .type_attribute("Scalar", "#[serde(into = \"crate::config::Scalar\")]")
             .type_attribute("ViewDimensionsResp", "#[derive(serde::Deserialize)]")
             .type_attribute("TableValidateExprResp", "#[derive(serde::Deserialize)]")
             .type_attribute(
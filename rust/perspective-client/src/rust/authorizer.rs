@@ -0,0 +1,353 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use prost::Message;
+use thiserror::Error;
+
+use crate::proto::request::ClientReq;
+use crate::proto::response::ClientResp;
+use crate::proto::{Request, Response, ServerError, StatusCode};
+#[cfg(doc)]
+use crate::{Client, ClientError, ProxySession, Session, Table, View};
+
+/// The reason an [`Authorizer`] denied a request. The [`Client`] which sent
+/// the request receives it as a [`ClientError::Unauthorized`].
+#[derive(Clone, Debug, Error)]
+#[error("{0}")]
+pub struct AuthError(pub String);
+
+impl AuthError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        AuthError(reason.into())
+    }
+}
+
+/// What the entity id of a request names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestTarget {
+    /// Nothing, e.g. `get_hosted_tables` or `system_info`.
+    Server,
+
+    /// A [`Table`], or the [`Table`] to create.
+    Table,
+
+    /// A [`View`].
+    View,
+}
+
+/// A request made through a [`Session`], as seen by an [`Authorizer`].
+#[derive(Clone, Copy, Debug)]
+pub struct AuthRequest<'a> {
+    /// The identity the [`Session`] was created for, e.g. a user name.
+    pub identity: &'a str,
+
+    /// The name of the [`Table`] this request reads or writes: its entity
+    /// for [`Table`] requests, or the [`Table`] a [`View`] was created from
+    /// for [`View`] requests. `None` for requests which address no table
+    /// (e.g. `get_hosted_tables`). [`View`] requests whose [`Table`] cannot
+    /// be resolved are denied before they reach the [`Authorizer`].
+    pub table_name: Option<&'a str>,
+
+    request: &'a Request,
+}
+
+impl<'a> AuthRequest<'a> {
+    #[doc(hidden)]
+    pub fn new(identity: &'a str, request: &'a Request, table_name: Option<&'a str>) -> Self {
+        AuthRequest {
+            identity,
+            table_name,
+            request,
+        }
+    }
+
+    /// The id of the [`Table`] or [`View`] this request addresses.
+    pub fn entity_id(&self) -> &str {
+        &self.request.entity_id
+    }
+
+    /// The name of this request's message in `perspective.proto`, e.g.
+    /// `"TableUpdateReq"`, or `""` if the request is empty.
+    pub fn kind(&self) -> &'static str {
+        request_kind(self.request.client_req.as_ref()).0
+    }
+
+    /// What this request's [`AuthRequest::entity_id`] names.
+    pub fn target(&self) -> RequestTarget {
+        request_kind(self.request.client_req.as_ref()).1
+    }
+
+    /// Whether this request leaves the data of every [`Table`] unchanged.
+    /// Requests which create, update, replace, remove from or delete a
+    /// [`Table`] (or open a port to update it) are not read-only; [`View`]
+    /// requests and callbacks are.
+    pub fn is_read_only(&self) -> bool {
+        !matches!(
+            self.request.client_req,
+            Some(
+                ClientReq::MakeTableReq(_)
+                    | ClientReq::TableMakePortReq(_)
+                    | ClientReq::TableUpdateReq(_)
+                    | ClientReq::TableReplaceReq(_)
+                    | ClientReq::TableRemoveReq(_)
+                    | ClientReq::TableDeleteReq(_)
+            )
+        )
+    }
+
+    /// Whether this is an admin request, which lists or deletes the
+    /// [`View`]s and `Session`s of every [`Client`] of the server rather
+    /// than only this [`Session`]'s own. Admin requests only reach the
+    /// [`Authorizer`] of a [`Session`] which opted in to them, e.g. with
+    /// [`ProxySession::with_admin`].
+    pub fn is_admin(&self) -> bool {
+        matches!(
+            self.request.client_req,
+            Some(
+                ClientReq::GetHostedViewsReq(_)
+                    | ClientReq::GetSessionsReq(_)
+                    | ClientReq::DeleteHostedViewReq(_)
+                    | ClientReq::CloseSessionReq(_)
+            )
+        )
+    }
+}
+
+/// The name and [`RequestTarget`] of a request. Every request must be
+/// classified here, so that a new request cannot be authorized against the
+/// wrong [`Table`].
+fn request_kind(req: Option<&ClientReq>) -> (&'static str, RequestTarget) {
+    use RequestTarget::{Server, Table, View};
+    let Some(req) = req else {
+        return ("", Server);
+    };
+
+    match req {
+        ClientReq::GetFeaturesReq(_) => ("GetFeaturesReq", Server),
+        ClientReq::GetHostedTablesReq(_) => ("GetHostedTablesReq", Server),
+        ClientReq::ServerSystemInfoReq(_) => ("ServerSystemInfoReq", Server),
+        ClientReq::GetHostedViewsReq(_) => ("GetHostedViewsReq", Server),
+        ClientReq::GetSessionsReq(_) => ("GetSessionsReq", Server),
+        ClientReq::CloseSessionReq(_) => ("CloseSessionReq", Server),
+        ClientReq::MakeTableReq(_) => ("MakeTableReq", Table),
+        ClientReq::TableMakePortReq(_) => ("TableMakePortReq", Table),
+        ClientReq::TableMakeViewReq(_) => ("TableMakeViewReq", Table),
+        ClientReq::TableSchemaReq(_) => ("TableSchemaReq", Table),
+        ClientReq::TableSizeReq(_) => ("TableSizeReq", Table),
+        ClientReq::TableValidateExprReq(_) => ("TableValidateExprReq", Table),
+        ClientReq::TableDeleteReq(_) => ("TableDeleteReq", Table),
+        ClientReq::TableOnDeleteReq(_) => ("TableOnDeleteReq", Table),
+        ClientReq::TableRemoveDeleteReq(_) => ("TableRemoveDeleteReq", Table),
+        ClientReq::TableRemoveReq(_) => ("TableRemoveReq", Table),
+        ClientReq::TableReplaceReq(_) => ("TableReplaceReq", Table),
+        ClientReq::TableUpdateReq(_) => ("TableUpdateReq", Table),
+        ClientReq::ViewColumnPathsReq(_) => ("ViewColumnPathsReq", View),
+        ClientReq::ViewDeleteReq(_) => ("ViewDeleteReq", View),
+        ClientReq::ViewDimensionsReq(_) => ("ViewDimensionsReq", View),
+        ClientReq::ViewExpressionSchemaReq(_) => ("ViewExpressionSchemaReq", View),
+        ClientReq::ViewGetConfigReq(_) => ("ViewGetConfigReq", View),
+        ClientReq::ViewSchemaReq(_) => ("ViewSchemaReq", View),
+        ClientReq::ViewToArrowReq(_) => ("ViewToArrowReq", View),
+        ClientReq::ViewCollapseReq(_) => ("ViewCollapseReq", View),
+        ClientReq::ViewExpandReq(_) => ("ViewExpandReq", View),
+        ClientReq::ViewGetMinMaxReq(_) => ("ViewGetMinMaxReq", View),
+        ClientReq::ViewOnUpdateReq(_) => ("ViewOnUpdateReq", View),
+        ClientReq::ViewRemoveOnUpdateReq(_) => ("ViewRemoveOnUpdateReq", View),
+        ClientReq::ViewSetDepthReq(_) => ("ViewSetDepthReq", View),
+        ClientReq::ViewToColumnsStringReq(_) => ("ViewToColumnsStringReq", View),
+        ClientReq::ViewToCsvReq(_) => ("ViewToCSVReq", View),
+        ClientReq::ViewToRowsStringReq(_) => ("ViewToRowsStringReq", View),
+        ClientReq::ViewToNdjsonStringReq(_) => ("ViewToNdjsonStringReq", View),
+        ClientReq::ViewGetExpansionStateReq(_) => ("ViewGetExpansionStateReq", View),
+        ClientReq::ViewSetExpansionStateReq(_) => ("ViewSetExpansionStateReq", View),
+        ClientReq::ViewFindRowByPathReq(_) => ("ViewFindRowByPathReq", View),
+        ClientReq::ViewFindRowByIndexReq(_) => ("ViewFindRowByIndexReq", View),
+        ClientReq::ViewGetUnderlyingRowsReq(_) => ("ViewGetUnderlyingRowsReq", View),
+        ClientReq::ViewSetUpdateViewportReq(_) => ("ViewSetUpdateViewportReq", View),
+        ClientReq::ViewOnDeleteReq(_) => ("ViewOnDeleteReq", View),
+        ClientReq::ViewRemoveDeleteReq(_) => ("ViewRemoveDeleteReq", View),
+        ClientReq::DeleteHostedViewReq(_) => ("DeleteHostedViewReq", View),
+    }
+}
+
+/// Allows or denies the requests of a [`Session`] before they reach the
+/// engine. Implemented for closures, so a per-table ACL can be written as
+/// e.g.
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use perspective_client::{AuthError, AuthRequest, Authorizer};
+/// let acl = |req: &AuthRequest<'_>| match req.table_name {
+///     Some("secrets") if req.identity != "admin" => {
+///         Err(AuthError::new("`secrets` is restricted"))
+///     },
+///     _ => Ok(()),
+/// };
+///
+/// let authorizer: Arc<dyn Authorizer> = Arc::new(acl);
+/// ```
+pub trait Authorizer: Send + Sync {
+    fn authorize(&self, request: &AuthRequest<'_>) -> Result<(), AuthError>;
+}
+
+impl<F> Authorizer for F
+where
+    F: Fn(&AuthRequest<'_>) -> Result<(), AuthError> + Send + Sync,
+{
+    fn authorize(&self, request: &AuthRequest<'_>) -> Result<(), AuthError> {
+        self(request)
+    }
+}
+
+/// An [`Authorizer`] which denies every request that is not
+/// [`AuthRequest::is_read_only`], as well as every
+/// [`AuthRequest::is_admin`] request.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReadOnly;
+
+impl Authorizer for ReadOnly {
+    fn authorize(&self, request: &AuthRequest<'_>) -> Result<(), AuthError> {
+        if request.is_read_only() && !request.is_admin() {
+            Ok(())
+        } else {
+            Err(AuthError::new(format!(
+                "`{}` is read-only",
+                request.identity
+            )))
+        }
+    }
+}
+
+/// The [`Authorizer`] of a [`ProxySession`], and its restrictions. The
+/// server behind a [`ProxySession`] cannot be asked which [`Table`] a
+/// [`View`] belongs to, or which session created it, so this tracks the
+/// [`View`]s created through the [`ProxySession`], and denies requests for
+/// any other [`View`] when the [`ProxySession`] has an [`Authorizer`] or is
+/// restricted. Admin requests are denied unless the [`ProxySession`] opted in
+/// to them.
+#[derive(Clone, Default)]
+pub(crate) struct ProxyAuthorizer {
+    identity: String,
+    authorizer: Option<Arc<dyn Authorizer>>,
+    admin: bool,
+    restricted: bool,
+    views: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl ProxyAuthorizer {
+    pub fn set_authorizer(&mut self, identity: String, authorizer: Arc<dyn Authorizer>) {
+        self.identity = identity;
+        self.authorizer = Some(authorizer);
+    }
+
+    pub fn set_admin(&mut self) {
+        self.admin = true;
+    }
+
+    /// Restrict the [`ProxySession`] as its reads are, by `SessionFilters`
+    /// or `ColumnPolicies`, so that it cannot read around them: it may only
+    /// address the [`View`]s it created, and may not make `system_info` or
+    /// admin requests.
+    pub fn set_restricted(&mut self) {
+        self.restricted = true;
+    }
+
+    /// Authorize a decoded request, returning the encoded error response
+    /// which denies it.
+    pub fn authorize(&self, request: &Request) -> Result<(), Vec<u8>> {
+        let (kind, target) = request_kind(request.client_req.as_ref());
+        let auth_request = AuthRequest::new(&self.identity, request, None);
+        if auth_request.is_admin() && !self.admin {
+            let error = AuthError::new(format!("`{kind}` requires an admin session"));
+            return Err(denied(request, error));
+        }
+
+        if self.authorizer.is_none() && !self.restricted {
+            return Ok(());
+        }
+
+        if self.restricted && (auth_request.is_admin() || kind == "ServerSystemInfoReq") {
+            let error = AuthError::new(format!("`{kind}` is not allowed for this session"));
+            return Err(denied(request, error));
+        }
+
+        let table_name = match target {
+            RequestTarget::Server => None,
+            RequestTarget::Table => Some(request.entity_id.clone()),
+            RequestTarget::View => {
+                let views = self.views.lock().unwrap();
+                match views.get(&request.entity_id) {
+                    Some(table_name) => Some(table_name.clone()),
+                    None => {
+                        return Err(denied(
+                            request,
+                            AuthError::new(format!(
+                                "View `{}` was not created by this session",
+                                request.entity_id
+                            )),
+                        ))
+                    },
+                }
+            },
+        };
+
+        if let Some(authorizer) = &self.authorizer {
+            let auth_request = AuthRequest::new(&self.identity, request, table_name.as_deref());
+            if let Err(error) = authorizer.authorize(&auth_request) {
+                tracing::debug!("Denied request from `{}`: {}", self.identity, error);
+                return Err(denied(request, error));
+            }
+        }
+
+        if let Some(ClientReq::ViewDeleteReq(_) | ClientReq::DeleteHostedViewReq(_)) =
+            &request.client_req
+        {
+            self.views.lock().unwrap().remove(&request.entity_id);
+        }
+
+        Ok(())
+    }
+
+    /// Observe the parent's `response` to an authorized `request`, recording
+    /// the [`View`] a `TableMakeViewReq` created only once it has succeeded,
+    /// so that a failed request does not grant access to its `view_id`.
+    pub fn on_response(&self, request: &Request, response: &Response) {
+        if self.authorizer.is_none() && !self.restricted {
+            return;
+        }
+
+        if let (Some(ClientReq::TableMakeViewReq(req)), Some(ClientResp::TableMakeViewResp(_))) =
+            (&request.client_req, &response.client_resp)
+        {
+            self.views
+                .lock()
+                .unwrap()
+                .insert(req.view_id.clone(), request.entity_id.clone());
+        }
+    }
+}
+
+fn denied(request: &Request, error: AuthError) -> Vec<u8> {
+    let response = Response {
+        msg_id: request.msg_id,
+        entity_id: request.entity_id.clone(),
+        client_resp: Some(ClientResp::ServerError(ServerError {
+            message: error.to_string(),
+            status_code: StatusCode::Unauthorized.into(),
+        })),
+    };
+
+    response.encode_to_vec()
+}
@@ -26,8 +26,9 @@ use tracing_unwrap::{OptionExt, ResultExt};
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::{
-    self, ColumnType, GetFeaturesReq, GetFeaturesResp, GetHostedTablesReq, GetHostedTablesResp,
-    HostedTable, MakeTableReq, Request, Response, ServerSystemInfoReq,
+    self, CloseSessionReq, ColumnType, DeleteHostedViewReq, GetFeaturesReq, GetFeaturesResp,
+    GetHostedTablesReq, GetHostedTablesResp, GetHostedViewsReq, GetHostedViewsResp, GetSessionsReq,
+    GetSessionsResp, HostedTable, MakeTableReq, Request, Response, ServerSystemInfoReq,
 };
 use crate::table::{Table, TableInitOptions, TableOptions};
 use crate::table_data::{TableData, UpdateData};
@@ -38,12 +39,269 @@ use crate::view::ViewWindow;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SystemInfo {
     pub heap_size: f64,
+
+    /// The row count, view count and memory use of each hosted [`Table`].
+    pub tables: Vec<TableStats>,
+
+    /// The view and subscription counts of each open `Session`.
+    pub sessions: Vec<SessionStats>,
+
+    /// A histogram of the time taken to handle each type of request.
+    pub request_latency: Vec<RequestLatency>,
+
+    /// The upper bounds, in seconds, of the [`RequestLatency::buckets`].
+    pub latency_bounds: Vec<f64>,
+
+    /// The number of [`Table`]s with updates waiting for the next `poll`.
+    pub poll_queue_depth: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TableStats {
+    pub name: String,
+    pub num_rows: u64,
+    pub num_views: u32,
+    pub memory_bytes: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub id: u32,
+    pub num_views: u32,
+    pub num_subscriptions: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestLatency {
+    /// The request's name, e.g. `table_update_req`.
+    pub request_type: String,
+    pub count: u64,
+
+    /// The total time taken by these requests, in seconds.
+    pub sum: f64,
+
+    /// The count of requests per [`SystemInfo::latency_bounds`] bucket (not
+    /// cumulative), followed by the count of requests slower than every
+    /// bound.
+    pub buckets: Vec<u64>,
 }
 
 impl From<proto::ServerSystemInfoResp> for SystemInfo {
     fn from(value: proto::ServerSystemInfoResp) -> Self {
         SystemInfo {
             heap_size: value.heap_size,
+            tables: value
+                .tables
+                .into_iter()
+                .map(|x| TableStats {
+                    name: x.entity_id,
+                    num_rows: x.num_rows,
+                    num_views: x.num_views,
+                    memory_bytes: x.memory_bytes,
+                })
+                .collect(),
+            sessions: value
+                .sessions
+                .into_iter()
+                .map(|x| SessionStats {
+                    id: x.client_id,
+                    num_views: x.num_views,
+                    num_subscriptions: x.num_subscriptions,
+                })
+                .collect(),
+            request_latency: value
+                .request_latency
+                .into_iter()
+                .map(|x| RequestLatency {
+                    request_type: x.request_type,
+                    count: x.count,
+                    sum: x.sum,
+                    buckets: x.buckets,
+                })
+                .collect(),
+            latency_bounds: value.latency_bounds,
+            poll_queue_depth: value.poll_queue_depth,
+        }
+    }
+}
+
+impl SystemInfo {
+    /// Format these metrics in the Prometheus text exposition format, e.g.
+    /// as the body of a `/metrics` scrape endpoint.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        write_metric(&mut out, "heap_size", "gauge", "Engine heap size.", [(
+            String::new(),
+            self.heap_size.to_string(),
+        )]);
+
+        write_metric(
+            &mut out,
+            "poll_queue_depth",
+            "gauge",
+            "Tables with updates waiting for the next poll.",
+            [(String::new(), self.poll_queue_depth.to_string())],
+        );
+
+        let tables = |f: fn(&TableStats) -> u64| {
+            self.tables.iter().map(move |x| {
+                let label = format!("{{table=\"{}\"}}", escape_label(&x.name));
+                (label, f(x).to_string())
+            })
+        };
+
+        write_metric(
+            &mut out,
+            "table_rows",
+            "gauge",
+            "Rows per table.",
+            tables(|x| x.num_rows),
+        );
+
+        write_metric(
+            &mut out,
+            "table_views",
+            "gauge",
+            "Views per table.",
+            tables(|x| x.num_views as u64),
+        );
+
+        write_metric(
+            &mut out,
+            "table_memory_bytes",
+            "gauge",
+            "Memory allocated per table.",
+            tables(|x| x.memory_bytes),
+        );
+
+        let sessions = |f: fn(&SessionStats) -> u32| {
+            self.sessions
+                .iter()
+                .map(move |x| (format!("{{session=\"{}\"}}", x.id), f(x).to_string()))
+        };
+
+        write_metric(
+            &mut out,
+            "session_views",
+            "gauge",
+            "Views per session.",
+            sessions(|x| x.num_views),
+        );
+
+        write_metric(
+            &mut out,
+            "session_subscriptions",
+            "gauge",
+            "Subscriptions per session.",
+            sessions(|x| x.num_subscriptions),
+        );
+
+        let mut samples = vec![];
+        for latency in &self.request_latency {
+            let label = format!("request=\"{}\"", latency.request_type);
+            let bounds = self
+                .latency_bounds
+                .iter()
+                .map(|x| x.to_string())
+                .chain(["+Inf".to_owned()]);
+
+            let mut total = 0;
+            for (bound, count) in bounds.zip(&latency.buckets) {
+                total += count;
+                samples.push((
+                    format!("_bucket{{{label},le=\"{bound}\"}}"),
+                    total.to_string(),
+                ));
+            }
+
+            samples.push((format!("_sum{{{label}}}"), latency.sum.to_string()));
+            samples.push((format!("_count{{{label}}}"), latency.count.to_string()));
+        }
+
+        write_metric(
+            &mut out,
+            "request_duration_seconds",
+            "histogram",
+            "Time taken to handle each type of request.",
+            samples,
+        );
+
+        out
+    }
+}
+
+/// Write one Prometheus metric family, whose samples are each a suffix (e.g.
+/// labels) of `name` and a value.
+fn write_metric(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: impl IntoIterator<Item = (String, String)>,
+) {
+    out.push_str(&format!("# HELP perspective_{name} {help}\n"));
+    out.push_str(&format!("# TYPE perspective_{name} {kind}\n"));
+    for (suffix, value) in samples {
+        out.push_str(&format!("perspective_{name}{suffix} {value}\n"));
+    }
+}
+
+/// Escape a Prometheus label value.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// A [`crate::View`] hosted by the `Server`, as listed by
+/// [`Client::get_hosted_views`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HostedView {
+    pub name: String,
+    pub table_name: String,
+
+    /// The id of the `Session` which created this view.
+    pub session_id: u32,
+    pub config: crate::config::ViewConfig,
+
+    /// The number of `on_update` and `on_delete` callbacks registered for
+    /// this view.
+    pub num_subscriptions: u32,
+}
+
+impl TryFrom<proto::HostedView> for HostedView {
+    type Error = ClientError;
+
+    fn try_from(value: proto::HostedView) -> Result<Self, ClientError> {
+        Ok(HostedView {
+            name: value.entity_id,
+            table_name: value.table_id,
+            session_id: value.session_id,
+            config: value.config.unwrap_or_default().try_into()?,
+            num_subscriptions: value.num_subscriptions,
+        })
+    }
+}
+
+/// An open `Session` of the `Server`, as listed by [`Client::get_sessions`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub id: u32,
+
+    /// The names of the [`crate::View`]s this session created.
+    pub view_names: Vec<String>,
+
+    /// The number of callbacks this session has registered.
+    pub num_subscriptions: u32,
+}
+
+impl From<proto::SessionInfo> for SessionInfo {
+    fn from(value: proto::SessionInfo) -> Self {
+        SessionInfo {
+            id: value.session_id,
+            view_names: value.view_ids,
+            num_subscriptions: value.num_subscriptions,
         }
     }
 }
@@ -289,6 +547,7 @@ impl Client {
             let on_update_token = view
                 .on_update(callback, crate::view::OnUpdateOptions {
                     mode: Some(crate::view::OnUpdateMode::Row),
+                    ..Default::default()
                 })
                 .await?;
 
@@ -382,4 +641,64 @@ impl Client {
             resp => Err(resp.into()),
         }
     }
+
+    #[doc = include_str!("../../docs/client/get_hosted_views.md")]
+    pub async fn get_hosted_views(&self) -> ClientResult<Vec<HostedView>> {
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: "".to_owned(),
+            client_req: Some(ClientReq::GetHostedViewsReq(GetHostedViewsReq {})),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::GetHostedViewsResp(GetHostedViewsResp { views }) => {
+                views.into_iter().map(|x| x.try_into()).collect()
+            },
+            resp => Err(resp.into()),
+        }
+    }
+
+    #[doc = include_str!("../../docs/client/get_sessions.md")]
+    pub async fn get_sessions(&self) -> ClientResult<Vec<SessionInfo>> {
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: "".to_owned(),
+            client_req: Some(ClientReq::GetSessionsReq(GetSessionsReq {})),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::GetSessionsResp(GetSessionsResp { sessions }) => {
+                Ok(sessions.into_iter().map(|x| x.into()).collect())
+            },
+            resp => Err(resp.into()),
+        }
+    }
+
+    #[doc = include_str!("../../docs/client/delete_hosted_view.md")]
+    pub async fn delete_hosted_view(&self, name: &str) -> ClientResult<()> {
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: name.to_owned(),
+            client_req: Some(ClientReq::DeleteHostedViewReq(DeleteHostedViewReq {})),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::DeleteHostedViewResp(_) => Ok(()),
+            resp => Err(resp.into()),
+        }
+    }
+
+    #[doc = include_str!("../../docs/client/close_session.md")]
+    pub async fn close_session(&self, session_id: u32) -> ClientResult<()> {
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: "".to_owned(),
+            client_req: Some(ClientReq::CloseSessionReq(CloseSessionReq { session_id })),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::CloseSessionResp(_) => Ok(()),
+            resp => Err(resp.into()),
+        }
+    }
 }
@@ -0,0 +1,146 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+use std::collections::HashMap;
+
+use prost::Message;
+
+use crate::proto::request::ClientReq;
+use crate::proto::{ColumnMask, ColumnPolicy, Request};
+#[cfg(doc)]
+use crate::{ProxySession, Session, Table, View};
+
+/// What a [`Session`] can read of a column it may not read in full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnAccess {
+    /// The column is omitted from [`Table::schema`] and [`View`]s, and
+    /// naming it is an error, as for a column which does not exist.
+    Hidden,
+
+    /// The column's non-null values are displayed as `"****"`.
+    Redacted,
+
+    /// The column's non-null values are displayed as a hex digest, so that
+    /// equal values remain equal. A digest of a value from a small domain
+    /// is easily reversed, so this is no substitute for [`Self::Hidden`].
+    Hashed,
+}
+
+/// Restrictions on the columns a [`Session`] can read from each [`Table`],
+/// keyed by [`Table`] name. e.g., to hide the `ssn` column of the `people`
+/// [`Table`] and redact its `email` column:
+///
+/// ```rust
+/// # use perspective_client::{ColumnAccess, ColumnPolicies};
+/// let policies = ColumnPolicies::default()
+///     .with_column("people", "ssn", ColumnAccess::Hidden)
+///     .with_column("people", "email", ColumnAccess::Redacted);
+/// ```
+///
+/// Redacted and hashed columns have type `"string"` and may be displayed
+/// (or aggregated by `"count"`), but not grouped, split, filtered, sorted or
+/// read by expressions. Expressions which read a hidden or masked column are
+/// also rejected by [`Table::validate_expressions`]. The index column of a
+/// [`Table`] cannot be hidden or masked. As with `SessionFilters`, a
+/// [`Session`] with column policies can only address the [`View`]s it
+/// created, and cannot make `system_info` or admin requests.
+#[derive(Clone, Debug, Default)]
+pub struct ColumnPolicies(HashMap<String, HashMap<String, ColumnAccess>>);
+
+impl ColumnPolicies {
+    /// Restrict the column `column` of the [`Table`] `table_name` to
+    /// `access`.
+    pub fn with_column(
+        mut self,
+        table_name: impl Into<String>,
+        column: impl Into<String>,
+        access: ColumnAccess,
+    ) -> Self {
+        self.0
+            .entry(table_name.into())
+            .or_default()
+            .insert(column.into(), access);
+
+        self
+    }
+
+    /// The restricted columns of the [`Table`] `table_name`.
+    pub fn get(&self, table_name: &str) -> Option<&HashMap<String, ColumnAccess>> {
+        self.0.get(table_name)
+    }
+
+    /// Add the restrictions of the [`Table`] `table_name` to `policy`,
+    /// keeping the stricter where both restrict a column: hiding a column is
+    /// stricter than masking it, and redacting it stricter than hashing it.
+    fn restrict(&self, table_name: &str, policy: &mut ColumnPolicy) {
+        let Some(columns) = self.get(table_name) else {
+            return;
+        };
+
+        for (column, access) in columns {
+            if policy.hidden.contains(column) {
+                continue;
+            }
+
+            let mask = match access {
+                ColumnAccess::Hidden => {
+                    policy.masked.remove(column);
+                    policy.hidden.push(column.clone());
+                    continue;
+                },
+                ColumnAccess::Redacted => ColumnMask::Redact,
+                ColumnAccess::Hashed => ColumnMask::Hash,
+            };
+
+            let masked = policy.masked.entry(column.clone()).or_insert(mask.into());
+            if mask == ColumnMask::Redact {
+                *masked = mask.into();
+            }
+        }
+
+        policy.hidden.sort();
+    }
+
+    /// Add the policy of the [`Table`] a decoded request addresses to its
+    /// `column_policy`, returning whether the request changed. A policy
+    /// already on the request, e.g. that of a [`ProxySession`] this request
+    /// was forwarded through, is kept, and where both restrict a column the
+    /// stricter restriction applies.
+    pub fn apply(&self, request: &mut Request) -> bool {
+        let column_policy = match request.client_req.as_mut() {
+            Some(ClientReq::TableMakeViewReq(req)) => &mut req.column_policy,
+            Some(ClientReq::TableSchemaReq(req)) => &mut req.column_policy,
+            Some(ClientReq::TableValidateExprReq(req)) => &mut req.column_policy,
+            _ => return false,
+        };
+
+        if self.get(&request.entity_id).is_none() {
+            return false;
+        }
+
+        let mut policy = column_policy.clone().unwrap_or_default();
+        self.restrict(&request.entity_id, &mut policy);
+        if column_policy.as_ref() == Some(&policy) {
+            false
+        } else {
+            *column_policy = Some(policy);
+            true
+        }
+    }
+
+    /// [`ColumnPolicies::apply`] to an encoded request, returning the
+    /// re-encoded request if it changed. Requests which cannot be decoded
+    /// are left unchanged, to be rejected by the engine.
+    pub fn apply_encoded(&self, request: &[u8]) -> Option<Vec<u8>> {
+        let mut request = Request::decode(request).ok()?;
+        self.apply(&mut request).then(|| request.encode_to_vec())
+    }
+}
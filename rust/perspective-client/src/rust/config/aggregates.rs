@@ -10,14 +10,15 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
+use std::cmp::Ordering;
 use std::fmt::Display;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use crate::proto;
 use crate::proto::view_config;
+use crate::{proto, ClientError};
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize, TS)]
 #[serde()]
@@ -108,6 +109,15 @@ pub enum SingleAggregate {
 
     #[serde(rename = "var")]
     Var,
+
+    #[serde(rename = "mode")]
+    Mode,
+
+    #[serde(rename = "count distinct approx")]
+    DistinctCountApprox,
+
+    #[serde(rename = "null count")]
+    NullCount,
 }
 
 impl Display for SingleAggregate {
@@ -142,6 +152,9 @@ impl Display for SingleAggregate {
             Self::Var => "var",
             Self::Max => "max",
             Self::Min => "min",
+            Self::Mode => "mode",
+            Self::DistinctCountApprox => "count distinct approx",
+            Self::NullCount => "null count",
         };
 
         write!(fmt, "{}", term)
@@ -182,33 +195,210 @@ impl FromStr for SingleAggregate {
             "high minus low" => Ok(Self::HighMinusLow),
             "stddev" => Ok(Self::StdDev),
             "var" => Ok(Self::Var),
+            "mode" => Ok(Self::Mode),
+            "count distinct approx" => Ok(Self::DistinctCountApprox),
+            "null count" => Ok(Self::NullCount),
             x => Err(format!("Unknown aggregate `{}`", x)),
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize, TS)]
+/// Aggregates parameterized by a second column, serialized as e.g.
+/// `["weighted mean", "weights"]`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize, TS)]
 #[serde()]
 pub enum MultiAggregate {
+    /// `sum(x * by) / sum(by)`
     #[serde(rename = "weighted mean")]
     WeightedMean,
+
+    /// `sum(x) / sum(by)`
+    #[serde(rename = "sum ratio")]
+    SumRatio,
+
+    /// The value of `x` at the max of `by`.
+    #[serde(rename = "last by")]
+    LastBy,
+
+    /// The value of `x` at the min of `by`.
+    #[serde(rename = "first by")]
+    FirstBy,
+
+    /// Population covariance of `x` and `by`.
+    #[serde(rename = "covariance")]
+    Covariance,
+
+    /// Pearson correlation of `x` and `by`.
+    #[serde(rename = "correlation")]
+    Correlation,
 }
 
-impl Display for MultiAggregate {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+const MULTI_AGGREGATES: &[MultiAggregate] = &[
+    MultiAggregate::WeightedMean,
+    MultiAggregate::SumRatio,
+    MultiAggregate::LastBy,
+    MultiAggregate::FirstBy,
+    MultiAggregate::Covariance,
+    MultiAggregate::Correlation,
+];
+
+impl MultiAggregate {
+    /// All `MultiAggregate` variants, in display order.
+    pub fn iter_all() -> impl Iterator<Item = MultiAggregate> {
+        MULTI_AGGREGATES.iter().copied()
+    }
+
+    /// The human-readable prefix for this aggregate, which precedes the
+    /// argument column in `Aggregate`'s `Display`, e.g.
+    /// `"weighted mean by"`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WeightedMean => "weighted mean by",
+            Self::SumRatio => "sum ratio over",
+            Self::LastBy => "last by",
+            Self::FirstBy => "first by",
+            Self::Covariance => "covariance with",
+            Self::Correlation => "correlation with",
+        }
+    }
+
+    /// Can this aggregate be applied to a column of type `col_type` with an
+    /// argument column of type `by_type`?
+    pub fn supports(&self, col_type: proto::ColumnType, by_type: proto::ColumnType) -> bool {
+        use proto::ColumnType::*;
         match self {
-            MultiAggregate::WeightedMean => write!(f, "weighted mean"),
+            Self::LastBy | Self::FirstBy => true,
+            Self::WeightedMean | Self::SumRatio | Self::Covariance | Self::Correlation => {
+                matches!(col_type, Integer | Float) && matches!(by_type, Integer | Float)
+            },
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize, TS)]
+impl Display for MultiAggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let term = match self {
+            Self::WeightedMean => "weighted mean",
+            Self::SumRatio => "sum ratio",
+            Self::LastBy => "last by",
+            Self::FirstBy => "first by",
+            Self::Covariance => "covariance",
+            Self::Correlation => "correlation",
+        };
+
+        write!(f, "{}", term)
+    }
+}
+
+impl FromStr for MultiAggregate {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        MULTI_AGGREGATES
+            .iter()
+            .find(|x| x.to_string() == value)
+            .copied()
+            .ok_or_else(|| format!("Unknown aggregate `{}`", value))
+    }
+}
+
+/// `Aggregate` is `Eq` and `Ord`, as `Percentile` quantiles are compared with
+/// [`f64::total_cmp`].
+#[derive(Clone, Debug, Deserialize, Serialize, TS)]
 #[serde(untagged)]
 pub enum Aggregate {
     SingleAggregate(SingleAggregate),
     MultiAggregate(MultiAggregate, String),
+
+    /// The value at quantile `0.0 < q < 1.0` of the group, serialized as
+    /// `["percentile", q]`.
+    Percentile(
+        #[serde(with = "percentile")]
+        #[ts(type = "[\"percentile\", number]")]
+        f64,
+    ),
+
+    /// An ExprTK expression evaluated over the group, where each `"column"`
+    /// reference is a vector of the group's values, e.g.
+    /// `sum("x" * "y") / sum("y")`. Serialized as `["custom", expr]`.
+    Custom(
+        #[serde(with = "custom")]
+        #[ts(type = "[\"custom\", string]")]
+        String,
+    ),
 }
 
+/// Generates a `serde(with = ...)` module which (de)serializes an aggregate's
+/// argument as the pair `[name, argument]`.
+macro_rules! tagged_aggregate {
+    ($module:ident, $name:literal, $ty:ty) => {
+        mod $module {
+            use serde::de::Error;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            pub fn serialize<S: Serializer>(value: &$ty, serializer: S) -> Result<S::Ok, S::Error> {
+                ($name, value).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$ty, D::Error> {
+                let (name, value) = <(String, $ty)>::deserialize(deserializer)?;
+                if name == $name {
+                    Ok(value)
+                } else {
+                    Err(D::Error::custom(format!("Unknown aggregate `{}`", name)))
+                }
+            }
+        }
+    };
+}
+
+tagged_aggregate!(percentile, "percentile", f64);
+tagged_aggregate!(custom, "custom", String);
+
+impl Aggregate {
+    /// Is this a `Percentile` with a quantile in the open interval `(0, 1)`?
+    pub fn is_valid_percentile(&self) -> bool {
+        matches!(self, Self::Percentile(q) if *q > 0.0 && *q < 1.0)
+    }
+
+    fn variant_index(&self) -> u8 {
+        match self {
+            Self::SingleAggregate(_) => 0,
+            Self::MultiAggregate(..) => 1,
+            Self::Percentile(_) => 2,
+            Self::Custom(_) => 3,
+        }
+    }
+}
+
+impl Ord for Aggregate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::SingleAggregate(x), Self::SingleAggregate(y)) => x.cmp(y),
+            (Self::MultiAggregate(x, a), Self::MultiAggregate(y, b)) => (x, a).cmp(&(y, b)),
+            (Self::Percentile(x), Self::Percentile(y)) => x.total_cmp(y),
+            (Self::Custom(x), Self::Custom(y)) => x.cmp(y),
+            _ => self.variant_index().cmp(&other.variant_index()),
+        }
+    }
+}
+
+impl PartialOrd for Aggregate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Aggregate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Aggregate {}
+
 impl From<&'static str> for Aggregate {
     fn from(value: &'static str) -> Self {
         Self::from_str(value).expect("Unknown aggregate")
@@ -219,9 +409,9 @@ impl Display for Aggregate {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
         match self {
             Self::SingleAggregate(x) => write!(fmt, "{}", x)?,
-            Self::MultiAggregate(MultiAggregate::WeightedMean, x) => {
-                write!(fmt, "weighted mean by {}", x)?
-            },
+            Self::MultiAggregate(x, column) => write!(fmt, "{} {}", x.label(), column)?,
+            Self::Percentile(x) => write!(fmt, "percentile {}", x)?,
+            Self::Custom(x) => write!(fmt, "custom:{}", x)?,
         };
         Ok(())
     }
@@ -230,14 +420,33 @@ impl Display for Aggregate {
 impl FromStr for Aggregate {
     type Err = String;
 
+    /// `SingleAggregate` names are matched first, so e.g. `"last by index"`
+    /// parses as `SingleAggregate::LastByIndex` rather than as
+    /// `MultiAggregate::LastBy` over a column named `"index"`.
     fn from_str(input: &str) -> Result<Self, String> {
-        Ok(
-            if let Some(stripped) = input.strip_prefix("weighted mean by ") {
-                Self::MultiAggregate(MultiAggregate::WeightedMean, stripped.to_owned())
-            } else {
-                Self::SingleAggregate(SingleAggregate::from_str(input)?)
-            },
-        )
+        if let Some(stripped) = input.strip_prefix("percentile ") {
+            return Ok(Self::Percentile(
+                stripped
+                    .parse()
+                    .map_err(|_| format!("Invalid percentile `{}`", stripped))?,
+            ));
+        }
+
+        if let Some(stripped) = input.strip_prefix("custom:") {
+            return Ok(Self::Custom(stripped.trim().to_owned()));
+        }
+
+        if let Ok(x) = SingleAggregate::from_str(input) {
+            return Ok(Self::SingleAggregate(x));
+        }
+
+        MULTI_AGGREGATES
+            .iter()
+            .find_map(|x| {
+                let column = input.strip_prefix(x.label())?.strip_prefix(' ')?;
+                Some(Self::MultiAggregate(*x, column.to_owned()))
+            })
+            .ok_or_else(|| format!("Unknown aggregate `{}`", input))
     }
 }
 
@@ -251,6 +460,9 @@ const STRING_AGGREGATES: &[SingleAggregate] = &[
     SingleAggregate::Last,
     SingleAggregate::LastByIndex,
     SingleAggregate::Median,
+    SingleAggregate::Mode,
+    SingleAggregate::DistinctCountApprox,
+    SingleAggregate::NullCount,
     SingleAggregate::Q1,
     SingleAggregate::Q3,
     SingleAggregate::Unique,
@@ -274,6 +486,9 @@ const NUMBER_AGGREGATES: &[SingleAggregate] = &[
     SingleAggregate::Last,
     SingleAggregate::Mean,
     SingleAggregate::Median,
+    SingleAggregate::Mode,
+    SingleAggregate::DistinctCountApprox,
+    SingleAggregate::NullCount,
     SingleAggregate::Q1,
     SingleAggregate::Q3,
     SingleAggregate::PctSumParent,
@@ -300,18 +515,26 @@ const DATETIME_AGGREGATES: &[SingleAggregate] = &[
     SingleAggregate::LastByIndex,
     SingleAggregate::Last,
     SingleAggregate::Median,
+    SingleAggregate::Mode,
+    SingleAggregate::DistinctCountApprox,
+    SingleAggregate::NullCount,
     SingleAggregate::Q1,
     SingleAggregate::Q3,
     SingleAggregate::Unique,
 ];
 
+/// Preset quantiles offered for `Aggregate::Percentile` on orderable
+/// (numeric and datetime) columns.
+const PERCENTILES: &[f64] = &[0.9, 0.95, 0.99];
+
 impl proto::ColumnType {
     pub fn aggregates_iter(&self) -> Box<dyn Iterator<Item = Aggregate>> {
         match self {
             Self::Date | Self::Datetime => Box::new(
                 DATETIME_AGGREGATES
                     .iter()
-                    .map(|x| Aggregate::SingleAggregate(*x)),
+                    .map(|x| Aggregate::SingleAggregate(*x))
+                    .chain(PERCENTILES.iter().map(|x| Aggregate::Percentile(*x))),
             ),
             Self::Boolean | Self::String => Box::new(
                 STRING_AGGREGATES
@@ -321,7 +544,8 @@ impl proto::ColumnType {
             Self::Integer | Self::Float => Box::new(
                 NUMBER_AGGREGATES
                     .iter()
-                    .map(|x| Aggregate::SingleAggregate(*x)),
+                    .map(|x| Aggregate::SingleAggregate(*x))
+                    .chain(PERCENTILES.iter().map(|x| Aggregate::Percentile(*x))),
             ),
         }
     }
@@ -342,15 +566,30 @@ impl From<Aggregate> for view_config::AggList {
             aggregations: match value {
                 Aggregate::SingleAggregate(x) => vec![format!("{}", x)],
                 Aggregate::MultiAggregate(x, y) => vec![format!("{}", x), format!("{}", y)],
+                Aggregate::Percentile(x) => vec!["percentile".to_owned(), format!("{}", x)],
+                Aggregate::Custom(x) => vec!["custom".to_owned(), x],
             },
         }
     }
 }
 
-impl From<view_config::AggList> for Aggregate {
-    fn from(value: view_config::AggList) -> Self {
-        Aggregate::SingleAggregate(
-            SingleAggregate::from_str(value.aggregations.first().unwrap()).unwrap(),
-        )
+impl TryFrom<view_config::AggList> for Aggregate {
+    type Error = ClientError;
+
+    fn try_from(value: view_config::AggList) -> Result<Self, ClientError> {
+        let aggregate = match value.aggregations.as_slice() {
+            [name, quantile] if name == "percentile" => quantile
+                .parse()
+                .map(Aggregate::Percentile)
+                .map_err(|_| format!("Invalid percentile `{}`", quantile)),
+            [name, expr] if name == "custom" => Ok(Aggregate::Custom(expr.clone())),
+            [name, column] => {
+                MultiAggregate::from_str(name).map(|x| Aggregate::MultiAggregate(x, column.clone()))
+            },
+            [name] => SingleAggregate::from_str(name).map(Aggregate::SingleAggregate),
+            x => Err(format!("Invalid aggregate {:?}", x)),
+        };
+
+        aggregate.map_err(ClientError::Internal)
     }
 }
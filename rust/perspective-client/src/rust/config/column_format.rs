@@ -0,0 +1,119 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::proto;
+use crate::proto::column_format::{DatetimeStyle, NumberStyle};
+
+/// How the values of a column are written by the text exporters of a `View`
+/// (`to_csv`, `to_json_string`, etc.), after the number and datetime column
+/// styles of `<perspective-viewer>`. Unset fields default to the viewer's
+/// defaults for the column's type.
+///
+/// This is a subset of the viewer's styles: other `Intl.NumberFormat` options
+/// such as `currency`, `notation` and `signDisplay`, and `useGrouping` values
+/// other than `true` and `false`, are rejected rather than ignored.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ColumnFormat {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub style: Option<NumberFormatStyle>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub minimum_fraction_digits: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub maximum_fraction_digits: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub use_grouping: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub date_style: Option<DatetimeFormatStyle>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub time_style: Option<DatetimeFormatStyle>,
+}
+
+/// Whether a number is written as-is, or multiplied by 100 with a `%` suffix.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, TS)]
+pub enum NumberFormatStyle {
+    #[default]
+    #[serde(rename = "decimal")]
+    Decimal,
+
+    #[serde(rename = "percent")]
+    Percent,
+}
+
+/// The date or time part of a `date` or `datetime` value, as the
+/// `Intl.DateTimeFormat` `dateStyle` and `timeStyle` options. `"disabled"`
+/// omits the part.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, TS)]
+pub enum DatetimeFormatStyle {
+    #[serde(rename = "full")]
+    Full,
+
+    #[serde(rename = "long")]
+    Long,
+
+    #[serde(rename = "medium")]
+    Medium,
+
+    #[serde(rename = "short")]
+    Short,
+
+    #[serde(rename = "disabled")]
+    Disabled,
+}
+
+impl From<NumberFormatStyle> for NumberStyle {
+    fn from(value: NumberFormatStyle) -> Self {
+        match value {
+            NumberFormatStyle::Decimal => NumberStyle::Decimal,
+            NumberFormatStyle::Percent => NumberStyle::Percent,
+        }
+    }
+}
+
+impl From<DatetimeFormatStyle> for DatetimeStyle {
+    fn from(value: DatetimeFormatStyle) -> Self {
+        match value {
+            DatetimeFormatStyle::Full => DatetimeStyle::Full,
+            DatetimeFormatStyle::Long => DatetimeStyle::Long,
+            DatetimeFormatStyle::Medium => DatetimeStyle::Medium,
+            DatetimeFormatStyle::Short => DatetimeStyle::Short,
+            DatetimeFormatStyle::Disabled => DatetimeStyle::Disabled,
+        }
+    }
+}
+
+impl From<ColumnFormat> for proto::ColumnFormat {
+    fn from(value: ColumnFormat) -> Self {
+        proto::ColumnFormat {
+            style: value.style.map(|x| NumberStyle::from(x).into()),
+            minimum_fraction_digits: value.minimum_fraction_digits,
+            maximum_fraction_digits: value.maximum_fraction_digits,
+            use_grouping: value.use_grouping,
+            date_style: value.date_style.map(|x| DatetimeStyle::from(x).into()),
+            time_style: value.time_style.map(|x| DatetimeStyle::from(x).into()),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::Scalar;
+use crate::proto;
+
+/// The expand/collapse state of a `View` with `group_by`, as the row paths (the
+/// `group_by` values from the root to the row) of its collapsed rows. A `View`
+/// is fully expanded by default, so the default `ExpansionState` is empty.
+/// Unlike row indices, row paths are stable across `Table::update` and
+/// re-creating the `View`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, TS)]
+#[serde(deny_unknown_fields)]
+pub struct ExpansionState {
+    #[serde(default)]
+    pub collapsed: Vec<Vec<Scalar>>,
+}
+
+impl From<ExpansionState> for Vec<proto::RowPath> {
+    fn from(value: ExpansionState) -> Self {
+        value
+            .collapsed
+            .into_iter()
+            .map(|path| proto::RowPath {
+                path: path.into_iter().map(|x| x.into()).collect(),
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<proto::RowPath>> for ExpansionState {
+    fn from(value: Vec<proto::RowPath>) -> Self {
+        ExpansionState {
+            collapsed: value
+                .into_iter()
+                .map(|row_path| row_path.path.into_iter().map(|x| x.into()).collect())
+                .collect(),
+        }
+    }
+}
@@ -16,8 +16,9 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use super::validate::ConfigError;
 use crate::proto;
-use crate::proto::scalar;
+use crate::proto::{scalar, ColumnType, GetFeaturesResp};
 
 /// This type represents the ViewConfig serializable type, which must be JSON
 /// safe.
@@ -131,8 +132,49 @@ impl Filter {
     pub fn term_mut(&mut self) -> &mut FilterTerm {
         &mut self.2
     }
+
+    /// Does this filter's op take a list of terms rather than a single
+    /// `Scalar` (e.g. `in`)?
+    pub fn is_list_op(&self) -> bool {
+        LIST_FILTER_OPS.contains(&self.op())
+    }
+
+    /// Validate this `Filter` against the `filter_ops` advertised by the
+    /// `Server` for a column of type `col_type`.
+    pub fn validate(
+        &self,
+        col_type: ColumnType,
+        features: &GetFeaturesResp,
+    ) -> Result<(), ConfigError> {
+        let is_supported = features
+            .filter_ops
+            .get(&(col_type as u32))
+            .map(|x| x.options.iter().any(|op| op == self.op()))
+            .unwrap_or_default();
+
+        if !is_supported {
+            return Err(ConfigError::InvalidFilterOp {
+                column: self.column().to_owned(),
+                op: self.op().to_owned(),
+                column_type: col_type,
+            });
+        }
+
+        match (self.op(), self.term()) {
+            ("matches", FilterTerm::Scalar(Scalar::String(_))) => Ok(()),
+            ("matches", _) => Err(ConfigError::InvalidFilterTerm {
+                column: self.column().to_owned(),
+                op: self.op().to_owned(),
+                message: "expected a string pattern".to_owned(),
+            }),
+            _ => Ok(()),
+        }
+    }
 }
 
+/// Filter ops whose term is a list of values.
+const LIST_FILTER_OPS: &[&str] = &["in", "not in"];
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, TS)]
 pub enum FilterReducer {
     #[serde(rename = "and")]
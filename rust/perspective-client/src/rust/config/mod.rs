@@ -15,16 +15,28 @@
 //! features.
 
 mod aggregates;
+mod column_format;
 mod column_type;
+mod expansion_state;
 pub mod expressions;
 mod filters;
 mod plugin;
 mod sort;
+mod split_by_order;
+mod totals;
+mod validate;
 mod view_config;
+mod window;
 
 pub use aggregates::*;
+pub use column_format::*;
+pub use expansion_state::*;
 pub use expressions::*;
 pub use filters::*;
 pub use plugin::*;
 pub use sort::*;
+pub use split_by_order::*;
+pub use totals::*;
+pub use validate::*;
 pub use view_config::*;
+pub use window::*;
@@ -0,0 +1,62 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::proto;
+
+/// The order of a `split_by` column's values, and therefore of the column
+/// paths they generate.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, TS)]
+#[serde(untagged)]
+pub enum SplitByOrder {
+    /// Order values as strings, comparing runs of digits numerically, so e.g.
+    /// `"Q2"` precedes `"Q10"`. Serialized as `"natural"`.
+    Natural(NaturalOrder),
+
+    /// Order these values first, in the order given. Values which are not
+    /// listed follow in their default order. Values are matched against the
+    /// string form of the `split_by` value, as it appears in column paths.
+    Values(Vec<String>),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, TS)]
+pub enum NaturalOrder {
+    #[serde(rename = "natural")]
+    Natural,
+}
+
+impl From<SplitByOrder> for proto::view_config::SplitByOrder {
+    fn from(value: SplitByOrder) -> Self {
+        match value {
+            SplitByOrder::Natural(_) => proto::view_config::SplitByOrder {
+                natural: true,
+                values: vec![],
+            },
+            SplitByOrder::Values(values) => proto::view_config::SplitByOrder {
+                natural: false,
+                values,
+            },
+        }
+    }
+}
+
+impl From<proto::view_config::SplitByOrder> for SplitByOrder {
+    fn from(value: proto::view_config::SplitByOrder) -> Self {
+        if value.natural {
+            SplitByOrder::Natural(NaturalOrder::Natural)
+        } else {
+            SplitByOrder::Values(value.values)
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::proto;
+
+/// Which total rows a `View` with `group_by` emits, and where they are placed
+/// relative to the rows they total.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, TS)]
+#[serde(deny_unknown_fields)]
+pub struct Totals {
+    /// Whether to emit the grand total (top-level) row.
+    #[serde(skip_serializing_if = "is_true")]
+    #[serde(default = "default_true")]
+    #[ts(optional)]
+    pub grand_total: bool,
+
+    /// Whether to emit a total row for each expanded group, or only its
+    /// leaves.
+    #[serde(skip_serializing_if = "is_true")]
+    #[serde(default = "default_true")]
+    #[ts(optional)]
+    pub subtotals: bool,
+
+    #[serde(skip_serializing_if = "is_default_position")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub position: TotalsPosition,
+}
+
+impl Default for Totals {
+    fn default() -> Self {
+        Self {
+            grand_total: true,
+            subtotals: true,
+            position: TotalsPosition::default(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+fn is_default_position(position: &TotalsPosition) -> bool {
+    position == &TotalsPosition::default()
+}
+
+/// Whether a total row precedes (`"top"`) or follows (`"bottom"`) the rows of
+/// its group.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, TS)]
+pub enum TotalsPosition {
+    #[default]
+    #[serde(rename = "top")]
+    Top,
+
+    #[serde(rename = "bottom")]
+    Bottom,
+}
+
+impl Display for TotalsPosition {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "{}", match self {
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+        })
+    }
+}
+
+impl From<Totals> for proto::view_config::Totals {
+    fn from(value: Totals) -> Self {
+        proto::view_config::Totals {
+            hide_grand_total: !value.grand_total,
+            hide_subtotals: !value.subtotals,
+            position: match value.position {
+                TotalsPosition::Top => proto::view_config::TotalsPosition::Top,
+                TotalsPosition::Bottom => proto::view_config::TotalsPosition::Bottom,
+            } as i32,
+        }
+    }
+}
+
+impl From<proto::view_config::Totals> for Totals {
+    fn from(value: proto::view_config::Totals) -> Self {
+        Totals {
+            grand_total: !value.hide_grand_total,
+            subtotals: !value.hide_subtotals,
+            position: match proto::view_config::TotalsPosition::try_from(value.position) {
+                Ok(proto::view_config::TotalsPosition::Bottom) => TotalsPosition::Bottom,
+                _ => TotalsPosition::Top,
+            },
+        }
+    }
+}
@@ -0,0 +1,404 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use super::aggregates::*;
+use super::expressions::*;
+use super::filters::*;
+use super::sort::*;
+use super::view_config::*;
+use super::window::*;
+use crate::client::Features;
+use crate::proto::ColumnType;
+use crate::table::{Schema, ValidateExpressionsData};
+
+/// The `ViewConfigUpdate` field a [`ConfigError`] was found in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigField {
+    GroupBy,
+    SplitBy,
+    Columns,
+    Filter,
+    Sort,
+    Expressions,
+    Aggregates,
+    Window,
+    SplitByOrder,
+}
+
+impl Display for ConfigField {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{}", match self {
+            Self::GroupBy => "group_by",
+            Self::SplitBy => "split_by",
+            Self::Columns => "columns",
+            Self::Filter => "filter",
+            Self::Sort => "sort",
+            Self::Expressions => "expressions",
+            Self::Aggregates => "aggregates",
+            Self::Window => "window",
+            Self::SplitByOrder => "split_by_order",
+        })
+    }
+}
+
+/// A single problem found by [`ViewConfigUpdate::validate`].
+#[derive(Clone, Debug, Error, PartialEq, Serialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum ConfigError {
+    #[error("`{field}` is not supported by this `Server`")]
+    UnsupportedField { field: ConfigField },
+
+    #[error("Unknown \"{column}\" in `{field}`")]
+    UnknownColumn { field: ConfigField, column: String },
+
+    #[error("Invalid expression \"{name}\" at {line}:{column}: {message}")]
+    InvalidExpression {
+        name: String,
+        message: String,
+        line: u32,
+        column: u32,
+    },
+
+    #[error("Invalid custom aggregate for \"{name}\" at {line}:{column}: {message}")]
+    InvalidCustomAggregate {
+        name: String,
+        message: String,
+        line: u32,
+        column: u32,
+    },
+
+    #[error(
+        "Aggregate `{aggregate}` not supported for column \"{column}\" of type `{column_type}`"
+    )]
+    InvalidAggregate {
+        column: String,
+        aggregate: String,
+        column_type: ColumnType,
+    },
+
+    #[error("Filter op `{op}` not supported for column \"{column}\" of type `{column_type}`")]
+    InvalidFilterOp {
+        column: String,
+        op: String,
+        column_type: ColumnType,
+    },
+
+    #[error("Invalid term for filter op `{op}` on column \"{column}\": {message}")]
+    InvalidFilterTerm {
+        column: String,
+        op: String,
+        message: String,
+    },
+
+    #[error("Invalid window \"{name}\": {message}")]
+    InvalidWindow { name: String, message: String },
+}
+
+/// Resolves column names to types from the `Table`'s schema and the
+/// `expressions` and `window` columns of the config being validated.
+struct ColumnTypes<'a> {
+    schema: &'a Schema,
+    expressions: &'a HashMap<String, ColumnType>,
+    windows: HashMap<String, ColumnType>,
+}
+
+impl ColumnTypes<'_> {
+    fn get(&self, name: &str) -> Option<ColumnType> {
+        self.get_input(name)
+            .or_else(|| self.windows.get(name).copied())
+    }
+
+    /// Like [`Self::get`], but excluding `window` columns, which may not be
+    /// the input of another window.
+    fn get_input(&self, name: &str) -> Option<ColumnType> {
+        self.schema
+            .get(name)
+            .or_else(|| self.expressions.get(name))
+            .copied()
+    }
+
+    fn check(
+        &self,
+        field: ConfigField,
+        name: &str,
+        errors: &mut Vec<ConfigError>,
+    ) -> Option<ColumnType> {
+        let col_type = self.get(name);
+        if col_type.is_none() {
+            errors.push(ConfigError::UnknownColumn {
+                field,
+                column: name.to_owned(),
+            });
+        }
+
+        col_type
+    }
+}
+
+fn is_valid_aggregate(col_type: ColumnType, aggregate: &Aggregate, types: &ColumnTypes) -> bool {
+    match aggregate {
+        Aggregate::SingleAggregate(_) => col_type.aggregates_iter().any(|x| &x == aggregate),
+        Aggregate::MultiAggregate(x, column) => types
+            .get(column)
+            .map(|by_type| x.supports(col_type, by_type))
+            .unwrap_or_default(),
+        Aggregate::Percentile(_) => {
+            aggregate.is_valid_percentile()
+                && col_type
+                    .aggregates_iter()
+                    .any(|x| matches!(x, Aggregate::Percentile(_)))
+        },
+        Aggregate::Custom(_) => true,
+    }
+}
+
+/// Validate a single `window` column, returning its output type if it is
+/// valid.
+fn validate_window(
+    name: &str,
+    window: &Window,
+    types: &ColumnTypes,
+    errors: &mut Vec<ConfigError>,
+) -> Option<ColumnType> {
+    let num_errors = errors.len();
+    let invalid = |message: String| ConfigError::InvalidWindow {
+        name: name.to_owned(),
+        message,
+    };
+
+    if types.get_input(name).is_some() {
+        errors.push(invalid("Name conflicts with an existing column".into()));
+    }
+
+    let mut col_type = None;
+    match (&window.column, window.function.has_column()) {
+        (None, true) => errors.push(invalid(format!(
+            "`{}` requires a `column`",
+            window.function
+        ))),
+        (Some(_), false) => errors.push(invalid(format!(
+            "`{}` does not take a `column`",
+            window.function
+        ))),
+        (Some(column), true) => match types.get_input(column) {
+            None => errors.push(ConfigError::UnknownColumn {
+                field: ConfigField::Window,
+                column: column.to_owned(),
+            }),
+            Some(x) if !window.function.supports(x) => errors.push(invalid(format!(
+                "`{}` not supported for column \"{}\" of type `{}`",
+                window.function, column, x
+            ))),
+            Some(x) => col_type = Some(x),
+        },
+        (None, false) => {},
+    }
+
+    for column in &window.partition_by {
+        if types.get_input(column).is_none() {
+            errors.push(ConfigError::UnknownColumn {
+                field: ConfigField::Window,
+                column: column.to_owned(),
+            });
+        }
+    }
+
+    for Sort(column, dir) in &window.order_by {
+        if types.get_input(column).is_none() {
+            errors.push(ConfigError::UnknownColumn {
+                field: ConfigField::Window,
+                column: column.to_owned(),
+            });
+        } else if !matches!(dir, SortDir::Asc | SortDir::Desc) {
+            errors.push(invalid(format!(
+                "`order_by` direction must be `asc` or `desc`, not `{}`",
+                dir
+            )));
+        }
+    }
+
+    if errors.len() == num_errors {
+        Some(window.function.output_type(col_type))
+    } else {
+        None
+    }
+}
+
+impl ViewConfigUpdate {
+    /// Validate this `ViewConfigUpdate` against a `Table`'s `Schema`, the
+    /// `Features` of the `Server` which hosts it, and the result of
+    /// [`crate::Table::validate_expressions`] for this config's
+    /// `expressions`. Unset fields are not validated.
+    ///
+    /// Returns every problem found rather than just the first, so callers
+    /// can report errors per-field.
+    pub fn validate(
+        &self,
+        schema: &Schema,
+        features: &Features,
+        expressions: &ValidateExpressionsData,
+    ) -> Result<(), Vec<ConfigError>> {
+        let mut errors = vec![];
+        let mut types = ColumnTypes {
+            schema,
+            expressions: &expressions.expression_schema,
+            windows: HashMap::default(),
+        };
+
+        let mut expr_errors = expressions.errors.iter().collect::<Vec<_>>();
+        expr_errors.sort_by(|x, y| x.0.cmp(y.0));
+        for (name, err) in expr_errors {
+            errors.push(ConfigError::InvalidExpression {
+                name: name.to_owned(),
+                message: err.error_message.clone(),
+                line: err.line,
+                column: err.column,
+            });
+        }
+
+        if let Some(group_by) = &self.group_by {
+            if !group_by.is_empty() && !features.group_by {
+                errors.push(ConfigError::UnsupportedField {
+                    field: ConfigField::GroupBy,
+                });
+            }
+
+            for column in group_by {
+                types.check(ConfigField::GroupBy, column, &mut errors);
+            }
+        }
+
+        if let Some(split_by) = &self.split_by {
+            if !split_by.is_empty() && !features.split_by {
+                errors.push(ConfigError::UnsupportedField {
+                    field: ConfigField::SplitBy,
+                });
+            }
+
+            for column in split_by {
+                types.check(ConfigField::SplitBy, column, &mut errors);
+            }
+        }
+
+        if let Some(split_by_order) = &self.split_by_order {
+            let mut columns = split_by_order.keys().collect::<Vec<_>>();
+            columns.sort();
+            for column in columns {
+                types.check(ConfigField::SplitByOrder, column, &mut errors);
+            }
+        }
+
+        if let Some(exprs) = &self.expressions {
+            if !exprs.is_empty() && !features.expressions {
+                errors.push(ConfigError::UnsupportedField {
+                    field: ConfigField::Expressions,
+                });
+            }
+        }
+
+        if let Some(windows) = &self.window {
+            let mut windows = windows.iter().collect::<Vec<_>>();
+            windows.sort_by(|x, y| x.0.cmp(y.0));
+            for (name, window) in windows {
+                if let Some(col_type) = validate_window(name, window, &types, &mut errors) {
+                    types.windows.insert(name.to_owned(), col_type);
+                }
+            }
+        }
+
+        for column in self.columns.iter().flatten().flatten() {
+            types.check(ConfigField::Columns, column, &mut errors);
+        }
+
+        for sort in self.sort.iter().flatten() {
+            types.check(ConfigField::Sort, &sort.0, &mut errors);
+        }
+
+        for filter in self.filter.iter().flatten() {
+            if let Some(col_type) = types.check(ConfigField::Filter, filter.column(), &mut errors) {
+                if let Err(err) = filter.validate(col_type, features) {
+                    errors.push(err);
+                }
+            }
+        }
+
+        if let Some(aggregates) = &self.aggregates {
+            let mut aggregates = aggregates.iter().collect::<Vec<_>>();
+            aggregates.sort_by(|x, y| x.0.cmp(y.0));
+            for (column, aggregate) in aggregates {
+                if let Some(col_type) = types.check(ConfigField::Aggregates, column, &mut errors) {
+                    if !is_valid_aggregate(col_type, aggregate, &types) {
+                        errors.push(ConfigError::InvalidAggregate {
+                            column: column.to_owned(),
+                            aggregate: aggregate.to_string(),
+                            column_type: col_type,
+                        });
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The expressions of this config's `Aggregate::Custom` aggregates, keyed
+    /// by the column they aggregate, for validation with
+    /// [`crate::Table::validate_expressions`].
+    pub fn custom_aggregate_expressions(&self) -> Expressions {
+        Expressions(
+            self.aggregates
+                .iter()
+                .flatten()
+                .filter_map(|(column, aggregate)| match aggregate {
+                    Aggregate::Custom(expr) => Some((column.to_owned(), expr.to_owned())),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Convert the result of validating [`Self::custom_aggregate_expressions`]
+    /// to [`ConfigError`]s.
+    pub fn validate_custom_aggregates(
+        &self,
+        results: &ValidateExpressionsData,
+    ) -> Result<(), Vec<ConfigError>> {
+        let mut errors = results
+            .errors
+            .iter()
+            .map(|(name, err)| ConfigError::InvalidCustomAggregate {
+                name: name.to_owned(),
+                message: err.error_message.clone(),
+                line: err.line,
+                column: err.column,
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            errors.sort_by(|x, y| x.to_string().cmp(&y.to_string()));
+            Err(errors)
+        }
+    }
+}
@@ -19,8 +19,11 @@ use super::aggregates::*;
 use super::expressions::*;
 use super::filters::*;
 use super::sort::*;
-use crate::proto;
+use super::split_by_order::*;
+use super::totals::*;
+use super::window::*;
 use crate::proto::columns_update;
+use crate::{proto, ClientError};
 
 #[derive(Clone, Debug, Deserialize, Default, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -53,6 +56,18 @@ pub struct ViewConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub group_by_depth: Option<u32>,
+
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    pub window: HashMap<String, Window>,
+
+    #[serde(skip_serializing_if = "is_default_value")]
+    #[serde(default)]
+    pub totals: Totals,
+
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    pub split_by_order: HashMap<String, SplitByOrder>,
 }
 
 fn is_default_value<A: Default + PartialEq>(value: &A) -> bool {
@@ -106,6 +121,21 @@ pub struct ViewConfigUpdate {
     #[serde(default)]
     #[ts(optional)]
     pub group_by_depth: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub window: Option<HashMap<String, Window>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub totals: Option<Totals>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub split_by_order: Option<HashMap<String, SplitByOrder>>,
 }
 
 impl From<ViewConfigUpdate> for proto::ViewConfig {
@@ -144,6 +174,19 @@ impl From<ViewConfigUpdate> for proto::ViewConfig {
                 .map(|(x, y)| (x, y.into()))
                 .collect(),
             group_by_depth: value.group_by_depth,
+            window: value
+                .window
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(x, y)| (x, y.into()))
+                .collect(),
+            totals: value.totals.map(|x| x.into()),
+            split_by_order: value
+                .split_by_order
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(x, y)| (x, y.into()))
+                .collect(),
         }
     }
 }
@@ -178,21 +221,28 @@ impl From<ViewConfig> for ViewConfigUpdate {
             expressions: Some(value.expressions),
             aggregates: Some(value.aggregates),
             group_by_depth: value.group_by_depth,
+            window: Some(value.window),
+            totals: Some(value.totals),
+            split_by_order: Some(value.split_by_order),
         }
     }
 }
 
-impl From<proto::ViewConfig> for ViewConfig {
-    fn from(value: proto::ViewConfig) -> Self {
-        ViewConfig {
+impl TryFrom<proto::ViewConfig> for ViewConfig {
+    type Error = ClientError;
+
+    fn try_from(value: proto::ViewConfig) -> Result<Self, ClientError> {
+        Ok(ViewConfig {
             group_by: value.group_by,
             split_by: value.split_by,
-            columns: match value.columns.unwrap().opt_columns {
+            columns: match value.columns.and_then(|x| x.opt_columns) {
                 Some(columns_update::OptColumns::Columns(x)) => {
                     x.columns.into_iter().map(Some).collect()
                 },
                 _ => {
-                    panic!("Expected columns in ViewConfig")
+                    return Err(ClientError::Internal(
+                        "Expected columns in ViewConfig".to_owned(),
+                    ));
                 },
             },
             filter: value.filter.into_iter().map(|x| x.into()).collect(),
@@ -204,10 +254,21 @@ impl From<proto::ViewConfig> for ViewConfig {
             aggregates: value
                 .aggregates
                 .into_iter()
+                .map(|(x, y)| Ok((x, y.try_into()?)))
+                .collect::<Result<_, ClientError>>()?,
+            group_by_depth: value.group_by_depth,
+            window: value
+                .window
+                .into_iter()
                 .map(|(x, y)| (x, y.into()))
                 .collect(),
-            group_by_depth: value.group_by_depth,
-        }
+            totals: value.totals.map(|x| x.into()).unwrap_or_default(),
+            split_by_order: value
+                .split_by_order
+                .into_iter()
+                .map(|(x, y)| (x, y.into()))
+                .collect(),
+        })
     }
 }
 
@@ -241,6 +302,9 @@ impl ViewConfig {
         changed = Self::_apply(&mut self.sort, update.sort) || changed;
         changed = Self::_apply(&mut self.aggregates, update.aggregates) || changed;
         changed = Self::_apply(&mut self.expressions, update.expressions) || changed;
+        changed = Self::_apply(&mut self.window, update.window) || changed;
+        changed = Self::_apply(&mut self.totals, update.totals) || changed;
+        changed = Self::_apply(&mut self.split_by_order, update.split_by_order) || changed;
         changed
     }
 
@@ -254,7 +318,11 @@ impl ViewConfig {
             || self.split_by.contains(&name)
             || self.sort.iter().any(|x| x.0 == name)
             || self.filter.iter().any(|x| x.column() == name)
-            || self.columns.contains(&Some(name))
+            || self.columns.contains(&Some(name.clone()))
+            || self
+                .window
+                .values()
+                .any(|x| x.dependencies().any(|x| x == &name))
     }
 
     /// `ViewConfig` carries additional metadata in the form of `None` columns
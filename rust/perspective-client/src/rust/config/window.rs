@@ -0,0 +1,242 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::sort::*;
+use crate::proto;
+use crate::proto::ColumnType;
+
+/// A window function column, computed per-row over the rows of its
+/// `partition_by` group in `order_by` order.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, TS)]
+#[serde(deny_unknown_fields)]
+pub struct Window {
+    pub function: WindowFunction,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub column: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub partition_by: Vec<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub order_by: Vec<Sort>,
+
+    #[serde(skip_serializing_if = "is_default_frame")]
+    #[serde(default)]
+    #[ts(optional)]
+    pub frame: WindowFrame,
+
+    #[serde(skip_serializing_if = "is_default_offset")]
+    #[serde(default = "default_offset")]
+    #[ts(optional)]
+    pub offset: u32,
+}
+
+fn is_default_frame(frame: &WindowFrame) -> bool {
+    frame == &WindowFrame::default()
+}
+
+fn default_offset() -> u32 {
+    1
+}
+
+fn is_default_offset(offset: &u32) -> bool {
+    *offset == default_offset()
+}
+
+/// The rows of a partition, relative to the current row, which are input to
+/// an aggregate [`WindowFunction`], as a `[preceding, following]` pair.
+/// `None` is unbounded, and the default is a running total from the start of
+/// the partition to the current row, e.g. `[null, 0]`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, TS)]
+#[ts(type = "[number | null, number | null]")]
+pub struct WindowFrame(pub Option<u32>, pub Option<u32>);
+
+impl Default for WindowFrame {
+    fn default() -> Self {
+        Self(None, Some(0))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize, TS)]
+#[serde()]
+pub enum WindowFunction {
+    #[serde(rename = "sum")]
+    Sum,
+
+    #[serde(rename = "mean")]
+    Mean,
+
+    #[serde(rename = "min")]
+    Min,
+
+    #[serde(rename = "max")]
+    Max,
+
+    #[serde(rename = "count")]
+    Count,
+
+    #[serde(rename = "row number")]
+    RowNumber,
+
+    #[serde(rename = "rank")]
+    Rank,
+
+    #[serde(rename = "dense rank")]
+    DenseRank,
+
+    #[serde(rename = "lag")]
+    Lag,
+
+    #[serde(rename = "lead")]
+    Lead,
+
+    #[serde(rename = "percent of previous")]
+    PercentOfPrevious,
+}
+
+pub const WINDOW_FUNCTIONS: &[WindowFunction] = &[
+    WindowFunction::Sum,
+    WindowFunction::Mean,
+    WindowFunction::Min,
+    WindowFunction::Max,
+    WindowFunction::Count,
+    WindowFunction::RowNumber,
+    WindowFunction::Rank,
+    WindowFunction::DenseRank,
+    WindowFunction::Lag,
+    WindowFunction::Lead,
+    WindowFunction::PercentOfPrevious,
+];
+
+impl Display for WindowFunction {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let term = match self {
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Count => "count",
+            Self::RowNumber => "row number",
+            Self::Rank => "rank",
+            Self::DenseRank => "dense rank",
+            Self::Lag => "lag",
+            Self::Lead => "lead",
+            Self::PercentOfPrevious => "percent of previous",
+        };
+
+        write!(fmt, "{}", term)
+    }
+}
+
+impl FromStr for WindowFunction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        WINDOW_FUNCTIONS
+            .iter()
+            .find(|x| x.to_string() == value)
+            .copied()
+            .ok_or_else(|| format!("Unknown window function `{}`", value))
+    }
+}
+
+impl WindowFunction {
+    /// Whether this function reads the values of a `column`, or only the
+    /// position of each row in its partition.
+    pub fn has_column(&self) -> bool {
+        !matches!(self, Self::RowNumber | Self::Rank | Self::DenseRank)
+    }
+
+    /// Whether this function aggregates the rows in its [`WindowFrame`].
+    pub fn has_frame(&self) -> bool {
+        matches!(
+            self,
+            Self::Sum | Self::Mean | Self::Min | Self::Max | Self::Count
+        )
+    }
+
+    /// Whether this function reads the row `offset` rows from the current one.
+    pub fn has_offset(&self) -> bool {
+        matches!(self, Self::Lag | Self::Lead)
+    }
+
+    /// Whether this function can be applied to a `column` of type `col_type`.
+    pub fn supports(&self, col_type: ColumnType) -> bool {
+        match self {
+            Self::Sum | Self::Mean | Self::PercentOfPrevious => {
+                matches!(col_type, ColumnType::Integer | ColumnType::Float)
+            },
+            _ => true,
+        }
+    }
+
+    /// The type of the column this function outputs, given the type of its
+    /// input `column`.
+    pub fn output_type(&self, col_type: Option<ColumnType>) -> ColumnType {
+        match self {
+            Self::Sum | Self::Mean | Self::PercentOfPrevious => ColumnType::Float,
+            Self::Count | Self::RowNumber | Self::Rank | Self::DenseRank => ColumnType::Integer,
+            Self::Min | Self::Max | Self::Lag | Self::Lead => col_type.unwrap_or(ColumnType::Float),
+        }
+    }
+}
+
+impl Window {
+    /// All columns this window reads from, in `column`, `partition_by`,
+    /// `order_by` order.
+    pub fn dependencies(&self) -> impl Iterator<Item = &String> {
+        self.column
+            .iter()
+            .chain(self.partition_by.iter())
+            .chain(self.order_by.iter().map(|x| &x.0))
+    }
+}
+
+impl From<Window> for proto::view_config::Window {
+    fn from(value: Window) -> Self {
+        proto::view_config::Window {
+            function: value.function.to_string(),
+            column: value.column,
+            partition_by: value.partition_by,
+            order_by: value.order_by.into_iter().map(|x| x.into()).collect(),
+            preceding: value.frame.0,
+            following: value.frame.1,
+            offset: value.offset,
+        }
+    }
+}
+
+impl From<proto::view_config::Window> for Window {
+    fn from(value: proto::view_config::Window) -> Self {
+        Window {
+            function: value.function.parse().unwrap(),
+            column: value.column,
+            partition_by: value.partition_by,
+            order_by: value.order_by.into_iter().map(|x| x.into()).collect(),
+            frame: WindowFrame(value.preceding, value.following),
+            offset: value.offset,
+        }
+    }
+}
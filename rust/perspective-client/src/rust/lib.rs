@@ -16,22 +16,41 @@
     clippy::await_holding_refcell_ref
 )]
 
+mod authorizer;
 mod client;
+mod column_policy;
 mod session;
+mod session_filter;
 mod table;
 mod table_data;
 mod view;
 
 pub mod config;
 
+// Public only with the `server-proto` feature, so that `perspective-server`
+// can enforce session policies on the requests it decodes; not part of this
+// crate's API.
+#[cfg(feature = "server-proto")]
+#[doc(hidden)]
+#[allow(unknown_lints)]
+#[allow(clippy::all)]
+pub mod proto;
+
+#[cfg(not(feature = "server-proto"))]
 #[allow(unknown_lints)]
 #[allow(clippy::all)]
 mod proto;
 pub mod utils;
 
-pub use crate::client::{Client, ClientHandler, Features, SystemInfo};
+pub use crate::authorizer::{AuthError, AuthRequest, Authorizer, ReadOnly, RequestTarget};
+pub use crate::client::{
+    Client, ClientHandler, Features, HostedView, RequestLatency, SessionInfo, SessionStats,
+    SystemInfo, TableStats,
+};
+pub use crate::column_policy::{ColumnAccess, ColumnPolicies};
 pub use crate::proto::{ColumnType, SortOp, ViewOnUpdateResp};
 pub use crate::session::{ProxySession, Session};
+pub use crate::session_filter::SessionFilters;
 pub use crate::table::{
     Schema, Table, TableInitOptions, TableReadFormat, UpdateOptions, ValidateExpressionsData,
 };
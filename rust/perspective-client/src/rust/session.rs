@@ -16,8 +16,11 @@ use std::sync::Arc;
 use futures::Future;
 use prost::Message;
 
+use crate::authorizer::{Authorizer, ProxyAuthorizer};
+use crate::column_policy::ColumnPolicies;
 use crate::proto::request::ClientReq;
 use crate::proto::{Request, Response};
+use crate::session_filter::SessionFilters;
 use crate::{Client, ClientError};
 #[cfg(doc)]
 use crate::{Table, View};
@@ -87,6 +90,9 @@ type ProxyCallback =
 pub struct ProxySession {
     parent: Client,
     callback: ProxyCallback,
+    authorizer: ProxyAuthorizer,
+    filters: Option<SessionFilters>,
+    column_policies: Option<ColumnPolicies>,
 }
 
 impl ProxySession {
@@ -100,8 +106,53 @@ impl ProxySession {
         ProxySession {
             parent: client,
             callback: Arc::new(send_response),
+            authorizer: ProxyAuthorizer::default(),
+            filters: None,
+            column_policies: None,
         }
     }
+
+    /// Authorize each request of this [`ProxySession`] with `authorizer`, as
+    /// `identity`, before it is forwarded to the parent [`Client`]. Denied
+    /// requests are answered with a [`ClientError::Unauthorized`] error, as
+    /// are requests for [`View`]s not created through this [`ProxySession`].
+    pub fn with_authorizer(
+        mut self,
+        identity: impl Into<String>,
+        authorizer: Arc<dyn Authorizer>,
+    ) -> Self {
+        self.authorizer.set_authorizer(identity.into(), authorizer);
+        self
+    }
+
+    /// Allow this [`ProxySession`]'s admin requests (see
+    /// [`crate::AuthRequest::is_admin`]), which are denied by default. They
+    /// are forwarded to the parent [`Client`], whose own session must also
+    /// allow them.
+    pub fn with_admin(mut self) -> Self {
+        self.authorizer.set_admin();
+        self
+    }
+
+    /// Restrict the rows this [`ProxySession`] can read from the parent
+    /// [`Client`]'s [`Table`]s to those which pass `filters`. A filtered
+    /// [`ProxySession`] can only address the [`View`]s created through it,
+    /// and cannot make `system_info` or admin requests.
+    pub fn with_filters(mut self, filters: SessionFilters) -> Self {
+        self.authorizer.set_restricted();
+        self.filters = Some(filters);
+        self
+    }
+
+    /// Restrict the columns this [`ProxySession`] can read from the parent
+    /// [`Client`]'s [`Table`]s per `policies`. As with
+    /// [`ProxySession::with_filters`], it can then only address the
+    /// [`View`]s created through it.
+    pub fn with_column_policies(mut self, policies: ColumnPolicies) -> Self {
+        self.authorizer.set_restricted();
+        self.column_policies = Some(policies);
+        self
+    }
 }
 
 fn encode(response: Response, callback: ProxyCallback) -> Result<(), ClientError> {
@@ -113,7 +164,20 @@ fn encode(response: Response, callback: ProxyCallback) -> Result<(), ClientError
 
 impl Session<ClientError> for ProxySession {
     async fn handle_request(&self, request: &[u8]) -> Result<(), ClientError> {
-        let req = Request::decode(request)?;
+        let mut req = Request::decode(request)?;
+        if let Err(denied) = self.authorizer.authorize(&req) {
+            (self.callback)(&denied)?;
+            return Ok(());
+        }
+
+        if let Some(filters) = &self.filters {
+            filters.apply(&mut req);
+        }
+
+        if let Some(policies) = &self.column_policies {
+            policies.apply(&mut req);
+        }
+
         let callback = self.callback.clone();
         match req.client_req.as_ref() {
             Some(ClientReq::ViewOnUpdateReq(_)) => {
@@ -127,7 +191,13 @@ impl Session<ClientError> for ProxySession {
                 self.parent.subscribe(&req, Box::new(on_update)).await?
             },
             Some(_) => {
-                let on_update = move |response| encode(response, callback);
+                let authorizer = self.authorizer.clone();
+                let request = req.clone();
+                let on_update = move |response: Response| {
+                    authorizer.on_response(&request, &response);
+                    encode(response, callback)
+                };
+
                 self.parent
                     .subscribe_once(&req, Box::new(on_update))
                     .await?
@@ -0,0 +1,90 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+use std::collections::HashMap;
+
+use prost::Message;
+
+use crate::config::Filter;
+use crate::proto::request::ClientReq;
+use crate::proto::{view_config, Request};
+#[cfg(doc)]
+use crate::{ProxySession, Session, Table, View};
+
+/// Filters which restrict the rows a [`Session`] can read from each [`Table`],
+/// keyed by [`Table`] name. e.g., to limit a [`Session`] to the rows of the
+/// `trades` [`Table`] whose `desk` is `"rates"`:
+///
+/// ```rust
+/// # use perspective_client::SessionFilters;
+/// # use perspective_client::config::{Filter, FilterTerm};
+/// let desk = FilterTerm::Scalar("rates".into());
+/// let filters = SessionFilters::default().with_filter("trades", Filter::new("desk", "==", desk));
+/// ```
+///
+/// Every [`View`] the [`Session`] creates from a filtered [`Table`] only
+/// contains rows which pass all of that [`Table`]'s filters, whatever its own
+/// `filter` and `filter_op`, and so do its exports. [`Table::size`] counts
+/// only these rows. The filters are added by the [`Session`] as requests
+/// arrive, so they cannot be removed by the [`Client`](crate::Client), and
+/// they do not appear in [`View::get_config`]. So that they cannot be read
+/// around, a filtered [`Session`] can only address the [`View`]s it created,
+/// and cannot make `system_info` or admin requests.
+#[derive(Clone, Debug, Default)]
+pub struct SessionFilters(HashMap<String, Vec<Filter>>);
+
+impl SessionFilters {
+    /// Add `filter` to the filters of the [`Table`] `table_name`.
+    pub fn with_filter(mut self, table_name: impl Into<String>, filter: Filter) -> Self {
+        self.0.entry(table_name.into()).or_default().push(filter);
+        self
+    }
+
+    /// The filters of the [`Table`] `table_name`.
+    pub fn get(&self, table_name: &str) -> &[Filter] {
+        self.0
+            .get(table_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Add the filters of the [`Table`] a decoded request addresses to its
+    /// `session_filter`, returning whether the request changed. Filters
+    /// already in the `session_filter`, e.g. those of a [`ProxySession`]
+    /// this request was forwarded through, are kept, so that the request
+    /// reads only rows which pass both.
+    pub fn apply(&self, request: &mut Request) -> bool {
+        let session_filter = match request.client_req.as_mut() {
+            Some(ClientReq::TableMakeViewReq(req)) => &mut req.session_filter,
+            Some(ClientReq::TableSizeReq(req)) => &mut req.session_filter,
+            _ => return false,
+        };
+
+        let mut changed = false;
+        for filter in self.get(&request.entity_id) {
+            let filter = view_config::Filter::from(filter.clone());
+            if !session_filter.contains(&filter) {
+                session_filter.push(filter);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// [`SessionFilters::apply`] to an encoded request, returning the
+    /// re-encoded request if it changed. Requests which cannot be decoded
+    /// are left unchanged, to be rejected by the engine.
+    pub fn apply_encoded(&self, request: &[u8]) -> Option<Vec<u8>> {
+        let mut request = Request::decode(request).ok()?;
+        self.apply(&mut request).then(|| request.encode_to_vec())
+    }
+}
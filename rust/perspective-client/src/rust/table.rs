@@ -19,7 +19,7 @@ use ts_rs::TS;
 
 use crate::assert_table_api;
 use crate::client::{Client, Features};
-use crate::config::{Expressions, ViewConfigUpdate};
+use crate::config::{ConfigError, Expressions, ViewConfigUpdate};
 use crate::proto::make_table_req::make_table_options::MakeTableType;
 use crate::proto::make_table_req::MakeTableOptions;
 use crate::proto::request::ClientReq;
@@ -220,7 +220,7 @@ impl Table {
 
     #[doc = include_str!("../../docs/table/columns.md")]
     pub async fn columns(&self) -> ClientResult<Vec<String>> {
-        let msg = self.client_message(ClientReq::TableSchemaReq(TableSchemaReq {}));
+        let msg = self.client_message(ClientReq::TableSchemaReq(TableSchemaReq::default()));
         match self.client.oneshot(&msg).await? {
             ClientResp::TableSchemaResp(TableSchemaResp { schema }) => Ok(schema
                 .map(|x| x.schema.into_iter().map(|x| x.name.to_owned()).collect())
@@ -231,7 +231,7 @@ impl Table {
 
     #[doc = include_str!("../../docs/table/size.md")]
     pub async fn size(&self) -> ClientResult<usize> {
-        let msg = self.client_message(ClientReq::TableSizeReq(TableSizeReq {}));
+        let msg = self.client_message(ClientReq::TableSizeReq(TableSizeReq::default()));
         match self.client.oneshot(&msg).await? {
             ClientResp::TableSizeResp(TableSizeResp { size }) => Ok(size as usize),
             resp => Err(resp.into()),
@@ -240,7 +240,7 @@ impl Table {
 
     #[doc = include_str!("../../docs/table/schema.md")]
     pub async fn schema(&self) -> ClientResult<HashMap<String, ColumnType>> {
-        let msg = self.client_message(ClientReq::TableSchemaReq(TableSchemaReq {}));
+        let msg = self.client_message(ClientReq::TableSchemaReq(TableSchemaReq::default()));
         match self.client.oneshot(&msg).await? {
             ClientResp::TableSchemaResp(TableSchemaResp { schema }) => Ok(schema
                 .map(|x| {
@@ -337,6 +337,8 @@ impl Table {
     ) -> ClientResult<ValidateExpressionsData> {
         let msg = self.client_message(ClientReq::TableValidateExprReq(TableValidateExprReq {
             column_to_expr: expressions.0,
+            column_policy: None,
+            custom_aggregates: HashMap::default(),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -353,6 +355,56 @@ impl Table {
         }
     }
 
+    #[doc = include_str!("../../docs/table/validate_view_config.md")]
+    pub async fn validate_view_config(
+        &self,
+        config: &ViewConfigUpdate,
+    ) -> ClientResult<Vec<ConfigError>> {
+        let features = self.get_features()?;
+        let schema = self.schema().await?;
+        let expressions = self
+            .validate_expressions(config.expressions.clone().unwrap_or_default())
+            .await?;
+
+        let mut errors = config
+            .validate(&schema, &features, &expressions)
+            .err()
+            .unwrap_or_default();
+
+        let custom_aggregates = config.custom_aggregate_expressions();
+        if !custom_aggregates.is_empty() {
+            let results = self.validate_custom_aggregates(custom_aggregates).await?;
+            if let Err(custom_errors) = config.validate_custom_aggregates(&results) {
+                errors.extend(custom_errors);
+            }
+        }
+
+        Ok(errors)
+    }
+
+    /// Validate `custom` aggregate expressions over vectors of each group's
+    /// values, as the server evaluates them, rather than per row as
+    /// [`Table::validate_expressions`] does.
+    async fn validate_custom_aggregates(
+        &self,
+        aggregates: Expressions,
+    ) -> ClientResult<ValidateExpressionsData> {
+        let msg = self.client_message(ClientReq::TableValidateExprReq(TableValidateExprReq {
+            column_to_expr: HashMap::default(),
+            column_policy: None,
+            custom_aggregates: aggregates.0,
+        }));
+
+        match self.client.oneshot(&msg).await? {
+            ClientResp::TableValidateExprResp(result) => Ok(ValidateExpressionsData {
+                errors: result.errors,
+                expression_alias: result.expression_alias,
+                expression_schema: HashMap::default(),
+            }),
+            resp => Err(resp.into()),
+        }
+    }
+
     #[doc = include_str!("../../docs/table/view.md")]
     pub async fn view(&self, config: Option<ViewConfigUpdate>) -> ClientResult<View> {
         let view_name = nanoid!();
@@ -362,6 +414,8 @@ impl Table {
             client_req: ClientReq::TableMakeViewReq(TableMakeViewReq {
                 view_id: view_name.clone(),
                 config: config.map(|x| x.into()),
+                session_filter: vec![],
+                column_policy: None,
             })
             .into(),
         };
@@ -27,6 +27,12 @@ pub enum ClientError {
     #[error("View not found")]
     ViewNotFound,
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
     #[error("Abort(): {0}")]
     Internal(String),
 
@@ -72,6 +78,8 @@ impl From<proto::response::ClientResp> for ClientError {
             proto::response::ClientResp::ServerError(x) => match x.status_code() {
                 proto::StatusCode::ServerError => ClientError::Internal(x.message),
                 proto::StatusCode::ViewNotFound => ClientError::ViewNotFound,
+                proto::StatusCode::Unauthorized => ClientError::Unauthorized(x.message),
+                proto::StatusCode::QuotaExceeded => ClientError::QuotaExceeded(x.message),
             },
             x => ClientError::ResponseFailed(Box::new(x)),
         }
@@ -15,6 +15,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use futures::{Future, FutureExt};
+use nanoid::*;
 use prost::bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -22,6 +23,7 @@ use ts_rs::TS;
 use self::view_on_update_req::Mode;
 use crate::assert_view_api;
 use crate::client::Client;
+use crate::config::{ColumnFormat, FilterReducer, ViewConfigUpdate};
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::*;
@@ -32,6 +34,15 @@ pub use crate::utils::*;
 #[derive(Default, Debug, Deserialize, TS)]
 pub struct OnUpdateOptions {
     pub mode: Option<OnUpdateMode>,
+
+    #[serde(default)]
+    pub viewport: Option<ViewWindow>,
+
+    #[serde(default)]
+    pub include_viewport_data: Option<bool>,
+
+    #[serde(default)]
+    pub throttle_ms: Option<u32>,
 }
 
 #[derive(Default, Debug, Deserialize, TS)]
@@ -39,20 +50,47 @@ pub enum OnUpdateMode {
     #[default]
     #[serde(rename = "row")]
     Row,
+
+    #[serde(rename = "detail")]
+    Detail,
 }
 
 impl FromStr for OnUpdateMode {
     type Err = ClientError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "row" {
-            Ok(OnUpdateMode::Row)
-        } else {
-            Err(ClientError::Option)
+        match s {
+            "row" => Ok(OnUpdateMode::Row),
+            "detail" => Ok(OnUpdateMode::Detail),
+            _ => Err(ClientError::Option),
+        }
+    }
+}
+
+impl From<OnUpdateMode> for Mode {
+    fn from(value: OnUpdateMode) -> Self {
+        match value {
+            OnUpdateMode::Row => Mode::Row,
+            OnUpdateMode::Detail => Mode::Detail,
         }
     }
 }
 
+/// Serializes [`view_on_update_resp::RowOp`] as `"insert"`, `"update"` or
+/// `"delete"`, rather than as its protobuf tag.
+pub(crate) fn serialize_row_op<S>(op: &i32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use self::view_on_update_resp::RowOp;
+    match RowOp::try_from(*op) {
+        Ok(RowOp::RowInsert) => serializer.serialize_str("insert"),
+        Ok(RowOp::RowUpdate) => serializer.serialize_str("update"),
+        Ok(RowOp::RowDelete) => serializer.serialize_str("delete"),
+        Err(_) => Err(serde::ser::Error::custom("Unknown RowOp")),
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct Dimensions {
     pub num_view_rows: usize,
@@ -89,6 +127,41 @@ pub struct ViewWindow {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compression: Option<String>,
+
+    /// The BCP 47 locale of formatted numbers and dates, e.g. `"de-DE"`.
+    /// Locales without built-in support are an error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// The time zone of formatted `datetime` values, `"UTC"` or a fixed
+    /// offset such as `"+05:30"`. IANA time zone names are an error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+
+    /// The [`ColumnFormat`] of each column, by column name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formats: Option<HashMap<String, ColumnFormat>>,
+}
+
+impl ViewWindow {
+    /// The [`FormatOptions`] of this window, if it sets any format options.
+    fn format_options(&self) -> Option<FormatOptions> {
+        if self.locale.is_none() && self.time_zone.is_none() && self.formats.is_none() {
+            return None;
+        }
+
+        Some(FormatOptions {
+            locale: self.locale.clone(),
+            time_zone: self.time_zone.clone(),
+            columns: self
+                .formats
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(column, format)| (column, format.into()))
+                .collect(),
+        })
+    }
 }
 
 impl From<ViewWindow> for ViewPort {
@@ -165,7 +238,7 @@ impl View {
         match self.client.oneshot(&msg).await? {
             ClientResp::ViewGetConfigResp(ViewGetConfigResp {
                 config: Some(config),
-            }) => Ok(config.into()),
+            }) => config.try_into(),
             resp => Err(resp.into()),
         }
     }
@@ -208,6 +281,7 @@ impl View {
             index: window.index,
             formatted: window.formatted,
             leaves_only: window.leaves_only,
+            format: window.format_options(),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -233,6 +307,7 @@ impl View {
             index: window.index,
             formatted: window.formatted,
             leaves_only: window.leaves_only,
+            format: window.format_options(),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -258,6 +333,7 @@ impl View {
             index: window.index,
             formatted: window.formatted,
             leaves_only: window.leaves_only,
+            format: window.format_options(),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -271,6 +347,8 @@ impl View {
     #[doc = include_str!("../../docs/view/to_csv.md")]
     pub async fn to_csv(&self, window: ViewWindow) -> ClientResult<String> {
         let msg = self.client_message(ClientReq::ViewToCsvReq(ViewToCsvReq {
+            formatted: window.formatted,
+            format: window.format_options(),
             viewport: Some(window.into()),
         }));
 
@@ -325,7 +403,10 @@ impl View {
         };
 
         let msg = self.client_message(ClientReq::ViewOnUpdateReq(ViewOnUpdateReq {
-            mode: options.mode.map(|OnUpdateMode::Row| Mode::Row as i32),
+            mode: options.mode.map(|x| Mode::from(x) as i32),
+            viewport: options.viewport.map(|x| x.into()),
+            include_viewport_data: options.include_viewport_data.unwrap_or_default(),
+            throttle_ms: options.throttle_ms,
         }));
 
         self.client.subscribe(&msg, Box::new(callback)).await?;
@@ -345,6 +426,25 @@ impl View {
         }
     }
 
+    #[doc = include_str!("../../docs/view/set_update_viewport.md")]
+    pub async fn set_update_viewport(
+        &self,
+        update_id: u32,
+        viewport: ViewWindow,
+    ) -> ClientResult<()> {
+        let msg = self.client_message(ClientReq::ViewSetUpdateViewportReq(
+            ViewSetUpdateViewportReq {
+                id: update_id,
+                viewport: Some(viewport.into()),
+            },
+        ));
+
+        match self.client.oneshot(&msg).await? {
+            ClientResp::ViewSetUpdateViewportResp(_) => Ok(()),
+            resp => Err(resp.into()),
+        }
+    }
+
     #[doc = include_str!("../../docs/view/on_delete.md")]
     pub async fn on_delete(
         &self,
@@ -401,4 +501,167 @@ impl View {
             resp => Err(resp.into()),
         }
     }
+
+    #[doc = include_str!("../../docs/view/get_expansion_state.md")]
+    pub async fn get_expansion_state(&self) -> ClientResult<crate::config::ExpansionState> {
+        let msg = self.client_message(ClientReq::ViewGetExpansionStateReq(
+            ViewGetExpansionStateReq {},
+        ));
+
+        match self.client.oneshot(&msg).await? {
+            ClientResp::ViewGetExpansionStateResp(ViewGetExpansionStateResp { collapsed }) => {
+                Ok(collapsed.into())
+            },
+            resp => Err(resp.into()),
+        }
+    }
+
+    #[doc = include_str!("../../docs/view/set_expansion_state.md")]
+    pub async fn set_expansion_state(
+        &self,
+        state: crate::config::ExpansionState,
+    ) -> ClientResult<()> {
+        let msg = self.client_message(ClientReq::ViewSetExpansionStateReq(
+            ViewSetExpansionStateReq {
+                collapsed: state.into(),
+            },
+        ));
+
+        match self.client.oneshot(&msg).await? {
+            ClientResp::ViewSetExpansionStateResp(_) => Ok(()),
+            resp => Err(resp.into()),
+        }
+    }
+
+    #[doc = include_str!("../../docs/view/find_row_by_path.md")]
+    pub async fn find_row_by_path(
+        &self,
+        path: Vec<crate::config::Scalar>,
+    ) -> ClientResult<Option<u32>> {
+        let msg = self.client_message(ClientReq::ViewFindRowByPathReq(ViewFindRowByPathReq {
+            path: path.into_iter().map(|x| x.into()).collect(),
+        }));
+
+        match self.client.oneshot(&msg).await? {
+            ClientResp::ViewFindRowByPathResp(ViewFindRowByPathResp { row_index }) => Ok(row_index),
+            resp => Err(resp.into()),
+        }
+    }
+
+    #[doc = include_str!("../../docs/view/find_row_by_index.md")]
+    pub async fn find_row_by_index(
+        &self,
+        index: crate::config::Scalar,
+    ) -> ClientResult<Option<u32>> {
+        let msg = self.client_message(ClientReq::ViewFindRowByIndexReq(ViewFindRowByIndexReq {
+            index: Some(index.into()),
+        }));
+
+        match self.client.oneshot(&msg).await? {
+            ClientResp::ViewFindRowByIndexResp(ViewFindRowByIndexResp { row_index }) => {
+                Ok(row_index)
+            },
+            resp => Err(resp.into()),
+        }
+    }
+
+    #[doc = include_str!("../../docs/view/get_underlying_config.md")]
+    pub async fn get_underlying_config(
+        &self,
+        row_index: u32,
+        column_path: Vec<String>,
+    ) -> ClientResult<ViewConfigUpdate> {
+        Ok(self.underlying_config(row_index, column_path).await?.1)
+    }
+
+    #[doc = include_str!("../../docs/view/get_underlying_rows.md")]
+    pub async fn get_underlying_rows(
+        &self,
+        row_index: u32,
+        column_path: Vec<String>,
+        window: ViewWindow,
+    ) -> ClientResult<String> {
+        let (table_id, config) = self.underlying_config(row_index, column_path).await?;
+        let view_name = nanoid!();
+        let msg = Request {
+            msg_id: self.client.gen_id(),
+            entity_id: table_id,
+            client_req: ClientReq::TableMakeViewReq(TableMakeViewReq {
+                view_id: view_name.clone(),
+                config: Some(config.into()),
+                session_filter: vec![],
+                column_policy: None,
+            })
+            .into(),
+        };
+
+        let view = match self.client.oneshot(&msg).await? {
+            ClientResp::TableMakeViewResp(TableMakeViewResp { view_id })
+                if view_id == view_name =>
+            {
+                View::new(view_name, self.client.clone())
+            },
+            resp => return Err(resp.into()),
+        };
+
+        let rows = view.to_json_string(window).await;
+        view.delete().await?;
+        rows
+    }
+
+    /// The name of the source [`Table`] and the flat [`ViewConfigUpdate`]
+    /// selecting the rows aggregated into a cell of this [`View`].
+    async fn underlying_config(
+        &self,
+        row_index: u32,
+        column_path: Vec<String>,
+    ) -> ClientResult<(String, ViewConfigUpdate)> {
+        let config = self.get_config().await?;
+        if config.filter_op == FilterReducer::Or && !config.filter.is_empty() {
+            return Err(ClientError::NotImplemented(
+                "underlying rows of a `View` with `filter_op: \"or\"`",
+            ));
+        }
+
+        let msg = self.client_message(ClientReq::ViewGetUnderlyingRowsReq(
+            ViewGetUnderlyingRowsReq {
+                row_index,
+                column_path,
+            },
+        ));
+
+        let ViewGetUnderlyingRowsResp {
+            table_id,
+            filter,
+            mut columns,
+        } = match self.client.oneshot(&msg).await? {
+            ClientResp::ViewGetUnderlyingRowsResp(resp) => resp,
+            resp => return Err(resp.into()),
+        };
+
+        // Keep the expression columns this `View` shows alongside the
+        // `Table`'s own columns.
+        for column in config.columns.iter().flatten() {
+            if !columns.contains(column) {
+                columns.push(column.clone());
+            }
+        }
+
+        let mut filters = config.filter;
+        filters.extend(filter.into_iter().map(|x| x.into()));
+        Ok((table_id, ViewConfigUpdate {
+            group_by: Some(vec![]),
+            split_by: Some(vec![]),
+            columns: Some(columns.into_iter().map(Some).collect()),
+            filter: Some(filters),
+            filter_op: Some(FilterReducer::And),
+            sort: Some(config.sort),
+            expressions: Some(config.expressions),
+            aggregates: Some(HashMap::default()),
+            group_by_depth: None,
+            window: Some(config.window),
+            totals: None,
+            split_by_order: None,
+        }))
+    }
 }
@@ -361,6 +361,19 @@ impl Table {
         Ok(JsValue::from_serde_ext(&columns)?)
     }
 
+    #[apply(inherit_docs)]
+    #[inherit_doc = "table/validate_view_config.md"]
+    #[wasm_bindgen]
+    pub async fn validate_view_config(&self, config: JsViewConfig) -> ApiResult<JsValue> {
+        let config = js_sys::JSON::stringify(&config)?
+            .as_string()
+            .into_apierror()?;
+
+        let config: ViewConfigUpdate = serde_json::from_str(config.as_str())?;
+        let errors = self.0.validate_view_config(&config).await?;
+        Ok(JsValue::from_serde_ext(&errors)?)
+    }
+
     #[allow(clippy::use_self)]
     #[doc(hidden)]
     pub fn unsafe_get_model(&self) -> *const Table {
@@ -221,6 +221,18 @@ impl View {
         Ok(self.0.remove_update(callback_id).await?)
     }
 
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/set_update_viewport.md"]
+    #[wasm_bindgen]
+    pub async fn set_update_viewport(
+        &self,
+        callback_id: u32,
+        viewport: JsViewWindow,
+    ) -> ApiResult<()> {
+        let viewport = viewport.into_serde_ext::<ViewWindow>()?;
+        Ok(self.0.set_update_viewport(callback_id, viewport).await?)
+    }
+
     #[apply(inherit_docs)]
     #[inherit_doc = "view/on_delete.md"]
     #[wasm_bindgen]
@@ -270,4 +282,67 @@ impl View {
     pub async fn set_depth(&self, depth: u32) -> ApiResult<()> {
         Ok(self.0.set_depth(depth).await?)
     }
+
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/get_expansion_state.md"]
+    #[wasm_bindgen]
+    pub async fn get_expansion_state(&self) -> ApiResult<JsValue> {
+        let state = self.0.get_expansion_state().await?;
+        Ok(JsValue::from_serde_ext(&state)?)
+    }
+
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/set_expansion_state.md"]
+    #[wasm_bindgen]
+    pub async fn set_expansion_state(&self, state: JsValue) -> ApiResult<()> {
+        let state = state.into_serde_ext()?;
+        Ok(self.0.set_expansion_state(state).await?)
+    }
+
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/find_row_by_path.md"]
+    #[wasm_bindgen]
+    pub async fn find_row_by_path(&self, path: JsValue) -> ApiResult<Option<u32>> {
+        let path = path.into_serde_ext()?;
+        Ok(self.0.find_row_by_path(path).await?)
+    }
+
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/find_row_by_index.md"]
+    #[wasm_bindgen]
+    pub async fn find_row_by_index(&self, index: JsValue) -> ApiResult<Option<u32>> {
+        let index = index.into_serde_ext()?;
+        Ok(self.0.find_row_by_index(index).await?)
+    }
+
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/get_underlying_config.md"]
+    #[wasm_bindgen]
+    pub async fn get_underlying_config(
+        &self,
+        row_index: u32,
+        column_path: JsValue,
+    ) -> ApiResult<JsValue> {
+        let column_path = column_path.into_serde_ext()?;
+        let config = self.0.get_underlying_config(row_index, column_path).await?;
+        Ok(JsValue::from_serde_ext(&config)?)
+    }
+
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/get_underlying_rows.md"]
+    #[wasm_bindgen]
+    pub async fn get_underlying_rows(
+        &self,
+        row_index: u32,
+        column_path: JsValue,
+        window: Option<JsViewWindow>,
+    ) -> ApiResult<Array> {
+        let column_path = column_path.into_serde_ext()?;
+        let window = window.into_serde_ext::<Option<ViewWindow>>()?;
+        let json = self
+            .0
+            .get_underlying_rows(row_index, column_path, window.unwrap_or_default())
+            .await?;
+        Ok(js_sys::JSON::parse(&json)?.unchecked_into())
+    }
 }
@@ -85,6 +85,18 @@ export class PerspectiveSession {
         });
     }
 
+    /**
+     * Throttle the `on_update` callbacks of this session's views which do not
+     * set their own `throttle_ms`, coalescing updates within the interval.
+     */
+    set_update_throttle(throttle_ms: number) {
+        this.mod._psp_set_session_update_throttle(
+            this.server as any,
+            this.client_id,
+            throttle_ms
+        );
+    }
+
     close() {
         this.mod._psp_close_session(this.server as any, this.client_id);
     }
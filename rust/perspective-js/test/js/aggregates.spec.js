@@ -248,6 +248,102 @@ const std = (nums) => {
             table.delete();
         });
 
+        test("['z'], custom", async function () {
+            var table = await perspective.table(data2);
+            var view = await table.view({
+                group_by: ["z"],
+                aggregates: { x: ["custom", 'sum("x" * "y") / sum("y")'] },
+                columns: ["x"],
+            });
+            var answer = [
+                { __ROW_PATH__: [], x: 2.8333333333333335 },
+                { __ROW_PATH__: [false], x: 3.3333333333333335 },
+                { __ROW_PATH__: [true], x: 2.3333333333333335 },
+            ];
+            let result = await view.to_json();
+            expect(result).toEqual(answer);
+            expect(await view.schema()).toEqual({ x: "float" });
+            expect((await view.get_config()).aggregates).toEqual({
+                x: ["custom", 'sum("x" * "y") / sum("y")'],
+            });
+            view.delete();
+            table.delete();
+        });
+
+        test("['z'], sum ratio", async function () {
+            var table = await perspective.table(data2);
+            var view = await table.view({
+                group_by: ["z"],
+                aggregates: { x: ["sum ratio", "y"] },
+                columns: ["x"],
+            });
+            var answer = [
+                { __ROW_PATH__: [], x: 10 / 6 },
+                { __ROW_PATH__: [false], x: 2 },
+                { __ROW_PATH__: [true], x: 4 / 3 },
+            ];
+            let result = await view.to_json();
+            expect(result).toEqual(answer);
+            expect(await view.schema()).toEqual({ x: "float" });
+            view.delete();
+            table.delete();
+        });
+
+        test("['g'], last by and first by", async function () {
+            var table = await perspective.table([
+                { x: 1, t: 3, g: "a" },
+                { x: 2, t: 1, g: "a" },
+                { x: 3, t: 2, g: "b" },
+                { x: 4, t: 5, g: "b" },
+            ]);
+            var view = await table.view({
+                group_by: ["g"],
+                aggregates: {
+                    x: ["last by", "t"],
+                    "first x": ["first by", "t"],
+                },
+                expressions: { "first x": `"x"` },
+                columns: ["x", "first x"],
+            });
+            var answer = [
+                { __ROW_PATH__: [], x: 4, "first x": 2 },
+                { __ROW_PATH__: ["a"], x: 1, "first x": 2 },
+                { __ROW_PATH__: ["b"], x: 4, "first x": 3 },
+            ];
+            let result = await view.to_json();
+            expect(result).toEqual(answer);
+            view.delete();
+            table.delete();
+        });
+
+        test("['z'], covariance and correlation", async function () {
+            var table = await perspective.table(data2);
+            var view = await table.view({
+                group_by: ["z"],
+                aggregates: {
+                    x: ["covariance", "y"],
+                    x2: ["correlation", "y"],
+                },
+                expressions: { x2: `"x"` },
+                columns: ["x", "x2"],
+            });
+            let result = await view.to_columns();
+            expect(result.__ROW_PATH__).toEqual([[], [false], [true]]);
+            for (const [actual, expected] of [
+                [result.x[0], 0.5],
+                [result.x[1], 0.5],
+                [result.x[2], 0.5],
+                [result.x2[0], 0.5 / Math.sqrt(1.25 * 0.25)],
+                [result.x2[1], 1],
+                [result.x2[2], 1],
+            ]) {
+                expect(actual).toBeCloseTo(expected);
+            }
+
+            view.delete();
+            table.delete();
+        });
+
         test("['z'], mean", async function () {
             var table = await perspective.table(data);
             var view = await table.view({
@@ -505,6 +601,194 @@ const std = (nums) => {
                     table.delete();
                 });
             });
+
+            test.describe("percentile", function () {
+                test("['z'] with 16 elements, p90", async function () {
+                    var table = await perspective.table(data3);
+                    var view = await table.view({
+                        group_by: ["z"],
+                        columns: ["x"],
+                        aggregates: { x: ["percentile", 0.9] },
+                    });
+
+                    var answer = [
+                        { __ROW_PATH__: [], x: 15 },
+                        { __ROW_PATH__: [false], x: 16 },
+                        { __ROW_PATH__: [true], x: 15 },
+                    ];
+
+                    let result = await view.to_json();
+                    expect(result).toEqual(answer);
+                    view.delete();
+                    table.delete();
+                });
+
+                test("p75 is equivalent to q3", async function () {
+                    var table = await perspective.table(data3);
+                    var view = await table.view({
+                        group_by: ["z"],
+                        columns: ["x"],
+                        aggregates: { x: ["percentile", 0.75] },
+                    });
+
+                    var answer = [
+                        { __ROW_PATH__: [], x: 13 },
+                        { __ROW_PATH__: [false], x: 14 },
+                        { __ROW_PATH__: [true], x: 13 },
+                    ];
+
+                    let result = await view.to_json();
+                    expect(result).toEqual(answer);
+                    view.delete();
+                    table.delete();
+                });
+
+                test("round trips through get_config", async function () {
+                    var table = await perspective.table(data3);
+                    var view = await table.view({
+                        group_by: ["z"],
+                        columns: ["x"],
+                        aggregates: { x: ["percentile", 0.95] },
+                    });
+
+                    let config = await view.get_config();
+                    expect(config.aggregates).toEqual({
+                        x: ["percentile", 0.95],
+                    });
+
+                    view.delete();
+                    table.delete();
+                });
+
+                test("sorts split_by columns by the quantile", async function () {
+                    var table = await perspective.table({
+                        x: [1, 100, 2, 50, 60, 55],
+                        y: ["a", "a", "a", "b", "b", "b"],
+                    });
+                    var view = await table.view({
+                        split_by: ["y"],
+                        columns: ["x"],
+                        aggregates: { x: ["percentile", 0.9] },
+                        sort: [["x", "col asc"]],
+                    });
+
+                    let result = await view.to_columns();
+                    expect(Object.keys(result)).toEqual(["b|x", "a|x"]);
+                    view.delete();
+                    table.delete();
+                });
+
+                test("rejects a quantile outside of (0, 1)", async function () {
+                    var table = await perspective.table(data3);
+                    let error;
+                    try {
+                        await table.view({
+                            group_by: ["z"],
+                            columns: ["x"],
+                            aggregates: { x: ["percentile", 1.5] },
+                        });
+                    } catch (e) {
+                        error = e;
+                    }
+
+                    expect(error).toBeDefined();
+                    table.delete();
+                });
+            });
+        });
+
+        test.describe("mode and null count", function () {
+            var data5 = [
+                { x: 1, y: "a" },
+                { x: 1, y: "a" },
+                { x: null, y: "a" },
+                { x: 2, y: "b" },
+                { x: null, y: "b" },
+                { x: null, y: "b" },
+            ];
+
+            test("['y'], mode ignores nulls", async function () {
+                var table = await perspective.table(data5);
+                var view = await table.view({
+                    group_by: ["y"],
+                    columns: ["x"],
+                    aggregates: { x: "mode" },
+                });
+
+                var answer = [
+                    { __ROW_PATH__: [], x: 1 },
+                    { __ROW_PATH__: ["a"], x: 1 },
+                    { __ROW_PATH__: ["b"], x: 2 },
+                ];
+
+                let result = await view.to_json();
+                expect(result).toEqual(answer);
+                view.delete();
+                table.delete();
+            });
+
+            test("['y'], null count", async function () {
+                var table = await perspective.table(data5);
+                var view = await table.view({
+                    group_by: ["y"],
+                    columns: ["x"],
+                    aggregates: { x: "null count" },
+                });
+
+                var answer = [
+                    { __ROW_PATH__: [], x: 3 },
+                    { __ROW_PATH__: ["a"], x: 1 },
+                    { __ROW_PATH__: ["b"], x: 2 },
+                ];
+
+                let result = await view.to_json();
+                expect(result).toEqual(answer);
+                expect(await view.schema()).toEqual({ x: "integer" });
+                view.delete();
+                table.delete();
+            });
+
+            test("['y'], approx distinct skips nulls", async function () {
+                var table = await perspective.table(data5);
+                var view = await table.view({
+                    group_by: ["y"],
+                    columns: ["x"],
+                    aggregates: { x: "count distinct approx" },
+                });
+
+                var answer = [
+                    { __ROW_PATH__: [], x: 2 },
+                    { __ROW_PATH__: ["a"], x: 1 },
+                    { __ROW_PATH__: ["b"], x: 1 },
+                ];
+
+                let result = await view.to_json();
+                expect(result).toEqual(answer);
+                view.delete();
+                table.delete();
+            });
+        });
+
+        test("['z'], count distinct approx", async function () {
+            var table = await perspective.table(data3);
+            var view = await table.view({
+                group_by: ["z"],
+                columns: ["x"],
+                aggregates: { x: "count distinct approx" },
+            });
+
+            let result = await view.to_columns();
+            expect(result.__ROW_PATH__).toEqual([[], [false], [true]]);
+            for (const [estimate, actual] of [
+                [result.x[0], 16],
+                [result.x[1], 8],
+                [result.x[2], 8],
+            ]) {
+                expect(Math.abs(estimate - actual)).toBeLessThanOrEqual(1);
+            }
+
+            view.delete();
+            table.delete();
         });
 
         test("['z'], first by index", async function () {
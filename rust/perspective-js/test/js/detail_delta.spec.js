@@ -0,0 +1,152 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = [
+    { x: 1, y: "a", z: true },
+    { x: 2, y: "b", z: false },
+    { x: 3, y: "c", z: true },
+    { x: 4, y: "d", z: false },
+];
+
+async function subscribe_detail(view) {
+    let resolve;
+    const updated = new Promise((x) => {
+        resolve = x;
+    });
+
+    await view.on_update(resolve, { mode: "detail" });
+    return () => updated;
+}
+
+((perspective) => {
+    test.describe("Detail delta", function () {
+        test("reports changed cells of updated rows", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const next_update = await subscribe_detail(view);
+            await table.update([{ x: 2, y: "string2" }]);
+            const { row_deltas } = await next_update();
+            expect(row_deltas).toEqual([
+                {
+                    op: "update",
+                    id: [2],
+                    row_index: 1,
+                    old_row_index: 1,
+                    cells: [
+                        { column: "y", old_value: "b", new_value: "string2" },
+                    ],
+                },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("reports inserted rows", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const next_update = await subscribe_detail(view);
+            await table.update([{ x: 5, y: "e", z: true }]);
+            const { row_deltas } = await next_update();
+            expect(row_deltas).toEqual([
+                {
+                    op: "insert",
+                    id: [5],
+                    row_index: 4,
+                    old_row_index: null,
+                    cells: [
+                        { column: "x", old_value: null, new_value: 5 },
+                        { column: "y", old_value: null, new_value: "e" },
+                        { column: "z", old_value: null, new_value: true },
+                    ],
+                },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("reports deleted rows", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const next_update = await subscribe_detail(view);
+            await table.remove([2]);
+            const { row_deltas } = await next_update();
+            expect(row_deltas).toEqual([
+                {
+                    op: "delete",
+                    id: [2],
+                    row_index: null,
+                    old_row_index: 1,
+                    cells: [],
+                },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("reports rows moved by a sort", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view({ sort: [["y", "asc"]] });
+            const next_update = await subscribe_detail(view);
+            await table.update([{ x: 1, y: "e" }]);
+            const { row_deltas } = await next_update();
+            expect(row_deltas).toEqual([
+                {
+                    op: "update",
+                    id: [1],
+                    row_index: 3,
+                    old_row_index: 0,
+                    cells: [{ column: "y", old_value: "a", new_value: "e" }],
+                },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("reports aggregated rows by row path", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view({
+                group_by: ["y"],
+                columns: ["x"],
+                aggregates: { x: "sum" },
+            });
+
+            const next_update = await subscribe_detail(view);
+            await table.update([{ x: 2, y: "a" }]);
+            const { row_deltas } = await next_update();
+            expect(row_deltas).toEqual([
+                {
+                    op: "delete",
+                    id: ["b"],
+                    row_index: null,
+                    old_row_index: 2,
+                    cells: [],
+                },
+                {
+                    op: "update",
+                    id: ["a"],
+                    row_index: 1,
+                    old_row_index: 1,
+                    cells: [{ column: "x", old_value: 1, new_value: 3 }],
+                },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
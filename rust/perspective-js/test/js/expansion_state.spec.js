@@ -0,0 +1,183 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    g1: ["a", "a", "b", "c"],
+    g2: ["x", "y", "x", "z"],
+    v: [1, 2, 3, 4],
+};
+
+((perspective) => {
+    test.describe("Expansion state", function () {
+        test("is empty for a fully expanded view", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            expect(await view.get_expansion_state()).toEqual({
+                collapsed: [],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("is empty for a view without group_by", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({ columns: ["v"] });
+            expect(await view.get_expansion_state()).toEqual({
+                collapsed: [],
+            });
+
+            await view.set_expansion_state({ collapsed: [["a"]] });
+            expect(await view.num_rows()).toEqual(4);
+            view.delete();
+            table.delete();
+        });
+
+        test("returns collapsed rows by row path", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            await view.collapse(4);
+            expect(await view.get_expansion_state()).toEqual({
+                collapsed: [["b"]],
+            });
+
+            await view.set_depth(0);
+            expect(await view.get_expansion_state()).toEqual({
+                collapsed: [["a"], ["b"], ["c"]],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("restores collapsed rows by row path", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            await view.set_expansion_state({
+                collapsed: [["a"], ["c"], ["missing"]],
+            });
+
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [[], ["a"], ["b"], ["b", "x"], ["c"]],
+                v: [10, 3, 3, 3, 4],
+            });
+
+            expect(await view.get_expansion_state()).toEqual({
+                collapsed: [["a"], ["c"]],
+            });
+
+            await view.set_expansion_state({ collapsed: [] });
+            expect(await view.num_rows()).toEqual(8);
+            view.delete();
+            table.delete();
+        });
+
+        test("survives an update", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            await view.set_expansion_state({ collapsed: [["a"]] });
+            await table.update({ g1: ["a", "b"], g2: ["x", "w"], v: [5, 6] });
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [
+                    [],
+                    ["a"],
+                    ["b"],
+                    ["b", "w"],
+                    ["b", "x"],
+                    ["c"],
+                    ["c", "z"],
+                ],
+                v: [21, 8, 9, 6, 3, 4, 4],
+            });
+
+            expect(await view.get_expansion_state()).toEqual({
+                collapsed: [["a"]],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("can be applied to a new view", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            await view.set_depth(0);
+            await view.expand(2);
+            const state = await view.get_expansion_state();
+            expect(state).toEqual({ collapsed: [["a"], ["c"]] });
+            const expected = await view.to_columns();
+            view.delete();
+
+            const view2 = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            await view2.set_expansion_state(state);
+            expect(await view2.to_columns()).toEqual(expected);
+            view2.delete();
+            table.delete();
+        });
+
+        test("matches numeric row paths", async function () {
+            const table = await perspective.table({
+                g: [1, 1, 2],
+                h: ["x", "y", "x"],
+                v: [1, 2, 3],
+            });
+
+            const view = await table.view({
+                group_by: ["g", "h"],
+                columns: ["v"],
+            });
+
+            await view.set_expansion_state({ collapsed: [[2]] });
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [[], [1], [1, "x"], [1, "y"], [2]],
+                v: [6, 3, 1, 2, 3],
+            });
+
+            expect(await view.get_expansion_state()).toEqual({
+                collapsed: [[2]],
+            });
+
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
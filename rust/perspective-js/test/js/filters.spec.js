@@ -472,6 +472,93 @@ const datetime_data_local = [
             });
         });
 
+        test.describe("not contains", function () {
+            test("y not contains 'a'", async function () {
+                var table = await perspective.table(data);
+                var view = await table.view({
+                    filter: [["y", "not contains", "a"]],
+                });
+                let json = await view.to_json();
+                expect(json).toEqual(rdata.slice(1));
+                view.delete();
+                table.delete();
+            });
+        });
+
+        test.describe("case sensitivity", function () {
+            test("y contains 'AB' ignores case", async function () {
+                var table = await perspective.table({
+                    y: ["ABC", "abd", "xyz", null],
+                });
+                var view = await table.view({
+                    filter: [["y", "contains", "AB"]],
+                });
+                let json = await view.to_columns();
+                expect(json).toEqual({ y: ["ABC", "abd"] });
+                view.delete();
+                table.delete();
+            });
+
+            test("y icontains 'AB' is an alias of contains", async function () {
+                var table = await perspective.table({
+                    y: ["ABC", "abd", "xyz", null],
+                });
+                var view = await table.view({
+                    filter: [["y", "icontains", "AB"]],
+                });
+                let json = await view.to_columns();
+                expect(json).toEqual({ y: ["ABC", "abd"] });
+                view.delete();
+                table.delete();
+            });
+
+            test("y matches '^AB' respects case", async function () {
+                var table = await perspective.table({
+                    y: ["ABC", "abd", "xyz", null],
+                });
+                var view = await table.view({
+                    filter: [["y", "matches", "^AB"]],
+                });
+                expect(await view.to_columns()).toEqual({ y: ["ABC"] });
+                view.delete();
+
+                view = await table.view({
+                    filter: [["y", "matches", "(?i)^AB"]],
+                });
+                expect(await view.to_columns()).toEqual({ y: ["ABC", "abd"] });
+                view.delete();
+                table.delete();
+            });
+        });
+
+        test.describe("matches", function () {
+            test("y matches '^[ab]'", async function () {
+                var table = await perspective.table(data);
+                var view = await table.view({
+                    filter: [["y", "matches", "^[ab]"]],
+                });
+                let json = await view.to_json();
+                expect(json).toEqual(rdata.slice(0, 2));
+                view.delete();
+                table.delete();
+            });
+
+            test("invalid regex is rejected", async function () {
+                var table = await perspective.table(data);
+                let error;
+                try {
+                    await table.view({
+                        filter: [["y", "matches", "(a"]],
+                    });
+                } catch (e) {
+                    error = e;
+                }
+
+                expect(error).toBeDefined();
+                table.delete();
+            });
+        });
+
         test.describe("Arrow types", function () {
             // https://github.com/finos/perspective/issues/2881
             test("Arrow float32 filters", async function () {
@@ -0,0 +1,135 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    id: ["w", "x", "y", "z"],
+    g1: ["a", "a", "b", "c"],
+    g2: ["x", "y", "x", "z"],
+    v: [1, 2, 3, 4],
+};
+
+((perspective) => {
+    test.describe("find_row_by_path", function () {
+        test("finds group rows by row path", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            expect(await view.find_row_by_path([])).toEqual(0);
+            expect(await view.find_row_by_path(["a"])).toEqual(1);
+            expect(await view.find_row_by_path(["b", "x"])).toEqual(5);
+            expect(await view.find_row_by_path(["d"])).toBeUndefined();
+            expect(await view.find_row_by_path(["b", "y"])).toBeUndefined();
+            view.delete();
+            table.delete();
+        });
+
+        test("tracks rows across updates", async function () {
+            const table = await perspective.table(data, { index: "id" });
+            const view = await table.view({
+                group_by: ["g1"],
+                columns: ["v"],
+            });
+
+            expect(await view.find_row_by_path(["c"])).toEqual(3);
+            await table.update({ id: ["q"], g1: ["0"], g2: ["x"], v: [5] });
+            expect(await view.find_row_by_path(["c"])).toEqual(4);
+            view.delete();
+            table.delete();
+        });
+
+        test("skips rows in collapsed groups", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            await view.collapse(1);
+            expect(await view.find_row_by_path(["a", "x"])).toBeUndefined();
+            expect(await view.find_row_by_path(["b"])).toEqual(2);
+            view.delete();
+            table.delete();
+        });
+
+        test("is undefined without group_by", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({ split_by: ["g1"] });
+            expect(await view.find_row_by_path([])).toBeUndefined();
+            view.delete();
+            table.delete();
+        });
+    });
+
+    test.describe("find_row_by_index", function () {
+        test("finds rows by index", async function () {
+            const table = await perspective.table(data, { index: "id" });
+            const view = await table.view({
+                sort: [["v", "desc"]],
+            });
+
+            expect(await view.find_row_by_index("w")).toEqual(3);
+            expect(await view.find_row_by_index("z")).toEqual(0);
+            expect(await view.find_row_by_index("missing")).toBeUndefined();
+            view.delete();
+            table.delete();
+        });
+
+        test("finds rows by row number without an index", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                filter: [["g1", "==", "a"]],
+                sort: [["v", "desc"]],
+            });
+
+            expect(await view.find_row_by_index(0)).toEqual(1);
+            expect(await view.find_row_by_index(1)).toEqual(0);
+            expect(await view.find_row_by_index(2)).toBeUndefined();
+            view.delete();
+            table.delete();
+        });
+
+        test("finds the deepest visible group row", async function () {
+            const table = await perspective.table(data, { index: "id" });
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            expect(await view.find_row_by_index("x")).toEqual(3);
+            expect(await view.find_row_by_index("y")).toEqual(5);
+            await view.collapse(1);
+            expect(await view.find_row_by_index("x")).toEqual(1);
+            expect(await view.find_row_by_index("y")).toEqual(3);
+            view.delete();
+            table.delete();
+        });
+
+        test("finds rows of a split_by only view", async function () {
+            const table = await perspective.table(data, { index: "id" });
+            const view = await table.view({
+                split_by: ["g1"],
+                columns: ["v"],
+            });
+
+            expect(await view.num_rows()).toEqual(4);
+            expect(await view.find_row_by_index("y")).toEqual(2);
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
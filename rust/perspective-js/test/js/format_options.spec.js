@@ -0,0 +1,186 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const schema = {
+    x: "float",
+    y: "integer",
+    t: "datetime",
+    s: "string",
+};
+
+const data = {
+    x: [1234.5, -0.3],
+    y: [1, 3000],
+    t: [Date.UTC(2024, 0, 2, 15, 4, 5), Date.UTC(2024, 6, 14, 9, 30, 0)],
+    s: ["a", "b"],
+};
+
+async function make_table(perspective) {
+    const table = await perspective.table(schema);
+    await table.update(data);
+    return table;
+}
+
+((perspective) => {
+    test.describe("Format options", function () {
+        test("to_csv formatted uses the viewer's defaults", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view();
+            const csv = await view.to_csv({ formatted: true });
+            expect(csv).toEqual(
+                '"x","y","t","s"\n' +
+                    '"1,234.50","1","1/2/24, 3:04:05 PM","a"\n' +
+                    '"-0.30","3,000","7/14/24, 9:30:00 AM","b"\n'
+            );
+
+            view.delete();
+            table.delete();
+        });
+
+        test("to_csv applies locale, time zone and column formats", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view();
+            const csv = await view.to_csv({
+                locale: "de-DE",
+                time_zone: "+01:00",
+                formats: {
+                    x: { maximumFractionDigits: 1 },
+                    y: { style: "percent" },
+                    t: { dateStyle: "short", timeStyle: "short" },
+                },
+            });
+
+            expect(csv).toEqual(
+                '"x","y","t","s"\n' +
+                    '"1.234,5","100%","02.01.2024, 16:04","a"\n' +
+                    '"-0,3","300.000%","14.07.2024, 10:30","b"\n'
+            );
+
+            view.delete();
+            table.delete();
+        });
+
+        test("to_csv without format options is unchanged", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view({ columns: ["x", "y"] });
+            const csv = await view.to_csv();
+            expect(csv).toEqual('"x","y"\n1234.5,1\n-0.3,3000\n');
+            view.delete();
+            table.delete();
+        });
+
+        test("to_columns_string formats aggregates and row paths", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view({
+                group_by: ["s"],
+                columns: ["x"],
+            });
+
+            const json = await view.to_columns_string({ formats: {} });
+            expect(JSON.parse(json)).toEqual({
+                __ROW_PATH__: [[], ["a"], ["b"]],
+                x: ["1,234.20", "1,234.50", "-0.30"],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("to_json_string formats datetime row paths", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view({
+                group_by: ["t"],
+                columns: ["y"],
+            });
+
+            const json = await view.to_json_string({
+                formats: { t: { timeStyle: "disabled" } },
+            });
+
+            expect(JSON.parse(json)).toEqual([
+                { __ROW_PATH__: [], y: "3,001" },
+                { __ROW_PATH__: ["1/2/24"], y: "1" },
+                { __ROW_PATH__: ["7/14/24"], y: "3,000" },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("split_by columns are matched by their last segment", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view({
+                split_by: ["s"],
+                columns: ["x"],
+            });
+
+            const json = await view.to_columns_string({
+                formats: { x: { minimumFractionDigits: 3 } },
+            });
+
+            expect(JSON.parse(json)).toEqual({
+                "a|x": ["1,234.500", null],
+                "b|x": [null, "-0.300"],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("unsupported time zones are an error", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view();
+            await expect(
+                view.to_csv({ time_zone: "America/New_York" })
+            ).rejects.toThrow("Unsupported time zone");
+
+            view.delete();
+            table.delete();
+        });
+
+        test("unsupported locales are an error", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view();
+            await expect(view.to_csv({ locale: "tr-TR" })).rejects.toThrow(
+                "Unsupported locale"
+            );
+
+            await expect(
+                view.to_csv({
+                    locale: "de-DE",
+                    formats: { t: { dateStyle: "long" } },
+                })
+            ).rejects.toThrow("Unsupported date style");
+
+            view.delete();
+            table.delete();
+        });
+
+        test("unsupported viewer styles are an error", async function () {
+            const table = await make_table(perspective);
+            const view = await table.view();
+            await expect(
+                view.to_csv({ formats: { x: { style: "currency" } } })
+            ).rejects.toThrow();
+
+            await expect(
+                view.to_csv({ formats: { x: { notation: "compact" } } })
+            ).rejects.toThrow();
+
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
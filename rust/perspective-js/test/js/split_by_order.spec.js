@@ -0,0 +1,175 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    g: ["a", "a", "b", "b"],
+    q: ["Q10", "Q2", "Q1", "Q2"],
+    r: ["East", "West", "North", "East"],
+    v: [1, 2, 3, 4],
+};
+
+((perspective) => {
+    test.describe("Split by order", function () {
+        test("defaults to sorted order", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g"],
+                split_by: ["q"],
+                columns: ["v"],
+            });
+
+            const paths = await view.column_paths();
+            expect(paths).toEqual(["__ROW_PATH__", "Q1|v", "Q10|v", "Q2|v"]);
+            view.delete();
+            table.delete();
+        });
+
+        test("natural order", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g"],
+                split_by: ["q"],
+                columns: ["v"],
+                split_by_order: { q: "natural" },
+            });
+
+            const paths = await view.column_paths();
+            expect(paths).toEqual(["__ROW_PATH__", "Q1|v", "Q2|v", "Q10|v"]);
+            view.delete();
+            table.delete();
+        });
+
+        test("explicit order", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g"],
+                split_by: ["r"],
+                columns: ["v"],
+                split_by_order: { r: ["West", "North", "East"] },
+            });
+
+            const paths = await view.column_paths();
+            expect(paths).toEqual([
+                "__ROW_PATH__",
+                "West|v",
+                "North|v",
+                "East|v",
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("unlisted values follow listed values", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g"],
+                split_by: ["r"],
+                columns: ["v"],
+                split_by_order: { r: ["West"] },
+            });
+
+            const paths = await view.column_paths();
+            expect(paths).toEqual([
+                "__ROW_PATH__",
+                "West|v",
+                "East|v",
+                "North|v",
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("applies to each split_by level", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                split_by: ["r", "q"],
+                columns: ["v"],
+                split_by_order: { r: ["West", "East"], q: "natural" },
+            });
+
+            const paths = await view.column_paths();
+            expect(paths).toEqual([
+                "West|Q2|v",
+                "East|Q2|v",
+                "East|Q10|v",
+                "North|Q1|v",
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("applies to to_columns_string", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g"],
+                split_by: ["r"],
+                columns: ["v"],
+                split_by_order: { r: ["West", "North", "East"] },
+            });
+
+            const result = JSON.parse(await view.to_columns_string());
+            expect(Object.keys(result)).toEqual([
+                "__ROW_PATH__",
+                "West|v",
+                "North|v",
+                "East|v",
+            ]);
+
+            expect(result["West|v"]).toEqual([2, 2, null]);
+            view.delete();
+            table.delete();
+        });
+
+        test("is kept when the table is updated", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g"],
+                split_by: ["q"],
+                columns: ["v"],
+                split_by_order: { q: "natural" },
+            });
+
+            await table.update({ g: ["a"], q: ["Q3"], r: ["East"], v: [5] });
+            const paths = await view.column_paths();
+            expect(paths).toEqual([
+                "__ROW_PATH__",
+                "Q1|v",
+                "Q2|v",
+                "Q3|v",
+                "Q10|v",
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("is returned by get_config", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                split_by: ["q"],
+                columns: ["v"],
+                split_by_order: { q: ["Q2", "Q1"] },
+            });
+
+            const config = await view.get_config();
+            expect(config.split_by_order).toEqual({ q: ["Q2", "Q1"] });
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
@@ -0,0 +1,190 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    g1: ["a", "a", "b"],
+    g2: ["x", "y", "x"],
+    s: ["p", "q", "p"],
+    v: [1, 2, 3],
+};
+
+((perspective) => {
+    test.describe("Totals", function () {
+        test("shows all totals at the top by default", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+            });
+
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [
+                    [],
+                    ["a"],
+                    ["a", "x"],
+                    ["a", "y"],
+                    ["b"],
+                    ["b", "x"],
+                ],
+                v: [6, 3, 1, 2, 3, 3],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("hides the grand total", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+                totals: { grand_total: false },
+            });
+
+            expect(await view.num_rows()).toEqual(5);
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [
+                    ["a"],
+                    ["a", "x"],
+                    ["a", "y"],
+                    ["b"],
+                    ["b", "x"],
+                ],
+                v: [3, 1, 2, 3, 3],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("hides subtotals", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+                totals: { subtotals: false },
+            });
+
+            expect(await view.num_rows()).toEqual(4);
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [[], ["a", "x"], ["a", "y"], ["b", "x"]],
+                v: [6, 1, 2, 3],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("places totals at the bottom", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+                totals: { position: "bottom" },
+            });
+
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [
+                    ["a", "x"],
+                    ["a", "y"],
+                    ["a"],
+                    ["b", "x"],
+                    ["b"],
+                    [],
+                ],
+                v: [1, 2, 3, 3, 3, 6],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("bottom totals follow collapsed groups", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1", "g2"],
+                columns: ["v"],
+                totals: { position: "bottom" },
+            });
+
+            // Row 2 is `["a"]`, which has no visible children once collapsed.
+            await view.collapse(2);
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [["a"], ["b", "x"], ["b"], []],
+                v: [3, 3, 3, 6],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("applies to views with split_by", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                split_by: ["s"],
+                columns: ["v"],
+                totals: { grand_total: false, position: "bottom" },
+            });
+
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                __ROW_PATH__: [["a"], ["b"]],
+                "p|v": [1, 3],
+                "q|v": [2, null],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("applies to to_csv", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                columns: ["v"],
+                totals: { grand_total: false },
+            });
+
+            const result = await view.to_csv();
+            expect(result).toEqual('"g1 (Group by 1)","v"\na,3\nb,3\n');
+            view.delete();
+            table.delete();
+        });
+
+        test("are returned by get_config", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                columns: ["v"],
+                totals: { subtotals: false, position: "bottom" },
+            });
+
+            const config = await view.get_config();
+            expect(config.totals).toEqual({
+                subtotals: false,
+                position: "bottom",
+            });
+
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
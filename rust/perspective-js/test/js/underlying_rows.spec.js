@@ -0,0 +1,149 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    id: ["w", "x", "y", "z"],
+    g1: ["a", "a", "b", "c"],
+    g2: ["x", "y", "x", "z"],
+    v: [1, 2, 3, 4],
+};
+
+((perspective) => {
+    test.describe("get_underlying_rows", function () {
+        test("returns the source rows of a group", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                columns: ["v"],
+            });
+
+            expect(await view.get_underlying_rows(1, [])).toEqual([
+                { id: "w", g1: "a", g2: "x", v: 1 },
+                { id: "x", g1: "a", g2: "y", v: 2 },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("returns the source rows of a split cell", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                split_by: ["g2"],
+                columns: ["v"],
+            });
+
+            expect(await view.get_underlying_rows(1, ["y", "v"])).toEqual([
+                { id: "x", g1: "a", g2: "y", v: 2 },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("handles column-only views", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                split_by: ["g2"],
+                columns: ["v"],
+            });
+
+            expect(await view.get_underlying_rows(0, ["x", "v"])).toEqual([
+                { id: "w", g1: "a", g2: "x", v: 1 },
+                { id: "y", g1: "b", g2: "x", v: 3 },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("matches numeric group values", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["v"],
+                columns: ["id"],
+            });
+
+            expect(await view.get_underlying_rows(3, [])).toEqual([
+                { id: "y", g1: "b", g2: "x", v: 3 },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("applies the view's filters", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                columns: ["v"],
+                filter: [["v", ">", 1]],
+            });
+
+            expect(await view.get_underlying_rows(0, [])).toEqual([
+                { id: "x", g1: "a", g2: "y", v: 2 },
+                { id: "y", g1: "b", g2: "x", v: 3 },
+                { id: "z", g1: "c", g2: "z", v: 4 },
+            ]);
+
+            expect(
+                await view.get_underlying_rows(0, [], { start_row: 1 })
+            ).toEqual([
+                { id: "y", g1: "b", g2: "x", v: 3 },
+                { id: "z", g1: "c", g2: "z", v: 4 },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+
+        test("rejects views with an `or` filter", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                columns: ["v"],
+                filter: [["v", ">", 1]],
+                filter_op: "or",
+            });
+
+            await expect(view.get_underlying_rows(1, [])).rejects.toThrow();
+            view.delete();
+            table.delete();
+        });
+    });
+
+    test.describe("get_underlying_config", function () {
+        test("returns a flat config of the source rows", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                group_by: ["g1"],
+                split_by: ["g2"],
+                columns: ["v"],
+            });
+
+            const config = await view.get_underlying_config(1, ["x", "v"]);
+            expect(config.group_by).toEqual([]);
+            expect(config.split_by).toEqual([]);
+            expect(config.columns).toEqual(["id", "g1", "g2", "v"]);
+            expect(config.filter).toEqual([
+                ["g1", "==", "a"],
+                ["g2", "==", "x"],
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
@@ -0,0 +1,93 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    x: [1, 2, 3, 4],
+    y: ["a", "b", "c", "d"],
+};
+
+function collect_updates() {
+    const updates = [];
+    let resolve;
+    let next = new Promise((x) => {
+        resolve = x;
+    });
+
+    const callback = (update) => {
+        updates.push(update);
+        resolve(update);
+        next = new Promise((x) => {
+            resolve = x;
+        });
+    };
+
+    return { updates, callback, next_update: () => next };
+}
+
+((perspective) => {
+    test.describe("on_update throttle_ms", function () {
+        test("coalesces row deltas within the interval", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const { updates, callback, next_update } = collect_updates();
+            await view.on_update(callback, { mode: "row", throttle_ms: 500 });
+
+            let updated = next_update();
+            await table.update({ x: [1], y: ["z"] });
+            await updated;
+
+            updated = next_update();
+            await table.update({ x: [2], y: ["z"] });
+            await table.update({ x: [3], y: ["z"] });
+            await table.size();
+            expect(updates.length).toEqual(1);
+
+            await new Promise((x) => setTimeout(x, 600));
+            await table.size();
+            const { delta } = await updated;
+            expect(updates.length).toEqual(2);
+            const coalesced = await perspective.table(delta.slice());
+            expect(await coalesced.size()).toEqual(2);
+            coalesced.delete();
+            view.delete();
+            table.delete();
+        });
+
+        test("coalesces detail mode row deltas", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const { callback, next_update } = collect_updates();
+            await view.on_update(callback, {
+                mode: "detail",
+                throttle_ms: 500,
+            });
+
+            let updated = next_update();
+            await table.update({ x: [1], y: ["z"] });
+            await updated;
+
+            updated = next_update();
+            await table.update({ x: [2], y: ["z"] });
+            await table.update({ x: [2], y: ["b"] });
+            await table.update({ x: [3], y: ["z"] });
+            await new Promise((x) => setTimeout(x, 600));
+            await table.size();
+            const { row_deltas } = await updated;
+            expect(row_deltas.map(({ id }) => id)).toEqual([[3]]);
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
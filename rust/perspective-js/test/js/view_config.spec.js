@@ -40,5 +40,127 @@ import perspective from "./perspective_client";
             view.delete();
             table.delete();
         });
+
+        test.describe("validate_view_config", function () {
+            test("A valid config returns no errors", async function () {
+                const table = await perspective.table({
+                    x: [1, 2],
+                    y: ["a", "b"],
+                });
+
+                const errors = await table.validate_view_config({
+                    group_by: ["y"],
+                    columns: ["x", "z"],
+                    expressions: { z: '"x" + 1' },
+                    aggregates: { x: "mean" },
+                    filter: [["y", "matches", "^a"]],
+                });
+
+                expect(errors).toEqual([]);
+                table.delete();
+            });
+
+            test("Invalid fields are reported per-field", async function () {
+                const table = await perspective.table({
+                    x: [1, 2],
+                    y: ["a", "b"],
+                });
+
+                const errors = await table.validate_view_config({
+                    group_by: ["w"],
+                    aggregates: { y: "sum" },
+                    filter: [["x", "contains", 1]],
+                });
+
+                expect(errors).toEqual([
+                    { error: "unknown_column", field: "group_by", column: "w" },
+                    {
+                        error: "invalid_filter_op",
+                        column: "x",
+                        op: "contains",
+                        column_type: "integer",
+                    },
+                    {
+                        error: "invalid_aggregate",
+                        column: "y",
+                        aggregate: "sum",
+                        column_type: "string",
+                    },
+                ]);
+
+                table.delete();
+            });
+
+            test("Invalid custom aggregates are reported", async function () {
+                const table = await perspective.table({
+                    x: [1, 2],
+                    y: ["a", "b"],
+                });
+
+                const errors = await table.validate_view_config({
+                    group_by: ["y"],
+                    aggregates: { x: ["custom", 'sum("x" +'] },
+                });
+
+                expect(errors.length).toEqual(1);
+                expect(errors[0].error).toEqual("invalid_custom_aggregate");
+                expect(errors[0].name).toEqual("x");
+                table.delete();
+            });
+
+            test("Custom aggregates are validated over vectors", async function () {
+                const table = await perspective.table({
+                    x: [1, 2],
+                    y: ["a", "b"],
+                });
+
+                // Indexing is only valid on the group's vector of values,
+                // while a string result can't be aggregated.
+                let errors = await table.validate_view_config({
+                    group_by: ["y"],
+                    aggregates: { x: ["custom", '"x"[0] + sum("x")'] },
+                });
+
+                expect(errors).toEqual([]);
+                errors = await table.validate_view_config({
+                    group_by: ["y"],
+                    aggregates: { x: ["custom", "'a'"] },
+                });
+
+                expect(errors.length).toEqual(1);
+                expect(errors[0].error).toEqual("invalid_custom_aggregate");
+                table.delete();
+            });
+
+            test("Invalid windows are reported", async function () {
+                const table = await perspective.table({
+                    x: [1, 2],
+                    y: ["a", "b"],
+                });
+
+                const errors = await table.validate_view_config({
+                    window: {
+                        x: { function: "rank", order_by: [["y", "asc"]] },
+                        total: { function: "sum", column: "y" },
+                    },
+                });
+
+                expect(errors).toEqual([
+                    {
+                        error: "invalid_window",
+                        name: "total",
+                        message:
+                            '`sum` not supported for column "y" of type `string`',
+                    },
+                    {
+                        error: "invalid_window",
+                        name: "x",
+                        message: "Name conflicts with an existing column",
+                    },
+                ]);
+
+                table.delete();
+            });
+        });
     });
 })(perspective);
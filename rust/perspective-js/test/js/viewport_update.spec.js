@@ -0,0 +1,130 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    x: [1, 2, 3, 4, 5, 6, 7, 8],
+    y: ["a", "b", "c", "d", "e", "f", "g", "h"],
+};
+
+function collect_updates() {
+    const updates = [];
+    let resolve;
+    let next = new Promise((x) => {
+        resolve = x;
+    });
+
+    const callback = (update) => {
+        updates.push(update);
+        resolve(update);
+        next = new Promise((x) => {
+            resolve = x;
+        });
+    };
+
+    return { updates, callback, next_update: () => next };
+}
+
+((perspective) => {
+    test.describe("on_update viewport", function () {
+        test("only notifies when the viewport changes", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const { updates, callback, next_update } = collect_updates();
+            await view.on_update(callback, {
+                viewport: { start_row: 0, end_row: 2 },
+            });
+
+            const updated = next_update();
+            await table.update({ x: [8], y: ["z"] });
+            await table.update({ x: [2], y: ["z"] });
+            await updated;
+            await table.size();
+            expect(updates.length).toEqual(1);
+            expect(updates[0].viewport_data).toBeNull();
+            view.delete();
+            table.delete();
+        });
+
+        test("includes the viewport data", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const { callback, next_update } = collect_updates();
+            await view.on_update(callback, {
+                viewport: { start_row: 1, end_row: 3 },
+                include_viewport_data: true,
+            });
+
+            const updated = next_update();
+            await table.update({ x: [3], y: ["z"] });
+            const { viewport_data } = await updated;
+            const viewport = await perspective.table(viewport_data.slice());
+            const viewport_view = await viewport.view();
+            expect(await viewport_view.to_columns()).toEqual({
+                x: [2, 3],
+                y: ["b", "z"],
+            });
+
+            viewport_view.delete();
+            viewport.delete();
+            view.delete();
+            table.delete();
+        });
+
+        test("changes the viewport without resubscribing", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const { updates, callback, next_update } = collect_updates();
+            const id = await view.on_update(callback, {
+                viewport: { start_row: 0, end_row: 2 },
+            });
+
+            await view.set_update_viewport(id, { start_row: 6, end_row: 8 });
+            const updated = next_update();
+            await table.update({ x: [1], y: ["z"] });
+            await table.update({ x: [7], y: ["z"] });
+            await updated;
+            await table.size();
+            expect(updates.length).toEqual(1);
+            view.delete();
+            table.delete();
+        });
+
+        test("scopes detail mode row deltas", async function () {
+            const table = await perspective.table(data, { index: "x" });
+            const view = await table.view();
+            const { callback, next_update } = collect_updates();
+            await view.on_update(callback, {
+                mode: "detail",
+                viewport: { start_row: 4, end_row: 6 },
+            });
+
+            const updated = next_update();
+            await table.update({ x: [1, 6], y: ["z", "z"] });
+            const { row_deltas } = await updated;
+            expect(row_deltas).toEqual([
+                {
+                    op: "update",
+                    id: [6],
+                    row_index: 5,
+                    old_row_index: 5,
+                    cells: [{ column: "y", old_value: "f", new_value: "z" }],
+                },
+            ]);
+
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
@@ -0,0 +1,202 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+const data = {
+    id: [1, 2, 3, 4, 5],
+    g: ["a", "a", "a", "b", "b"],
+    t: [1, 2, 3, 1, 2],
+    x: [10, 20, 40, 5, 5],
+};
+
+const running_sum = {
+    function: "sum",
+    column: "x",
+    partition_by: ["g"],
+    order_by: [["t", "asc"]],
+};
+
+((perspective) => {
+    test.describe("Window", function () {
+        test("running sum", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                columns: ["x", "running"],
+                window: { running: running_sum },
+            });
+
+            const result = await view.to_columns();
+            expect(result.running).toEqual([10, 30, 70, 5, 10]);
+            view.delete();
+            table.delete();
+        });
+
+        test("moving mean", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                columns: ["moving"],
+                window: {
+                    moving: {
+                        function: "mean",
+                        column: "x",
+                        partition_by: ["g"],
+                        order_by: [["t", "asc"]],
+                        frame: [1, 0],
+                    },
+                },
+            });
+
+            const result = await view.to_columns();
+            expect(result.moving).toEqual([10, 15, 30, 5, 5]);
+            view.delete();
+            table.delete();
+        });
+
+        test("rank, lag and percent of previous", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                columns: ["rank", "lag", "pct"],
+                window: {
+                    rank: { function: "rank", order_by: [["x", "desc"]] },
+                    lag: {
+                        function: "lag",
+                        column: "x",
+                        partition_by: ["g"],
+                        order_by: [["t", "asc"]],
+                    },
+                    pct: {
+                        function: "percent of previous",
+                        column: "x",
+                        partition_by: ["g"],
+                        order_by: [["t", "asc"]],
+                    },
+                },
+            });
+
+            const result = await view.to_columns();
+            expect(result).toEqual({
+                rank: [3, 2, 1, 4, 4],
+                lag: [null, 10, 20, null, 5],
+                pct: [null, 200, 200, null, 100],
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("window columns appear in the schema", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                columns: ["running", "rank", "lag"],
+                window: {
+                    running: running_sum,
+                    rank: { function: "rank", order_by: [["x", "desc"]] },
+                    lag: {
+                        function: "lag",
+                        column: "x",
+                        order_by: [["t", "asc"]],
+                    },
+                },
+            });
+
+            expect(await view.schema()).toEqual({
+                running: "float",
+                rank: "integer",
+                lag: "integer",
+            });
+
+            view.delete();
+            table.delete();
+        });
+
+        test("get_config returns window", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                columns: ["running"],
+                window: { running: running_sum },
+            });
+
+            const config = await view.get_config();
+            expect(config.window).toEqual({ running: running_sum });
+            view.delete();
+            table.delete();
+        });
+
+        test("updates recalculate rows outside the update", async function () {
+            const table = await perspective.table(data, { index: "id" });
+            const view = await table.view({
+                columns: ["running"],
+                window: { running: running_sum },
+            });
+
+            await table.update({
+                id: [1, 6],
+                g: ["a", "b"],
+                t: [1, 3],
+                x: [20, 1],
+            });
+
+            const result = await view.to_columns();
+            expect(result.running).toEqual([20, 40, 80, 5, 10, 11]);
+            view.delete();
+            table.delete();
+        });
+
+        test("group_by aggregates window columns", async function () {
+            const table = await perspective.table(data);
+            const view = await table.view({
+                columns: ["running"],
+                group_by: ["g"],
+                aggregates: { running: "max" },
+                window: { running: running_sum },
+            });
+
+            const result = await view.to_columns();
+            expect(result.running).toEqual([70, 70, 10]);
+            view.delete();
+            table.delete();
+        });
+
+        test("appended rows update grouped window columns", async function () {
+            const table = await perspective.table(data, { index: "id" });
+            const view = await table.view({
+                columns: ["running"],
+                group_by: ["g"],
+                aggregates: { running: "sum" },
+                window: { running: running_sum },
+            });
+
+            await table.update({ id: [6], g: ["a"], t: [4], x: [30] });
+            await table.update({ id: [6], g: ["a"], t: [4], x: [50] });
+            const result = await view.to_columns();
+            expect(result.running).toEqual([245, 230, 15]);
+            view.delete();
+            table.delete();
+        });
+
+        test("removed rows recalculate the partition", async function () {
+            const table = await perspective.table(data, { index: "id" });
+            const view = await table.view({
+                columns: ["running"],
+                window: { running: running_sum },
+            });
+
+            await table.remove([1]);
+            const result = await view.to_columns();
+            expect(result.running).toEqual([20, 60, 5, 10]);
+            view.delete();
+            table.delete();
+        });
+    });
+})(perspective);
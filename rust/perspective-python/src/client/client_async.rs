@@ -23,7 +23,7 @@ use perspective_client::{
 };
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyBytes, PyDict, PyString};
+use pyo3::types::{PyAny, PyBytes, PyDict, PyString, PyTuple};
 use pythonize::depythonize;
 
 use super::pandas::arrow_to_pandas;
@@ -306,6 +306,22 @@ impl AsyncTable {
         Python::with_gil(|py| Ok(pythonize::pythonize(py, &records)?.unbind()))
     }
 
+    #[pyo3(signature = (**kwargs))]
+    pub async fn validate_view_config(&self, kwargs: Option<Py<PyDict>>) -> PyResult<Py<PyAny>> {
+        let config = kwargs
+            .map(|config| Python::with_gil(|py| depythonize(config.bind(py))))
+            .transpose()?
+            .unwrap_or_default();
+
+        let errors = self
+            .table
+            .validate_view_config(&config)
+            .await
+            .into_pyerr()?;
+
+        Python::with_gil(|py| Ok(pythonize::pythonize(py, &errors)?.unbind()))
+    }
+
     pub async fn schema(&self) -> PyResult<HashMap<String, String>> {
         let schema = self.table.schema().await.into_pyerr()?;
         Ok(schema
@@ -360,6 +376,64 @@ impl AsyncView {
         self.view.collapse(index).await.into_pyerr()
     }
 
+    pub async fn get_expansion_state(&self) -> PyResult<Py<PyAny>> {
+        let state = self.view.get_expansion_state().await.into_pyerr()?;
+        Python::with_gil(|py| Ok(pythonize::pythonize(py, &state)?.unbind()))
+    }
+
+    pub async fn set_expansion_state(&self, state: Py<PyAny>) -> PyResult<()> {
+        let state = Python::with_gil(|py| depythonize(state.bind(py)))?;
+        self.view.set_expansion_state(state).await.into_pyerr()
+    }
+
+    pub async fn find_row_by_path(&self, path: Py<PyAny>) -> PyResult<Option<u32>> {
+        let path = Python::with_gil(|py| depythonize(path.bind(py)))?;
+        self.view.find_row_by_path(path).await.into_pyerr()
+    }
+
+    pub async fn find_row_by_index(&self, index: Py<PyAny>) -> PyResult<Option<u32>> {
+        let index = Python::with_gil(|py| depythonize(index.bind(py)))?;
+        self.view.find_row_by_index(index).await.into_pyerr()
+    }
+
+    pub async fn get_underlying_config(
+        &self,
+        row_index: u32,
+        column_path: Vec<String>,
+    ) -> PyResult<Py<PyAny>> {
+        let config = self
+            .view
+            .get_underlying_config(row_index, column_path)
+            .await
+            .into_pyerr()?;
+
+        Python::with_gil(|py| Ok(pythonize::pythonize(py, &config)?.unbind()))
+    }
+
+    #[pyo3(signature = (row_index, column_path, **window))]
+    pub async fn get_underlying_rows(
+        &self,
+        row_index: u32,
+        column_path: Vec<String>,
+        window: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let window: ViewWindow = Python::with_gil(|py| window.map(|x| depythonize(x.bind(py))))
+            .transpose()?
+            .unwrap_or_default();
+
+        let json = self
+            .view
+            .get_underlying_rows(row_index, column_path, window)
+            .await
+            .into_pyerr()?;
+
+        Python::with_gil(|py| {
+            let json_module = PyModule::import(py, "json")?;
+            let records = json_module.call_method1("loads", (json,))?;
+            Ok(records.unbind())
+        })
+    }
+
     pub async fn expression_schema(&self) -> PyResult<HashMap<String, String>> {
         Ok(self
             .view
@@ -422,8 +496,24 @@ impl AsyncView {
         self.view.remove_delete(callback_id).await.into_pyerr()
     }
 
-    #[pyo3(signature=(callback, mode=None))]
-    pub async fn on_update(&self, callback: Py<PyAny>, mode: Option<String>) -> PyResult<u32> {
+    #[pyo3(signature=(callback, mode=None, viewport=None, include_viewport_data=None, throttle_ms=None))]
+    pub async fn on_update(
+        &self,
+        callback: Py<PyAny>,
+        mode: Option<String>,
+        viewport: Option<Py<PyDict>>,
+        include_viewport_data: Option<bool>,
+        throttle_ms: Option<u32>,
+    ) -> PyResult<u32> {
+        let mode = mode
+            .map(|x| OnUpdateMode::from_str(x.as_str()))
+            .transpose()
+            .into_pyerr()?;
+
+        let viewport: Option<ViewWindow> =
+            Python::with_gil(|py| viewport.map(|x| depythonize(x.bind(py))).transpose())?;
+
+        let is_detail = matches!(mode, Some(OnUpdateMode::Detail));
         let locked_val = self.client.loop_cb.read().await;
         let loop_cb = Python::with_gil(|py| locked_val.as_ref().map(|v| Py::clone_ref(v, py)));
         let callback = move |x: ViewOnUpdateResp| {
@@ -433,14 +523,22 @@ impl AsyncView {
                 let aggregate_errors: PyResult<()> = {
                     let callback = Python::with_gil(|py| Py::clone_ref(&callback, py));
                     Python::with_gil(|py| {
-                        match (&x.delta, &loop_cb) {
-                            (None, None) => callback.call1(py, (x.port_id,))?,
-                            (None, Some(loop_cb)) => loop_cb.call1(py, (&callback, x.port_id))?,
-                            (Some(delta), None) => {
-                                callback.call1(py, (x.port_id, PyBytes::new(py, delta)))?
-                            },
-                            (Some(delta), Some(loop_cb)) => {
-                                loop_cb.call1(py, (callback, x.port_id, PyBytes::new(py, delta)))?
+                        let mut args = vec![x.port_id.into_pyobject(py)?.into_any()];
+                        if is_detail {
+                            args.push(pythonize::pythonize(py, &x.row_deltas)?);
+                        } else if let Some(delta) = &x.delta {
+                            args.push(PyBytes::new(py, delta).into_any());
+                        }
+
+                        if let Some(data) = &x.viewport_data {
+                            args.push(PyBytes::new(py, data).into_any());
+                        }
+
+                        match &loop_cb {
+                            None => callback.call1(py, PyTuple::new(py, args)?)?,
+                            Some(loop_cb) => {
+                                args.insert(0, callback.bind(py).clone());
+                                loop_cb.call1(py, PyTuple::new(py, args)?)?
                             },
                         };
 
@@ -455,13 +553,25 @@ impl AsyncView {
             .boxed()
         };
 
-        let mode = mode
-            .map(|x| OnUpdateMode::from_str(x.as_str()))
-            .transpose()
-            .into_pyerr()?;
+        self.view
+            .on_update(Box::new(callback), OnUpdateOptions {
+                mode,
+                viewport,
+                include_viewport_data,
+                throttle_ms,
+            })
+            .await
+            .into_pyerr()
+    }
 
+    pub async fn set_update_viewport(
+        &self,
+        callback_id: u32,
+        viewport: Py<PyDict>,
+    ) -> PyResult<()> {
+        let viewport: ViewWindow = Python::with_gil(|py| depythonize(viewport.bind(py)))?;
         self.view
-            .on_update(Box::new(callback), OnUpdateOptions { mode })
+            .set_update_viewport(callback_id, viewport)
             .await
             .into_pyerr()
     }
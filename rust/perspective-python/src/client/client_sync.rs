@@ -256,6 +256,18 @@ impl Table {
         table.validate_expressions(expression).py_block_on(py)
     }
 
+    #[apply(inherit_doc)]
+    #[inherit_doc = "table/validate_view_config.md"]
+    #[pyo3(signature = (**config))]
+    pub fn validate_view_config(
+        &self,
+        py: Python<'_>,
+        config: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let table = self.0.clone();
+        table.validate_view_config(config).py_block_on(py)
+    }
+
     #[apply(inherit_doc)]
     #[inherit_doc = "table/view.md"]
     #[pyo3(signature = (**config))]
@@ -428,6 +440,58 @@ impl View {
         self.0.collapse(index).py_block_on(py)
     }
 
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/get_expansion_state.md"]
+    pub fn get_expansion_state(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.0.get_expansion_state().py_block_on(py)
+    }
+
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/set_expansion_state.md"]
+    pub fn set_expansion_state(&self, py: Python<'_>, state: Py<PyAny>) -> PyResult<()> {
+        self.0.set_expansion_state(state).py_block_on(py)
+    }
+
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/find_row_by_path.md"]
+    pub fn find_row_by_path(&self, py: Python<'_>, path: Py<PyAny>) -> PyResult<Option<u32>> {
+        self.0.find_row_by_path(path).py_block_on(py)
+    }
+
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/find_row_by_index.md"]
+    pub fn find_row_by_index(&self, py: Python<'_>, index: Py<PyAny>) -> PyResult<Option<u32>> {
+        self.0.find_row_by_index(index).py_block_on(py)
+    }
+
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/get_underlying_config.md"]
+    pub fn get_underlying_config(
+        &self,
+        py: Python<'_>,
+        row_index: u32,
+        column_path: Vec<String>,
+    ) -> PyResult<Py<PyAny>> {
+        self.0
+            .get_underlying_config(row_index, column_path)
+            .py_block_on(py)
+    }
+
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/get_underlying_rows.md"]
+    #[pyo3(signature = (row_index, column_path, **window))]
+    pub fn get_underlying_rows(
+        &self,
+        py: Python<'_>,
+        row_index: u32,
+        column_path: Vec<String>,
+        window: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        self.0
+            .get_underlying_rows(row_index, column_path, window)
+            .py_block_on(py)
+    }
+
     #[apply(inherit_doc)]
     #[inherit_doc = "view/dimensions.md"]
     pub fn dimensions(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
@@ -478,14 +542,32 @@ impl View {
 
     #[apply(inherit_doc)]
     #[inherit_doc = "view/on_update.md"]
-    #[pyo3(signature = (callback, mode=None))]
+    #[pyo3(signature = (callback, mode=None, viewport=None, include_viewport_data=None, throttle_ms=None))]
     pub fn on_update(
         &self,
         py: Python<'_>,
         callback: Py<PyAny>,
         mode: Option<String>,
+        viewport: Option<Py<PyDict>>,
+        include_viewport_data: Option<bool>,
+        throttle_ms: Option<u32>,
     ) -> PyResult<u32> {
-        self.0.on_update(callback, mode).py_block_on(py)
+        self.0
+            .on_update(callback, mode, viewport, include_viewport_data, throttle_ms)
+            .py_block_on(py)
+    }
+
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/set_update_viewport.md"]
+    pub fn set_update_viewport(
+        &self,
+        py: Python<'_>,
+        callback_id: u32,
+        viewport: Py<PyDict>,
+    ) -> PyResult<()> {
+        self.0
+            .set_update_viewport(callback_id, viewport)
+            .py_block_on(py)
     }
 
     #[apply(inherit_doc)]
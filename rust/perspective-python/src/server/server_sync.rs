@@ -122,6 +122,10 @@ impl PySyncSession {
         })?
     }
 
+    pub fn set_update_throttle(&self, _py: Python<'_>, throttle_ms: u32) -> PyResult<()> {
+        self.with_session(|session| session.set_update_throttle(throttle_ms))
+    }
+
     pub fn close(&self, _py: Python<'_>) -> PyResult<()> {
         let mut lock = self.session.write().block_on();
         lock.take()
@@ -0,0 +1,133 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::sync::Arc;
+
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{Request, Response, ServerError, StatusCode};
+use perspective_client::{AuthRequest, Authorizer, RequestTarget};
+use prost::Message;
+
+use crate::ffi;
+
+/// The [`Authorizer`] of a single [`crate::LocalSession`]. [`View`] requests
+/// are authorized against the [`Table`] the engine created the [`View`]
+/// from, whichever session created it; requests for a [`View`] the engine
+/// does not know are denied.
+///
+/// [`View`]: perspective_client::View
+/// [`Table`]: perspective_client::Table
+#[derive(Clone)]
+pub(crate) struct SessionAuthorizer {
+    identity: String,
+    authorizer: Arc<dyn Authorizer>,
+}
+
+impl std::fmt::Debug for SessionAuthorizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionAuthorizer")
+            .field("identity", &self.identity)
+            .finish()
+    }
+}
+
+impl SessionAuthorizer {
+    pub fn new(identity: impl Into<String>, authorizer: Arc<dyn Authorizer>) -> Self {
+        SessionAuthorizer {
+            identity: identity.into(),
+            authorizer,
+        }
+    }
+
+    /// Authorize a decoded request, returning the encoded error response to
+    /// send in its place if it is denied.
+    pub fn authorize(&self, server: &ffi::Server, request: &Request) -> Result<(), Vec<u8>> {
+        let auth_request = AuthRequest::new(&self.identity, request, None);
+        let table_name = match auth_request.target() {
+            RequestTarget::Server => None,
+            RequestTarget::Table => Some(request.entity_id.clone()),
+            RequestTarget::View => match server.view_table_id(&request.entity_id) {
+                Some(table_name) => Some(table_name),
+                None => {
+                    let reason = format!("View `{}` not found", request.entity_id);
+                    return Err(denied(request, reason));
+                },
+            },
+        };
+
+        let auth_request = AuthRequest::new(&self.identity, request, table_name.as_deref());
+        self.authorizer.authorize(&auth_request).map_err(|error| {
+            tracing::debug!("Denied request from `{}`: {}", self.identity, error);
+            denied(request, error.to_string())
+        })
+    }
+}
+
+/// Deny the admin requests of a session which has not opted in to them with
+/// [`crate::LocalSession::with_admin`], as they list and delete the views and
+/// sessions of every client. See [`AuthRequest::is_admin`].
+pub(crate) fn authorize_admin(request: &Request) -> Result<(), Vec<u8>> {
+    let auth_request = AuthRequest::new("", request, None);
+    if auth_request.is_admin() {
+        let reason = format!("`{}` requires an admin session", auth_request.kind());
+        return Err(denied(request, reason));
+    }
+
+    Ok(())
+}
+
+/// Deny the requests of a session whose reads are restricted, which would
+/// read around its restrictions: requests for [`View`]s created by other
+/// sessions, `system_info` (which counts every row of every [`Table`]) and
+/// admin requests. Returns the encoded error response to send in place of a
+/// denied request.
+///
+/// [`View`]: perspective_client::View
+/// [`Table`]: perspective_client::Table
+pub(crate) fn authorize_restricted(
+    server: &ffi::Server,
+    session_id: u32,
+    request: &Request,
+) -> Result<(), Vec<u8>> {
+    let auth_request = AuthRequest::new("", request, None);
+    if auth_request.is_admin() || auth_request.kind() == "ServerSystemInfoReq" {
+        let reason = format!("`{}` is not allowed for this session", auth_request.kind());
+        return Err(denied(request, reason));
+    }
+
+    if auth_request.target() == RequestTarget::View
+        && server.view_session_id(&request.entity_id) != Some(session_id)
+    {
+        let reason = format!(
+            "View `{}` was not created by this session",
+            request.entity_id
+        );
+
+        return Err(denied(request, reason));
+    }
+
+    Ok(())
+}
+
+/// The encoded error response which denies `request`.
+pub(crate) fn denied(request: &Request, reason: String) -> Vec<u8> {
+    let response = Response {
+        msg_id: request.msg_id,
+        entity_id: request.entity_id.clone(),
+        client_resp: Some(ClientResp::ServerError(ServerError {
+            message: reason,
+            status_code: StatusCode::Unauthorized.into(),
+        })),
+    };
+
+    response.encode_to_vec()
+}
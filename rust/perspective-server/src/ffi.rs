@@ -37,6 +37,22 @@ extern "C" {
     ) -> ResponseBatch;
     fn psp_poll(server: *const u8) -> ResponseBatch;
     fn psp_close_session(server: *const u8, client_id: u32);
+    fn psp_set_session_update_throttle(server: *const u8, client_id: u32, throttle_ms: u32);
+    fn psp_session_num_views(server: *const u8, client_id: u32) -> u32;
+    fn psp_session_num_subscriptions(server: *const u8, client_id: u32) -> u32;
+    fn psp_view_num_rows(server: *const u8, view_id_ptr: *const u8, view_id_len: usize) -> i64;
+    fn psp_view_session_id(server: *const u8, view_id_ptr: *const u8, view_id_len: usize) -> i64;
+    fn psp_view_table_id(
+        server: *const u8,
+        view_id_ptr: *const u8,
+        view_id_len: usize,
+        table_id_len: *mut usize,
+    ) -> *mut u8;
+    fn psp_process_table(
+        server: *const u8,
+        table_id_ptr: *const u8,
+        table_id_len: usize,
+    ) -> ResponseBatch;
 }
 
 pub struct Response(*const CppResponse);
@@ -129,6 +145,46 @@ impl Server {
     pub fn close_session(&self, session_id: u32) {
         unsafe { psp_close_session(self.0, session_id) }
     }
+
+    pub fn set_session_update_throttle(&self, session_id: u32, throttle_ms: u32) {
+        unsafe { psp_set_session_update_throttle(self.0, session_id, throttle_ms) }
+    }
+
+    pub fn session_num_views(&self, session_id: u32) -> u32 {
+        unsafe { psp_session_num_views(self.0, session_id) }
+    }
+
+    pub fn session_num_subscriptions(&self, session_id: u32) -> u32 {
+        unsafe { psp_session_num_subscriptions(self.0, session_id) }
+    }
+
+    pub fn view_num_rows(&self, view_id: &str) -> Option<u32> {
+        let num_rows = unsafe { psp_view_num_rows(self.0, view_id.as_ptr(), view_id.len()) };
+        u32::try_from(num_rows).ok()
+    }
+
+    pub fn view_session_id(&self, view_id: &str) -> Option<u32> {
+        let session_id = unsafe { psp_view_session_id(self.0, view_id.as_ptr(), view_id.len()) };
+        u32::try_from(session_id).ok()
+    }
+
+    pub fn view_table_id(&self, view_id: &str) -> Option<String> {
+        let mut len = 0;
+        let ptr = unsafe { psp_view_table_id(self.0, view_id.as_ptr(), view_id.len(), &mut len) };
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        let table_id = unsafe { std::slice::from_raw_parts(ptr, len) };
+        let table_id = String::from_utf8_lossy(table_id).into_owned();
+        unsafe { psp_free(ptr) };
+        Some(table_id)
+    }
+
+    pub fn process_table(&self, table_id: &str) -> ResponseBatch {
+        unsafe { psp_process_table(self.0, table_id.as_ptr(), table_id.len()) }
+    }
 }
 
 impl Drop for Server {
@@ -12,11 +12,24 @@
 
 extern crate link_cplusplus;
 
+mod authorizer;
+mod executor;
 mod ffi;
 mod local_client;
 mod local_session;
+mod poller;
+mod quota;
+mod replica;
+mod request_lock;
+mod scheduler;
 mod server;
+#[cfg(feature = "tokio")]
+mod tokio_session;
 
 pub use local_client::LocalClient;
 pub use local_session::LocalSession;
+pub use quota::SessionQuotas;
+pub use replica::{Replica, ReplicationLag};
 pub use server::{Server, ServerError, SessionHandler};
+#[cfg(feature = "tokio")]
+pub use tokio_session::TokioSession;
@@ -17,6 +17,7 @@ use async_lock::{RwLock, RwLockReadGuard};
 use perspective_client::*;
 
 use crate::local_session::LocalSession;
+use crate::quota::SessionQuotas;
 use crate::server::{Server, ServerError, SessionHandler};
 
 #[derive(Clone, Default)]
@@ -24,6 +25,11 @@ struct LocalClientState {
     client: Arc<OnceLock<Client>>,
     session: Arc<OnceLock<RwLock<Option<LocalSession>>>>,
     server: Server,
+    admin: bool,
+    authorization: Option<(String, Arc<dyn Authorizer>)>,
+    filters: Option<SessionFilters>,
+    column_policies: Option<ColumnPolicies>,
+    quotas: Option<SessionQuotas>,
 }
 
 impl SessionHandler for LocalClientState {
@@ -41,7 +47,10 @@ impl ClientHandler for LocalClientState {
         let session_lock = self.get_session().await;
         let session = session_lock.as_ref().unwrap();
         session.handle_request(msg).await?;
-        session.poll().await?;
+        if !self.server.poll_on_update {
+            session.poll().await?;
+        }
+
         Ok(())
     }
 }
@@ -53,7 +62,27 @@ impl LocalClientState {
 
     async fn get_session(&self) -> RwLockReadGuard<'_, Option<LocalSession>> {
         if self.session.get().is_none() {
-            let session = self.server.new_session(self.clone()).await;
+            let mut session = self.server.new_session(self.clone()).await;
+            if self.admin {
+                session = session.with_admin();
+            }
+
+            if let Some((identity, authorizer)) = &self.authorization {
+                session = session.with_authorizer(identity.clone(), authorizer.clone());
+            }
+
+            if let Some(filters) = &self.filters {
+                session = session.with_filters(filters.clone());
+            }
+
+            if let Some(policies) = &self.column_policies {
+                session = session.with_column_policies(policies.clone());
+            }
+
+            if let Some(quotas) = &self.quotas {
+                session = session.with_quotas(quotas.clone());
+            }
+
             self.session
                 .get_or_init(|| RwLock::new(Some(session)))
                 .read()
@@ -96,11 +125,69 @@ impl LocalClient {
             server: server.clone(),
             client: Arc::default(),
             session: Arc::default(),
+            admin: false,
+            authorization: None,
+            filters: None,
+            column_policies: None,
+            quotas: None,
         };
 
         LocalClient(Some(state))
     }
 
+    /// Allow this [`LocalClient`]'s admin requests, e.g.
+    /// [`Client::get_hosted_views`]. See [`LocalSession::with_admin`].
+    ///
+    /// As with the other `with_*` methods, this configures the
+    /// [`LocalSession`] the [`LocalClient`] creates on its first request, and
+    /// so must be called before the [`LocalClient`] is used.
+    pub fn with_admin(mut self) -> Self {
+        self.state_mut().admin = true;
+        self
+    }
+
+    /// Check this [`LocalClient`]'s requests with `authorizer`, as
+    /// `identity`. See [`LocalSession::with_authorizer`].
+    pub fn with_authorizer(
+        mut self,
+        identity: impl Into<String>,
+        authorizer: Arc<dyn Authorizer>,
+    ) -> Self {
+        self.state_mut().authorization = Some((identity.into(), authorizer));
+        self
+    }
+
+    /// Restrict this [`LocalClient`] to the rows of each [`Table`] which
+    /// pass `filters`. See [`LocalSession::with_filters`].
+    pub fn with_filters(mut self, filters: SessionFilters) -> Self {
+        self.state_mut().filters = Some(filters);
+        self
+    }
+
+    /// Restrict this [`LocalClient`]'s access to the columns of each
+    /// [`Table`] per `policies`. See [`LocalSession::with_column_policies`].
+    pub fn with_column_policies(mut self, policies: ColumnPolicies) -> Self {
+        self.state_mut().column_policies = Some(policies);
+        self
+    }
+
+    /// Limit this [`LocalClient`]'s resource use per `quotas`. See
+    /// [`LocalSession::with_quotas`].
+    pub fn with_quotas(mut self, quotas: SessionQuotas) -> Self {
+        self.state_mut().quotas = Some(quotas);
+        self
+    }
+
+    fn state_mut(&mut self) -> &mut LocalClientState {
+        let state = self.0.as_mut().unwrap();
+        debug_assert!(
+            state.client.get().is_none(),
+            "`LocalClient` configured after use"
+        );
+
+        state
+    }
+
     pub fn take(mut self) -> Result<Client, &'static str> {
         self.0.take().map(|x| x.get_client().clone()).ok_or("Empty")
     }
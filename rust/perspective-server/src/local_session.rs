@@ -10,9 +10,15 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use perspective_client::Session;
+use std::sync::Arc;
 
+use perspective_client::proto::Request;
+use perspective_client::{Authorizer, ColumnPolicies, Session, SessionFilters};
+use prost::Message;
+
+use crate::authorizer::{authorize_admin, authorize_restricted, denied, SessionAuthorizer};
 use crate::ffi;
+use crate::quota::{QuotaUsage, SessionQuota, SessionQuotas};
 use crate::server::{Server, ServerError};
 
 /// A struct for implementing [`perspective_client::Session`] against an
@@ -25,6 +31,19 @@ pub struct LocalSession {
     pub(crate) id: u32,
     pub(crate) server: Server,
     pub(crate) closed: bool,
+    pub(crate) checks: SessionChecks,
+}
+
+/// The checks a [`LocalSession`] runs on each of its requests before the
+/// engine handles it. They read the engine's views and sessions, so they
+/// run under the same lock as the request itself.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SessionChecks {
+    admin: bool,
+    authorizer: Option<SessionAuthorizer>,
+    filters: Option<SessionFilters>,
+    column_policies: Option<ColumnPolicies>,
+    quota: Option<SessionQuota>,
 }
 
 impl Drop for LocalSession {
@@ -35,30 +54,126 @@ impl Drop for LocalSession {
     }
 }
 
-impl Session<ServerError> for LocalSession {
-    async fn handle_request(&self, request: &[u8]) -> Result<(), ServerError> {
-        let request = ffi::Request::from(request);
-        let responses = self.server.server.handle_request(self.id, &request);
-        for response in responses.iter_responses() {
-            let cb = self
-                .server
-                .callbacks
-                .read()
-                .await
-                .get(&response.client_id())
-                .cloned();
+impl LocalSession {
+    /// Authorize each request of this session with `authorizer`, as
+    /// `identity`, before it reaches the engine. Denied requests are answered
+    /// with a [`perspective_client::ClientError::Unauthorized`] error and
+    /// are not executed. Requests for a [`perspective_client::View`] are
+    /// authorized against the [`perspective_client::Table`] it was created
+    /// from.
+    pub fn with_authorizer(
+        mut self,
+        identity: impl Into<String>,
+        authorizer: Arc<dyn Authorizer>,
+    ) -> Self {
+        self.checks.authorizer = Some(SessionAuthorizer::new(identity, authorizer));
+        self
+    }
 
-            if let Some(f) = cb {
-                f(response.msg()).await?;
-            }
+    /// Allow this session's admin requests (see
+    /// [`perspective_client::AuthRequest::is_admin`]), which list and delete
+    /// the views and sessions of every client of the [`Server`], and are
+    /// denied by default. They are still checked by the session's
+    /// [`Authorizer`], and denied if it has filters or column policies.
+    pub fn with_admin(mut self) -> Self {
+        self.checks.admin = true;
+        self
+    }
+
+    /// Restrict the rows this session can read from each
+    /// [`perspective_client::Table`] to those which pass its `filters`. See
+    /// [`SessionFilters`]. A filtered session can only address the
+    /// [`perspective_client::View`]s it created, and cannot make
+    /// `system_info` or admin requests.
+    pub fn with_filters(mut self, filters: SessionFilters) -> Self {
+        self.checks.filters = Some(filters);
+        self
+    }
+
+    /// Hide or mask the columns of each [`perspective_client::Table`] this
+    /// session can read per `policies`. See [`ColumnPolicies`]. As with
+    /// [`LocalSession::with_filters`], the session can then only address the
+    /// [`perspective_client::View`]s it created.
+    pub fn with_column_policies(mut self, policies: ColumnPolicies) -> Self {
+        self.checks.column_policies = Some(policies);
+        self
+    }
+
+    /// Limit the views, subscriptions, response sizes, export sizes and
+    /// request rate of this session per `quotas`. Requests which would exceed
+    /// them are answered with a
+    /// [`perspective_client::ClientError::QuotaExceeded`] error and are not
+    /// executed, except for the response size limit, which can only be
+    /// checked once the request has executed. See [`SessionQuotas`].
+    pub fn with_quotas(mut self, quotas: SessionQuotas) -> Self {
+        self.checks.quota = Some(SessionQuota::new(quotas));
+        self
+    }
+
+    /// Throttle the `on_update` callbacks of this session's
+    /// [`perspective_client::View`]s which do not set their own
+    /// [`perspective_client::OnUpdateOptions::throttle_ms`], so that each
+    /// receives at most one update per `throttle_ms`. Updates withheld within
+    /// the interval are coalesced into the next one, which is sent by the
+    /// first [`Session::poll`] after the interval has elapsed. `0` disables
+    /// the throttle, which is the default.
+    pub fn set_update_throttle(&self, throttle_ms: u32) {
+        self.server
+            .server
+            .set_session_update_throttle(self.id, throttle_ms);
+    }
+}
+
+impl LocalSession {
+    /// Send a response to this session's own callback.
+    pub(crate) async fn send_response(&self, response: &[u8]) -> Result<(), ServerError> {
+        let cb = self.server.callbacks.read().await.get(&self.id).cloned();
+        if let Some(f) = cb {
+            f(response).await?;
         }
 
         Ok(())
     }
 
-    async fn poll(&self) -> Result<(), ServerError> {
-        let responses = self.server.server.poll();
-        for response in responses.iter_responses() {
+    /// Check a request and run it on the engine, on this task unless the
+    /// [`Server`] is concurrent. Returns the response which denies the
+    /// request if it fails its checks.
+    pub(crate) async fn execute(&self, request: &[u8]) -> Result<Vec<ffi::ResponseBatch>, Vec<u8>> {
+        match &self.server.scheduler {
+            Some(scheduler) => {
+                let checks = self.checks.clone();
+                let id = self.id;
+                scheduler
+                    .handle_request(&self.server.server, id, request, move |server, request| {
+                        checks.check(server, id, request)
+                    })
+                    .await
+            },
+            None => {
+                let _engine = self.server.engine_lock.lock().await;
+                let request = self.checks.check(&self.server.server, self.id, request)?;
+                Ok(vec![self.server.server.handle_request(self.id, &request)])
+            },
+        }
+    }
+
+    /// Send each response of `batches` to the callback of the session it
+    /// is addressed to. Responses to this session's own `request` which
+    /// exceed its quotas are replaced with an error.
+    pub(crate) async fn dispatch_responses(
+        &self,
+        request: Option<&[u8]>,
+        batches: &[ffi::ResponseBatch],
+    ) -> Result<(), ServerError> {
+        for response in batches.iter().flat_map(|x| x.iter_responses()) {
+            let exceeded = self
+                .checks
+                .quota
+                .as_ref()
+                .zip(request)
+                .filter(|_| response.client_id() == self.id)
+                .and_then(|(x, request)| x.check_response(request, response.msg()));
+
             let cb = self
                 .server
                 .callbacks
@@ -68,16 +183,127 @@ impl Session<ServerError> for LocalSession {
                 .cloned();
 
             if let Some(f) = cb {
-                f(response.msg()).await?;
+                match exceeded {
+                    Some(exceeded) => f(&exceeded).await?,
+                    None => f(response.msg()).await?,
+                }
             }
         }
 
         Ok(())
     }
+}
+
+impl SessionChecks {
+    /// Whether any check applies to this session's requests.
+    fn is_checked(&self) -> bool {
+        !self.admin
+            || self.authorizer.is_some()
+            || self.filters.is_some()
+            || self.column_policies.is_some()
+            || self.quota.is_some()
+    }
+
+    /// Authorize a request and check it against the session's quotas, then
+    /// apply its filters and column policies. Returns the request the engine
+    /// should handle, or the response which denies it. Requests which cannot
+    /// be decoded are denied unless the session has no checks to skip, i.e.
+    /// it is an admin session with no other checks. Must be called under the
+    /// lock the request runs under.
+    pub(crate) fn check(
+        &self,
+        server: &ffi::Server,
+        id: u32,
+        request: &[u8],
+    ) -> Result<ffi::Request, Vec<u8>> {
+        let mut decoded = match Request::decode(request) {
+            Ok(decoded) => decoded,
+            Err(err) if self.is_checked() => {
+                return Err(denied(&Request::default(), err.to_string()))
+            },
+            Err(_) => return Ok(ffi::Request::from(request)),
+        };
+
+        if !self.admin {
+            authorize_admin(&decoded)?;
+        }
+
+        if self.filters.is_some() || self.column_policies.is_some() {
+            authorize_restricted(server, id, &decoded)?;
+        }
+
+        if let Some(authorizer) = &self.authorizer {
+            authorizer.authorize(server, &decoded)?;
+        }
+
+        if let Some(quota) = &self.quota {
+            quota.check(&decoded, &ServerUsage { server, id })?;
+        }
+
+        let filtered = self.filters.as_ref().is_some_and(|x| x.apply(&mut decoded));
+
+        let masked = self
+            .column_policies
+            .as_ref()
+            .is_some_and(|x| x.apply(&mut decoded));
+
+        if filtered || masked {
+            Ok(ffi::Request::from(decoded.encode_to_vec().as_slice()))
+        } else {
+            Ok(ffi::Request::from(request))
+        }
+    }
+}
+
+/// The usage of a [`LocalSession`] counted against its [`SessionQuotas`].
+struct ServerUsage<'a> {
+    server: &'a ffi::Server,
+    id: u32,
+}
+
+impl QuotaUsage for ServerUsage<'_> {
+    fn num_views(&self) -> u32 {
+        self.server.session_num_views(self.id)
+    }
+
+    fn num_subscriptions(&self) -> u32 {
+        self.server.session_num_subscriptions(self.id)
+    }
+
+    fn num_view_rows(&self, view_id: &str) -> Option<u32> {
+        self.server.view_num_rows(view_id)
+    }
+}
+
+impl Session<ServerError> for LocalSession {
+    async fn handle_request(&self, request: &[u8]) -> Result<(), ServerError> {
+        let batches = match self.execute(request).await {
+            Ok(batches) => batches,
+            Err(response) => return self.send_response(&response).await,
+        };
+
+        self.dispatch_responses(Some(request), &batches).await?;
+        if self.server.poll_on_update {
+            self.server.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn poll(&self) -> Result<(), ServerError> {
+        self.server.flush().await
+    }
 
     async fn close(mut self) {
         self.closed = true;
-        self.server.server.close_session(self.id);
+        match &self.server.scheduler {
+            Some(scheduler) => scheduler.close_session(&self.server.server, self.id).await,
+            None => {
+                let _engine = self.server.engine_lock.lock().await;
+                self.server.server.close_session(self.id)
+            },
+        }
+
         self.server
             .callbacks
             .write()
@@ -0,0 +1,249 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{Request, Response, ServerError, StatusCode, ViewPort};
+#[cfg(doc)]
+use perspective_client::{Client, ClientError, Session, View};
+use prost::Message;
+
+/// Limits on the resources a single [`Session`] may use, so that one
+/// misbehaving [`Client`] cannot exhaust a shared server. e.g., to allow a
+/// [`Session`] at most 100 [`View`]s and 10,000 rows per export:
+///
+/// ```rust
+/// # use perspective_server::SessionQuotas;
+/// let quotas = SessionQuotas::default()
+///     .with_max_views(100)
+///     .with_max_export_rows(10_000);
+/// ```
+///
+/// Requests which would exceed a limit are answered with a
+/// [`ClientError::QuotaExceeded`] error and are not executed, except for
+/// [`SessionQuotas::with_max_response_bytes`], which is checked after the
+/// request executes. Every limit is unset by default.
+#[derive(Clone, Debug, Default)]
+pub struct SessionQuotas {
+    max_views: Option<u32>,
+    max_subscriptions: Option<u32>,
+    max_response_bytes: Option<usize>,
+    max_export_rows: Option<u32>,
+    max_requests_per_second: Option<u32>,
+}
+
+impl SessionQuotas {
+    /// The most [`View`]s the [`Session`] may have open at once.
+    pub fn with_max_views(mut self, max: u32) -> Self {
+        self.max_views = Some(max);
+        self
+    }
+
+    /// The most `on_update` and `on_delete` callbacks the [`Session`] may
+    /// have registered at once.
+    pub fn with_max_subscriptions(mut self, max: u32) -> Self {
+        self.max_subscriptions = Some(max);
+        self
+    }
+
+    /// The largest encoded response the [`Session`] may receive. Responses
+    /// to its `on_update` callbacks are not limited. A response's size is
+    /// only known once its request has executed, so an oversized request
+    /// still does all of its work (and e.g. a [`View::to_arrow`] still
+    /// serializes its rows) before its response is replaced with the error;
+    /// use [`SessionQuotas::with_max_export_rows`] to bound that work.
+    pub fn with_max_response_bytes(mut self, max: usize) -> Self {
+        self.max_response_bytes = Some(max);
+        self
+    }
+
+    /// The most rows the [`Session`] may read from a [`View`] in one export,
+    /// e.g. [`View::to_arrow`], or with each update of an `on_update`
+    /// subscription which includes its viewport's data. Such a subscription
+    /// must set a viewport `end_row` within this limit.
+    pub fn with_max_export_rows(mut self, max: u32) -> Self {
+        self.max_export_rows = Some(max);
+        self
+    }
+
+    /// The most requests the [`Session`] may make per second.
+    pub fn with_max_requests_per_second(mut self, max: u32) -> Self {
+        self.max_requests_per_second = Some(max);
+        self
+    }
+}
+
+/// The usage of a [`Session`] which is counted against its
+/// [`SessionQuotas`], as reported by the server it is a [`Session`] of.
+pub(crate) trait QuotaUsage {
+    fn num_views(&self) -> u32;
+    fn num_subscriptions(&self) -> u32;
+
+    /// The row count of the [`View`] `view_id`, `None` if it does not exist.
+    fn num_view_rows(&self, view_id: &str) -> Option<u32>;
+}
+
+/// The [`SessionQuotas`] of a single [`Session`], which tracks the rate of
+/// its requests.
+#[derive(Clone, Debug)]
+pub(crate) struct SessionQuota {
+    quotas: SessionQuotas,
+    window: Arc<Mutex<(Instant, u32)>>,
+}
+
+impl SessionQuota {
+    pub fn new(quotas: SessionQuotas) -> Self {
+        SessionQuota {
+            quotas,
+            window: Arc::new(Mutex::new((Instant::now(), 0))),
+        }
+    }
+
+    /// Check a decoded request against these quotas, before it is executed,
+    /// returning the encoded error response to send in its place if it would
+    /// exceed them.
+    pub fn check(&self, request: &Request, usage: &impl QuotaUsage) -> Result<(), Vec<u8>> {
+        if let Some(max) = self.quotas.max_requests_per_second {
+            let mut window = self.window.lock().unwrap();
+            let now = Instant::now();
+            if now.duration_since(window.0) >= Duration::from_secs(1) {
+                *window = (now, 0);
+            }
+
+            window.1 += 1;
+            if window.1 > max {
+                return Err(exceeded(
+                    request,
+                    format!("More than {max} requests per second"),
+                ));
+            }
+        }
+
+        match &request.client_req {
+            Some(ClientReq::TableMakeViewReq(_)) => {
+                if let Some(max) = self.quotas.max_views {
+                    if usage.num_views() >= max {
+                        return Err(exceeded(request, format!("More than {max} views")));
+                    }
+                }
+            },
+            Some(
+                ClientReq::ViewOnUpdateReq(_)
+                | ClientReq::ViewOnDeleteReq(_)
+                | ClientReq::TableOnDeleteReq(_),
+            ) => {
+                if let Some(max) = self.quotas.max_subscriptions {
+                    if usage.num_subscriptions() >= max {
+                        return Err(exceeded(request, format!("More than {max} subscriptions")));
+                    }
+                }
+
+                if let Some(ClientReq::ViewOnUpdateReq(req)) = &request.client_req {
+                    if req.include_viewport_data {
+                        self.check_update_rows(request, req.viewport.as_ref())?;
+                    }
+                }
+            },
+            Some(ClientReq::ViewSetUpdateViewportReq(req)) => {
+                self.check_update_rows(request, req.viewport.as_ref())?;
+            },
+            Some(req) => {
+                if let (Some(max), Some(viewport)) =
+                    (self.quotas.max_export_rows, export_viewport(req))
+                {
+                    let start_row = viewport.start_row.unwrap_or_default();
+                    let end_row = match viewport.end_row {
+                        Some(end_row) if end_row.saturating_sub(start_row) <= max => end_row,
+                        end_row => {
+                            let num_rows = usage.num_view_rows(&request.entity_id).unwrap_or(0);
+                            end_row.map_or(num_rows, |x| x.min(num_rows))
+                        },
+                    };
+
+                    if end_row.saturating_sub(start_row) > max {
+                        return Err(exceeded(request, format!("More than {max} rows")));
+                    }
+                }
+            },
+            None => {},
+        }
+
+        Ok(())
+    }
+
+    /// Check the viewport of an `on_update` subscription which sends its
+    /// rows with each update. Unlike an export, the subscription outlives
+    /// the request and its [`View`] may grow, so its viewport must bound its
+    /// rows explicitly.
+    fn check_update_rows(
+        &self,
+        request: &Request,
+        viewport: Option<&ViewPort>,
+    ) -> Result<(), Vec<u8>> {
+        let Some(max) = self.quotas.max_export_rows else {
+            return Ok(());
+        };
+
+        let start_row = viewport.and_then(|x| x.start_row).unwrap_or_default();
+        match viewport.and_then(|x| x.end_row) {
+            Some(end_row) if end_row.saturating_sub(start_row) <= max => Ok(()),
+            _ => Err(exceeded(request, format!("More than {max} rows"))),
+        }
+    }
+
+    /// Check the size of an encoded response to the encoded `request`,
+    /// returning the error response to send in its place if it is too large.
+    pub fn check_response(&self, request: &[u8], response: &[u8]) -> Option<Vec<u8>> {
+        let max = self.quotas.max_response_bytes?;
+        if response.len() <= max {
+            return None;
+        }
+
+        // Only the response to `request` itself is limited, not e.g. the
+        // `on_update` notifications it triggers.
+        let request = Request::decode(request).unwrap_or_default();
+        let response = Response::decode(response).ok()?;
+        (response.msg_id == request.msg_id)
+            .then(|| exceeded(&request, format!("Response larger than {max} bytes")))
+    }
+}
+
+/// The [`ViewPort`] of a request which exports rows from a [`View`].
+fn export_viewport(req: &ClientReq) -> Option<ViewPort> {
+    let viewport = match req {
+        ClientReq::ViewToArrowReq(req) => &req.viewport,
+        ClientReq::ViewToCsvReq(req) => &req.viewport,
+        ClientReq::ViewToColumnsStringReq(req) => &req.viewport,
+        ClientReq::ViewToRowsStringReq(req) => &req.viewport,
+        ClientReq::ViewToNdjsonStringReq(req) => &req.viewport,
+        _ => return None,
+    };
+
+    Some(viewport.clone().unwrap_or_default())
+}
+
+fn exceeded(request: &Request, reason: String) -> Vec<u8> {
+    tracing::debug!("Refused request: {}", reason);
+    let response = Response {
+        msg_id: request.msg_id,
+        entity_id: request.entity_id.clone(),
+        client_resp: Some(ClientResp::ServerError(ServerError {
+            message: reason,
+            status_code: StatusCode::QuotaExceeded.into(),
+        })),
+    };
+
+    response.encode_to_vec()
+}
@@ -0,0 +1,567 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use perspective_client::utils::ClientResult;
+use perspective_client::{
+    Client, ClientError, OnUpdateMode, OnUpdateOptions, Table, TableInitOptions, UpdateData,
+    UpdateOptions, View, ViewOnUpdateResp, ViewWindow,
+};
+
+use crate::executor::Executor;
+use crate::local_client::LocalClient;
+use crate::server::Server;
+
+/// The replication lag of one [`Table`] of a [`Replica`], as returned by
+/// [`Replica::lag`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReplicationLag {
+    /// How long the local [`Table`] has been behind the remote one: the age
+    /// of the oldest update received but not yet applied, or of the
+    /// disconnect (or deleted subscription) which made it stale. Zero if
+    /// the local [`Table`] is up to date.
+    pub behind: Duration,
+
+    /// Whether the local [`Table`] has lost its subscription to the remote
+    /// one, and will be re-synced on the next [`Replica::sync`].
+    pub stale: bool,
+
+    /// The number of updates received but not yet applied.
+    pub pending_updates: usize,
+
+    /// The number of updates applied since the [`Table`] was last synced.
+    pub updates_applied: u64,
+
+    /// The time since the last update was applied, if any was.
+    pub since_last_update: Option<Duration>,
+}
+
+#[derive(Default)]
+struct MirrorStatus {
+    /// Incremented on each re-sync, so that the updates of a previous
+    /// subscription are ignored.
+    generation: u64,
+    stale_since: Option<Instant>,
+    pending: VecDeque<Instant>,
+    updates_applied: u64,
+    last_update: Option<Instant>,
+
+    /// `updates_applied` as of the last sync which found the local size
+    /// different from the remote one, if the last sync did.
+    size_mismatch: Option<u64>,
+}
+
+impl MirrorStatus {
+    fn mark_stale(&mut self, since: Instant) {
+        self.stale_since.get_or_insert(since);
+    }
+
+    fn lag(&self, now: Instant) -> ReplicationLag {
+        let since = self
+            .pending
+            .front()
+            .copied()
+            .into_iter()
+            .chain(self.stale_since);
+        ReplicationLag {
+            behind: since
+                .map(|x| now.saturating_duration_since(x))
+                .max()
+                .unwrap_or_default(),
+            stale: self.stale_since.is_some(),
+            pending_updates: self.pending.len(),
+            updates_applied: self.updates_applied,
+            since_last_update: self.last_update.map(|x| now.saturating_duration_since(x)),
+        }
+    }
+}
+
+/// A replicated [`Table`]: the local copy, and the remote [`View`] whose
+/// updates are applied to it.
+#[derive(Clone)]
+struct Mirror {
+    local: Table,
+    view: Option<View>,
+    status: Arc<Mutex<MirrorStatus>>,
+}
+
+enum Signal {
+    Sync,
+    Stop,
+}
+
+struct ReplicaState {
+    local: Client,
+    remote: Mutex<Client>,
+    mirrors: Mutex<HashMap<String, Mirror>>,
+
+    /// Serializes [`ReplicaState::sync`], which holds no other lock while
+    /// it awaits the remote [`Client`].
+    sync_lock: async_lock::Mutex<()>,
+    signal: Mutex<Option<mpsc::Sender<Signal>>>,
+}
+
+impl ReplicaState {
+    fn remote(&self) -> Client {
+        self.remote.lock().unwrap().clone()
+    }
+
+    fn mirror(&self, name: &str) -> Option<Mirror> {
+        self.mirrors.lock().unwrap().get(name).cloned()
+    }
+
+    /// Mark every [`Mirror`] stale, e.g. because the remote [`Client`] is
+    /// unreachable.
+    fn mark_all_stale(&self) {
+        let now = Instant::now();
+        for mirror in self.mirrors.lock().unwrap().values() {
+            mirror.status.lock().unwrap().mark_stale(now);
+        }
+    }
+
+    /// Ask the sync thread, if there is one, to sync now rather than at the
+    /// end of its interval.
+    fn wake(&self) {
+        if let Some(signal) = &*self.signal.lock().unwrap() {
+            let _ = signal.send(Signal::Sync);
+        }
+    }
+
+    async fn sync(self: &Arc<Self>) -> ClientResult<()> {
+        let _guard = self.sync_lock.lock().await;
+        self.sync_locked().await
+    }
+
+    /// [`ReplicaState::sync`], for a caller which holds the `sync_lock`.
+    async fn sync_locked(self: &Arc<Self>) -> ClientResult<()> {
+        let remote = self.remote();
+        let names = match remote.get_hosted_table_names().await {
+            Ok(names) => names,
+            Err(err) => {
+                self.mark_all_stale();
+                return Err(err);
+            },
+        };
+
+        let mut result = Ok(());
+        for name in names.iter() {
+            if let Err(err) = self.sync_table(&remote, name).await {
+                tracing::warn!("Failed to replicate table {}: {}", name, err);
+                if let Some(mirror) = self.mirror(name) {
+                    mirror.status.lock().unwrap().mark_stale(Instant::now());
+                }
+
+                result = result.and(Err(err));
+            }
+        }
+
+        let deleted: Vec<(String, Mirror)> = self
+            .mirrors
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| !names.contains(name))
+            .map(|(name, mirror)| (name.clone(), mirror.clone()))
+            .collect();
+
+        for (name, mirror) in deleted {
+            match mirror.local.delete().await {
+                Ok(()) => {
+                    self.mirrors.lock().unwrap().remove(&name);
+                },
+                Err(err) => {
+                    tracing::warn!("Failed to delete replicated table {}: {}", name, err);
+                    mirror.status.lock().unwrap().mark_stale(Instant::now());
+                    result = result.and(Err(err));
+                },
+            }
+        }
+
+        result
+    }
+
+    /// Mirror the remote [`Table`] `name` if it is new, re-sync it if it is
+    /// stale, and check that its size still matches otherwise, as `Row`
+    /// mode updates do not carry removed rows.
+    async fn sync_table(self: &Arc<Self>, remote: &Client, name: &str) -> ClientResult<()> {
+        let mirror = self.mirror(name);
+        if let Some(mirror) = &mirror {
+            if !self.is_diverged(remote, name, mirror).await? {
+                return Ok(());
+            }
+        }
+
+        // A previous subscription through the same remote `Client` may still
+        // be live, and would otherwise leak its `View`.
+        if let Some(view) = mirror.as_ref().and_then(|x| x.view.as_ref()) {
+            if let Err(err) = view.delete().await {
+                tracing::debug!("Failed to delete replica view: {}", err);
+            }
+        }
+
+        let table = remote.open_table(name.to_owned()).await?;
+        let view = table.view(None).await?;
+        let snapshot = view.to_arrow(ViewWindow::default()).await?;
+        let (local, status) = match mirror {
+            Some(mirror) => {
+                mirror.local.replace(UpdateData::Arrow(snapshot)).await?;
+                (mirror.local, mirror.status)
+            },
+            None => {
+                let local = self
+                    .create_table(&table, UpdateData::Arrow(snapshot))
+                    .await?;
+                (local, Arc::default())
+            },
+        };
+
+        let generation = {
+            let mut status = status.lock().unwrap();
+            status.generation += 1;
+            status.stale_since = None;
+            status.pending.clear();
+            status.updates_applied = 0;
+            status.size_mismatch = None;
+            status.generation
+        };
+
+        self.mirrors
+            .lock()
+            .unwrap()
+            .insert(name.to_owned(), Mirror {
+                local: local.clone(),
+                view: Some(view.clone()),
+                status: status.clone(),
+            });
+
+        let on_update = {
+            let status = status.clone();
+            move |update: ViewOnUpdateResp| {
+                let local = local.clone();
+                let status = status.clone();
+                async move {
+                    let Some(delta) = update.delta else {
+                        return;
+                    };
+
+                    let received = Instant::now();
+                    {
+                        let mut status = status.lock().unwrap();
+                        if status.generation != generation {
+                            return;
+                        }
+
+                        status.pending.push_back(received);
+                    }
+
+                    let result = local
+                        .update(UpdateData::Arrow(delta.into()), UpdateOptions::default())
+                        .await;
+
+                    let mut status = status.lock().unwrap();
+                    status.pending.pop_front();
+                    match result {
+                        Ok(()) => {
+                            status.updates_applied += 1;
+                            status.last_update = Some(Instant::now());
+                        },
+                        Err(err) => {
+                            tracing::error!("Failed to apply replicated update: {}", err);
+                            status.mark_stale(received);
+                        },
+                    }
+                }
+            }
+        };
+
+        view.on_update(on_update, OnUpdateOptions {
+            mode: Some(OnUpdateMode::Row),
+            ..OnUpdateOptions::default()
+        })
+        .await?;
+
+        // Deleting the remote `Table` requires deleting this `View` first, so
+        // only the former wakes the sync thread, lest it re-subscribe before
+        // the `Table` is deleted.
+        view.on_delete(self.on_delete(&status, generation, false))
+            .await?;
+        table
+            .on_delete(self.on_delete(&status, generation, true))
+            .await?;
+        Ok(())
+    }
+
+    /// Whether the `mirror` of the remote [`Table`] `name` must be re-synced:
+    /// because it is stale, or because its size has differed from the remote
+    /// one on two syncs in a row, with no update received in between. A
+    /// single mismatch may only be updates still in flight.
+    async fn is_diverged(
+        &self,
+        remote: &Client,
+        name: &str,
+        mirror: &Mirror,
+    ) -> ClientResult<bool> {
+        {
+            let status = mirror.status.lock().unwrap();
+            if status.stale_since.is_some() {
+                return Ok(true);
+            } else if !status.pending.is_empty() {
+                return Ok(false);
+            }
+        }
+
+        let remote_size = remote.open_table(name.to_owned()).await?.size().await?;
+        let local_size = mirror.local.size().await?;
+        let mut status = mirror.status.lock().unwrap();
+        if local_size == remote_size || !status.pending.is_empty() {
+            status.size_mismatch = None;
+            return Ok(false);
+        }
+
+        let updates_applied = status.updates_applied;
+        Ok(status.size_mismatch.replace(updates_applied) == Some(updates_applied))
+    }
+
+    /// Create the local copy of `remote`, which must not already exist: the
+    /// [`Replica`] does not overwrite the local [`Server`]'s own [`Table`]s.
+    async fn create_table(&self, remote: &Table, snapshot: UpdateData) -> ClientResult<Table> {
+        let name = remote.get_name().to_owned();
+        if self.local.get_hosted_table_names().await?.contains(&name) {
+            return Err(ClientError::Internal(format!(
+                "Table `{}` already exists on the replica server",
+                name
+            )));
+        }
+
+        let options = TableInitOptions {
+            name: Some(name),
+            index: remote.get_index(),
+            limit: remote.get_limit(),
+            format: None,
+        };
+
+        self.local.table(snapshot.into(), options).await
+    }
+
+    fn on_delete(
+        self: &Arc<Self>,
+        status: &Arc<Mutex<MirrorStatus>>,
+        generation: u64,
+        wake: bool,
+    ) -> Box<dyn Fn() + Send + Sync + 'static> {
+        let state = Arc::downgrade(self);
+        let status = status.clone();
+        Box::new(move || {
+            let mut status = status.lock().unwrap();
+            if status.generation == generation {
+                status.mark_stale(Instant::now());
+                if let Some(state) = state.upgrade().filter(|_| wake) {
+                    state.wake();
+                }
+            }
+        })
+    }
+}
+
+/// Replicates the hosted [`Table`]s of a remote [`Client`] to a local
+/// [`Server`], so that several [`Server`]s can host the same [`Table`]s.
+///
+/// Each remote [`Table`] is copied from a snapshot of a [`View`] of it, then
+/// kept up to date by applying that [`View`]'s `Row` mode updates, as
+/// [`perspective_client::TableData::View`] does. [`Replica::sync`] mirrors
+/// the [`Table`]s created and deleted since the last sync, and re-syncs any
+/// [`Table`] whose subscription was lost or whose size no longer matches on
+/// two syncs in a row with no update in between (e.g. after a
+/// [`Table::remove`]). Call it periodically, or use
+/// [`Replica::with_sync_interval`].
+///
+/// A remote [`Table`] is not replicated if the local [`Server`] already
+/// hosts a [`Table`] of the same name; [`Replica::sync`] fails instead.
+///
+/// A remote [`Table`] can only be deleted once the [`View`] replicating it
+/// is, e.g. with [`Client::delete_hosted_view`]. If the [`Table`] still
+/// exists on the next sync, it is subscribed to again.
+///
+/// The remote [`Client`] must be driven by its own transport (and the
+/// remote server polled), as the [`Replica`] only sends it requests.
+///
+/// Close a [`Replica`] with [`Replica::close`] once it is no longer needed.
+/// Dropping it instead is only a last resort, which logs an error.
+pub struct Replica {
+    state: Arc<ReplicaState>,
+    local: Option<LocalClient>,
+}
+
+impl Replica {
+    /// Create a [`Replica`] of the hosted [`Table`]s of `remote` on `server`,
+    /// and sync it once.
+    pub async fn new(server: &Server, remote: Client) -> ClientResult<Self> {
+        let local = LocalClient::new(server);
+        let replica = Replica {
+            state: Arc::new(ReplicaState {
+                local: (*local).clone(),
+                remote: Mutex::new(remote),
+                mirrors: Mutex::default(),
+                sync_lock: async_lock::Mutex::new(()),
+                signal: Mutex::default(),
+            }),
+            local: Some(local),
+        };
+
+        if let Err(err) = replica.sync().await {
+            replica.close().await;
+            return Err(err);
+        }
+
+        Ok(replica)
+    }
+
+    /// Call [`Replica::sync`] every `interval` on a dedicated thread, and as
+    /// soon as a replicated remote [`Table`] is deleted, until this
+    /// [`Replica`] is closed or dropped. A sync which fails marks every
+    /// [`Table`] stale, so [`Replica::lag`] keeps growing while the remote
+    /// [`Client`] is unreachable. Syncs run on the runtime of the calling
+    /// task, if any (see [`Server::with_poll_interval`]).
+    pub fn with_sync_interval(self, interval: Duration) -> Self {
+        let (signal, signals) = mpsc::channel();
+        let state = self.state.clone();
+        let executor = Executor::current();
+        thread::Builder::new()
+            .name("perspective-replica".to_owned())
+            .spawn(move || loop {
+                match signals.recv_timeout(interval) {
+                    Ok(Signal::Sync) | Err(RecvTimeoutError::Timeout) => {
+                        if let Err(err) = executor.block_on(state.sync()) {
+                            tracing::error!("Replica sync failed: {}", err);
+                        }
+                    },
+                    Ok(Signal::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+            })
+            .expect("Failed to spawn replica thread");
+
+        *self.state.signal.lock().unwrap() = Some(signal);
+        self
+    }
+
+    /// Mirror the remote [`Table`]s created since the last sync, delete the
+    /// local copies of those deleted, and re-sync the stale ones.
+    ///
+    /// A local [`Table`] which still has [`View`]s can't be deleted; it is
+    /// left stale and retried on the next sync.
+    pub async fn sync(&self) -> ClientResult<()> {
+        self.state.sync().await
+    }
+
+    /// Replace the remote [`Client`], e.g. after reconnecting, and re-sync
+    /// every [`Table`] from it. The subscriptions made through the previous
+    /// [`Client`] are ignored from now on, but not removed, as it is
+    /// presumably disconnected.
+    pub async fn resync(&self, remote: Client) -> ClientResult<()> {
+        let _guard = self.state.sync_lock.lock().await;
+        let now = Instant::now();
+        for mirror in self.state.mirrors.lock().unwrap().values_mut() {
+            mirror.status.lock().unwrap().mark_stale(now);
+            mirror.view = None;
+        }
+
+        *self.state.remote.lock().unwrap() = remote;
+        self.state.sync_locked().await
+    }
+
+    /// The [`ReplicationLag`] of each replicated [`Table`], by name.
+    pub fn lag(&self) -> HashMap<String, ReplicationLag> {
+        let now = Instant::now();
+        self.state
+            .mirrors
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, mirror)| (name.clone(), mirror.status.lock().unwrap().lag(now)))
+            .collect()
+    }
+
+    /// The names of the replicated [`Table`]s.
+    pub fn table_names(&self) -> Vec<String> {
+        self.state.mirrors.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Stop replicating, delete the remote [`View`]s this [`Replica`]
+    /// subscribes to, and close its session of the local [`Server`]. The
+    /// local [`Table`]s are kept, but no longer updated. Await this rather
+    /// than dropping the [`Replica`], which can only start the same teardown
+    /// in the background.
+    pub async fn close(mut self) {
+        self.stop();
+        teardown(self.state.clone(), self.local.take()).await;
+    }
+
+    fn stop(&self) {
+        if let Some(signal) = self.state.signal.lock().unwrap().take() {
+            let _ = signal.send(Signal::Stop);
+        }
+    }
+}
+
+/// The teardown of [`Replica::close`], which takes the `sync_lock` so that
+/// no sync re-subscribes to a remote [`Table`] afterwards.
+async fn teardown(state: Arc<ReplicaState>, local: Option<LocalClient>) {
+    let _guard = state.sync_lock.lock().await;
+    let views: Vec<View> = state
+        .mirrors
+        .lock()
+        .unwrap()
+        .values_mut()
+        .filter_map(|mirror| {
+            mirror.status.lock().unwrap().generation += 1;
+            mirror.view.take()
+        })
+        .collect();
+
+    for view in views {
+        if let Err(err) = view.delete().await {
+            tracing::debug!("Failed to delete replica view: {}", err);
+        }
+    }
+
+    if let Some(local) = local {
+        local.close().await;
+    }
+}
+
+/// A last resort for a [`Replica`] which was not closed with
+/// [`Replica::close`]: the sync thread is stopped at once, but the remote
+/// [`View`]s and the local session can only be torn down asynchronously, so
+/// that runs on a thread of its own, on the runtime of the dropping task if
+/// any, and nothing waits for it to finish.
+impl Drop for Replica {
+    fn drop(&mut self) {
+        self.stop();
+        if let Some(local) = self.local.take() {
+            tracing::error!("`Replica` dropped without `Replica::close`");
+            let state = self.state.clone();
+            let executor = Executor::current();
+            let spawned = thread::Builder::new()
+                .name("perspective-replica-close".to_owned())
+                .spawn(move || executor.block_on(teardown(state, Some(local))));
+
+            if let Err(err) = spawned {
+                tracing::error!("Failed to close dropped `Replica`: {}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::Request;
+#[cfg(doc)]
+use perspective_client::{Table, View};
+use prost::Message;
+
+/// Whether a request reads or writes the [`Table`] it addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LockMode {
+    /// Reads the [`Table`] or one of its [`View`]s, e.g. `to_arrow`. Runs
+    /// concurrently with other `Read` requests for the same [`Table`].
+    Read,
+
+    /// Changes the [`Table`], its pending updates, one of its [`View`]s or
+    /// their subscriptions, e.g. `update`, `expand` or `on_update`. Runs alone
+    /// on its [`Table`].
+    Write,
+}
+
+/// The lock a request must hold to run concurrently with the requests of
+/// other sessions on the same server. Requests for different [`Table`]s
+/// never contend with each other, except with [`RequestLock::Exclusive`]
+/// requests, which address every [`Table`] at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RequestLock {
+    /// Addresses the [`Table`] with this name.
+    Table(String, LockMode),
+
+    /// Addresses the [`View`] with this id, and so its [`Table`], which the
+    /// server must look up.
+    View(String, LockMode),
+
+    /// Reads only the server's own list of [`Table`]s, [`View`]s or
+    /// sessions, e.g. `get_hosted_tables`.
+    Shared,
+
+    /// Reads or changes every [`Table`], e.g. `system_info`, or could not be
+    /// decoded.
+    Exclusive,
+}
+
+impl RequestLock {
+    /// The lock of an encoded request.
+    pub fn of(request: &[u8]) -> Self {
+        let Ok(request) = Request::decode(request) else {
+            return RequestLock::Exclusive;
+        };
+
+        let Some(req) = request.client_req else {
+            return RequestLock::Exclusive;
+        };
+
+        let table = |mode| RequestLock::Table(request.entity_id.clone(), mode);
+        let view = |mode| RequestLock::View(request.entity_id.clone(), mode);
+        match req {
+            ClientReq::GetFeaturesReq(_)
+            | ClientReq::GetHostedTablesReq(_)
+            | ClientReq::GetHostedViewsReq(_)
+            | ClientReq::GetSessionsReq(_) => RequestLock::Shared,
+            ClientReq::ServerSystemInfoReq(_) | ClientReq::CloseSessionReq(_) => {
+                RequestLock::Exclusive
+            },
+            ClientReq::TableSizeReq(_)
+            | ClientReq::TableSchemaReq(_)
+            | ClientReq::TableValidateExprReq(_) => table(LockMode::Read),
+            ClientReq::MakeTableReq(_)
+            | ClientReq::TableMakePortReq(_)
+            | ClientReq::TableMakeViewReq(_)
+            | ClientReq::TableUpdateReq(_)
+            | ClientReq::TableReplaceReq(_)
+            | ClientReq::TableRemoveReq(_)
+            | ClientReq::TableDeleteReq(_)
+            | ClientReq::TableOnDeleteReq(_)
+            | ClientReq::TableRemoveDeleteReq(_) => table(LockMode::Write),
+            ClientReq::ViewColumnPathsReq(_)
+            | ClientReq::ViewDimensionsReq(_)
+            | ClientReq::ViewExpressionSchemaReq(_)
+            | ClientReq::ViewGetConfigReq(_)
+            | ClientReq::ViewSchemaReq(_)
+            | ClientReq::ViewToArrowReq(_)
+            | ClientReq::ViewGetMinMaxReq(_)
+            | ClientReq::ViewToColumnsStringReq(_)
+            | ClientReq::ViewToCsvReq(_)
+            | ClientReq::ViewToRowsStringReq(_)
+            | ClientReq::ViewToNdjsonStringReq(_)
+            | ClientReq::ViewGetExpansionStateReq(_)
+            | ClientReq::ViewFindRowByPathReq(_)
+            | ClientReq::ViewFindRowByIndexReq(_)
+            | ClientReq::ViewGetUnderlyingRowsReq(_) => view(LockMode::Read),
+            ClientReq::ViewDeleteReq(_)
+            | ClientReq::ViewCollapseReq(_)
+            | ClientReq::ViewExpandReq(_)
+            | ClientReq::ViewSetDepthReq(_)
+            | ClientReq::ViewSetExpansionStateReq(_)
+            | ClientReq::ViewSetUpdateViewportReq(_)
+            | ClientReq::ViewOnUpdateReq(_)
+            | ClientReq::ViewRemoveOnUpdateReq(_)
+            | ClientReq::ViewOnDeleteReq(_)
+            | ClientReq::ViewRemoveDeleteReq(_)
+            | ClientReq::DeleteHostedViewReq(_) => view(LockMode::Write),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use async_lock::RwLock;
+use futures::channel::oneshot;
+
+use crate::ffi;
+use crate::request_lock::{LockMode, RequestLock};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Runs the engine calls of a concurrent [`crate::Server`] on a pool of
+/// engine threads, locking each against the [`perspective_client::Table`]
+/// it addresses so that requests for different tables run in parallel.
+///
+/// Pending updates are processed by the request which queued them while it
+/// still holds its table's write lock, so tables are never dirty under a read
+/// lock and `Read` requests never process a table themselves.
+pub(crate) struct Scheduler {
+    server_lock: RwLock<()>,
+    poll_lock: async_lock::Mutex<()>,
+    table_locks: Mutex<HashMap<String, Arc<RwLock<()>>>>,
+    jobs: Mutex<mpsc::Sender<Job>>,
+}
+
+impl std::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler").finish()
+    }
+}
+
+impl Scheduler {
+    pub fn new(num_threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for idx in 0..num_threads.max(1) {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("perspective-engine-{}", idx))
+                .spawn(move || loop {
+                    // Exits when the `Scheduler`, and so `sender`, is dropped.
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+                .expect("Failed to spawn engine thread");
+        }
+
+        Scheduler {
+            server_lock: RwLock::default(),
+            poll_lock: async_lock::Mutex::default(),
+            table_locks: Mutex::default(),
+            jobs: Mutex::new(sender),
+        }
+    }
+
+    /// Run `f` on an engine thread, without blocking the calling executor.
+    async fn run<T, F>(&self, f: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let job = Box::new(move || {
+            let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(f)));
+        });
+
+        self.jobs
+            .lock()
+            .unwrap()
+            .send(job)
+            .expect("Engine threads stopped");
+
+        match receiver.await.expect("Engine thread stopped") {
+            Ok(result) => result,
+            Err(err) => panic::resume_unwind(err),
+        }
+    }
+
+    /// The lock of the table named `table_id`. Locks are kept for every table
+    /// ever addressed, so that a table re-created under the same name cannot
+    /// be locked twice.
+    fn table_lock(&self, table_id: &str) -> Arc<RwLock<()>> {
+        self.table_locks
+            .lock()
+            .unwrap()
+            .entry(table_id.to_owned())
+            .or_default()
+            .clone()
+    }
+
+    /// Handle a request of session `client_id` once the lock it needs is
+    /// free. `check` runs under the same lock, on the engine thread, and
+    /// returns the request to handle or the response which denies it.
+    /// Requests which update a table also return the `on_update` responses
+    /// of processing it.
+    pub async fn handle_request<F>(
+        &self,
+        server: &Arc<ffi::Server>,
+        client_id: u32,
+        request: &[u8],
+        check: F,
+    ) -> Result<Vec<ffi::ResponseBatch>, Vec<u8>>
+    where
+        F: FnOnce(&ffi::Server, &[u8]) -> Result<ffi::Request, Vec<u8>> + Send + 'static,
+    {
+        let engine = server.clone();
+        let request_lock = RequestLock::of(request);
+        let request = request.to_vec();
+        let handle = move || -> Result<ffi::ResponseBatch, Vec<u8>> {
+            let request = check(&engine, &request)?;
+            Ok(engine.handle_request(client_id, &request))
+        };
+
+        match request_lock {
+            RequestLock::Table(table_id, mode) => {
+                let _guard = self.server_lock.read().await;
+                self.run_on_table(server, table_id, mode, handle).await
+            },
+            RequestLock::View(view_id, mode) => {
+                // The view's table is looked up under `server_lock`, so that
+                // no `Exclusive` request can change it in between.
+                let guard = self.server_lock.read().await;
+                if let Some(table_id) = server.view_table_id(&view_id) {
+                    return self.run_on_table(server, table_id, mode, handle).await;
+                }
+
+                // Requests for views which do not exist only fail, but must
+                // still not race the request which deletes or creates them.
+                drop(guard);
+                let _guard = self.server_lock.write().await;
+                self.run(move || handle().map(|x| vec![x])).await
+            },
+            RequestLock::Shared => {
+                let _guard = self.server_lock.read().await;
+                self.run(move || handle().map(|x| vec![x])).await
+            },
+            RequestLock::Exclusive => {
+                let _guard = self.server_lock.write().await;
+                self.run(move || handle().map(|x| vec![x])).await
+            },
+        }
+    }
+
+    /// Run `handle` under the lock of the table named `table_id`, with
+    /// `server_lock` already held for reading.
+    async fn run_on_table<H>(
+        &self,
+        server: &Arc<ffi::Server>,
+        table_id: String,
+        mode: LockMode,
+        handle: H,
+    ) -> Result<Vec<ffi::ResponseBatch>, Vec<u8>>
+    where
+        H: FnOnce() -> Result<ffi::ResponseBatch, Vec<u8>> + Send + 'static,
+    {
+        let table_lock = self.table_lock(&table_id);
+        match mode {
+            LockMode::Read => {
+                let _table_guard = table_lock.read().await;
+                self.run(move || handle().map(|x| vec![x])).await
+            },
+            LockMode::Write => {
+                let _table_guard = table_lock.write().await;
+                let server = server.clone();
+                self.run(move || {
+                    handle().map(|responses| vec![responses, server.process_table(&table_id)])
+                })
+                .await
+            },
+        }
+    }
+
+    /// Flush throttled updates once no other request is writing a table.
+    /// Tables are processed by the requests which update them, so this is all
+    /// `poll` has left to do, but it must wait for those requests rather than
+    /// skip the flush, as updates throttled while a table is busy would
+    /// otherwise wait for a `poll` which may never come.
+    pub async fn poll(&self, server: &Arc<ffi::Server>) -> ffi::ResponseBatch {
+        let _poll_guard = self.poll_lock.lock().await;
+        let _guard = self.server_lock.read().await;
+        let table_locks = self
+            .table_locks
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // Each request holds at most one table lock, so these can be taken
+        // in any order without deadlocking against them.
+        let mut table_guards = Vec::with_capacity(table_locks.len());
+        for table_lock in table_locks.iter() {
+            table_guards.push(table_lock.read().await);
+        }
+
+        let server = server.clone();
+        self.run(move || server.poll()).await
+    }
+
+    /// Close a session, which deletes its views of every table.
+    pub async fn close_session(&self, server: &Arc<ffi::Server>, client_id: u32) {
+        let server = server.clone();
+        let _guard = self.server_lock.write().await;
+        self.run(move || server.close_session(client_id)).await
+    }
+}
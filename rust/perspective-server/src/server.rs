@@ -13,15 +13,20 @@
 use std::collections::HashMap;
 use std::error::Error;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_lock::RwLock;
 use futures::future::BoxFuture;
 use futures::Future;
 pub use perspective_client::Session;
+use perspective_client::{ColumnPolicies, SessionFilters};
 
 use crate::ffi;
 use crate::local_client::LocalClient;
-use crate::local_session::LocalSession;
+use crate::local_session::{LocalSession, SessionChecks};
+use crate::poller::Poller;
+use crate::quota::SessionQuotas;
+use crate::scheduler::Scheduler;
 
 pub type ServerError = Box<dyn Error + Send + Sync>;
 
@@ -49,6 +54,13 @@ pub trait SessionHandler: Send + Sync {
 pub struct Server {
     pub(crate) server: Arc<ffi::Server>,
     pub(crate) callbacks: Arc<RwLock<HashMap<u32, SessionCallback>>>,
+    pub(crate) scheduler: Option<Arc<Scheduler>>,
+
+    /// Serializes the engine calls of a [`Server`] which is not concurrent.
+    pub(crate) engine_lock: Arc<async_lock::Mutex<()>>,
+
+    pub(crate) poller: Option<Arc<Poller>>,
+    pub(crate) poll_on_update: bool,
 }
 
 impl std::fmt::Debug for Server {
@@ -63,11 +75,100 @@ impl Default for Server {
     fn default() -> Self {
         let server = Arc::new(ffi::Server::new());
         let callbacks = Arc::default();
-        Self { server, callbacks }
+        Self {
+            server,
+            callbacks,
+            scheduler: None,
+            engine_lock: Arc::default(),
+            poller: None,
+            poll_on_update: false,
+        }
     }
 }
 
 impl Server {
+    /// Create a [`Server`] which runs the requests of its [`Session`]s on a
+    /// pool of `num_threads` engine threads, rather than on the task which
+    /// calls [`Session::handle_request`], so that engine work never blocks
+    /// the async executor.
+    ///
+    /// Requests for different [`perspective_client::Table`]s (and their
+    /// [`perspective_client::View`]s) run in parallel, and read-only
+    /// requests such as [`perspective_client::View::to_arrow`] run
+    /// concurrently with each other on the same
+    /// [`perspective_client::Table`]. Requests which write a
+    /// [`perspective_client::Table`] or its subscriptions, such as
+    /// [`perspective_client::Table::update`] or
+    /// [`perspective_client::View::on_update`], run alone on it, and process
+    /// the update before they return rather than on the next
+    /// [`Session::poll`].
+    pub fn new_concurrent(num_threads: usize) -> Self {
+        Self {
+            scheduler: Some(Arc::new(Scheduler::new(num_threads))),
+            ..Self::default()
+        }
+    }
+
+    /// Flush this [`Server`] every `interval` on a dedicated thread, as
+    /// [`Session::poll`] does, so that the `on_update` callbacks of its
+    /// [`perspective_client::View`]s fire without any [`Session`] having to
+    /// call [`Session::poll`]. The updates made within each `interval` are
+    /// coalesced, so each [`perspective_client::Table`] is processed at most
+    /// once per `interval`, and throttled updates (see
+    /// [`LocalSession::set_update_throttle`]) are sent once they are due.
+    ///
+    /// Responses are sent from the poll thread, which stops when the last
+    /// clone of this [`Server`] is dropped. With the `tokio` feature, and
+    /// when called from within a tokio runtime, the [`SessionHandler`]
+    /// callbacks of the poll thread run on that runtime. Otherwise they run
+    /// on a plain executor, and must not depend on any async runtime.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        let server = Server {
+            poller: None,
+            ..self.clone()
+        };
+
+        self.poller = Some(Arc::new(Poller::new(server, interval)));
+        self
+    }
+
+    /// Flush this [`Server`] after each request of its [`Session`]s, as
+    /// [`Session::poll`] does, so that updates are sent to other
+    /// [`Session`]s as soon as they are made, without any [`Session`] having
+    /// to call [`Session::poll`]. Combine with [`Server::with_poll_interval`]
+    /// to also send throttled updates without waiting for the next request.
+    pub fn with_poll_on_update(mut self) -> Self {
+        self.poll_on_update = true;
+        self
+    }
+
+    /// Process the pending updates of every [`perspective_client::Table`],
+    /// and send the resulting responses to the [`Session`]s they are
+    /// addressed to.
+    pub(crate) async fn flush(&self) -> Result<(), ServerError> {
+        let responses = match &self.scheduler {
+            Some(scheduler) => scheduler.poll(&self.server).await,
+            None => {
+                let _engine = self.engine_lock.lock().await;
+                self.server.poll()
+            },
+        };
+
+        for response in responses.iter_responses() {
+            let cb = self
+                .callbacks
+                .read()
+                .await
+                .get(&response.client_id())
+                .cloned();
+            if let Some(f) = cb {
+                f(response.msg()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// An alternative method for creating a new [`Session`] for this
     /// [`Server`], from a callback closure instead of a via a trait.
     /// See [`Server::new_session`] for details.
@@ -92,6 +193,7 @@ impl Server {
             id,
             server,
             closed: false,
+            checks: SessionChecks::default(),
         }
     }
 
@@ -118,6 +220,54 @@ impl Server {
         .await
     }
 
+    /// Create a [`Session`] for this [`Server`], as [`Server::new_session`],
+    /// which can only read the rows of each [`perspective_client::Table`]
+    /// that pass its `filters`, e.g. to share one [`perspective_client::Table`]
+    /// between users who may each only see their own rows.
+    pub async fn new_session_with_filters<F>(
+        &self,
+        session_handler: F,
+        filters: SessionFilters,
+    ) -> LocalSession
+    where
+        F: SessionHandler + 'static + Sync + Send + Clone,
+    {
+        self.new_session(session_handler)
+            .await
+            .with_filters(filters)
+    }
+
+    /// Create a [`Session`] for this [`Server`], as [`Server::new_session`],
+    /// which cannot read the columns `policies` hide, and reads the columns
+    /// it masks as redacted or hashed strings, e.g. to share a
+    /// [`perspective_client::Table`] containing personal data.
+    pub async fn new_session_with_column_policies<F>(
+        &self,
+        session_handler: F,
+        policies: ColumnPolicies,
+    ) -> LocalSession
+    where
+        F: SessionHandler + 'static + Sync + Send + Clone,
+    {
+        self.new_session(session_handler)
+            .await
+            .with_column_policies(policies)
+    }
+
+    /// Create a [`Session`] for this [`Server`], as [`Server::new_session`],
+    /// whose resource use is limited by `quotas`, e.g. so that one browser
+    /// tab cannot exhaust a [`Server`] shared by many.
+    pub async fn new_session_with_quotas<F>(
+        &self,
+        session_handler: F,
+        quotas: SessionQuotas,
+    ) -> LocalSession
+    where
+        F: SessionHandler + 'static + Sync + Send + Clone,
+    {
+        self.new_session(session_handler).await.with_quotas(quotas)
+    }
+
     pub fn new_local_client(&self) -> LocalClient {
         LocalClient::new(self)
     }
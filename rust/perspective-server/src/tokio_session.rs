@@ -0,0 +1,136 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::ops::Deref;
+
+use perspective_client::Session;
+use tokio::sync::Mutex;
+
+use crate::ffi;
+use crate::local_session::LocalSession;
+use crate::server::ServerError;
+#[cfg(doc)]
+use crate::Server;
+
+/// A [`Session`] which runs the engine calls of a [`LocalSession`] on
+/// tokio's blocking thread pool, via [`tokio::task::spawn_blocking`], so
+/// that heavy requests such as [`perspective_client::View::to_arrow`] never
+/// block an async worker thread. Responses are still dispatched to the
+/// sessions' callbacks on the calling task.
+///
+/// The requests of one [`TokioSession`] are executed, and their responses
+/// dispatched, in the order [`Session::handle_request`] was called, even
+/// when it is called concurrently. The engine calls of different sessions
+/// of a [`Server`] run one at a time, unless the [`Server`] was created with
+/// [`Server::new_concurrent`], in which case they run on its own engine
+/// threads instead.
+///
+/// ```rust,ignore
+/// let session = TokioSession::new(server.new_session(handler).await);
+/// session.handle_request(&request).await?;
+/// ```
+pub struct TokioSession {
+    session: LocalSession,
+    order: Mutex<()>,
+}
+
+impl From<LocalSession> for TokioSession {
+    fn from(session: LocalSession) -> Self {
+        TokioSession::new(session)
+    }
+}
+
+impl Deref for TokioSession {
+    type Target = LocalSession;
+
+    fn deref(&self) -> &Self::Target {
+        &self.session
+    }
+}
+
+impl TokioSession {
+    pub fn new(session: LocalSession) -> Self {
+        TokioSession {
+            session,
+            order: Mutex::default(),
+        }
+    }
+
+    /// Flush the [`Server`] as [`Session::poll`], on the blocking thread pool.
+    async fn flush(&self) -> Result<(), ServerError> {
+        let server = &self.session.server;
+        if server.scheduler.is_some() {
+            return server.flush().await;
+        }
+
+        let engine_guard = server.engine_lock.lock_arc().await;
+        let engine = server.server.clone();
+        let responses = tokio::task::spawn_blocking(move || {
+            let _engine_guard = engine_guard;
+            engine.poll()
+        })
+        .await?;
+
+        self.session.dispatch_responses(None, &[responses]).await
+    }
+}
+
+impl Session<ServerError> for TokioSession {
+    async fn handle_request(&self, request: &[u8]) -> Result<(), ServerError> {
+        let _order = self.order.lock().await;
+        let server = &self.session.server;
+        let batches = if server.scheduler.is_some() {
+            self.session.execute(request).await
+        } else {
+            // The guard moves to the engine call, so that it is held until
+            // the call returns even if this future is dropped first. The
+            // session's checks read the engine, so they run under it too.
+            let engine_guard = server.engine_lock.lock_arc().await;
+            let engine = server.server.clone();
+            let checks = self.session.checks.clone();
+            let id = self.session.id;
+            let request = request.to_vec();
+            tokio::task::spawn_blocking(move || -> Result<Vec<ffi::ResponseBatch>, Vec<u8>> {
+                let _engine_guard = engine_guard;
+                let request = checks.check(&engine, id, &request)?;
+                Ok(vec![engine.handle_request(id, &request)])
+            })
+            .await?
+        };
+
+        let batches = match batches {
+            Ok(batches) => batches,
+            Err(response) => return self.session.send_response(&response).await,
+        };
+
+        self.session
+            .dispatch_responses(Some(request), &batches)
+            .await?;
+
+        if server.poll_on_update {
+            self.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn poll(&self) -> Result<(), ServerError> {
+        let _order = self.order.lock().await;
+        self.flush().await
+    }
+
+    async fn close(self) {
+        let TokioSession { session, order } = self;
+        let _order = order.lock().await;
+        session.close().await
+    }
+}
@@ -378,6 +378,7 @@ impl Component for ActiveColumn {
                     .callback(|event: MouseEvent| MouseEnter(event.which() == 0));
 
                 let is_expression = ctx.props().session.metadata().is_column_expression(&name);
+                let is_window = ctx.props().session.metadata().is_column_window(&name);
                 let mut class = ctx.props().renderer.metadata().mode.css();
                 if self.is_required {
                     class.push("required");
@@ -387,7 +388,7 @@ impl Component for ActiveColumn {
                     .props()
                     .can_render_column_styles(&name)
                     .unwrap_or_default();
-                let show_edit_btn = is_expression || can_render_styles;
+                let show_edit_btn = is_expression || is_window || can_render_styles;
 
                 html! {
                     <div
@@ -426,6 +427,7 @@ impl Component for ActiveColumn {
                                         name={name.clone()}
                                         on_open_expr_panel={&ctx.props().on_open_expr_panel}
                                         {is_expression}
+                                        {is_window}
                                         is_editing={ctx.props().is_editing}
                                     />
                                 }
@@ -129,27 +129,53 @@ impl AggregateSelector {
             .expect("Bad Aggs")
             .collect::<Vec<_>>();
 
-        let multi_aggregates = aggregates
+        let multi_aggregates2 = MultiAggregate::iter_all()
+            .filter_map(|agg| {
+                let items = aggregates
+                    .iter()
+                    .filter(|x| matches!(x, Aggregate::MultiAggregate(y, _) if *y == agg))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                (!items.is_empty()).then(|| SelectItem::OptGroup(agg.to_string().into(), items))
+            })
+            .collect::<Vec<_>>();
+
+        // A `percentile` set via the API may not be one of the presets, so
+        // make sure the current selection is listed.
+        let mut percentiles = aggregates
             .iter()
-            .filter(|x| matches!(x, Aggregate::MultiAggregate(_, _)))
+            .filter(|x| matches!(x, Aggregate::Percentile(_)))
             .cloned()
             .collect::<Vec<_>>();
 
-        let multi_aggregates2 = if !multi_aggregates.is_empty() {
-            vec![SelectItem::OptGroup(
-                "weighted mean".into(),
-                multi_aggregates,
-            )]
+        if let Some(aggregate @ Aggregate::Percentile(_)) = &ctx.props().aggregate {
+            if !percentiles.contains(aggregate) {
+                percentiles.push(aggregate.clone());
+            }
+        }
+
+        let percentiles2 = if !percentiles.is_empty() {
+            vec![SelectItem::OptGroup("percentile".into(), percentiles)]
         } else {
             vec![]
         };
 
+        // `custom` aggregates can only be set via the API, but should still
+        // display when selected.
+        let custom = match &ctx.props().aggregate {
+            Some(aggregate @ Aggregate::Custom(_)) => Some(SelectItem::Option(aggregate.clone())),
+            _ => None,
+        };
+
         let s = aggregates
             .iter()
             .filter(|x| matches!(x, Aggregate::SingleAggregate(_)))
             .cloned()
             .map(SelectItem::Option)
-            .chain(multi_aggregates2);
+            .chain(percentiles2)
+            .chain(multi_aggregates2)
+            .chain(custom);
 
         s.collect::<Vec<_>>()
     }
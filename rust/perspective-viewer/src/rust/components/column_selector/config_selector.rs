@@ -19,6 +19,7 @@ use yew::prelude::*;
 use super::filter_column::*;
 use super::pivot_column::*;
 use super::sort_column::*;
+use super::totals_selector::*;
 use super::InPlaceColumn;
 use crate::components::containers::dragdrop_list::*;
 use crate::components::style::LocalStyle;
@@ -69,6 +70,8 @@ pub enum ConfigSelectorMsg {
     Close(usize, DragTarget),
     SetFilterValue(usize, String),
     TransposePivots,
+    SetTotals(Totals),
+    SetSplitByOrder(String, Option<SplitByOrder>),
     ViewCreated,
     New(DragTarget, InPlaceColumn),
 }
@@ -309,12 +312,37 @@ impl Component for ConfigSelector {
                 ctx.props().onselect.emit(());
                 false
             },
+            ConfigSelectorMsg::SetTotals(totals) => {
+                let update = ViewConfigUpdate {
+                    totals: Some(totals),
+                    ..ViewConfigUpdate::default()
+                };
+
+                ApiFuture::spawn(ctx.props().update_and_render(update));
+                false
+            },
+            ConfigSelectorMsg::SetSplitByOrder(column, order) => {
+                let mut split_by_order =
+                    ctx.props().session.get_view_config().split_by_order.clone();
+                match order {
+                    Some(order) => split_by_order.insert(column, order),
+                    None => split_by_order.remove(&column),
+                };
+
+                let update = ViewConfigUpdate {
+                    split_by_order: Some(split_by_order),
+                    ..ViewConfigUpdate::default()
+                };
+
+                ApiFuture::spawn(ctx.props().update_and_render(update));
+                false
+            },
             ConfigSelectorMsg::SetFilterValue(index, input) => {
                 let mut filter = ctx.props().session.get_view_config().filter.clone();
 
                 // TODO Can't special case these - need to make this part of the
                 // Features API.
-                let update = if filter[index].op() == "in" || filter[index].op() == "not in" {
+                let update = if filter[index].is_list_op() {
                     let current = filter[index].term().to_string();
                     let mut tokens = current.split(',').collect::<Vec<_>>();
                     tokens.pop();
@@ -476,6 +504,10 @@ impl Component for ConfigSelector {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let config = ctx.props().session.get_view_config();
         let transpose = ctx.link().callback(|_| ConfigSelectorMsg::TransposePivots);
+        let on_totals = ctx.link().callback(ConfigSelectorMsg::SetTotals);
+        let on_order = ctx
+            .link()
+            .callback(|(column, order)| ConfigSelectorMsg::SetSplitByOrder(column, order));
         let column_dropdown = self.column_dropdown.clone();
         let class = if ctx.props().dragdrop.get_drag_column().is_some() {
             "dragdrop-highlight"
@@ -518,6 +550,12 @@ impl Component for ConfigSelector {
                             }
                         }) }
                 </GroupBySelector>
+                if !config.group_by.is_empty() {
+                    <TotalsSelector
+                        totals={config.totals.clone()}
+                        on_change={on_totals}
+                    />
+                }
                 if !config.split_by.is_empty() {
                     <span
                         id="transpose_button"
@@ -540,6 +578,8 @@ impl Component for ConfigSelector {
                                     dragdrop={ &ctx.props().dragdrop }
                                     session={ &ctx.props().session }
                                     action={ DragTarget::SplitBy }
+                                    order={ config.split_by_order.get(split_by).cloned() }
+                                    on_order={ on_order.clone() }
                                     column={ split_by.clone() }>
                                 </PivotColumn>
                             }
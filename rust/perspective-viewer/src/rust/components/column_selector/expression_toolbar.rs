@@ -18,6 +18,8 @@ use super::ColumnLocator;
 pub struct ExprEditButtonProps {
     pub name: String,
     pub is_expression: bool,
+    #[prop_or_default]
+    pub is_window: bool,
     pub on_open_expr_panel: Callback<ColumnLocator>,
     pub is_editing: bool,
 }
@@ -29,6 +31,8 @@ pub fn ExprEditButton(p: &ExprEditButtonProps) -> Html {
     let onmousedown = yew::use_callback(p.clone(), |_, p| {
         let name = if p.is_expression {
             ColumnLocator::Expression(p.name.clone())
+        } else if p.is_window {
+            ColumnLocator::Window(p.name.clone())
         } else {
             ColumnLocator::Table(p.name.clone())
         };
@@ -77,10 +77,7 @@ impl FilterColumnProps {
     fn is_suggestable(&self) -> bool {
         // TODO This needs to be moved to Features API. Or ... we just do this
         // all string column type filters, or otherwise "fix" this in the UI?
-        (self.filter.op() == "=="
-            || self.filter.op() == "!="
-            || self.filter.op() == "in"
-            || self.filter.op() == "not in")
+        (self.filter.op() == "==" || self.filter.op() == "!=" || self.filter.is_list_op())
             && self.get_filter_type() == Some(ColumnType::String)
     }
 
@@ -152,7 +149,7 @@ impl FilterColumnProps {
         let filter_column = &mut filter.get_mut(self.idx).expect("Filter on no column");
 
         // TODO This belongs in the Features API.
-        let filter_input = if filter_column.op() == "in" || filter_column.op() == "not in" {
+        let filter_input = if filter_column.is_list_op() {
             Some(FilterTerm::Array(
                 val.split(',')
                     .map(|x| Scalar::String(x.trim().to_owned()))
@@ -254,7 +251,7 @@ impl Component for FilterColumn {
                 if ctx.props().is_suggestable() {
                     ctx.props().filter_dropdown.autocomplete(
                         column,
-                        if ctx.props().filter.op() == "in" || ctx.props().filter.op() == "not in" {
+                        if ctx.props().filter.is_list_op() {
                             input.split(',').last().unwrap().to_owned()
                         } else {
                             input.clone()
@@ -176,6 +176,12 @@ impl Component for InactiveColumn {
             .metadata()
             .is_column_expression(&ctx.props().name);
 
+        let is_window = ctx
+            .props()
+            .session
+            .metadata()
+            .is_column_window(&ctx.props().name);
+
         let is_active_class = ctx.props().renderer.metadata().mode.css();
         let mut class = classes!("column-selector-column");
         if !ctx.props().visible {
@@ -200,11 +206,12 @@ impl Component for InactiveColumn {
                         <TypeIcon ty={col_type} />
                         <span class="column_name">{ ctx.props().name.clone() }</span>
                         <span class="column-selector--spacer" />
-                        if is_expression {
+                        if is_expression || is_window {
                             <ExprEditButton
                                 name={ctx.props().name.clone()}
                                 on_open_expr_panel={&ctx.props().on_open_expr_panel}
-                                is_expression=true
+                                {is_expression}
+                                {is_window}
                                 is_editing={ctx.props().is_editing}
                             />
                         }
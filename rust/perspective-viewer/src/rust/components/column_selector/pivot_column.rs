@@ -11,6 +11,7 @@
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
 // use perspective_client::ColumnType;
+use perspective_client::config::*;
 use web_sys::*;
 use yew::prelude::*;
 
@@ -27,11 +28,41 @@ pub struct PivotColumnProps {
     pub column: String,
     pub dragdrop: DragDrop,
     pub action: DragTarget,
+
+    /// The `split_by_order` of this column, editable when `on_order` is set.
+    #[prop_or_default]
+    pub order: Option<SplitByOrder>,
+
+    #[prop_or_default]
+    pub on_order: Option<Callback<(String, Option<SplitByOrder>)>>,
 }
 
 impl PartialEq for PivotColumnProps {
     fn eq(&self, other: &Self) -> bool {
-        self.column == other.column && self.action == other.action
+        self.column == other.column && self.action == other.action && self.order == other.order
+    }
+}
+
+/// Parses the text of the order input, which is either `"natural"` or a
+/// comma-separated list of values. Empty text resets the order.
+fn parse_order(text: &str) -> Option<SplitByOrder> {
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else if text == "natural" {
+        Some(SplitByOrder::Natural(NaturalOrder::Natural))
+    } else {
+        Some(SplitByOrder::Values(
+            text.split(',').map(|x| x.trim().to_owned()).collect(),
+        ))
+    }
+}
+
+fn format_order(order: &Option<SplitByOrder>) -> String {
+    match order {
+        None => "".to_owned(),
+        Some(SplitByOrder::Natural(_)) => "natural".to_owned(),
+        Some(SplitByOrder::Values(values)) => values.join(", "),
     }
 }
 
@@ -70,6 +101,26 @@ impl Component for PivotColumn {
             .get_column_table_type(&ctx.props().column)
             .expect("Unknown column");
 
+        let order_input = ctx.props().on_order.as_ref().map(|on_order| {
+            let onchange = on_order.reform({
+                let column = ctx.props().column.clone();
+                move |event: Event| {
+                    let text = event.target_unchecked_into::<HtmlInputElement>().value();
+                    (column.clone(), parse_order(&text))
+                }
+            });
+
+            html! {
+                <input
+                    class="split-by-order"
+                    placeholder="Order"
+                    title="\"natural\", or a comma-separated list of values"
+                    value={format_order(&ctx.props().order)}
+                    {onchange}
+                />
+            }
+        });
+
         html! {
             <div
                 class="pivot-column-draggable"
@@ -81,6 +132,7 @@ impl Component for PivotColumn {
                     <TypeIcon ty={col_type} />
                     // <TypeIcon ty={ColumnType::String} />
                     <span class="column_name">{ ctx.props().column.clone() }</span>
+                    { order_input }
                 </div>
             </div>
         }
@@ -0,0 +1,90 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use perspective_client::config::*;
+use web_sys::*;
+use yew::prelude::*;
+
+use crate::components::containers::select::*;
+
+#[derive(PartialEq, Properties)]
+pub struct TotalsSelectorProps {
+    pub totals: Totals,
+    pub on_change: Callback<Totals>,
+}
+
+/// Controls for the grand total and subtotal rows of a `group_by` view, which
+/// can be hidden individually or moved below the rows they total.
+#[function_component]
+pub fn TotalsSelector(props: &TotalsSelectorProps) -> Html {
+    let on_position = yew::use_callback(
+        (props.totals.clone(), props.on_change.clone()),
+        |position, (totals, on_change)| {
+            on_change.emit(Totals {
+                position,
+                ..totals.clone()
+            })
+        },
+    );
+
+    let on_grand_total = yew::use_callback(
+        (props.totals.clone(), props.on_change.clone()),
+        |event: InputEvent, (totals, on_change)| {
+            on_change.emit(Totals {
+                grand_total: event.target_unchecked_into::<HtmlInputElement>().checked(),
+                ..totals.clone()
+            })
+        },
+    );
+
+    let on_subtotals = yew::use_callback(
+        (props.totals.clone(), props.on_change.clone()),
+        |event: InputEvent, (totals, on_change)| {
+            on_change.emit(Totals {
+                subtotals: event.target_unchecked_into::<HtmlInputElement>().checked(),
+                ..totals.clone()
+            })
+        },
+    );
+
+    let values = vec![
+        SelectItem::Option(TotalsPosition::Top),
+        SelectItem::Option(TotalsPosition::Bottom),
+    ];
+
+    html! {
+        <div id="totals_selector" class="rrow">
+            <label class="totals-toggle">
+                <input
+                    type="checkbox"
+                    checked={props.totals.grand_total}
+                    oninput={on_grand_total}
+                />
+                { "Grand Total" }
+            </label>
+            <label class="totals-toggle">
+                <input
+                    type="checkbox"
+                    checked={props.totals.subtotals}
+                    oninput={on_subtotals}
+                />
+                { "Subtotals" }
+            </label>
+            <Select<TotalsPosition>
+                id="totals_position"
+                {values}
+                selected={props.totals.position}
+                on_select={on_position}
+            />
+        </div>
+    }
+}
@@ -14,10 +14,12 @@ use yew::{function_component, html, Html, Properties};
 
 use super::save_settings::{SaveSettings, SaveSettingsProps};
 use crate::components::expression_editor::{ExpressionEditor, ExpressionEditorProps};
+use crate::components::window_editor::{WindowEditor, WindowEditorProps};
 
 #[derive(PartialEq, Properties, Clone)]
 pub struct AttributesTabProps {
     pub expr_editor: ExpressionEditorProps,
+    pub window_editor: Option<WindowEditorProps>,
     pub save_section: SaveSettingsProps,
 }
 
@@ -26,7 +28,11 @@ pub fn AttributesTab(p: &AttributesTabProps) -> Html {
     html! {
         <div id="attributes-tab">
             <div class="tab-section" id="attributes-expr">
-                <ExpressionEditor ..p.expr_editor.clone() />
+                if let Some(window_editor) = &p.window_editor {
+                    <WindowEditor ..window_editor.clone() />
+                } else {
+                    <ExpressionEditor ..p.expr_editor.clone() />
+                }
             </div>
             <div class="tab-section"><SaveSettings ..p.save_section.clone() /></div>
         </div>
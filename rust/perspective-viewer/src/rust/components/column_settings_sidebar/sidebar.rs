@@ -31,6 +31,7 @@ use crate::components::expression_editor::ExpressionEditorProps;
 use crate::components::style::LocalStyle;
 use crate::components::type_icon::TypeIconType;
 use crate::components::viewer::ColumnLocator;
+use crate::components::window_editor::WindowEditorProps;
 use crate::custom_events::CustomEvents;
 use crate::model::*;
 use crate::presentation::Presentation;
@@ -131,15 +132,23 @@ impl ColumnSettingsSidebar {
             .props()
             .selected_column
             .name_or_default(&ctx.props().session);
-        let initial_expr_value = ctx
-            .props()
-            .session
-            .metadata()
-            .get_expression_by_alias(&column_name)
-            .unwrap_or_default();
-        let initial_expr_value = Rc::new(initial_expr_value);
+        let is_window = ctx.props().selected_column.is_window();
+        let initial_expr_value = if is_window {
+            ctx.props()
+                .session
+                .metadata()
+                .get_window(&column_name)
+                .and_then(|window| serde_json::to_string_pretty(&window).ok())
+        } else {
+            ctx.props()
+                .session
+                .metadata()
+                .get_expression_by_alias(&column_name)
+        };
+
+        let initial_expr_value = Rc::new(initial_expr_value.unwrap_or_default());
         let initial_header_value =
-            (*initial_expr_value != column_name).then_some(column_name.clone());
+            (is_window || *initial_expr_value != column_name).then_some(column_name.clone());
         let maybe_ty = ctx.props().selected_column.view_type(ctx.props().session());
 
         let tabs = {
@@ -155,7 +164,7 @@ impl ColumnSettingsSidebar {
                 tabs.push(ColumnSettingsTab::Style);
             }
 
-            if ctx.props().selected_column.is_expr() {
+            if ctx.props().selected_column.is_expr() || is_window {
                 tabs.push(ColumnSettingsTab::Attributes);
             }
             tabs
@@ -279,6 +288,14 @@ impl Component for ColumnSettingsSidebar {
                         ctx.props().update_expr(name.clone(), new_expr)
                     },
                     ColumnLocator::NewExpression => ctx.props().save_expr(new_expr),
+                    ColumnLocator::Window(name) => match serde_json::from_str(&self.expr_value) {
+                        Ok(window) => {
+                            let new_name =
+                                self.header_value.clone().unwrap_or_else(|| name.clone());
+                            ctx.props().update_window(name, new_name, window)
+                        },
+                        Err(err) => tracing::error!("Invalid window: {}", err),
+                    },
                 }
 
                 self.initial_expr_value.clone_from(&self.expr_value);
@@ -291,6 +308,8 @@ impl Component for ColumnSettingsSidebar {
             ColumnSettingsMsg::OnDelete(()) => {
                 if ctx.props().selected_column.is_saved_expr() {
                     ctx.props().delete_expr(&self.column_name);
+                } else if ctx.props().selected_column.is_window() {
+                    ctx.props().delete_window(&self.column_name);
                 }
                 ctx.props().on_close.emit(());
                 true
@@ -318,7 +337,8 @@ impl Component for ColumnSettingsSidebar {
                     ColumnSettingsMsg::SetHeaderValid(valid),
                 ]
             }),
-            editable: ctx.props().selected_column.is_expr()
+            editable: (ctx.props().selected_column.is_expr()
+                || ctx.props().selected_column.is_window())
                 && matches!(self.selected_tab, ColumnSettingsTab::Attributes),
             initial_value: self.initial_header_value.clone(),
             placeholder: self.expr_value.clone(),
@@ -343,12 +363,27 @@ impl Component for ColumnSettingsSidebar {
             on_reset: ctx.link().callback(ColumnSettingsMsg::OnResetAttributes),
             on_save: ctx.link().callback(ColumnSettingsMsg::OnSaveAttributes),
             on_delete: ctx.link().callback(ColumnSettingsMsg::OnDelete),
-            show_danger_zone: ctx.props().selected_column.is_saved_expr(),
+            show_danger_zone: ctx.props().selected_column.is_saved_expr()
+                || ctx.props().selected_column.is_window(),
             disable_delete: ctx.props().is_active,
         };
 
+        let window_editor = ctx
+            .props()
+            .selected_column
+            .is_window()
+            .then(|| WindowEditorProps {
+                session: ctx.props().session.clone(),
+                on_input: self.on_input.clone(),
+                on_save: self.on_save.clone(),
+                on_validate: self.on_validate.clone(),
+                name: ctx.props().selected_column.name().cloned(),
+                reset_count: self.reset_count,
+            });
+
         let attrs_tab = AttributesTabProps {
             expr_editor,
+            window_editor,
             save_section,
         };
 
@@ -35,6 +35,7 @@ pub mod string_column_style;
 pub mod style;
 pub mod type_icon;
 pub mod viewer;
+pub mod window_editor;
 
 pub mod column_settings_sidebar;
 pub mod style_controls;
@@ -44,6 +44,7 @@ use crate::*;
 pub enum ColumnLocator {
     Table(String),
     Expression(String),
+    Window(String),
     NewExpression,
 }
 impl ColumnLocator {
@@ -52,14 +53,14 @@ impl ColumnLocator {
     /// function will return None.
     pub fn name(&self) -> Option<&String> {
         match self {
-            Self::Table(s) | Self::Expression(s) => Some(s),
+            Self::Table(s) | Self::Expression(s) | Self::Window(s) => Some(s),
             Self::NewExpression => None,
         }
     }
 
     pub fn name_or_default(&self, session: &Session) -> String {
         match self {
-            Self::Table(s) | Self::Expression(s) => s.clone(),
+            Self::Table(s) | Self::Expression(s) | Self::Window(s) => s.clone(),
             Self::NewExpression => session.metadata().make_new_column_name(None),
         }
     }
@@ -83,6 +84,11 @@ impl ColumnLocator {
         )
     }
 
+    #[inline(always)]
+    pub fn is_window(&self) -> bool {
+        matches!(self, ColumnLocator::Window(_))
+    }
+
     #[inline(always)]
     pub fn is_new_expr(&self) -> bool {
         matches!(self, ColumnLocator::NewExpression)
@@ -0,0 +1,176 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::rc::Rc;
+
+use perspective_client::config::{Sort, Window};
+use perspective_client::ExprValidationError;
+use yew::prelude::*;
+
+use super::form::code_editor::*;
+use super::style::LocalStyle;
+use crate::session::Session;
+use crate::*;
+
+#[derive(Debug)]
+pub enum WindowEditorMsg {
+    SetExpr(Rc<String>),
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct WindowEditorProps {
+    pub session: Session,
+    pub on_save: Callback<()>,
+    pub on_validate: Callback<bool>,
+    pub on_input: Callback<Rc<String>>,
+    pub name: Option<String>,
+    #[prop_or_default]
+    pub reset_count: u8,
+}
+
+impl WindowEditorProps {
+    fn initial_expr(&self) -> Rc<String> {
+        self.name
+            .as_ref()
+            .and_then(|name| self.session.metadata().get_window(name))
+            .and_then(|window| serde_json::to_string_pretty(&window).ok())
+            .unwrap_or_default()
+            .into()
+    }
+
+    /// Parse and check a JSON `Window` definition against the `Table`'s
+    /// columns, returning the first error.
+    fn validate(&self, expr: &str) -> Option<ExprValidationError> {
+        let window = match serde_json::from_str::<Window>(expr) {
+            Ok(window) => window,
+            Err(err) => {
+                return Some(ExprValidationError {
+                    error_message: err.to_string(),
+                    line: err.line().saturating_sub(1) as u32,
+                    column: err.column().saturating_sub(1) as u32,
+                })
+            },
+        };
+
+        let metadata = self.session.metadata();
+        let is_table_column = |column: &String| {
+            metadata.get_column_table_type(column).is_some() && !metadata.is_column_window(column)
+        };
+
+        let error_message = match &window.column {
+            None if window.function.has_column() => {
+                Some(format!("`{}` requires a `column`", window.function))
+            },
+            Some(_) if !window.function.has_column() => {
+                Some(format!("`{}` does not take a `column`", window.function))
+            },
+            Some(column) if !is_table_column(column) => {
+                Some(format!("Unknown column \"{column}\""))
+            },
+            Some(column) => metadata
+                .get_column_table_type(column)
+                .filter(|col_type| !window.function.supports(*col_type))
+                .map(|col_type| {
+                    format!(
+                        "`{}` not supported for column of type `{}`",
+                        window.function, col_type
+                    )
+                }),
+            None => None,
+        }
+        .or_else(|| {
+            window
+                .partition_by
+                .iter()
+                .chain(window.order_by.iter().map(|Sort(column, _)| column))
+                .find(|column| !is_table_column(column))
+                .map(|column| format!("Unknown column \"{column}\""))
+        });
+
+        error_message.map(|error_message| ExprValidationError {
+            error_message,
+            line: 0,
+            column: 0,
+        })
+    }
+}
+
+/// Window editor component, a `CodeEditor` for the JSON `Window` definition
+/// of a window column.
+pub struct WindowEditor {
+    expr: Rc<String>,
+    error: Option<ExprValidationError>,
+    oninput: Callback<Rc<String>>,
+}
+
+impl Component for WindowEditor {
+    type Message = WindowEditorMsg;
+    type Properties = WindowEditorProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let oninput = ctx.link().callback(WindowEditorMsg::SetExpr);
+        let expr = ctx.props().initial_expr();
+        ctx.link()
+            .send_message(Self::Message::SetExpr(expr.clone()));
+        Self {
+            error: None,
+            expr,
+            oninput,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            WindowEditorMsg::SetExpr(val) => {
+                ctx.props().on_input.emit(val.clone());
+                self.error = ctx.props().validate(&val);
+                self.expr = val;
+                ctx.props().on_validate.emit(self.error.is_none());
+                true
+            },
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <>
+                <LocalStyle href={css!("expression-editor")} />
+                <label class="item_title">{ "Window" }</label>
+                <div id="editor-container">
+                    <CodeEditor
+                        autofocus=true
+                        expr={&self.expr}
+                        error={self.error.clone()}
+                        disabled=false
+                        oninput={self.oninput.clone()}
+                        onsave={ctx.props().on_save.clone()}
+                    />
+                    <div id="psp-expression-editor-meta">
+                        <div class="error">
+                            { &self.error.clone().map(|e| e.error_message).unwrap_or_default() }
+                        </div>
+                    </div>
+                </div>
+            </>
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().name != old_props.name || ctx.props().reset_count != old_props.reset_count {
+            ctx.link()
+                .send_message(WindowEditorMsg::SetExpr(ctx.props().initial_expr()));
+            false
+        } else {
+            true
+        }
+    }
+}
@@ -63,6 +63,9 @@ pub struct ViewerConfig {
     pub theme: Option<String>,
     pub title: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expansion_state: Option<ExpansionState>,
+
     #[serde(flatten)]
     pub view_config: ViewConfig,
 }
@@ -78,9 +81,13 @@ type ViewerConfigBinarySerialFormat<'a> = (
     &'a Option<String>,
     &'a Option<String>,
     &'a ViewConfig,
+    &'a Option<ExpansionState>,
 );
 
-type ViewerConfigBinaryDeserialFormat = (
+// Fields added after the initial format must be `#[serde(default)]`, so tokens
+// saved by earlier versions still decode.
+#[derive(Deserialize)]
+struct ViewerConfigBinaryDeserialFormat(
     VersionUpdate,
     ColumnConfigUpdate,
     PluginUpdate,
@@ -89,6 +96,7 @@ type ViewerConfigBinaryDeserialFormat = (
     ThemeUpdate,
     TitleUpdate,
     ViewConfigUpdate,
+    #[serde(default)] ExpansionStateUpdate,
 );
 
 pub static API_VERSION: LazyLock<&'static str> = LazyLock::new(|| {
@@ -112,6 +120,7 @@ impl ViewerConfig {
             &self.theme,
             &self.title,
             &self.view_config,
+            &self.expansion_state,
         )
     }
 
@@ -180,7 +189,7 @@ impl Deref for PluginConfig {
     }
 }
 
-#[derive(Clone, Deserialize, TS)]
+#[derive(Clone, Default, Deserialize, TS)]
 // #[serde(deny_unknown_fields)]
 pub struct ViewerConfigUpdate {
     #[serde(default)]
@@ -218,13 +227,28 @@ pub struct ViewerConfigUpdate {
     #[ts(optional)]
     pub columns_config: ColumnConfigUpdate,
 
+    #[serde(default)]
+    #[ts(as = "Option<_>")]
+    #[ts(optional)]
+    pub expansion_state: ExpansionStateUpdate,
+
     #[serde(flatten)]
     pub view_config: ViewConfigUpdate,
 }
 
 impl ViewerConfigUpdate {
     fn from_token(
-        (version, columns_config, plugin, plugin_config, settings, theme, title, view_config): ViewerConfigBinaryDeserialFormat,
+        ViewerConfigBinaryDeserialFormat(
+            version,
+            columns_config,
+            plugin,
+            plugin_config,
+            settings,
+            theme,
+            title,
+            view_config,
+            expansion_state,
+        ): ViewerConfigBinaryDeserialFormat,
     ) -> ViewerConfigUpdate {
         ViewerConfigUpdate {
             version,
@@ -234,6 +258,7 @@ impl ViewerConfigUpdate {
             settings,
             theme,
             title,
+            expansion_state,
             view_config,
         }
     }
@@ -303,6 +328,7 @@ pub type ThemeUpdate = OptionalUpdate<String>;
 pub type TitleUpdate = OptionalUpdate<String>;
 pub type VersionUpdate = OptionalUpdate<String>;
 pub type ColumnConfigUpdate = OptionalUpdate<HashMap<String, ColumnConfigValues>>;
+pub type ExpansionStateUpdate = OptionalUpdate<ExpansionState>;
 
 /// Handles `{}` when included as a field with `#[serde(default)]`.
 impl<T: Clone> Default for OptionalUpdate<T> {
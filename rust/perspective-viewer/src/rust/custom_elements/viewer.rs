@@ -604,6 +604,39 @@ impl PerspectiveViewerElement {
         Ok(())
     }
 
+    /// Replace this viewer's [`View`] with a flat [`View`] of the source rows
+    /// which were aggregated into one of its cells, as selected by
+    /// [`View::get_underlying_config`]. Other settings, such as the plugin and
+    /// column styles, are kept.
+    ///
+    /// # Arguments
+    ///
+    /// - `row_index` - The row index of the cell in the current [`View`].
+    /// - `column_path` - The column path of the cell, as returned by
+    ///   [`View::column_paths`] and split on `|`, or `[]` for the whole row.
+    ///
+    /// # JavaScript Examples
+    ///
+    /// ```javascript
+    /// await viewer.showUnderlyingRows(3, ["Furniture", "Sales"]);
+    /// ```
+    #[wasm_bindgen]
+    pub fn showUnderlyingRows(&self, row_index: u32, column_path: JsValue) -> ApiFuture<()> {
+        let this = self.clone();
+        ApiFuture::new(async move {
+            let column_path = column_path.into_serde_ext()?;
+            let view = this.session.get_view().ok_or("No table set")?;
+            let view_config = view.get_underlying_config(row_index, column_path).await?;
+            let update = ViewerConfigUpdate {
+                view_config,
+                ..ViewerConfigUpdate::default()
+            };
+
+            this.restore_and_render(update, async { Ok(()) }).await?;
+            Ok(())
+        })
+    }
+
     /// Get this viewer's edit port for the currently loaded [`Table`] (see
     /// [`Table::update`] for details on ports).
     #[wasm_bindgen]
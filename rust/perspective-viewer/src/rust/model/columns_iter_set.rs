@@ -62,7 +62,8 @@ type Label = Option<String>;
 ///
 /// Encapsulates the logic of determining which columns go in the "Active" and
 /// "Inactive" column sections of the `ColumnSelector` component, via the
-/// iterator returning functions `active()`, `inactive()` and `expression()`.
+/// iterator returning functions `active()`, `inactive()`, `expression()` and
+/// `window()`.
 pub struct ColumnsIteratorSet<'a> {
     config: &'a ViewConfig,
     session: &'a Session,
@@ -122,7 +123,8 @@ impl<'a> ColumnsIteratorSet<'a> {
                     .get_table_columns()
                     .map(|x| x.len())
                     .unwrap_or_default()
-                    + self.config.expressions.len();
+                    + self.config.expressions.len()
+                    + self.config.window.len();
 
         match &self.is_dragover_column {
             Some((to_index, from_column)) => {
@@ -253,6 +255,11 @@ impl<'a> ColumnsIteratorSet<'a> {
         self.order_columns(self.metadata.get_expression_columns())
     }
 
+    /// Generate an iterator for inactive window columns.
+    pub fn window(&'a self) -> impl Iterator<Item = OrderedColumn<'a>> {
+        self.order_columns(self.metadata.get_window_columns())
+    }
+
     /// Generate an iterator for inactive columns, which also shows the columns
     /// in sorted order by type, then name.
     pub fn inactive(&'a self) -> impl Iterator<Item = OrderedColumn<'a>> {
@@ -10,7 +10,7 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use perspective_client::config::{Expression, ViewConfigUpdate};
+use perspective_client::config::{Expression, ViewConfigUpdate, Window};
 
 use super::structural::*;
 use super::UpdateAndRender;
@@ -86,6 +86,54 @@ pub trait EditExpression: HasPresentation + HasRenderer + HasSession + UpdateAnd
         let task = self.update_and_render(config);
         ApiFuture::spawn(task);
     }
+
+    /// Replaces the window column `old_name` with `window`, renaming it to
+    /// `new_name` in `columns` if it is active. Spawns a future.
+    fn update_window(&self, old_name: &str, new_name: String, window: Window) {
+        let task = {
+            let config = self.session().get_view_config();
+            let mut windows = config.window.clone();
+            windows.remove(old_name);
+            windows.insert(new_name.clone(), window);
+            let columns = (old_name != new_name).then(|| {
+                config
+                    .columns
+                    .iter()
+                    .map(|col| match col {
+                        Some(col) if col == old_name => Some(new_name.clone()),
+                        col => col.clone(),
+                    })
+                    .collect()
+            });
+
+            drop(config);
+            self.presentation()
+                .set_open_column_settings(Some(OpenColumnSettings {
+                    locator: Some(ColumnLocator::Window(new_name)),
+                    tab: Some(ColumnSettingsTab::Attributes),
+                }));
+
+            self.update_and_render(ViewConfigUpdate {
+                window: Some(windows),
+                columns,
+                ..Default::default()
+            })
+        };
+
+        ApiFuture::spawn(task);
+    }
+
+    fn delete_window(&self, name: &str) {
+        let mut windows = self.session().get_view_config().window.clone();
+        windows.remove(name);
+        let config = ViewConfigUpdate {
+            window: Some(windows),
+            ..ViewConfigUpdate::default()
+        };
+
+        let task = self.update_and_render(config);
+        ApiFuture::spawn(task);
+    }
 }
 
 impl<T: HasRenderer + HasSession + HasPresentation + UpdateAndRender> EditExpression for T {}
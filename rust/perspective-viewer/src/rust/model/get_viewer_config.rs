@@ -58,6 +58,14 @@ pub trait GetViewerConfigModel: HasSession + HasRenderer + HasPresentation {
             let theme = presentation.get_selected_theme_name().await;
             let title = presentation.get_title();
             let columns_config = presentation.all_columns_configs();
+            let expansion_state = match session.get_view() {
+                Some(view) if !view_config.group_by.is_empty() => {
+                    Some(view.get_expansion_state().await?)
+                        .filter(|state| !state.collapsed.is_empty())
+                },
+                _ => None,
+            };
+
             Ok(ViewerConfig {
                 version,
                 plugin,
@@ -67,6 +75,7 @@ pub trait GetViewerConfigModel: HasSession + HasRenderer + HasPresentation {
                 settings,
                 view_config,
                 theme,
+                expansion_state,
             })
         })
     }
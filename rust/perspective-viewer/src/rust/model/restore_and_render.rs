@@ -11,6 +11,7 @@
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
 use futures::Future;
+use perspective_client::config::ExpansionState;
 
 use super::structural::*;
 use crate::config::{OptionalUpdate, ViewerConfigUpdate};
@@ -28,6 +29,7 @@ pub trait RestoreAndRender: HasRenderer + HasSession + HasPresentation {
             settings,
             theme: theme_name,
             title,
+            expansion_state,
             mut view_config,
             ..
         }: crate::config::ViewerConfigUpdate,
@@ -87,7 +89,18 @@ pub trait RestoreAndRender: HasRenderer + HasSession + HasPresentation {
                 presentation.update_columns_configs(columns_config);
                 let columns_config = presentation.all_columns_configs();
                 plugin.restore(&plugin_update, Some(&columns_config))?;
-                session.validate().await?.create_view().await
+                let session = session.validate().await?.create_view().await?;
+                let expansion_state = match expansion_state {
+                    OptionalUpdate::Update(x) => Some(x),
+                    OptionalUpdate::SetDefault => Some(ExpansionState::default()),
+                    OptionalUpdate::Missing => None,
+                };
+
+                if let (Some(state), Some(view)) = (expansion_state, session.get_view()) {
+                    view.set_expansion_state(state).await?;
+                }
+
+                Ok(session)
             });
 
             draw_task.await?;
@@ -494,10 +494,6 @@ impl Session {
             .cloned()
             .collect::<Vec<String>>();
 
-        let all_columns: HashSet<String> = table_columns.iter().cloned().collect();
-
-        let mut view_columns: HashSet<&str> = HashSet::new();
-
         let table = self
             .borrow()
             .table
@@ -506,9 +502,10 @@ impl Session {
             .clone();
 
         let valid_recs = table.validate_expressions(config.expressions).await?;
-        let expression_names = self.metadata_mut().update_expressions(&valid_recs)?;
+        self.metadata_mut().update_expressions(&valid_recs)?;
+        self.metadata_mut().update_windows(&config.window);
 
-        // re-fetch config after `await`; `expressions` and `all_columns` are ok,
+        // re-fetch config after `await`; `expressions` and `table_columns` are ok,
         // but `config` may have changed as it is unlocked.
         let mut config = self.borrow().config.clone();
 
@@ -516,50 +513,37 @@ impl Session {
             config.columns = table_columns.into_iter().map(Some).collect();
         }
 
-        for column in config.columns.iter().flatten() {
-            if all_columns.contains(column) || expression_names.contains(column) {
-                let _existed = view_columns.insert(column);
-            } else {
-                return Err(format!("Unknown \"{}\" in `columns`", column).into());
-            }
-        }
-
-        for column in config.group_by.iter() {
-            if all_columns.contains(column) || expression_names.contains(column) {
-                let _existed = view_columns.insert(column);
-            } else {
-                return Err(format!("Unknown \"{}\" in `group_by`", column).into());
-            }
-        }
-
-        for column in config.split_by.iter() {
-            if all_columns.contains(column) || expression_names.contains(column) {
-                let _existed = view_columns.insert(column);
-            } else {
-                return Err(format!("Unknown \"{}\" in `split_by`", column).into());
-            }
-        }
-
-        for sort in config.sort.iter() {
-            if all_columns.contains(&sort.0) || expression_names.contains(&sort.0) {
-                let _existed = view_columns.insert(&sort.0);
-            } else {
-                return Err(format!("Unknown \"{}\" in `sort`", sort.0).into());
-            }
-        }
-
-        for filter in config.filter.iter() {
-            // TODO check filter op
-            if all_columns.contains(filter.column()) || expression_names.contains(filter.column()) {
-                let _existed = view_columns.insert(filter.column());
-            } else {
-                return Err(format!("Unknown \"{}\" in `filter`", filter.column()).into());
-            }
-        }
+        let view_columns: HashSet<&str> = config
+            .columns
+            .iter()
+            .flatten()
+            .chain(config.group_by.iter())
+            .chain(config.split_by.iter())
+            .chain(config.sort.iter().map(|x| &x.0))
+            .map(|x| x.as_str())
+            .chain(config.filter.iter().map(|x| x.column()))
+            .collect();
+
+        let mut aggregates = config.aggregates.clone();
+        aggregates.retain(|column, _| view_columns.contains(column.as_str()));
+        config.aggregates = aggregates;
+
+        let (schema, features) = {
+            let metadata = self.metadata();
+            let schema = metadata.get_table_schema().cloned().unwrap_or_default();
+            let features = metadata.get_features().cloned().unwrap_or_default();
+            (schema, features)
+        };
 
-        config
-            .aggregates
-            .retain(|column, _| view_columns.contains(column.as_str()));
+        ViewConfigUpdate::from(config.clone())
+            .validate(&schema, &features, &valid_recs)
+            .map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })?;
 
         self.borrow_mut().config = config;
         Ok(())
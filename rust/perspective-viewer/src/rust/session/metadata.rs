@@ -56,6 +56,7 @@ pub struct SessionMetadataState {
     edit_port: f64,
     view_schema: Option<HashMap<String, ColumnType>>,
     expr_meta: Option<SessionViewExpressionMetadata>,
+    windows: HashMap<String, Window>,
 }
 
 impl SessionMetadata {
@@ -107,6 +108,10 @@ impl SessionMetadata {
         Ok(valid_recs.expression_schema.keys().cloned().collect())
     }
 
+    pub(super) fn update_windows(&mut self, windows: &HashMap<String, Window>) {
+        self.as_mut().unwrap().windows = windows.clone();
+    }
+
     /// Get the `Table`'s supported features.
     pub fn get_features(&self) -> Option<&'_ perspective_client::Features> {
         Some(&self.as_ref()?.features)
@@ -127,6 +132,19 @@ impl SessionMetadata {
         .flatten()
     }
 
+    /// Returns the unique column names in this session that are window
+    /// columns.
+    pub fn get_window_columns(&self) -> impl Iterator<Item = &'_ String> {
+        self.as_ref()
+            .into_iter()
+            .flat_map(|meta| meta.windows.keys())
+    }
+
+    /// Returns the `Window` definition for a window column name.
+    pub fn get_window(&self, name: &str) -> Option<Window> {
+        self.as_ref()?.windows.get(name).cloned()
+    }
+
     /// Returns the full original expression `String` for an expression alias.
     /// TODO should expressions be `Rc`?
     ///
@@ -175,6 +193,11 @@ impl SessionMetadata {
             .remove(alias)))
     }
 
+    /// Get the `Table`'s `Schema`, not including expressions.
+    pub fn get_table_schema(&self) -> Option<&'_ perspective_client::Schema> {
+        self.as_ref().map(|meta| &meta.table_schema)
+    }
+
     pub fn get_table_columns(&self) -> Option<&'_ Vec<String>> {
         self.as_ref().map(|meta| &meta.column_names)
     }
@@ -192,6 +215,10 @@ impl SessionMetadata {
         is_expr.unwrap_or_default()
     }
 
+    pub fn is_column_window(&self, name: &str) -> bool {
+        maybe!(Some(self.as_ref()?.windows.contains_key(name))).unwrap_or_default()
+    }
+
     /// This function will find a currently existing column. If you want to
     /// create a new expression column, use ColumnLocator::Expr(None)
     pub fn get_column_locator(&self, name: Option<String>) -> Option<ColumnLocator> {
@@ -199,6 +226,8 @@ impl SessionMetadata {
             self.as_ref().and_then(|meta| {
                 if self.is_column_expression(&name) {
                     Some(ColumnLocator::Expression(name))
+                } else if self.is_column_window(&name) {
+                    Some(ColumnLocator::Window(name))
                 } else {
                     meta.column_names
                         .iter()
@@ -227,8 +256,8 @@ impl SessionMetadata {
 
     /// Returns the type of a column name relative to the `Table`.  Despite the
     /// name, `get_column_table_type()` also returns the `Table` type for
-    /// Expressions, which despite living on the `View` still have a `table`
-    /// type associated with them pre-aggregation.
+    /// Expressions and Windows, which despite living on the `View` still have
+    /// a `table` type associated with them pre-aggregation.
     ///
     /// # Arguments
     /// - `name` The column name (or expresison alias) to retrieve a principal
@@ -236,14 +265,26 @@ impl SessionMetadata {
     pub fn get_column_table_type(&self, name: &str) -> Option<ColumnType> {
         maybe!({
             let meta = self.as_ref()?;
-            meta.table_schema.get(name).cloned().or_else(|| {
-                meta.expr_meta
-                    .as_ref()?
-                    .expressions
-                    .expression_schema
-                    .get(name)
-                    .cloned()
-            })
+            meta.table_schema
+                .get(name)
+                .cloned()
+                .or_else(|| {
+                    meta.expr_meta
+                        .as_ref()?
+                        .expressions
+                        .expression_schema
+                        .get(name)
+                        .cloned()
+                })
+                .or_else(|| {
+                    let window = meta.windows.get(name)?;
+                    let col_type = window
+                        .column
+                        .as_ref()
+                        .and_then(|column| self.get_column_table_type(column));
+
+                    Some(window.function.output_type(col_type))
+                })
         })
     }
 
@@ -267,28 +308,26 @@ impl SessionMetadata {
         maybe!({
             let coltype = self.get_column_table_type(name)?;
             let aggregates = coltype.aggregates_iter();
-            Some(match coltype {
-                ColumnType::Float | ColumnType::Integer => {
-                    let num_cols = self
-                        .get_expression_columns()
-                        .cloned()
-                        .chain(self.get_table_columns()?.clone().into_iter())
-                        .map(move |name| {
-                            self.get_column_table_type(&name)
-                                .map(|coltype| (name, coltype))
-                        })
-                        .collect::<Option<Vec<_>>>()?
-                        .into_iter()
-                        .filter(|(_, coltype)| {
-                            *coltype == ColumnType::Integer || *coltype == ColumnType::Float
-                        })
-                        .map(|(name, _)| {
-                            Aggregate::MultiAggregate(MultiAggregate::WeightedMean, name)
-                        });
-                    Box::new(aggregates.chain(num_cols))
-                },
-                _ => aggregates,
-            })
+            let columns = self
+                .get_expression_columns()
+                .cloned()
+                .chain(self.get_table_columns()?.clone().into_iter())
+                .map(move |name| {
+                    self.get_column_table_type(&name)
+                        .map(|coltype| (name, coltype))
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            let multi_aggregates = MultiAggregate::iter_all().flat_map(move |agg| {
+                columns
+                    .clone()
+                    .into_iter()
+                    .filter(move |(_, by_type)| agg.supports(coltype, *by_type))
+                    .map(move |(name, _)| Aggregate::MultiAggregate(agg, name))
+            });
+
+            Some(Box::new(aggregates.chain(multi_aggregates))
+                as Box<dyn Iterator<Item = Aggregate> + 'a>)
         })
     }
 }
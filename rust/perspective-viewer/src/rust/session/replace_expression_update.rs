@@ -39,6 +39,9 @@ pub impl ViewConfig {
             aggregates,
             filter_op: _,
             group_by_depth: _,
+            window,
+            totals: _,
+            split_by_order: _,
         } = self.clone();
 
         let expressions = expressions
@@ -116,6 +119,32 @@ pub impl ViewConfig {
             })
             .collect::<Vec<_>>();
 
+        let rename = |x: String| {
+            if x == old_expr.name {
+                new_expr.name.as_ref().to_owned()
+            } else {
+                x
+            }
+        };
+
+        let window = window
+            .into_iter()
+            .map(|(name, window)| {
+                let window = Window {
+                    column: window.column.map(rename),
+                    partition_by: window.partition_by.into_iter().map(rename).collect(),
+                    order_by: window
+                        .order_by
+                        .into_iter()
+                        .map(|Sort(x, dir)| Sort(rename(x), dir))
+                        .collect(),
+                    ..window
+                };
+
+                (name, window)
+            })
+            .collect::<HashMap<_, _>>();
+
         ViewConfigUpdate {
             columns: Some(columns),
             aggregates: Some(aggregates),
@@ -126,6 +155,9 @@ pub impl ViewConfig {
             filter: Some(filter),
             filter_op: None,
             group_by_depth: None,
+            window: Some(window),
+            totals: None,
+            split_by_order: None,
         }
     }
 }
@@ -11,18 +11,20 @@
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use axum::extract::connect_info::ConnectInfo;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::routing::{get, MethodRouter};
 use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use futures::future::{select, Either};
 use futures::{FutureExt, SinkExt, StreamExt};
 
-use crate::client::Session;
-use crate::server::{LocalSession, Server, SessionHandler};
+use crate::client::{Authorizer, Session};
+use crate::server::{LocalClient, Server, SessionHandler, TokioSession};
 
 /// A local error synonym for this module only.
 type PerspectiveWSError = Box<dyn std::error::Error + Send + Sync>;
@@ -60,7 +62,7 @@ impl SessionHandler for PerspectiveWSConnection {
 async fn process_message_loop(
     socket: &mut WebSocket,
     receiver: &mut UnboundedReceiver<Vec<u8>>,
-    session: &mut LocalSession,
+    session: &TokioSession,
 ) -> Result<(), PerspectiveWSError> {
     use Either::*;
     use Message::*;
@@ -88,6 +90,31 @@ async fn process_message_loop(
     Ok(())
 }
 
+/// The beginning-to-end lifecycle of a single WebSocket connection, with an
+/// optional identity and [`Authorizer`] for its [`Session`]. The engine calls
+/// of the [`Session`] run on tokio's blocking thread pool, so a slow request
+/// does not stall the other connections of this executor thread.
+async fn handle_socket(
+    mut socket: WebSocket,
+    server: Server,
+    addr: SocketAddr,
+    authorization: Option<(String, Arc<dyn Authorizer>)>,
+) {
+    let (send, mut receiver) = unbounded::<Vec<u8>>();
+    let mut session = server.new_session(PerspectiveWSConnection(send)).await;
+    if let Some((identity, authorizer)) = authorization {
+        session = session.with_authorizer(identity, authorizer);
+    }
+
+    let session = TokioSession::new(session);
+    if let Err(msg) = process_message_loop(&mut socket, &mut receiver, &session).await {
+        tracing::error!("Internal error {}", msg);
+    }
+
+    tracing::info!("{addr} Disconnected.");
+    session.close().await;
+}
+
 /// This handler is responsible for the beginning-to-end lifecycle of a
 /// single WebSocket connection to an [`axum`] server.
 ///
@@ -104,17 +131,122 @@ pub fn websocket_handler() -> MethodRouter<Server> {
         ConnectInfo(addr): ConnectInfo<SocketAddr>,
     ) -> impl IntoResponse {
         tracing::info!("{addr} Connected.");
-        ws.on_upgrade(move |mut socket| async move {
-            let (send, mut receiver) = unbounded::<Vec<u8>>();
-            let mut session = server.new_session(PerspectiveWSConnection(send)).await;
-            if let Err(msg) = process_message_loop(&mut socket, &mut receiver, &mut session).await {
-                tracing::error!("Internal error {}", msg);
+        ws.on_upgrade(move |socket| handle_socket(socket, server, addr, None))
+    }
+
+    get(websocket_handler_internal)
+}
+
+/// As [`websocket_handler`], but the requests of each connection's
+/// [`Session`] are checked by `authorizer` before they reach the engine.
+///
+/// `identify` maps the headers and address of a connection's upgrade request
+/// to the identity its [`Session`] is authorized as (e.g. from an
+/// `Authorization` header), or to the [`StatusCode`] to refuse the
+/// connection with.
+pub fn websocket_handler_with_authorizer<F>(
+    identify: F,
+    authorizer: Arc<dyn Authorizer>,
+) -> MethodRouter<Server>
+where
+    F: Fn(&HeaderMap, &SocketAddr) -> Result<String, StatusCode> + Clone + Send + Sync + 'static,
+{
+    get(
+        move |ws: WebSocketUpgrade,
+              State(server): State<Server>,
+              ConnectInfo(addr): ConnectInfo<SocketAddr>,
+              headers: HeaderMap| {
+            let identity = identify(&headers, &addr);
+            let authorizer = authorizer.clone();
+            async move {
+                let identity = match identity {
+                    Ok(identity) => identity,
+                    Err(status) => {
+                        tracing::info!("{addr} Refused with {status}.");
+                        return status.into_response();
+                    },
+                };
+
+                tracing::info!("{addr} Connected as `{identity}`.");
+                ws.on_upgrade(move |socket| {
+                    handle_socket(socket, server, addr, Some((identity, authorizer)))
+                })
+                .into_response()
             }
+        },
+    )
+}
 
-            tracing::info!("{addr} Disconnected.");
-            session.close().await;
-        })
+/// Report the [`crate::client::SystemInfo`] of `client`'s [`Server`] in the
+/// Prometheus text exposition format.
+async fn metrics_response(client: LocalClient) -> axum::response::Response {
+    let info = client.system_info().await;
+    client.close().await;
+    match info {
+        Ok(info) => (
+            StatusCode::OK,
+            [("content-type", "text/plain; version=0.0.4")],
+            info.to_prometheus(),
+        )
+            .into_response(),
+        Err(crate::client::ClientError::Unauthorized(err)) => {
+            tracing::info!("Refused metrics request: {}", err);
+            StatusCode::FORBIDDEN.into_response()
+        },
+        Err(err) => {
+            tracing::error!("Internal error {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        },
     }
+}
 
-    get(websocket_handler_internal)
+/// A handler which reports the [`crate::client::SystemInfo`] of an [`axum`]
+/// server's [`Server`] in the Prometheus text exposition format, for use as
+/// a `/metrics` scrape endpoint alongside [`websocket_handler`].
+///
+/// This handler is not authorized: it reports the names and row counts of
+/// every [`crate::client::Table`], whatever the [`Authorizer`],
+/// `SessionFilters` or `ColumnPolicies` of the server's [`Session`]s. Only
+/// route it where it is reachable by trusted scrapers, or use
+/// [`metrics_handler_with_authorizer`].
+pub fn metrics_handler_unauthorized() -> MethodRouter<Server> {
+    async fn metrics_handler_internal(State(server): State<Server>) -> impl IntoResponse {
+        metrics_response(LocalClient::new(&server)).await
+    }
+
+    get(metrics_handler_internal)
+}
+
+/// As [`metrics_handler_unauthorized`], but each scrape is checked by
+/// `authorizer` as the identity `identify` maps its headers and address to, as
+/// for [`websocket_handler_with_authorizer`]. Scrapes the [`Authorizer`] denies
+/// are refused with [`StatusCode::FORBIDDEN`].
+pub fn metrics_handler_with_authorizer<F>(
+    identify: F,
+    authorizer: Arc<dyn Authorizer>,
+) -> MethodRouter<Server>
+where
+    F: Fn(&HeaderMap, &SocketAddr) -> Result<String, StatusCode> + Clone + Send + Sync + 'static,
+{
+    get(
+        move |State(server): State<Server>,
+              ConnectInfo(addr): ConnectInfo<SocketAddr>,
+              headers: HeaderMap| {
+            let identity = identify(&headers, &addr);
+            let authorizer = authorizer.clone();
+            async move {
+                match identity {
+                    Ok(identity) => {
+                        let client =
+                            LocalClient::new(&server).with_authorizer(identity, authorizer);
+                        metrics_response(client).await
+                    },
+                    Err(status) => {
+                        tracing::info!("{addr} Refused with {status}.");
+                        status.into_response()
+                    },
+                }
+            }
+        },
+    )
 }
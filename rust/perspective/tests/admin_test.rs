@@ -0,0 +1,165 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use perspective_client::config::ViewConfigUpdate;
+use perspective_client::{ClientError, ReadOnly, TableInitOptions, UpdateData};
+use perspective_server::{LocalClient, Server};
+
+async fn host_trades(client: &LocalClient) -> Result<(), Box<dyn Error>> {
+    let options = TableInitOptions {
+        name: Some("trades".to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    };
+
+    let csv = "desk,x\nrates,1\nfx,2\nrates,3";
+    client
+        .table(UpdateData::Csv(csv.to_owned()).into(), options)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_views_and_sessions_of_other_clients() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let admin = LocalClient::new(&server).with_admin();
+    host_trades(&admin).await?;
+
+    let user = LocalClient::new(&server);
+    let table = user.open_table("trades".to_owned()).await?;
+    let config = ViewConfigUpdate {
+        group_by: Some(vec!["desk".to_owned()]),
+        ..ViewConfigUpdate::default()
+    };
+
+    let view = table.view(Some(config)).await?;
+    view.on_delete(Box::new(|| {})).await?;
+
+    let views = admin.get_hosted_views().await?;
+    assert_eq!(views.len(), 1);
+    assert_eq!(views[0].name, view.name);
+    assert_eq!(views[0].table_name, "trades");
+    assert_eq!(views[0].config.group_by, vec!["desk".to_owned()]);
+    assert_eq!(views[0].num_subscriptions, 1);
+
+    let sessions = admin.get_sessions().await?;
+    assert_eq!(sessions.len(), 2);
+    let owner = sessions
+        .iter()
+        .find(|x| x.id == views[0].session_id)
+        .unwrap();
+
+    assert_eq!(owner.view_names, vec![view.name.clone()]);
+    assert_eq!(owner.num_subscriptions, 1);
+
+    view.delete().await?;
+    user.close().await;
+    admin.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_hosted_view_of_other_client() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let admin = LocalClient::new(&server).with_admin();
+    host_trades(&admin).await?;
+
+    let user = LocalClient::new(&server);
+    let table = user.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    let deleted = Arc::new(AtomicBool::new(false));
+    view.on_delete(Box::new({
+        let deleted = deleted.clone();
+        move || deleted.store(true, Ordering::SeqCst)
+    }))
+    .await?;
+
+    admin.delete_hosted_view(&view.name).await?;
+    assert!(deleted.load(Ordering::SeqCst));
+    assert!(admin.get_hosted_views().await?.is_empty());
+    assert!(view.num_rows().await.is_err());
+
+    user.close().await;
+    admin.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_session_of_other_client() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let admin = LocalClient::new(&server).with_admin();
+    host_trades(&admin).await?;
+
+    let user = LocalClient::new(&server);
+    let table = user.open_table("trades".to_owned()).await?;
+    table.view(None).await?;
+    let session_id = admin.get_hosted_views().await?[0].session_id;
+
+    admin.close_session(session_id).await?;
+    assert!(admin.get_hosted_views().await?.is_empty());
+    assert_eq!(admin.get_sessions().await?.len(), 1);
+    assert!(table.size().await.is_err());
+
+    user.close().await;
+    admin.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_only_session_cannot_make_admin_requests() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let admin = LocalClient::new(&server).with_admin();
+    host_trades(&admin).await?;
+
+    let reader = LocalClient::new(&server)
+        .with_admin()
+        .with_authorizer("reader", Arc::new(ReadOnly));
+    let result = reader.get_sessions().await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    assert_eq!(
+        reader.open_table("trades".to_owned()).await?.size().await?,
+        3
+    );
+
+    reader.close().await;
+    admin.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_admin_requests_require_opt_in() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let admin = LocalClient::new(&server).with_admin();
+    host_trades(&admin).await?;
+
+    let user = LocalClient::new(&server);
+    let table = user.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    let result = user.get_hosted_views().await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    let result = user.delete_hosted_view(&view.name).await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    let result = user.close_session(0).await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    assert_eq!(view.num_rows().await?, 3);
+    assert_eq!(admin.get_sessions().await?.len(), 2);
+
+    user.close().await;
+    admin.close().await;
+    Ok(())
+}
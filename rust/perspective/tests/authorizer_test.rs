@@ -0,0 +1,113 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+use std::sync::Arc;
+
+use perspective_client::{
+    AuthError, AuthRequest, ClientError, ReadOnly, TableInitOptions, UpdateData, UpdateOptions,
+};
+use perspective_server::{LocalClient, Server};
+
+fn options(name: &str) -> TableInitOptions {
+    TableInitOptions {
+        name: Some(name.to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    }
+}
+
+#[tokio::test]
+async fn test_read_only_session_cannot_update_tables() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let writer = LocalClient::new(&server);
+    let table = writer
+        .table(
+            UpdateData::Csv("x,y\n1,2\n3,4".to_owned()).into(),
+            options("Table1"),
+        )
+        .await?;
+
+    let reader = LocalClient::new(&server).with_authorizer("reader", Arc::new(ReadOnly));
+    let table2 = reader.open_table("Table1".to_owned()).await?;
+    let view = table2.view(None).await?;
+    assert_eq!(view.num_rows().await?, 2);
+
+    let result = table2
+        .update(
+            UpdateData::Csv("x,y\n5,6".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    assert_eq!(table.size().await?, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_acl_covers_views() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let writer = LocalClient::new(&server);
+    for name in ["public", "secret"] {
+        writer
+            .table(UpdateData::Csv("x\n1".to_owned()).into(), options(name))
+            .await?;
+    }
+
+    let acl = |req: &AuthRequest<'_>| match req.table_name {
+        Some("secret") if req.identity != "admin" => Err(AuthError::new("`secret` is restricted")),
+        _ => Ok(()),
+    };
+
+    let guest = LocalClient::new(&server).with_authorizer("guest", Arc::new(acl));
+    let public = guest.open_table("public".to_owned()).await?;
+    let view = public.view(None).await?;
+    assert_eq!(view.num_rows().await?, 1);
+
+    let secret = guest.open_table("secret".to_owned()).await?;
+    let result = secret.view(None).await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+
+    let admin = LocalClient::new(&server).with_authorizer("admin", Arc::new(acl));
+    let secret = admin.open_table("secret".to_owned()).await?;
+    let view = secret.view(None).await?;
+    assert_eq!(view.num_rows().await?, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_acl_covers_other_sessions_views() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let writer = LocalClient::new(&server).with_admin();
+    let secret = writer
+        .table(UpdateData::Csv("x\n1".to_owned()).into(), options("secret"))
+        .await?;
+
+    let view = secret.view(None).await?;
+    let hosted = writer.get_hosted_views().await?;
+    let acl = |req: &AuthRequest<'_>| match req.table_name {
+        Some("secret") => Err(AuthError::new("`secret` is restricted")),
+        _ => Ok(()),
+    };
+
+    let guest = LocalClient::new(&server)
+        .with_admin()
+        .with_authorizer("guest", Arc::new(acl));
+    let result = guest.delete_hosted_view(&hosted[0].name).await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    let result = guest.delete_hosted_view("not_a_view").await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    assert_eq!(view.num_rows().await?, 1);
+    Ok(())
+}
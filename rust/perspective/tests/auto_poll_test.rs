@@ -0,0 +1,131 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use async_lock::RwLock;
+use perspective_client::{
+    Client, OnUpdateOptions, Session, TableInitOptions, UpdateData, UpdateOptions,
+};
+use perspective_server::{LocalClient, LocalSession, Server};
+use tokio::sync::Mutex;
+
+/// A [`Client`] connected to `server` through a [`LocalSession`] which never
+/// calls [`Session::poll`], and the session itself so that it can be closed.
+async fn unpolled_client(server: &Server) -> (Client, Arc<RwLock<Option<LocalSession>>>) {
+    let client: Arc<OnceLock<Client>> = Arc::default();
+    let session = server
+        .new_session_with_callback({
+            let client = client.clone();
+            move |msg| {
+                let client = client.clone();
+                Box::pin(async move {
+                    client.get().unwrap().handle_response(msg).await?;
+                    Ok(())
+                })
+            }
+        })
+        .await;
+
+    let session = Arc::new(RwLock::new(Some(session)));
+    client
+        .set(Client::new_with_callback({
+            let session = session.clone();
+            move |msg| {
+                let session = session.clone();
+                Box::pin(async move {
+                    let session = session.read().await;
+                    session.as_ref().unwrap().handle_request(msg).await
+                })
+            }
+        }))
+        .unwrap();
+
+    (client.get().unwrap().clone(), session)
+}
+
+/// Update a table through an unpolled session, and return whether a view of
+/// it on another session was notified, after waiting at most `wait`.
+async fn is_update_sent(server: Server, wait: Duration) -> Result<bool, Box<dyn Error>> {
+    let (writer, session) = unpolled_client(&server).await;
+    let options = TableInitOptions {
+        name: Some("trades".to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    };
+
+    let table = writer
+        .table(UpdateData::Csv("x,y\n1,2".to_owned()).into(), options)
+        .await?;
+
+    let reader = LocalClient::new(&server);
+    let view = reader
+        .open_table("trades".to_owned())
+        .await?
+        .view(None)
+        .await?;
+    let updated = Arc::new(Mutex::new(false));
+    let _sub = view
+        .on_update(
+            {
+                let updated = updated.clone();
+                move |_| {
+                    let updated = updated.clone();
+                    async move { *updated.lock().await = true }
+                }
+            },
+            OnUpdateOptions::default(),
+        )
+        .await?;
+
+    table
+        .update(
+            UpdateData::Csv("x,y\n3,4".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    let start = tokio::time::Instant::now();
+    while !*updated.lock().await && start.elapsed() < wait {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let result = *updated.lock().await;
+    view.delete().await?;
+    reader.close().await;
+    session.write().await.take().unwrap().close().await;
+    Ok(result)
+}
+
+#[tokio::test]
+async fn test_without_auto_poll() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    assert!(!is_update_sent(server, Duration::from_millis(100)).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_poll_on_update() -> Result<(), Box<dyn Error>> {
+    let server = Server::default().with_poll_on_update();
+    assert!(is_update_sent(server, Duration::ZERO).await?);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_poll_interval() -> Result<(), Box<dyn Error>> {
+    let server = Server::default().with_poll_interval(Duration::from_millis(10));
+    assert!(is_update_sent(server, Duration::from_secs(5)).await?);
+    Ok(())
+}
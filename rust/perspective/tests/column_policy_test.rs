@@ -0,0 +1,176 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{Arc, OnceLock};
+
+use perspective_client::config::{Expressions, Filter, FilterTerm, Scalar, ViewConfigUpdate};
+use perspective_client::{
+    Client, ClientError, ColumnAccess, ColumnPolicies, ColumnType, ProxySession, Session,
+    TableInitOptions, UpdateData,
+};
+use perspective_server::{LocalClient, Server};
+
+fn policies() -> ColumnPolicies {
+    ColumnPolicies::default()
+        .with_column("people", "ssn", ColumnAccess::Hidden)
+        .with_column("people", "email", ColumnAccess::Redacted)
+        .with_column("people", "name", ColumnAccess::Hashed)
+}
+
+async fn host_people(server: &Server) -> Result<(), Box<dyn Error>> {
+    let options = TableInitOptions {
+        name: Some("people".to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    };
+
+    let csv = "name,ssn,email,x\nalice,111,a@x.com,1\nbob,222,b@x.com,2\nalice,333,c@x.com,3";
+    LocalClient::new(server)
+        .table(UpdateData::Csv(csv.to_owned()).into(), options)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_policies_hide_and_mask_columns() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_people(&server).await?;
+    let client = LocalClient::new(&server).with_column_policies(policies());
+    let table = client.open_table("people".to_owned()).await?;
+
+    let schema = table.schema().await?;
+    assert!(!schema.contains_key("ssn"));
+    assert_eq!(schema.get("email"), Some(&ColumnType::String));
+    assert_eq!(schema.get("name"), Some(&ColumnType::String));
+
+    let view = table.view(None).await?;
+    let csv = view.to_csv(Default::default()).await?;
+    let rows = csv
+        .lines()
+        .map(|row| row.split(',').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    assert_eq!(rows[0], vec!["\"name\"", "\"email\"", "\"x\""]);
+    assert!(rows[1..].iter().all(|row| row[1] == "\"****\""));
+    assert_eq!(rows[3][2], "3");
+
+    // Hashes are stable, so equal values remain equal.
+    assert_eq!(rows[1][0], rows[3][0]);
+    assert_ne!(rows[1][0], rows[2][0]);
+    assert_ne!(rows[1][0], "\"alice\"");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_policies_reject_restricted_columns() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_people(&server).await?;
+    let client = LocalClient::new(&server).with_column_policies(policies());
+    let table = client.open_table("people".to_owned()).await?;
+
+    let hidden = ViewConfigUpdate {
+        columns: Some(vec![Some("ssn".to_owned())]),
+        ..ViewConfigUpdate::default()
+    };
+
+    assert!(table.view(Some(hidden)).await.is_err());
+
+    let term = FilterTerm::Scalar(Scalar::String("a@x.com".to_owned()));
+    let filtered = ViewConfigUpdate {
+        filter: Some(vec![Filter::new("email", "==", term)]),
+        ..ViewConfigUpdate::default()
+    };
+
+    assert!(table.view(Some(filtered)).await.is_err());
+
+    let expressions = Expressions(HashMap::from([
+        ("digits".to_owned(), "\"ssn\" + 1".to_owned()),
+        ("masked".to_owned(), "lower(\"email\")".to_owned()),
+        ("double".to_owned(), "\"x\" * 2".to_owned()),
+    ]));
+
+    let result = table.validate_expressions(expressions).await?;
+    assert!(result.errors.contains_key("digits"));
+    assert!(result.errors.contains_key("masked"));
+    assert!(!result.errors.contains_key("double"));
+    assert_eq!(result.expression_schema.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_policies_cannot_be_read_around() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_people(&server).await?;
+    let unrestricted = LocalClient::new(&server).with_admin();
+    let table = unrestricted.open_table("people".to_owned()).await?;
+    let view = table.view(None).await?;
+    let hosted = unrestricted.get_hosted_views().await?;
+
+    let client = LocalClient::new(&server).with_column_policies(policies());
+    let result = client.delete_hosted_view(&hosted[0].name).await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    let result = client.get_hosted_views().await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    assert_eq!(view.num_rows().await?, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_policies_keep_the_stricter_forwarded_policy() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_people(&server).await?;
+    let parent = LocalClient::new(&server).with_column_policies(policies());
+    let forwarded = ColumnPolicies::default()
+        .with_column("people", "name", ColumnAccess::Redacted)
+        .with_column("people", "email", ColumnAccess::Hashed)
+        .with_column("people", "x", ColumnAccess::Hidden);
+
+    let client: Arc<OnceLock<Client>> = Arc::default();
+    let proxy = ProxySession::new(parent.clone(), {
+        let client = client.clone();
+        move |msg| {
+            let client = client.clone();
+            let msg = msg.to_vec();
+            tokio::spawn(async move { client.get().unwrap().handle_response(&msg).await });
+            Ok(())
+        }
+    })
+    .with_column_policies(forwarded);
+
+    let proxy = Arc::new(proxy);
+    client
+        .set(Client::new_with_callback(move |msg| {
+            let proxy = proxy.clone();
+            Box::pin(async move { Ok(proxy.handle_request(msg).await?) })
+        }))
+        .unwrap();
+
+    let table = client
+        .get()
+        .unwrap()
+        .open_table("people".to_owned())
+        .await?;
+    let schema = table.schema().await?;
+    assert!(!schema.contains_key("ssn"));
+    assert!(!schema.contains_key("x"));
+
+    // `name` is redacted rather than hashed, and `email` stays redacted.
+    let view = table.view(None).await?;
+    let csv = view.to_csv(Default::default()).await?;
+    assert!(csv.lines().skip(1).all(|row| row == "\"****\",\"****\""));
+    view.delete().await?;
+    parent.close().await;
+    Ok(())
+}
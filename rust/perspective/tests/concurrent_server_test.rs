@@ -0,0 +1,120 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+use std::sync::Arc;
+
+use perspective_client::{OnUpdateOptions, TableInitOptions, UpdateData, UpdateOptions};
+use perspective_server::{LocalClient, Server};
+use tokio::sync::Mutex;
+
+fn table_options(name: &str) -> TableInitOptions {
+    TableInitOptions {
+        name: Some(name.to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_reads_and_updates() -> Result<(), Box<dyn Error>> {
+    let server = Server::new_concurrent(4);
+    let client = LocalClient::new(&server);
+    let csv = "x,y\n1,2\n3,4";
+    client
+        .table(UpdateData::Csv(csv.to_owned()).into(), table_options("a"))
+        .await?;
+
+    client
+        .table(UpdateData::Csv(csv.to_owned()).into(), table_options("b"))
+        .await?;
+
+    let mut tasks = vec![];
+    for idx in 0..8 {
+        let server = server.clone();
+        tasks.push(tokio::spawn(async move {
+            let client = LocalClient::new(&server);
+            if idx % 2 == 0 {
+                let table = client.open_table("a".to_owned()).await.unwrap();
+                for _ in 0..10 {
+                    let update = UpdateData::Csv("x,y\n5,6".to_owned());
+                    table
+                        .update(update, UpdateOptions::default())
+                        .await
+                        .unwrap();
+                }
+            } else {
+                let table = client.open_table("b".to_owned()).await.unwrap();
+                let view = table.view(None).await.unwrap();
+                let expected = view.to_csv(Default::default()).await.unwrap();
+                assert_eq!(expected.lines().count(), 3);
+                for _ in 0..10 {
+                    let csv = view.to_csv(Default::default()).await.unwrap();
+                    assert_eq!(csv, expected);
+                }
+
+                view.delete().await.unwrap();
+            }
+
+            client.close().await;
+        }));
+    }
+
+    for task in tasks {
+        task.await?;
+    }
+
+    let table = client.open_table("a".to_owned()).await?;
+    assert_eq!(table.size().await?, 42);
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_concurrent_update_notifies_other_clients() -> Result<(), Box<dyn Error>> {
+    let server = Server::new_concurrent(2);
+    let client1 = LocalClient::new(&server);
+    let client2 = LocalClient::new(&server);
+    let csv = "x,y\n1,2\n3,4";
+    let table = client1
+        .table(UpdateData::Csv(csv.to_owned()).into(), table_options("a"))
+        .await?;
+
+    let view = client2.open_table("a".to_owned()).await?.view(None).await?;
+    let result = Arc::new(Mutex::new(false));
+    let _sub = view
+        .on_update(
+            {
+                let result = result.clone();
+                move |_| {
+                    let result = result.clone();
+                    async move { *result.lock().await = true }
+                }
+            },
+            OnUpdateOptions::default(),
+        )
+        .await?;
+
+    table
+        .update(
+            UpdateData::Csv("x,y\n5,6".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    assert!(*result.lock().await);
+    assert_eq!(view.num_rows().await?, 3);
+    client1.close().await;
+    client2.close().await;
+    Ok(())
+}
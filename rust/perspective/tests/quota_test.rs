@@ -0,0 +1,174 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+
+use perspective_client::{ClientError, OnUpdateOptions, TableInitOptions, UpdateData, ViewWindow};
+use perspective_server::{LocalClient, Server, SessionQuotas};
+
+async fn host_trades(server: &Server) -> Result<(), Box<dyn Error>> {
+    let options = TableInitOptions {
+        name: Some("trades".to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    };
+
+    let csv = "desk,x\nrates,1\nfx,2\nrates,3";
+    LocalClient::new(server)
+        .table(UpdateData::Csv(csv.to_owned()).into(), options)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_views() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let quotas = SessionQuotas::default().with_max_views(2);
+    let client = LocalClient::new(&server).with_quotas(quotas);
+    let table = client.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    table.view(None).await?;
+    let result = table.view(None).await;
+    assert!(matches!(result, Err(ClientError::QuotaExceeded(_))));
+
+    view.delete().await?;
+    table.view(None).await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_subscriptions() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let quotas = SessionQuotas::default().with_max_subscriptions(1);
+    let client = LocalClient::new(&server).with_quotas(quotas);
+    let table = client.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    view.on_update(|_| async {}, OnUpdateOptions::default())
+        .await?;
+
+    let result = view
+        .on_update(|_| async {}, OnUpdateOptions::default())
+        .await;
+
+    assert!(matches!(result, Err(ClientError::QuotaExceeded(_))));
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_export_rows() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let quotas = SessionQuotas::default().with_max_export_rows(2);
+    let client = LocalClient::new(&server).with_quotas(quotas);
+    let table = client.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    let result = view.to_csv(ViewWindow::default()).await;
+    assert!(matches!(result, Err(ClientError::QuotaExceeded(_))));
+
+    let window = ViewWindow {
+        start_row: Some(1.0),
+        end_row: Some(3.0),
+        ..ViewWindow::default()
+    };
+
+    let csv = view.to_csv(window).await?;
+    assert_eq!(csv, "\"desk\",\"x\"\n\"fx\",2\n\"rates\",3\n");
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_export_rows_of_update_viewports() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let quotas = SessionQuotas::default().with_max_export_rows(2);
+    let client = LocalClient::new(&server).with_quotas(quotas);
+    let table = client.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    let options = |end_row: Option<f32>| OnUpdateOptions {
+        viewport: end_row.map(|end_row| ViewWindow {
+            end_row: Some(end_row),
+            ..ViewWindow::default()
+        }),
+        include_viewport_data: Some(true),
+        ..OnUpdateOptions::default()
+    };
+
+    // The data of an unbounded viewport may grow past the limit.
+    let result = view.on_update(|_| async {}, options(None)).await;
+    assert!(matches!(result, Err(ClientError::QuotaExceeded(_))));
+    let result = view.on_update(|_| async {}, options(Some(3.0))).await;
+    assert!(matches!(result, Err(ClientError::QuotaExceeded(_))));
+
+    let id = view.on_update(|_| async {}, options(Some(2.0))).await?;
+    let window = ViewWindow {
+        end_row: Some(3.0),
+        ..ViewWindow::default()
+    };
+
+    let result = view.set_update_viewport(id, window).await;
+    assert!(matches!(result, Err(ClientError::QuotaExceeded(_))));
+
+    let window = ViewWindow {
+        start_row: Some(1.0),
+        end_row: Some(3.0),
+        ..ViewWindow::default()
+    };
+
+    view.set_update_viewport(id, window).await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_response_bytes() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let quotas = SessionQuotas::default().with_max_response_bytes(256);
+    let client = LocalClient::new(&server).with_quotas(quotas);
+    let table = client.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    assert_eq!(view.num_rows().await?, 3);
+
+    let result = view.to_arrow(ViewWindow::default()).await;
+    assert!(matches!(result, Err(ClientError::QuotaExceeded(_))));
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_requests_per_second() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let quotas = SessionQuotas::default().with_max_requests_per_second(5);
+    let client = LocalClient::new(&server).with_quotas(quotas);
+    let table = client.open_table("trades".to_owned()).await?;
+    let mut results = vec![];
+    for _ in 0..10 {
+        results.push(table.size().await);
+    }
+
+    assert!(results.iter().any(|x| x.is_ok()));
+    assert!(matches!(
+        results.last(),
+        Some(Err(ClientError::QuotaExceeded(_)))
+    ));
+
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,241 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+use std::time::Duration;
+
+use perspective_client::{TableInitOptions, UpdateData, UpdateOptions};
+use perspective_server::{LocalClient, Replica, Server};
+
+async fn host_table(client: &LocalClient, name: &str) -> Result<(), Box<dyn Error>> {
+    let options = TableInitOptions {
+        name: Some(name.to_owned()),
+        index: Some("id".to_owned()),
+        limit: None,
+        format: None,
+    };
+
+    let csv = "id,x\n1,a\n2,b";
+    client
+        .table(UpdateData::Csv(csv.to_owned()).into(), options)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replica_mirrors_tables_and_updates() -> Result<(), Box<dyn Error>> {
+    let primary = Server::default();
+    let remote = LocalClient::new(&primary);
+    host_table(&remote, "trades").await?;
+
+    let server = Server::default();
+    let replica = Replica::new(&server, (*remote).clone()).await?;
+    let client = LocalClient::new(&server);
+    let trades = client.open_table("trades".to_owned()).await?;
+    assert_eq!(trades.get_index(), Some("id".to_owned()));
+    assert_eq!(trades.size().await?, 2);
+
+    remote
+        .open_table("trades".to_owned())
+        .await?
+        .update(
+            UpdateData::Csv("id,x\n2,c\n3,d".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    assert_eq!(trades.size().await?, 3);
+    let lag = replica.lag().remove("trades").unwrap();
+    assert_eq!(lag.updates_applied, 1);
+    assert_eq!(lag.pending_updates, 0);
+    assert!(!lag.stale);
+
+    host_table(&remote, "quotes").await?;
+    assert!(!client
+        .get_hosted_table_names()
+        .await?
+        .contains(&"quotes".to_owned()));
+
+    replica.sync().await?;
+    assert_eq!(
+        client.open_table("quotes".to_owned()).await?.size().await?,
+        2
+    );
+
+    replica.close().await;
+    client.close().await;
+    remote.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replica_mirrors_deletion() -> Result<(), Box<dyn Error>> {
+    let primary = Server::default();
+    let remote = LocalClient::new(&primary).with_admin();
+    host_table(&remote, "trades").await?;
+
+    let server = Server::default();
+    let replica = Replica::new(&server, (*remote).clone()).await?;
+    for view in remote.get_hosted_views().await? {
+        remote.delete_hosted_view(&view.name).await?;
+    }
+
+    assert!(replica.lag()["trades"].stale);
+    remote
+        .open_table("trades".to_owned())
+        .await?
+        .delete()
+        .await?;
+    replica.sync().await?;
+    assert!(replica.table_names().is_empty());
+
+    let client = LocalClient::new(&server);
+    assert!(client.get_hosted_table_names().await?.is_empty());
+
+    replica.close().await;
+    client.close().await;
+    remote.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replica_resyncs_after_reconnect() -> Result<(), Box<dyn Error>> {
+    let primary = Server::default();
+    let remote = LocalClient::new(&primary);
+    host_table(&remote, "trades").await?;
+
+    let server = Server::default();
+    let replica = Replica::new(&server, (*remote).clone()).await?;
+
+    // Updates made while the replica is disconnected are not applied until
+    // it re-syncs from a new connection.
+    remote.close().await;
+    let remote = LocalClient::new(&primary);
+    let table = remote.open_table("trades".to_owned()).await?;
+    table
+        .update(
+            UpdateData::Csv("id,x\n3,c".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    let client = LocalClient::new(&server);
+    let trades = client.open_table("trades".to_owned()).await?;
+    assert_eq!(trades.size().await?, 2);
+
+    replica.resync((*remote).clone()).await?;
+    assert_eq!(trades.size().await?, 3);
+    assert!(!replica.lag()["trades"].stale);
+
+    table
+        .update(
+            UpdateData::Csv("id,x\n4,d".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    assert_eq!(trades.size().await?, 4);
+    assert_eq!(replica.lag()["trades"].updates_applied, 1);
+
+    replica.close().await;
+    client.close().await;
+    remote.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replica_resyncs_removed_rows() -> Result<(), Box<dyn Error>> {
+    let primary = Server::default();
+    let remote = LocalClient::new(&primary);
+    host_table(&remote, "trades").await?;
+
+    let server = Server::default();
+    let replica = Replica::new(&server, (*remote).clone()).await?;
+    let client = LocalClient::new(&server);
+    let trades = client.open_table("trades".to_owned()).await?;
+    remote
+        .open_table("trades".to_owned())
+        .await?
+        .remove(UpdateData::JsonRows("[{\"id\": 1}]".to_owned()))
+        .await?;
+
+    // A single size mismatch may only be updates in flight.
+    replica.sync().await?;
+    assert_eq!(trades.size().await?, 2);
+    replica.sync().await?;
+    assert_eq!(trades.size().await?, 1);
+
+    replica.close().await;
+    client.close().await;
+    remote.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replica_keeps_local_tables() -> Result<(), Box<dyn Error>> {
+    let primary = Server::default();
+    let remote = LocalClient::new(&primary);
+    host_table(&remote, "trades").await?;
+
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    client
+        .table(
+            UpdateData::Csv("id,x\n9,z".to_owned()).into(),
+            TableInitOptions {
+                name: Some("trades".to_owned()),
+                index: None,
+                limit: None,
+                format: None,
+            },
+        )
+        .await?;
+
+    assert!(Replica::new(&server, (*remote).clone()).await.is_err());
+    let trades = client.open_table("trades".to_owned()).await?;
+    assert_eq!(trades.size().await?, 1);
+
+    client.close().await;
+    remote.close().await;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_replica_close_and_drop_delete_remote_views() -> Result<(), Box<dyn Error>> {
+    let primary = Server::default();
+    let remote = LocalClient::new(&primary).with_admin();
+    host_table(&remote, "trades").await?;
+
+    let server = Server::default();
+    let replica = Replica::new(&server, (*remote).clone()).await?;
+    assert_eq!(remote.get_hosted_views().await?.len(), 1);
+    replica.close().await;
+    assert!(remote.get_hosted_views().await?.is_empty());
+
+    // Dropping a `Replica` tears it down in the background.
+    let server = Server::default();
+    let replica = Replica::new(&server, (*remote).clone()).await?;
+    assert_eq!(remote.get_hosted_views().await?.len(), 1);
+    drop(replica);
+    for _ in 0..100 {
+        if remote.get_hosted_views().await?.is_empty() {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert!(remote.get_hosted_views().await?.is_empty());
+    remote.close().await;
+    Ok(())
+}
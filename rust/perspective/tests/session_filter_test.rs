@@ -0,0 +1,179 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+use std::error::Error;
+use std::sync::{Arc, OnceLock};
+
+use perspective_client::config::{
+    Filter, FilterReducer, FilterTerm, Scalar, ViewConfigUpdate, Window, WindowFrame,
+    WindowFunction,
+};
+use perspective_client::{
+    Client, ClientError, ProxySession, Session, SessionFilters, TableInitOptions, UpdateData,
+};
+use perspective_server::{LocalClient, Server};
+
+fn rates_only() -> SessionFilters {
+    let desk = FilterTerm::Scalar(Scalar::String("rates".to_owned()));
+    SessionFilters::default().with_filter("trades", Filter::new("desk", "==", desk))
+}
+
+async fn host_trades(server: &Server) -> Result<(), Box<dyn Error>> {
+    let options = TableInitOptions {
+        name: Some("trades".to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    };
+
+    let csv = "desk,x\nrates,1\nfx,2\nrates,3\nfx,4";
+    LocalClient::new(server)
+        .table(UpdateData::Csv(csv.to_owned()).into(), options)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_filters_restrict_views_and_size() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let client = LocalClient::new(&server).with_filters(rates_only());
+    let table = client.open_table("trades".to_owned()).await?;
+    assert_eq!(table.size().await?, 2);
+
+    let view = table.view(None).await?;
+    assert_eq!(view.num_rows().await?, 2);
+    assert!(view.get_config().await?.filter.is_empty());
+
+    let csv = view.to_csv(Default::default()).await?;
+    assert_eq!(csv, "\"desk\",\"x\"\n\"rates\",1\n\"rates\",3\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_filters_cannot_be_widened() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let client = LocalClient::new(&server).with_filters(rates_only());
+    let table = client.open_table("trades".to_owned()).await?;
+
+    let fx = FilterTerm::Scalar(Scalar::String("fx".to_owned()));
+    let big = FilterTerm::Scalar(Scalar::Float(1.0));
+    let config = ViewConfigUpdate {
+        filter: Some(vec![
+            Filter::new("desk", "==", fx),
+            Filter::new("x", ">", big),
+        ]),
+        filter_op: Some(FilterReducer::Or),
+        ..ViewConfigUpdate::default()
+    };
+
+    // Only the session's `rates` rows are candidates for the user's filters.
+    let view = table.view(Some(config)).await?;
+    assert_eq!(view.num_rows().await?, 1);
+    assert_eq!(view.get_config().await?.filter.len(), 2);
+
+    let unfiltered = LocalClient::new(&server);
+    let table = unfiltered.open_table("trades".to_owned()).await?;
+    assert_eq!(table.size().await?, 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_filters_deny_windows() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let client = LocalClient::new(&server).with_filters(rates_only());
+    let table = client.open_table("trades".to_owned()).await?;
+    let total = Window {
+        function: WindowFunction::Sum,
+        column: Some("x".to_owned()),
+        partition_by: vec![],
+        order_by: vec![],
+        frame: WindowFrame::default(),
+        offset: 1,
+    };
+
+    // A running total over the whole `Table` would include the `fx` rows.
+    let config = ViewConfigUpdate {
+        window: Some([("total".to_owned(), total)].into()),
+        ..ViewConfigUpdate::default()
+    };
+
+    assert!(table.view(Some(config)).await.is_err());
+    assert_eq!(table.size().await?, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_filters_cannot_be_read_around() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let unfiltered = LocalClient::new(&server).with_admin();
+    let table = unfiltered.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    let hosted = unfiltered.get_hosted_views().await?;
+
+    let client = LocalClient::new(&server).with_filters(rates_only());
+    let result = client.delete_hosted_view(&hosted[0].name).await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    let result = client.system_info().await;
+    assert!(matches!(result, Err(ClientError::Unauthorized(_))));
+    assert_eq!(view.num_rows().await?, 4);
+
+    let table = client.open_table("trades".to_owned()).await?;
+    let view = table.view(None).await?;
+    assert_eq!(view.num_rows().await?, 2);
+    view.delete().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_filters_add_to_forwarded_filters() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    host_trades(&server).await?;
+    let parent = LocalClient::new(&server).with_filters(rates_only());
+    let big = FilterTerm::Scalar(Scalar::Float(1.0));
+    let filters = SessionFilters::default().with_filter("trades", Filter::new("x", ">", big));
+    let client: Arc<OnceLock<Client>> = Arc::default();
+    let proxy = ProxySession::new(parent.clone(), {
+        let client = client.clone();
+        move |msg| {
+            let client = client.clone();
+            let msg = msg.to_vec();
+            tokio::spawn(async move { client.get().unwrap().handle_response(&msg).await });
+            Ok(())
+        }
+    })
+    .with_filters(filters);
+
+    let proxy = Arc::new(proxy);
+    client
+        .set(Client::new_with_callback(move |msg| {
+            let proxy = proxy.clone();
+            Box::pin(async move { Ok(proxy.handle_request(msg).await?) })
+        }))
+        .unwrap();
+
+    // Only the `rates` row with `x > 1` passes both sessions' filters.
+    let table = client
+        .get()
+        .unwrap()
+        .open_table("trades".to_owned())
+        .await?;
+    let view = table.view(None).await?;
+    assert_eq!(view.num_rows().await?, 1);
+    assert_eq!(table.size().await?, 1);
+    view.delete().await?;
+    parent.close().await;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+
+use perspective_client::{OnUpdateOptions, TableInitOptions, UpdateData};
+use perspective_server::{LocalClient, Server};
+
+#[tokio::test]
+async fn test_system_info_reports_tables_sessions_and_latency() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let options = TableInitOptions {
+        name: Some("trades".to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    };
+
+    let csv = "desk,x\nrates,1\nfx,2\nrates,3";
+    let table = client
+        .table(UpdateData::Csv(csv.to_owned()).into(), options)
+        .await?;
+
+    let view = table.view(None).await?;
+    view.on_update(|_| async {}, OnUpdateOptions::default())
+        .await?;
+
+    let info = client.system_info().await?;
+    assert_eq!(info.tables.len(), 1);
+    assert_eq!(info.tables[0].name, "trades");
+    assert_eq!(info.tables[0].num_rows, 3);
+    assert_eq!(info.tables[0].num_views, 1);
+    assert!(info.tables[0].memory_bytes > 0);
+
+    assert_eq!(info.sessions.len(), 1);
+    assert_eq!(info.sessions[0].num_views, 1);
+    assert_eq!(info.sessions[0].num_subscriptions, 1);
+    assert_eq!(info.poll_queue_depth, 0);
+
+    let make_view = info
+        .request_latency
+        .iter()
+        .find(|x| x.request_type == "table_make_view_req")
+        .unwrap();
+
+    assert_eq!(make_view.count, 1);
+    assert_eq!(make_view.buckets.len(), info.latency_bounds.len() + 1);
+    assert_eq!(make_view.buckets.iter().sum::<u64>(), 1);
+
+    let metrics = info.to_prometheus();
+    assert!(metrics.contains("# TYPE perspective_table_rows gauge\n"));
+    assert!(metrics.contains("perspective_table_rows{table=\"trades\"} 3\n"));
+    assert!(metrics.contains(
+        "perspective_request_duration_seconds_bucket{request=\"table_make_view_req\",le=\"+Inf\"} \
+         1\n"
+    ));
+
+    view.delete().await?;
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,110 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+use std::sync::{Arc, OnceLock};
+
+use async_lock::RwLock;
+use perspective_client::{
+    Client, OnUpdateOptions, Session, TableInitOptions, UpdateData, UpdateOptions,
+};
+use perspective_server::{LocalClient, Server, TokioSession};
+use tokio::sync::Mutex;
+
+/// A [`Client`] connected to `server` through a [`TokioSession`], and the
+/// session itself so that it can be closed.
+async fn tokio_client(server: &Server) -> (Client, Arc<RwLock<Option<TokioSession>>>) {
+    let client: Arc<OnceLock<Client>> = Arc::default();
+    let session = server
+        .new_session_with_callback({
+            let client = client.clone();
+            move |msg| {
+                let client = client.clone();
+                Box::pin(async move {
+                    client.get().unwrap().handle_response(msg).await?;
+                    Ok(())
+                })
+            }
+        })
+        .await;
+
+    let session = Arc::new(RwLock::new(Some(TokioSession::new(session))));
+    client
+        .set(Client::new_with_callback({
+            let session = session.clone();
+            move |msg| {
+                let session = session.clone();
+                Box::pin(async move {
+                    let session = session.read().await;
+                    let session = session.as_ref().unwrap();
+                    session.handle_request(msg).await?;
+                    session.poll().await
+                })
+            }
+        }))
+        .unwrap();
+
+    (client.get().unwrap().clone(), session)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_tokio_session_handles_requests() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let local = LocalClient::new(&server);
+    let options = TableInitOptions {
+        name: Some("trades".to_owned()),
+        index: None,
+        limit: None,
+        format: None,
+    };
+
+    let table = local
+        .table(UpdateData::Csv("x,y\n1,2\n3,4".to_owned()).into(), options)
+        .await?;
+
+    let (client, session) = tokio_client(&server).await;
+    let view = client
+        .open_table("trades".to_owned())
+        .await?
+        .view(None)
+        .await?;
+    let (num_rows, csv) = futures::join!(view.num_rows(), view.to_csv(Default::default()));
+    assert_eq!(num_rows?, 2);
+    assert_eq!(csv?.lines().count(), 3);
+
+    let updated = Arc::new(Mutex::new(false));
+    let _sub = view
+        .on_update(
+            {
+                let updated = updated.clone();
+                move |_| {
+                    let updated = updated.clone();
+                    async move { *updated.lock().await = true }
+                }
+            },
+            OnUpdateOptions::default(),
+        )
+        .await?;
+
+    table
+        .update(
+            UpdateData::Csv("x,y\n5,6".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    assert!(*updated.lock().await);
+    assert_eq!(view.num_rows().await?, 3);
+    session.write().await.take().unwrap().close().await;
+    local.close().await;
+    Ok(())
+}